@@ -0,0 +1,328 @@
+//! the colors of the strings with ANSI escape sequences, e.g. the captured output of a command,
+//! see [`crate::config::Config::interpret_ansi`]
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+const ESCAPE: char = '\x1b';
+
+/// the 8 colors of the SGR codes, from `30` to `37` and `40` to `47`
+const COLORS: [Color; 8] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+];
+
+/// the 8 bright colors of the SGR codes, from `90` to `97` and `100` to `107`
+const BRIGHT_COLORS: [Color; 8] = [
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// the end of the strings of the OSC, DCS, SOS, PM and APC sequences, e.g. the links of `ESC]8;;`,
+/// besides the string terminator `ESC\`
+const BELL: char = '\x07';
+
+/// tell whether *text* has escape sequences to interpret
+pub(crate) fn has_escapes(text: &str) -> bool {
+    text.contains(ESCAPE)
+}
+
+/// a piece of a text with escape sequences, see [`pieces`]
+#[derive(Debug, PartialEq)]
+enum Piece<'a> {
+    /// a character shown as is
+    Char(char),
+    /// an SGR sequence, i.e. `ESC[...m`, with its parameters
+    Sgr(&'a str),
+    /// any other escape sequence, which is not shown
+    Hidden(&'a str),
+}
+
+/// split *text* into its characters and its escape sequences, whole
+///
+/// the CSI sequences, i.e. `ESC[`, end with their final byte, from `@` to `~`, the OSC, DCS, SOS,
+/// PM and APC ones, e.g. `ESC]`, with a bell or a string terminator, and the others with the
+/// first byte after their intermediate bytes. the sequences that are not terminated go on until
+/// the end of *text*.
+fn pieces(text: &str) -> Vec<Piece<'_>> {
+    let mut pieces = vec![];
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != ESCAPE {
+            pieces.push(Piece::Char(c));
+            continue;
+        }
+
+        let mut end = text.len();
+        match chars.next() {
+            Some((i, '[')) => {
+                let mut sgr = None;
+                for (j, c) in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        end = j + c.len_utf8();
+                        if c == 'm' {
+                            sgr = Some(&text[i + 1..j]);
+                        }
+                        break;
+                    }
+                }
+                if let Some(params) = sgr {
+                    pieces.push(Piece::Sgr(params));
+                    continue;
+                }
+            }
+            Some((_, ']' | 'P' | 'X' | '^' | '_')) => {
+                while let Some((j, c)) = chars.next() {
+                    if c == BELL {
+                        end = j + c.len_utf8();
+                        break;
+                    }
+                    if c == ESCAPE && chars.peek().map(|(_, c)| *c) == Some('\\') {
+                        chars.next();
+                        end = j + 2;
+                        break;
+                    }
+                }
+            }
+            Some((_, c)) if ('\x20'..='\x2f').contains(&c) => {
+                for (j, c) in chars.by_ref() {
+                    if !('\x20'..='\x2f').contains(&c) {
+                        end = j + c.len_utf8();
+                        break;
+                    }
+                }
+            }
+            Some((i, c)) => end = i + c.len_utf8(),
+            None => {}
+        }
+        pieces.push(Piece::Hidden(&text[start..end]));
+    }
+    pieces
+}
+
+/// remove all the escape sequences of *text*, to give the characters that are shown
+pub(crate) fn strip(text: &str) -> String {
+    pieces(text)
+        .into_iter()
+        .filter_map(|piece| match piece {
+            Piece::Char(c) => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// cut each line of *text* into lines of *width* shown characters, the escape sequences being kept
+/// whole and not counted
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    for line in text.split('\n') {
+        let mut current = String::new();
+        let mut count = 0;
+        for piece in pieces(line) {
+            match piece {
+                Piece::Char(c) => {
+                    if count == width {
+                        lines.push(std::mem::take(&mut current));
+                        count = 0;
+                    }
+                    current.push(c);
+                    count += 1;
+                }
+                Piece::Sgr(params) => current.push_str(&format!("{ESCAPE}[{params}m")),
+                Piece::Hidden(raw) => current.push_str(raw),
+            }
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+/// turn *text* into lines of spans, styled by its SGR sequences, i.e. `ESC[...m`, on top of *base*
+///
+/// the styles go on from one line to the next, like in a terminal, and the other escape sequences,
+/// e.g. the moves of the cursor or the links, are dropped with their content, see [`pieces`].
+pub(crate) fn to_text(text: &str, base: Style) -> Text<'static> {
+    let mut lines = vec![];
+    let mut spans = vec![];
+    let mut buffer = String::new();
+    let mut style = base;
+
+    let flush = |buffer: &mut String, spans: &mut Vec<Span<'static>>, style: Style| {
+        if !buffer.is_empty() {
+            spans.push(Span::styled(std::mem::take(buffer), style));
+        }
+    };
+
+    for piece in pieces(text) {
+        match piece {
+            Piece::Sgr(params) => {
+                flush(&mut buffer, &mut spans, style);
+                style = apply_sgr(style, base, params);
+            }
+            Piece::Hidden(_) => {}
+            Piece::Char('\n') => {
+                flush(&mut buffer, &mut spans, style);
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+            Piece::Char(c) => buffer.push(c),
+        }
+    }
+    flush(&mut buffer, &mut spans, style);
+    lines.push(Line::from(spans));
+
+    Text::from(lines)
+}
+
+/// give the color of an extended SGR code, i.e. `5;n` for the 256 colors and `2;r;g;b` for the
+/// true colors, after a `38` or a `48`
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(codes.next()?)),
+        2 => Some(Color::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+/// remove *modifier* from *style*, unless it comes from *base*
+fn unset(mut style: Style, base: Style, modifier: Modifier) -> Style {
+    style.add_modifier = (style.add_modifier - modifier) | (base.add_modifier & modifier);
+    style
+}
+
+/// apply the SGR codes of *params*, separated by `;`, to *style*, a reset going back to *base*
+fn apply_sgr(mut style: Style, base: Style, params: &str) -> Style {
+    // NOTE: an empty code is a reset, e.g. in `ESC[m`
+    let mut codes = params
+        .split(';')
+        .map(|code| code.parse::<u8>().unwrap_or_default());
+
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => base,
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => unset(style, base, Modifier::BOLD | Modifier::DIM),
+            23 => unset(style, base, Modifier::ITALIC),
+            24 => unset(style, base, Modifier::UNDERLINED),
+            25 => unset(style, base, Modifier::SLOW_BLINK),
+            27 => unset(style, base, Modifier::REVERSED),
+            29 => unset(style, base, Modifier::CROSSED_OUT),
+            30..=37 => style.fg(COLORS[(code - 30) as usize]),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            39 => Style {
+                fg: base.fg,
+                ..style
+            },
+            40..=47 => style.bg(COLORS[(code - 40) as usize]),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            49 => Style {
+                bg: base.bg,
+                ..style
+            },
+            90..=97 => style.fg(BRIGHT_COLORS[(code - 90) as usize]),
+            100..=107 => style.bg(BRIGHT_COLORS[(code - 100) as usize]),
+            _ => style,
+        };
+    }
+
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        style::{Color, Modifier, Style},
+        text::{Line, Span, Text},
+    };
+
+    use super::{has_escapes, strip, to_text, wrap};
+
+    #[test]
+    fn interpret_colors() {
+        let base = Style::default().fg(Color::White);
+
+        assert_eq!(
+            to_text("a \x1b[31mred\x1b[0m word", base),
+            Text::from(Line::from(vec![
+                Span::styled("a ", base),
+                Span::styled("red", base.fg(Color::Red)),
+                Span::styled(" word", base),
+            ]))
+        );
+
+        assert_eq!(
+            to_text("\x1b[1;38;5;208mbold\x1b[22;39;44m blue\x1b[m", base),
+            Text::from(Line::from(vec![
+                Span::styled(
+                    "bold",
+                    base.fg(Color::Indexed(208)).add_modifier(Modifier::BOLD)
+                ),
+                Span::styled(" blue", base.bg(Color::Blue)),
+            ]))
+        );
+
+        // NOTE: the styles go on after the newlines and the other sequences are dropped
+        assert_eq!(
+            to_text("\x1b[38;2;1;2;3ma\nb\x1b[2K", base),
+            Text::from(vec![
+                Line::from(Span::styled("a", base.fg(Color::Rgb(1, 2, 3)))),
+                Line::from(Span::styled("b", base.fg(Color::Rgb(1, 2, 3)))),
+            ])
+        );
+    }
+
+    #[test]
+    fn hide_other_sequences() {
+        let link = "\x1b]8;;https://nushell.sh\x1b\\nushell\x1b]8;;\x07 and \x1b(Bmore\x1b=";
+        assert_eq!(
+            to_text(link, Style::default()),
+            Text::from(Line::from("nushell and more"))
+        );
+        assert_eq!(strip(link), "nushell and more");
+        assert_eq!(strip("cut \x1b[3"), "cut ");
+        assert_eq!(strip("cut \x1b]8;;https"), "cut ");
+    }
+
+    #[test]
+    fn wrap_without_cutting_sequences() {
+        assert_eq!(
+            wrap("ab\x1b[31mcd\x1b[0mef\ng", 3),
+            vec!["ab\x1b[31mc", "d\x1b[0mef", "g"]
+        );
+        assert_eq!(
+            wrap("\x1b]8;;url\x07link\x1b]8;;\x07", 2),
+            vec!["\x1b]8;;url\x07li", "nk\x1b]8;;\x07"]
+        );
+    }
+
+    #[test]
+    fn detect_escapes() {
+        assert!(has_escapes("\x1b[31mred"));
+        assert!(!has_escapes("plain"));
+    }
+}
@@ -1,10 +1,32 @@
 //! the higher level application
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crossterm::event::KeyCode;
 use nu_protocol::{
     ast::{CellPath, PathMember},
-    Span, Value,
+    Span, Type, Value,
 };
 
-use crate::edit::Editor;
+use crate::{
+    config::{BreadcrumbStyle, Config, StatusBarPosition},
+    edit::Editor,
+    fold::FoldedJson,
+    form::Form,
+    history::History,
+    navigation::{expand_hidden_groups, visible_columns, MAX_DEPTH},
+    nu::{
+        custom::convert_custom_values,
+        nuon::from_nuon,
+        value::{
+            count_nodes, diff_values, find_matches, group_digits, mutate_value_cell, NODE_COUNT_CAP,
+        },
+    },
+    tree::{expand_ancestors, ExpandedPaths},
+};
 
 /// the mode in which the application is
 #[derive(Clone, Debug, PartialEq)]
@@ -16,6 +38,13 @@ pub enum Mode {
     /// the PEEKING mode lets the user *peek* data out of the application, to be reused later
     Peeking,
     Bottom,
+    /// the SEARCH mode lets the user type a query to search in the data
+    Search,
+    /// the REPLACE mode lets the user type a text and its replacement in the strings around the
+    /// cursor
+    Replace,
+    /// the FORM mode lets the user edit all the fields of a record at once, see [`Form`]
+    Form,
 }
 
 impl Default for Mode {
@@ -31,11 +60,263 @@ impl std::fmt::Display for Mode {
             Self::Insert => "INSERT",
             Self::Peeking => "PEEKING",
             Self::Bottom => "BOTTOM",
+            Self::Search => "SEARCH",
+            Self::Replace => "REPLACE",
+            Self::Form => "FORM",
         };
         write!(f, "{}", repr)
     }
 }
 
+/// the format of the values peeked out of the data, see [`App::peek_format`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PeekFormat {
+    /// the value itself
+    Value,
+    /// the value as a string of pretty-printed JSON
+    Json,
+    /// the value as a string of NUON, on a single line
+    Nuon,
+    /// the value as a string of YAML
+    Yaml,
+    /// the cell path of the value instead of the value
+    CellPath,
+}
+
+impl Default for PeekFormat {
+    fn default() -> Self {
+        Self::Value
+    }
+}
+
+impl PeekFormat {
+    /// give the format after this one, going back to the first one after the last one
+    pub(crate) fn next(&self) -> Self {
+        match self {
+            Self::Value => Self::Json,
+            Self::Json => Self::Nuon,
+            Self::Nuon => Self::Yaml,
+            Self::Yaml => Self::CellPath,
+            Self::CellPath => Self::Value,
+        }
+    }
+}
+
+impl std::fmt::Display for PeekFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let repr = match self {
+            Self::Value => "value",
+            Self::Json => "json",
+            Self::Nuon => "nuon",
+            Self::Yaml => "yaml",
+            Self::CellPath => "cell-path",
+        };
+        write!(f, "{}", repr)
+    }
+}
+
+/// an operator waiting for the motion giving the range of items it applies to, like in Vim, see
+/// [`Config::operator_pending`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operator {
+    /// remove the items of the range from their list
+    Delete,
+}
+
+/// an [`Operator`] that has been started, with the count of its motion typed so far, if any
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingOperator {
+    pub operator: Operator,
+    pub count: Option<usize>,
+}
+
+/// a sequence of keys that has been started and waits for its next key
+#[derive(Clone, Debug, PartialEq)]
+pub enum PendingKind {
+    /// waiting for the letter of the mark to put on the current cell
+    Mark,
+    /// waiting for the letter of the mark to jump to
+    JumpToMark,
+    /// waiting for the letter of the type of the values to stop on, see [`App::nav_type_filter`]
+    TypeFilter,
+    /// waiting for the delimiter to split the string under the cursor with
+    SplitString,
+    /// waiting for the separator to join the list of strings under the cursor with
+    JoinList,
+    /// waiting for the kind of sort of the list around the cursor, `n` or `l`
+    Sort,
+    /// waiting for what to sort the container around the cursor by in the data, `k` or `v`, in
+    /// descending order when uppercase
+    CommitSort,
+    /// waiting for the letter of the mark to copy the path of the current cell from
+    YankRelativePath,
+    /// waiting for the first letter of the key of a sibling field to jump to
+    TypeAhead,
+}
+
+/// a temporary view hiding the data while it's explored, see [`App::hidden_data`]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum HiddenView {
+    /// the results of the search, see [`crate::config::KeyBindingsMap::search_results`]
+    SearchResults,
+    /// the string under the cursor parsed as `KEY=value` lines
+    Envish,
+    /// the table under the cursor with its nested records flattened
+    Flatten,
+    /// the schema of the value under the cursor
+    Schema,
+    /// the list around the cursor sorted, see [`crate::config::KeyBindingsMap::sort`]
+    Sort,
+}
+
+/// a function computing custom text from the value under the cursor, like the right prompt of
+/// Nushell
+///
+/// > **Note**  
+/// > the plugin protocol of Nushell 0.91 does not allow plugins to evaluate closures, so the
+/// > `--right-prompt` flag of the command takes a cell path instead, see [`cell_path_prompt`].
+pub type RightPrompt = Rc<dyn Fn(&Value) -> Result<String, String>>;
+
+/// show the cell at *cell_path* of the value under the cursor, e.g. `name` to see the name of the
+/// rows of a table while exploring them
+pub fn cell_path_prompt(cell_path: CellPath) -> RightPrompt {
+    Rc::new(move |value: &Value| {
+        value
+            .clone()
+            .follow_cell_path(&cell_path.members, false)
+            .map(|cell| cell.to_expanded_string(" ", &nu_protocol::Config::default()))
+            .map_err(|err| err.to_string())
+    })
+}
+
+/// a function telling whether an item of a list should be kept, see [`App::predicate`]
+///
+/// > **Note**  
+/// > the plugin cannot evaluate closures, see [`RightPrompt`], so the `--filter` flag of the
+/// > command takes a condition like the ones of `where` instead, see [`where_predicate`].
+pub type Predicate = Rc<dyn Fn(&Value) -> Result<bool, String>>;
+
+/// the comparisons of the conditions of [`where_predicate`]
+const COMPARISONS: [&str; 8] = ["==", "!=", "<=", ">=", "<", ">", "starts-with", "ends-with"];
+
+/// keep the items of a list matching *condition*, like the `where` command of Nushell, e.g.
+/// `size > 1kb` for the rows of a table or `!= null` for the items themselves
+///
+/// the condition is an optional cell path into the items, one of [`COMPARISONS`] and a NUON value.
+pub fn where_predicate(condition: &str) -> Result<Predicate, String> {
+    let condition = condition.trim();
+    let (first, rest) = condition
+        .split_once(char::is_whitespace)
+        .unwrap_or((condition, ""));
+    let (members, comparison, value) = if COMPARISONS.contains(&first) {
+        (vec![], first, rest)
+    } else {
+        let rest = rest.trim_start();
+        let (comparison, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let members = first
+            .split('.')
+            .map(|member| match member.parse::<usize>() {
+                Ok(val) => PathMember::Int {
+                    val,
+                    span: Span::unknown(),
+                    optional: false,
+                },
+                Err(_) => PathMember::String {
+                    val: member.into(),
+                    span: Span::unknown(),
+                    optional: false,
+                },
+            })
+            .collect();
+        (members, comparison, value)
+    };
+    let Some(&comparison) = COMPARISONS.iter().find(|c| **c == comparison) else {
+        return Err(format!(
+            "expected one of {} in `{condition}`",
+            COMPARISONS.join(", ")
+        ));
+    };
+    let expected =
+        from_nuon(value).map_err(|err| format!("invalid value in `{condition}`: {err}"))?;
+
+    Ok(Rc::new(move |item: &Value| {
+        let cell = item
+            .clone()
+            .follow_cell_path(&members, false)
+            .map_err(|err| err.to_string())?;
+        let (ty, expected_ty) = (cell.get_type(), expected.get_type());
+        if ty != expected_ty && !(ty.is_numeric() && expected_ty.is_numeric()) {
+            // NOTE: the values of different types are never equal, e.g. for `!= null`
+            return match comparison {
+                "==" => Ok(false),
+                "!=" => Ok(true),
+                _ => Err(format!("cannot compare {ty} with {expected_ty}")),
+            };
+        }
+        let ordering = cell.partial_cmp(&expected);
+        Ok(match comparison {
+            "==" => ordering == Some(Ordering::Equal),
+            "!=" => ordering != Some(Ordering::Equal),
+            "<" => ordering == Some(Ordering::Less),
+            "<=" => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            ">" => ordering == Some(Ordering::Greater),
+            ">=" => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            _ => match (&cell, &expected) {
+                (Value::String { val, .. }, Value::String { val: other, .. }) => {
+                    if comparison == "starts-with" {
+                        val.starts_with(other.as_str())
+                    } else {
+                        val.ends_with(other.as_str())
+                    }
+                }
+                _ => return Err(format!("`{comparison}` only compares strings")),
+            },
+        })
+    }))
+}
+
+/// a function computing the text of a cell of a table from its value, see [`RendererRegistry`]
+pub type Renderer = Box<dyn Fn(&Value) -> String>;
+
+/// the custom [`Renderer`]s of the cells of tables, by name of the type of their values, e.g. `int`
+///
+/// the values without a renderer for their type are shown as usual. by default, only the empty
+/// cells, i.e. of type `nothing`, have a renderer, which leaves them blank.
+///
+/// > **Note**  
+/// > this is not exposed to the users of the command yet.
+pub struct RendererRegistry {
+    renderers: HashMap<String, Renderer>,
+}
+
+impl Default for RendererRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            renderers: HashMap::new(),
+        };
+        registry.register("nothing", Box::new(|_| String::new()));
+        registry
+    }
+}
+
+impl RendererRegistry {
+    /// use *renderer* for all the values of the type called *type_name*, replacing any previous one
+    pub fn register(&mut self, type_name: impl Into<String>, renderer: Renderer) {
+        self.renderers.insert(type_name.into(), renderer);
+    }
+
+    /// render *value* with the renderer of its type, if there is one
+    pub fn render(&self, value: &Value) -> Option<String> {
+        self.renderers
+            .get(&value.get_type().to_string())
+            .map(|renderer| renderer(value))
+    }
+}
+
+/// how long to wait after the last change of the search query before counting its matches, see
+/// [`App::live_matches`]
+pub(crate) const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
 /// the complete state of the application
 pub struct App {
     /// the full current path in the data
@@ -44,8 +325,159 @@ pub struct App {
     pub mode: Mode,
     /// the editor to modify the cells of the data
     pub editor: Editor,
+    /// the form to edit the record around the cursor in FORM mode
+    pub form: Option<Form>,
     /// the value that is being explored
     pub value: Value,
+    /// a transient message to show to the user, cleared on the next key press
+    pub message: Option<String>,
+    /// the key sequence waiting for its next key, if any
+    pub pending_sequence: Option<PendingKind>,
+    /// the operator waiting for its motion, if any
+    pub pending_operator: Option<PendingOperator>,
+    /// the cell paths that have been marked, by letter
+    pub marks: HashMap<char, CellPath>,
+    /// the nodes that are expanded in the tree view
+    pub(crate) expanded: ExpandedPaths,
+    /// the query being typed in SEARCH mode or of the last search
+    pub search_query: String,
+    /// the cell paths to the matches of the last search
+    pub matches: Vec<CellPath>,
+    /// the text to replace, once it has been typed in REPLACE mode
+    pub(crate) replace_find: Option<String>,
+    /// the text being typed in REPLACE mode, either the one to replace or its replacement
+    pub(crate) replace_input: String,
+    /// the cell paths visited in the previous sessions, see [`Config::history_file`]
+    pub(crate) history: History,
+    /// the index in [`App::history`] of the last path jumped to, if any
+    pub(crate) history_index: Option<usize>,
+    /// the number of matches of the query being typed, counted once the user stops typing
+    pub live_matches: Option<usize>,
+    /// when the query being typed last changed, if its matches have not been counted since
+    pub(crate) search_changed_at: Option<Instant>,
+    /// the index of the match the user is on, in [`App::matches`]
+    pub match_index: usize,
+    /// the number of the match being typed, see [`App::matches`]
+    pub pending_match: Option<usize>,
+    /// the numbers labelling the visible rows, like *easymotion* in Vim, with the number of the
+    /// row being typed, `0` before the first digit
+    pub row_labels: Option<usize>,
+    /// the peeking key the user has been warned about, because peeking would lose some edits, see
+    /// [`PeekingConfig::warn_on_lost_edits`](crate::config::PeekingConfig::warn_on_lost_edits)
+    pub(crate) lost_edits_warning: Option<KeyCode>,
+    /// the format of the values peeked out of the data, cycled through in PEEKING mode
+    pub peek_format: PeekFormat,
+    /// the data, position and matches hidden while exploring a temporary view, e.g. the results
+    /// of a search, along with the kind of the view
+    pub(crate) hidden_data: Option<(HiddenView, Value, CellPath, Vec<CellPath>)>,
+    /// show the indices of lists relative to the selected item
+    pub relative_indices: bool,
+    /// show the lists and records in reverse order, without changing the data
+    pub reversed: bool,
+    /// keep the navigation inside the current container, neither going deeper nor back up
+    pub scope_lock: bool,
+    /// focus the keys of the fields of the current record instead of their values
+    ///
+    /// the cell path still points to the field, the keys being highlighted and edited instead of
+    /// the values, e.g. to rename them.
+    pub key_focus: bool,
+    /// the foldable JSON of the list or the record shown at the bottom of the data, if any, see
+    /// [`crate::config::KeyBindingsMap::fold_view`]
+    pub(crate) folded_json: Option<FoldedJson>,
+    /// wrap the value under the cursor over multiple lines instead of truncating it
+    pub wrap_focused: bool,
+    /// show the status bar and the cell path below the data
+    pub show_chrome: bool,
+    /// the current style of the cell path, starting from [`Config::breadcrumb_style`]
+    pub breadcrumb_style: BreadcrumbStyle,
+    /// show the legend of the colors
+    pub show_legend: bool,
+    /// show the keys of the records that need quotes in a cell path with their quotes, e.g.
+    /// `"my key"`
+    pub quote_keys: bool,
+    /// show the containers above the cursor, from the root down
+    pub show_ancestors: bool,
+    /// show a zoomed-out view of the whole data on the right, with the cursor marked
+    pub show_minimap: bool,
+    /// the column of a table and how many times each of its values appears, shown in a popup
+    pub histogram: Option<(String, Vec<(String, usize)>)>,
+    /// the number of lines the preview pane is scrolled by
+    pub preview_scroll: u16,
+    /// the index of the first row shown in the tree view
+    pub tree_offset: usize,
+    /// the height of the terminal, updated before each frame
+    pub height: u16,
+    /// the width of the terminal, updated before each frame
+    pub width: u16,
+    /// the index of the first column shown in the table view, when the table has more columns
+    /// than fit, see [`crate::navigation::page_table_columns`]
+    pub column_offset: usize,
+    /// the custom text to show to the right of the status bar
+    pub right_prompt: Option<RightPrompt>,
+    /// the predicate to filter the lists with, if any
+    pub predicate: Option<Predicate>,
+    /// the custom renderers of the cells of tables
+    pub renderers: RendererRegistry,
+    /// the fields that are shown first in all the records
+    pub pinned: HashSet<String>,
+    /// the groups of fields whose rows are hidden but the first one, by name, in all the records,
+    /// see [`Config::group_records`]
+    pub collapsed_groups: HashSet<String>,
+    /// a copy of the data to compare the current one with, see [`crate::nu::value::diff_values`]
+    ///
+    /// this is the full data, even when the view is re-rooted, see [`App::root_stack`].
+    pub snapshot: Option<Value>,
+    /// the differences between [`App::snapshot`] and the data, computed whenever one of them
+    /// changes rather than for every frame, see [`App::refresh_changes`]
+    pub changes: Vec<CellPath>,
+    /// the absolute paths of the nested roots of the view, the last one being the current root
+    ///
+    /// when the view is re-rooted, [`App::value`] and [`App::position`] are relative to the
+    /// current root and the full data is kept in [`App::full_data`].
+    pub root_stack: Vec<CellPath>,
+    /// the full data hidden while the view is re-rooted, see [`App::root_stack`]
+    pub(crate) full_data: Option<Value>,
+    /// show the types of all the values and forbid any change to the data
+    pub inspect: bool,
+    /// the number of levels of the data below which the user cannot go, if any
+    ///
+    /// the items and fields of the root are at depth 1. any move deeper, e.g. a jump, stops at this
+    /// depth, see [`App::clamp_to_max_depth`].
+    pub max_depth: Option<usize>,
+    /// a JSON Schema of the full data, e.g. to know the allowed values of the strings, see
+    /// [`crate::nu::schema`]
+    pub schema: Option<Value>,
+    /// the file of the config, given with `--config`, to reload it while exploring, see
+    /// [`Config::load_from_disk`]
+    pub config_file: Option<PathBuf>,
+    /// the absolute paths of the cells edited since the start, in the order of their first edit
+    pub edited: Vec<CellPath>,
+    /// the full data before the first edit, if any, see [`App::original_value`]
+    pub(crate) original: Option<Value>,
+    /// show the original data to the left of the edited one
+    pub show_compare: bool,
+    /// render the lists of records as a single table, with a column per key, instead of one row
+    /// per record
+    pub table_view: bool,
+    /// the index of the edited cell the user is on, in [`App::edited`], if any
+    pub edit_index: Option<usize>,
+    /// the absolute paths visited by the user, like the history of a browser, see [`App::visit`]
+    pub nav_history: Vec<CellPath>,
+    /// the index of the current position in [`App::nav_history`]
+    pub nav_index: usize,
+    /// the type of the only siblings to stop on when going up or down, if any
+    ///
+    /// only the kind of the type matters, e.g. `list<any>` stops on all the lists.
+    pub nav_type_filter: Option<Type>,
+    /// how long to highlight the cell landed on after a jump, see [`Config::flash_ms`]
+    pub(crate) flash: Duration,
+    /// the end of the highlight of the cell landed on after the last jump, if any
+    pub(crate) flash_until: Option<Instant>,
+    /// how long to wait for the next key of a pending sequence, see [`Config::prompt_timeout_ms`]
+    pub(crate) prompt_timeout: Duration,
+    /// when the pending sequence or confirmation, if any, was first seen waiting, see
+    /// [`App::expire_prompt`]
+    pub(crate) pending_since: Option<Instant>,
 }
 
 impl Default for App {
@@ -54,14 +486,164 @@ impl Default for App {
             position: CellPath { members: vec![] },
             mode: Mode::default(),
             editor: Editor::default(),
+            form: None,
             value: Value::default(),
+            message: None,
+            pending_sequence: None,
+            pending_operator: None,
+            marks: HashMap::new(),
+            expanded: ExpandedPaths::new(),
+            search_query: String::new(),
+            replace_find: None,
+            replace_input: String::new(),
+            matches: vec![],
+            history: History::default(),
+            history_index: None,
+            live_matches: None,
+            search_changed_at: None,
+            match_index: 0,
+            pending_match: None,
+            row_labels: None,
+            lost_edits_warning: None,
+            peek_format: PeekFormat::default(),
+            hidden_data: None,
+            relative_indices: false,
+            reversed: false,
+            scope_lock: false,
+            key_focus: false,
+            folded_json: None,
+            wrap_focused: false,
+            show_chrome: true,
+            breadcrumb_style: BreadcrumbStyle::default(),
+            show_legend: false,
+            quote_keys: false,
+            show_ancestors: false,
+            show_minimap: false,
+            histogram: None,
+            preview_scroll: 0,
+            tree_offset: 0,
+            height: 0,
+            width: 0,
+            column_offset: 0,
+            right_prompt: None,
+            predicate: None,
+            renderers: RendererRegistry::default(),
+            pinned: HashSet::new(),
+            collapsed_groups: HashSet::new(),
+            snapshot: None,
+            changes: vec![],
+            root_stack: vec![],
+            full_data: None,
+            inspect: false,
+            max_depth: None,
+            schema: None,
+            config_file: None,
+            edited: vec![],
+            original: None,
+            show_compare: false,
+            table_view: true,
+            edit_index: None,
+            nav_history: vec![],
+            nav_index: 0,
+            nav_type_filter: None,
+            flash: Duration::ZERO,
+            flash_until: None,
+            prompt_timeout: Duration::ZERO,
+            pending_since: None,
         }
     }
 }
 
 impl App {
     /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        self.update_live_matches(Instant::now());
+        self.expire_prompt(Instant::now());
+    }
+
+    /// tell whether the pending sequence or confirmation has waited for its next key for longer
+    /// than the timeout at *now*, a zero timeout never expiring
+    pub(crate) fn is_prompt_expired(&self, now: Instant) -> bool {
+        !self.prompt_timeout.is_zero()
+            && self
+                .pending_since
+                .is_some_and(|since| now.duration_since(since) >= self.prompt_timeout)
+    }
+
+    /// cancel the pending sequence, or the confirmation asked before losing some edits, if it has
+    /// waited for too long at *now*, see [`App::is_prompt_expired`] and
+    /// [`App::lost_edits_warning`]
+    ///
+    /// NOTE: the wait starts on the first tick after the sequence starts, so the prompts can last up
+    /// to a tick more than the timeout
+    pub(crate) fn expire_prompt(&mut self, now: Instant) {
+        if self.pending_sequence.is_none() && self.lost_edits_warning.is_none() {
+            self.pending_since = None;
+        } else if self.pending_since.is_none() {
+            self.pending_since = Some(now);
+        } else if self.is_prompt_expired(now) {
+            self.pending_sequence = None;
+            self.lost_edits_warning = None;
+            self.pending_since = None;
+            self.message = Some("the prompt timed out".into());
+        }
+    }
+
+    /// remember that the search query changed at *now*, see [`App::update_live_matches`]
+    pub(crate) fn search_changed(&mut self, now: Instant) {
+        if self.search_query.is_empty() {
+            self.live_matches = None;
+            self.search_changed_at = None;
+        } else {
+            self.search_changed_at = Some(now);
+        }
+    }
+
+    /// count the matches of the search query if it has not changed for [`SEARCH_DEBOUNCE`] at
+    /// *now*, so that the data is not searched again on every key press
+    pub(crate) fn update_live_matches(&mut self, now: Instant) {
+        match self.search_changed_at {
+            Some(at) if now.duration_since(at) >= SEARCH_DEBOUNCE => {
+                self.live_matches = Some(find_matches(&self.value, &self.search_query).len());
+                self.search_changed_at = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// build the initial state of the application to explore a value
+    ///
+    /// on top of [`App::from_value`], this
+    /// - focuses the first field shown to the user when the value is a record
+    /// - focuses [`Config::initial_key`] if the value is a record that has this field
+    /// - starts in [`Config::initial_mode`]
+    /// - applies the other settings of the *config* that live in the state of the application
+    pub fn new(value: &Value, config: &Config) -> Self {
+        let mut app = Self::from_value(convert_custom_values(value.clone()));
+
+        if let Value::Record { val: rec, .. } = value {
+            let cols = visible_columns(rec, config, &app);
+            let key = match &config.initial_key {
+                Some(key) if cols.contains(key) => Some(key),
+                _ => cols.first(),
+            };
+
+            if let Some(key) = key {
+                app.position.members = vec![PathMember::String {
+                    val: key.clone(),
+                    span: Span::unknown(),
+                    optional: false,
+                }];
+            }
+        }
+
+        app.mode = config.initial_mode.clone();
+        app.breadcrumb_style = config.breadcrumb_style.clone();
+        app.flash = Duration::from_millis(config.flash_ms as u64);
+        app.prompt_timeout = Duration::from_millis(config.prompt_timeout_ms as u64);
+
+        app
+    }
 
     pub(super) fn from_value(value: Value) -> Self {
         let mut app = Self::default();
@@ -85,22 +667,378 @@ impl App {
         app
     }
 
+    /// tell the user how big the explored value is, e.g. `loaded: 12,345 nodes`
+    pub(super) fn show_node_count(&mut self) {
+        self.message = Some(format!(
+            "loaded: {} nodes",
+            repr_node_count(&self.value, NODE_COUNT_CAP)
+        ));
+    }
+
+    /// go to the cell at the given *cell path*, making sure it is visible in the tree view and not
+    /// hidden in a collapsed group
+    pub(crate) fn jump_to(&mut self, path: &CellPath, config: &Config) {
+        self.position = path.clone();
+        if self.mode == Mode::Bottom {
+            self.mode = Mode::Normal;
+        }
+        self.clamp_to_max_depth();
+        expand_ancestors(&mut self.expanded, &self.position.members);
+        expand_hidden_groups(self, config);
+        if !self.flash.is_zero() {
+            self.flash_until = Some(Instant::now() + self.flash);
+        }
+    }
+
+    /// tell whether the cell landed on after the last jump is still highlighted at *now*
+    pub(crate) fn is_flashing(&self, now: Instant) -> bool {
+        self.flash_until.is_some_and(|until| now < until)
+    }
+
+    /// give the data before any edit, at the current root of the view
+    ///
+    /// this is the current data when nothing has been edited yet, or when the root is not in the
+    /// original data anymore.
+    pub(crate) fn original_value(&self) -> Value {
+        let Some(original) = &self.original else {
+            return self.value.clone();
+        };
+        let root = self
+            .root_stack
+            .last()
+            .map(|root| root.members.clone())
+            .unwrap_or_default();
+        original
+            .clone()
+            .follow_cell_path(&root, false)
+            .unwrap_or_else(|_| self.value.clone())
+    }
+
+    /// give the full data, with the changes made to the current root of the view
+    pub(crate) fn full_value(&self) -> Value {
+        self.full_value_with(&self.value)
+    }
+
+    /// give the full data as [`App::final_value`] would, without closing the temporary view
+    /// hiding it, if any
+    pub(crate) fn unhidden_value(&self) -> Value {
+        match &self.hidden_data {
+            Some((_, hidden, ..)) => self.full_value_with(hidden),
+            None => self.full_value(),
+        }
+    }
+
+    /// give the full data with *value* at the current root of the view
+    fn full_value_with(&self, value: &Value) -> Value {
+        match (&self.full_data, self.root_stack.last()) {
+            (Some(full), Some(root)) => mutate_value_cell(full, root, value),
+            _ => value.clone(),
+        }
+    }
+
+    /// replace the data with *value*, giving back the previous one
+    pub(crate) fn set_value(&mut self, value: Value) -> Value {
+        let previous = std::mem::replace(&mut self.value, value);
+        self.refresh_changes();
+        previous
+    }
+
+    /// explore the temporary *view* of kind *kind*, hiding the data, the position and the matches
+    /// until it's closed, see [`App::restore_hidden_data`]
+    pub(crate) fn hide_data(&mut self, kind: HiddenView, view: Value, position: CellPath) {
+        let data = std::mem::replace(&mut self.value, view);
+        let position = std::mem::replace(&mut self.position, position);
+        let matches = std::mem::take(&mut self.matches);
+        self.hidden_data = Some((kind, data, position, matches));
+        self.refresh_changes();
+    }
+
+    /// compute the differences between [`App::snapshot`] and the data again, see
+    /// [`App::changes`]
+    ///
+    /// the data is compared with the part of the snapshot at the current root of the view, all of
+    /// it being new when the root was not in the snapshot, and the data hidden by a temporary view
+    /// is compared instead of the view.
+    pub(crate) fn refresh_changes(&mut self) {
+        let root = self
+            .root_stack
+            .last()
+            .map(|root| root.members.clone())
+            .unwrap_or_default();
+        self.changes = self
+            .snapshot
+            .as_ref()
+            .map(|snapshot| {
+                let snapshot = snapshot
+                    .clone()
+                    .follow_cell_path(&root, false)
+                    .unwrap_or_else(|_| Value::nothing(Span::unknown()));
+                // NOTE: a temporary view is not a change of the data, see [`App::unhidden_value`]
+                let value = match &self.hidden_data {
+                    Some((_, hidden, ..)) => hidden,
+                    None => &self.value,
+                };
+                diff_values(&snapshot, value)
+            })
+            .unwrap_or_default();
+    }
+
+    /// set the *cell* at *path*, relative to the current root, and remember it has been edited
+    ///
+    /// the edits of a temporary view are not remembered, they are lost with the view and their
+    /// paths are not the ones of the data, see [`App::hidden_data`].
+    pub(crate) fn apply_edit(&mut self, path: &CellPath, cell: &Value) {
+        if self.hidden_data.is_some() {
+            self.set_value(mutate_value_cell(&self.value, path, cell));
+            return;
+        }
+
+        // NOTE: the data is only copied when it's about to change for the first time
+        if self.original.is_none() {
+            self.original = Some(self.unhidden_value());
+        }
+        self.set_value(mutate_value_cell(&self.value, path, cell));
+
+        let mut absolute = self
+            .root_stack
+            .last()
+            .map(|root| root.members.clone())
+            .unwrap_or_default();
+        absolute.extend(path.members.iter().cloned());
+        let absolute = CellPath { members: absolute };
+        if !self.edited.contains(&absolute) {
+            self.edited.push(absolute);
+        }
+    }
+
+    /// give the key of the field under the cursor when the keys are focused, see [`App::key_focus`]
+    pub(crate) fn focused_key(&self) -> Option<String> {
+        if !self.key_focus || self.is_at_bottom() {
+            return None;
+        }
+        match self.position.members.last() {
+            Some(PathMember::String { val, .. }) => Some(val.clone()),
+            _ => None,
+        }
+    }
+
+    /// give the path relative to the current root of an absolute *path*, if it's under the root
+    pub(crate) fn relative_to_root(&self, path: &CellPath) -> Option<CellPath> {
+        let root = self
+            .root_stack
+            .last()
+            .map(|root| root.members.as_slice())
+            .unwrap_or_default();
+        path.members.strip_prefix(root).map(|members| CellPath {
+            members: members.to_vec(),
+        })
+    }
+
+    /// bring back the data hidden by a temporary view, e.g. the results of a search, if any, see
+    /// [`App::hidden_data`]
+    pub(crate) fn restore_hidden_data(&mut self) -> bool {
+        match self.hidden_data.take() {
+            Some((_, value, position, matches)) => {
+                self.set_value(value);
+                self.position = position;
+                self.matches = matches;
+                self.mode = Mode::Normal;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// bring back the data hidden by the temporary *view* only, leaving any other view open, see
+    /// [`App::restore_hidden_data`]
+    pub(crate) fn restore_hidden_view(&mut self, view: HiddenView) -> bool {
+        match &self.hidden_data {
+            Some((hidden, ..)) if hidden == &view => self.restore_hidden_data(),
+            _ => false,
+        }
+    }
+
+    /// give the full data at the end of the session, to save it or merge it, see
+    /// [`App::full_value`]
+    ///
+    /// the data hidden by a temporary view is brought back first, for the view not to be saved
+    /// instead of the data.
+    pub(crate) fn final_value(&mut self) -> Value {
+        self.restore_hidden_data();
+        self.full_value()
+    }
+
+    /// merge the changes made in a *nested* session back into the data, at *path*
+    ///
+    /// only the cells edited in the nested session are set, so that they are remembered as edited
+    /// here too, see [`App::edited`].
+    pub(crate) fn merge_nested(&mut self, path: &CellPath, nested: &mut App) {
+        let value = nested.final_value();
+        for edit in &nested.edited {
+            if let Ok(cell) = value.clone().follow_cell_path(&edit.members, false) {
+                let mut members = path.members.clone();
+                members.extend(edit.members.iter().cloned());
+                self.apply_edit(&CellPath { members }, &cell);
+            }
+        }
+    }
+
+    /// remember that the user went from the absolute path *from* to *to*, see [`App::nav_history`]
+    ///
+    /// like in a browser, the positions after the current one in the history are forgotten. going
+    /// to a sibling of the current position replaces it in the history instead of adding a new
+    /// one, so that going back does not go through all the rows one by one.
+    pub(crate) fn visit(&mut self, from: CellPath, to: CellPath) {
+        if from == to || self.nav_history.get(self.nav_index) == Some(&to) {
+            return;
+        }
+
+        // NOTE: the position might have changed outside of the history, e.g. with the mouse
+        if self.nav_history.get(self.nav_index) != Some(&from) {
+            self.nav_history.truncate(self.nav_index + 1);
+            self.nav_history.push(from);
+            self.nav_index = self.nav_history.len() - 1;
+        }
+        self.nav_history.truncate(self.nav_index + 1);
+
+        let current = &self.nav_history[self.nav_index].members;
+        let is_sibling = current.len() == to.members.len()
+            && current.split_last().map(|(_, parent)| parent)
+                == to.members.split_last().map(|(_, parent)| parent);
+        if is_sibling {
+            self.nav_history[self.nav_index] = to;
+        } else {
+            self.nav_history.push(to);
+            self.nav_index += 1;
+        }
+    }
+
+    /// give the position in the full data, i.e. not relative to the current root of the view
+    pub(crate) fn absolute_position(&self) -> CellPath {
+        let mut members = self
+            .root_stack
+            .last()
+            .map(|root| root.members.clone())
+            .unwrap_or_default();
+        members.extend(self.position.members.iter().cloned());
+        CellPath { members }
+    }
+
+    /// give the number of lines taken by the bars around the data, i.e. the tabs above it and the
+    /// status bar and the cell path below it when they are shown, see [`App::show_chrome`],
+    /// [`Config::show_tabs`] and [`Config::show_cell_path`]
+    pub(crate) fn bars_height(&self, config: &Config) -> u16 {
+        if !self.show_chrome {
+            0
+        } else if config.show_cell_path {
+            2 + self.tabs_height(config)
+        } else {
+            1 + self.tabs_height(config)
+        }
+    }
+
+    /// tell whether the status bar is shown on the first line, see [`Config::status_bar_position`]
+    pub(crate) fn status_bar_on_top(&self, config: &Config) -> bool {
+        self.show_chrome && config.status_bar_position == StatusBarPosition::Top
+    }
+
+    /// give the number of lines taken by the bars above the data, i.e. the status bar when it's at
+    /// the top and the tabs
+    pub(crate) fn top_bars_height(&self, config: &Config) -> u16 {
+        self.status_bar_on_top(config) as u16 + self.tabs_height(config)
+    }
+
+    /// give the width of the border on each side of the data pane, see [`Config::show_border`]
+    pub(crate) fn border_width(&self, config: &Config) -> u16 {
+        config.show_border as u16
+    }
+
+    /// give the number of lines taken by the tabs above the data, see [`Config::show_tabs`]
+    pub(crate) fn tabs_height(&self, config: &Config) -> u16 {
+        (self.show_chrome && config.show_tabs) as u16
+    }
+
+    /// give the titles of the tabs of the containers above the cursor, from the root down, e.g.
+    /// `root` and `r` at `r.a`
+    pub(crate) fn ancestor_tabs(&self) -> Vec<String> {
+        let members = &self.position.members;
+        std::iter::once("root".to_string())
+            .chain(
+                members
+                    .iter()
+                    .take(members.len().saturating_sub(1))
+                    .map(|m| match m {
+                        PathMember::Int { val, .. } => val.to_string(),
+                        PathMember::String { val, .. } => val.clone(),
+                    }),
+            )
+            .collect()
+    }
+
+    /// give the maximum length of [`App::position`], relative to the current root, allowed by
+    /// [`App::max_depth`]
+    pub(crate) fn max_position_depth(&self) -> Option<usize> {
+        let root = self.root_stack.last().map_or(0, |root| root.members.len());
+        // NOTE: the cursor is always on a cell below the root
+        self.max_depth.map(|max| max.saturating_sub(root).max(1))
+    }
+
+    /// bring the cursor back up to [`App::max_depth`] when it went deeper, e.g. with a jump, and
+    /// tell whether it did
+    pub(crate) fn clamp_to_max_depth(&mut self) -> bool {
+        let Some(allowed) = self.max_position_depth() else {
+            return false;
+        };
+        if self.position.members.len() <= allowed {
+            return false;
+        }
+
+        self.position.members.truncate(allowed);
+        if self.mode == Mode::Bottom {
+            self.mode = Mode::Normal;
+        }
+        self.message = Some(MAX_DEPTH.into());
+        true
+    }
+
+    /// tell whether the cursor is as deep in the data as [`App::max_depth`] allows
+    pub(crate) fn is_at_max_depth(&self) -> bool {
+        self.max_depth
+            .is_some_and(|max| self.absolute_position().members.len() >= max)
+    }
+
+    /// tell whether the keys only move around the data, i.e. in NORMAL or BOTTOM mode and without
+    /// any prompt waiting for more keys, see [`Config::repeat_debounce_ms`]
+    pub(crate) fn is_navigating(&self) -> bool {
+        matches!(self.mode, Mode::Normal | Mode::Bottom)
+            && self.pending_sequence.is_none()
+            && self.pending_operator.is_none()
+            && self.row_labels.is_none()
+    }
+
     pub fn is_at_bottom(&self) -> bool {
         matches!(self.mode, Mode::Bottom)
     }
 
     pub fn hit_bottom(&mut self) {
         self.mode = Mode::Bottom;
+        self.folded_json = None;
     }
 
     pub(super) fn enter_editor(&mut self) -> Result<(), String> {
+        if let Some(key) = self.focused_key() {
+            self.mode = Mode::Insert;
+            self.editor = Editor::from_value(&Value::string(key, Span::unknown()));
+            return Ok(());
+        }
+
         let value = self
             .value
             .clone()
             .follow_cell_path(&self.position.members, false)
             .unwrap();
 
-        if matches!(value, Value::String { .. }) {
+        if matches!(value, Value::String { .. } | Value::Nothing { .. }) {
             self.mode = Mode::Insert;
             self.editor = Editor::from_value(&value);
 
@@ -108,9 +1046,340 @@ impl App {
         } else {
             // TODO: support more diverse cell edition
             Err(format!(
-                "can only edit string cells, found {}",
+                "can only edit string and null cells, found {}",
                 value.get_type()
             ))
         }
     }
 }
+
+/// count the nodes of *value* up to *cap*, e.g. `12,345`, the count being marked with a `+` when
+/// the data is bigger than that, e.g. `10,000+`, so that huge values do not take long to load
+fn repr_node_count(value: &Value, cap: usize) -> String {
+    // NOTE: one more node is counted to tell a value of exactly *cap* nodes from a bigger one
+    let count = count_nodes(value, cap.saturating_add(1));
+    if count > cap {
+        format!("{}+", group_digits(&cap.to_string(), ","))
+    } else {
+        group_digits(&count.to_string(), ",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::KeyCode;
+    use nu_protocol::{ast::CellPath, record, Value};
+
+    use super::{
+        cell_path_prompt, repr_node_count, where_predicate, App, HiddenView, Mode, PendingKind,
+    };
+    use crate::{
+        config::Config,
+        nu::cell_path::{to_path_member_vec, PM},
+    };
+
+    fn test_value() -> Value {
+        Value::test_record(record! {
+            "b" => Value::test_int(1),
+            "a" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        })
+    }
+
+    #[test]
+    fn node_count_with_cap() {
+        let value = Value::test_list((0..12_000).map(Value::test_int).collect());
+
+        assert_eq!(repr_node_count(&value, usize::MAX), "12,001");
+        assert_eq!(repr_node_count(&value, 10_000), "10,000+");
+        assert_eq!(repr_node_count(&value, 12_001), "12,001");
+        assert_eq!(repr_node_count(&value, 12_000), "12,000+");
+        assert_eq!(repr_node_count(&test_value(), 10_000), "4");
+    }
+
+    #[test]
+    fn flash_after_jump() {
+        let mut app = App::from_value(test_value());
+        let now = Instant::now();
+        assert!(!app.is_flashing(now));
+
+        app.flash_until = Some(now + Duration::from_millis(300));
+        assert!(app.is_flashing(now));
+        assert!(app.is_flashing(now + Duration::from_millis(299)));
+        assert!(!app.is_flashing(now + Duration::from_millis(300)));
+
+        // NOTE: the flash starts with the jumps, unless it's disabled
+        let mut app = App::new(&test_value(), &Config::default());
+        app.jump_to(
+            &CellPath {
+                members: to_path_member_vec(&[PM::S("c")]),
+            },
+            &Config::default(),
+        );
+        assert!(app.is_flashing(Instant::now()));
+
+        let config = Config {
+            flash_ms: 0,
+            ..Default::default()
+        };
+        let mut app = App::new(&test_value(), &config);
+        app.jump_to(
+            &CellPath {
+                members: to_path_member_vec(&[PM::S("c")]),
+            },
+            &config,
+        );
+        assert_eq!(app.flash_until, None);
+    }
+
+    #[test]
+    fn navigating() {
+        let mut app = App::new(&test_value(), &Config::default());
+        assert!(app.is_navigating());
+
+        app.hit_bottom();
+        assert!(app.is_navigating());
+
+        app.mode = Mode::Insert;
+        assert!(!app.is_navigating());
+
+        app.mode = Mode::Normal;
+        app.pending_sequence = Some(PendingKind::Mark);
+        assert!(!app.is_navigating());
+    }
+
+    #[test]
+    fn prompt_timeout() {
+        let config = Config {
+            prompt_timeout_ms: 500,
+            ..Default::default()
+        };
+        let mut app = App::new(&test_value(), &config);
+        let now = Instant::now();
+
+        app.pending_sequence = Some(PendingKind::Mark);
+        app.expire_prompt(now);
+        assert_eq!(app.pending_since, Some(now));
+        assert!(!app.is_prompt_expired(now + Duration::from_millis(499)));
+        assert!(app.is_prompt_expired(now + Duration::from_millis(501)));
+
+        app.expire_prompt(now + Duration::from_millis(501));
+        assert_eq!(app.pending_sequence, None);
+        assert_eq!(app.message, Some("the prompt timed out".into()));
+
+        // NOTE: the confirmation asked before losing some edits times out too
+        app.lost_edits_warning = Some(KeyCode::Enter);
+        app.expire_prompt(now);
+        app.expire_prompt(now + Duration::from_millis(499));
+        assert_eq!(app.lost_edits_warning, Some(KeyCode::Enter));
+        app.expire_prompt(now + Duration::from_millis(501));
+        assert_eq!(app.lost_edits_warning, None);
+        assert_eq!(app.pending_since, None);
+
+        // NOTE: a zero timeout waits forever
+        let mut app = App::new(&test_value(), &Config::default());
+        app.pending_sequence = Some(PendingKind::Mark);
+        app.expire_prompt(now);
+        assert!(!app.is_prompt_expired(now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn new_app_with_default_config() {
+        let app = App::new(&test_value(), &Config::default());
+
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("b")]));
+        assert!(app.mode == Mode::Normal);
+        assert_eq!(app.value, test_value());
+    }
+
+    #[test]
+    fn new_app_with_initial_key() {
+        let config = Config {
+            initial_key: Some("c".into()),
+            ..Default::default()
+        };
+        let app = App::new(&test_value(), &config);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("c")]));
+
+        let config = Config {
+            initial_key: Some("x".into()),
+            ..Default::default()
+        };
+        let app = App::new(&test_value(), &config);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("b")]));
+
+        let config = Config {
+            group_records: true,
+            ..Default::default()
+        };
+        let app = App::new(&test_value(), &config);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("a")]));
+    }
+
+    #[test]
+    fn new_app_with_initial_mode() {
+        let config = Config {
+            initial_mode: Mode::Peeking,
+            ..Default::default()
+        };
+        let app = App::new(&test_value(), &config);
+
+        assert!(app.mode == Mode::Peeking);
+    }
+
+    #[test]
+    fn new_app_applies_config_state() {
+        let config = Config {
+            flash_ms: 200,
+            prompt_timeout_ms: 1000,
+            ..Default::default()
+        };
+        let app = App::new(&test_value(), &config);
+
+        assert!(app.mode == Mode::Normal);
+        assert_eq!(app.flash, Duration::from_millis(200));
+        assert_eq!(app.prompt_timeout, Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn right_prompt_from_cell_path() {
+        let prompt = cell_path_prompt(CellPath {
+            members: to_path_member_vec(&[PM::S("name")]),
+        });
+
+        let row = Value::test_record(record! {
+            "name" => Value::test_string("foo"),
+            "size" => Value::test_int(1),
+        });
+        assert_eq!(prompt(&row), Ok("foo".into()));
+        assert!(prompt(&Value::test_int(1)).is_err());
+    }
+
+    #[test]
+    fn predicate_from_condition() {
+        let row = |name: &str, size: i64| {
+            Value::test_record(record! {
+                "name" => Value::test_string(name),
+                "size" => Value::test_filesize(size),
+            })
+        };
+
+        let big = where_predicate("size > 1kb").unwrap();
+        assert_eq!(big(&row("foo", 2000)), Ok(true));
+        assert_eq!(big(&row("bar", 1000)), Ok(false));
+        assert!(big(&Value::test_int(1)).is_err());
+
+        let rust = where_predicate(r#"name ends-with ".rs""#).unwrap();
+        assert_eq!(rust(&row("lib.rs", 0)), Ok(true));
+        assert_eq!(rust(&row("lib.py", 0)), Ok(false));
+
+        // NOTE: the items themselves are compared without a cell path, the ints with the floats
+        let small = where_predicate("<= 2.5").unwrap();
+        assert_eq!(small(&Value::test_int(2)), Ok(true));
+        assert_eq!(small(&Value::test_float(3.0)), Ok(false));
+        assert!(small(&Value::test_string("2")).is_err());
+
+        // NOTE: only the ordering needs the same types
+        let some = where_predicate("!= null").unwrap();
+        assert_eq!(some(&Value::test_int(1)), Ok(true));
+        assert_eq!(some(&Value::test_nothing()), Ok(false));
+        let none = where_predicate("name == null").unwrap();
+        assert_eq!(none(&row("foo", 0)), Ok(false));
+
+        assert!(where_predicate("size ~ 1kb").is_err());
+        assert!(where_predicate("size > [1").is_err());
+    }
+
+    #[test]
+    fn final_value_under_hidden_view() {
+        let mut app = App::new(&test_value(), &Config::default());
+        app.apply_edit(
+            &CellPath {
+                members: to_path_member_vec(&[PM::S("a")]),
+            },
+            &Value::test_int(20),
+        );
+        let expected = app.value.clone();
+
+        // NOTE: a temporary view hides the data, e.g. the results of a search
+        let data = app.set_value(Value::test_list(vec![Value::test_int(20)]));
+        app.hidden_data = Some((
+            HiddenView::SearchResults,
+            data,
+            app.position.clone(),
+            vec![],
+        ));
+
+        assert_eq!(app.unhidden_value(), expected);
+        assert!(app.hidden_data.is_some());
+
+        // NOTE: the edits of the view are not edits of the data
+        let edited = app.edited.clone();
+        let original = app.original.clone();
+        app.apply_edit(
+            &CellPath {
+                members: to_path_member_vec(&[PM::I(0)]),
+            },
+            &Value::test_int(30),
+        );
+        assert_eq!(app.value, Value::test_list(vec![Value::test_int(30)]));
+        assert_eq!(app.edited, edited);
+        assert_eq!(app.original, original);
+
+        assert_eq!(app.final_value(), expected);
+        assert_eq!(app.value, expected);
+        assert!(app.hidden_data.is_none());
+    }
+
+    #[test]
+    fn merge_nested_session() {
+        let value = Value::test_record(record! {
+            "n" => Value::test_record(record! {
+                "x" => Value::test_int(1),
+                "y" => Value::test_int(2),
+            }),
+            "m" => Value::test_int(3),
+        });
+        let mut app = App::new(&value, &Config::default());
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("n")]),
+        };
+
+        // NOTE: quitting a nested session without any change does not touch the data
+        let cell = app
+            .value
+            .clone()
+            .follow_cell_path(&path.members, false)
+            .unwrap();
+        let mut nested = App::new(&cell, &Config::default());
+        app.merge_nested(&path, &mut nested);
+        assert_eq!(app.value, value);
+        assert!(app.edited.is_empty());
+
+        nested.apply_edit(
+            &CellPath {
+                members: to_path_member_vec(&[PM::S("y")]),
+            },
+            &Value::test_string("foo"),
+        );
+        app.merge_nested(&path, &mut nested);
+        assert_eq!(
+            app.value,
+            Value::test_record(record! {
+                "n" => Value::test_record(record! {
+                    "x" => Value::test_int(1),
+                    "y" => Value::test_string("foo"),
+                }),
+                "m" => Value::test_int(3),
+            })
+        );
+        assert_eq!(
+            app.edited,
+            vec![CellPath {
+                members: to_path_member_vec(&[PM::S("n"), PM::S("y")]),
+            }]
+        );
+    }
+}
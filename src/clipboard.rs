@@ -0,0 +1,36 @@
+//! access to the system clipboard
+//!
+//! the clipboard is only available when `explore` is built with the `clipboard` feature, e.g.
+//! with `cargo install --path . --features clipboard`.
+
+#[cfg(not(feature = "clipboard"))]
+const NOT_ENABLED: &str =
+    "the clipboard is not available, please build `explore` with the `clipboard` feature";
+
+/// get the text content of the system clipboard
+#[cfg(feature = "clipboard")]
+pub(crate) fn get() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|err| format!("could not read the clipboard: {err}"))
+}
+
+/// get the text content of the system clipboard
+#[cfg(not(feature = "clipboard"))]
+pub(crate) fn get() -> Result<String, String> {
+    Err(NOT_ENABLED.into())
+}
+
+/// put some text into the system clipboard
+#[cfg(feature = "clipboard")]
+pub(crate) fn set(text: String) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|err| format!("could not write to the clipboard: {err}"))
+}
+
+/// put some text into the system clipboard
+#[cfg(not(feature = "clipboard"))]
+pub(crate) fn set(_text: String) -> Result<(), String> {
+    Err(NOT_ENABLED.into())
+}
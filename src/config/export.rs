@@ -0,0 +1,496 @@
+//! utilities to export a [`Config`] back into a
+//! [`Value`](https://docs.rs/nu-protocol/0.83.1/nu_protocol/enum.Value.html)
+//!
+//! the fields of the exported records always come in the same order, the one of the default
+//! config file `examples/config/default.nuon`, so that exports can be compared and diffed.
+use crossterm::event::KeyCode;
+use ratatui::style::{Color, Modifier};
+
+use nu_protocol::{record, Span, Value};
+
+use super::{
+    repr_keycode, BgFgColorConfig, BreadcrumbStyle, ColorConfig, Config, Layout, LeafView,
+    LeftAtRoot, MissingDisplay, NewEntryDefault, PathExportStyle, ScrollbarVisibility,
+    StatusBarPosition,
+};
+use crate::app::Mode;
+
+fn string(val: &str) -> Value {
+    Value::string(val, Span::unknown())
+}
+
+/// the inverse of [`super::parsing::try_color`]
+fn color(color: &Color) -> Value {
+    let name = match color {
+        Color::Reset => "reset",
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::Gray => "gray",
+        Color::DarkGray => "darkgray",
+        Color::LightRed => "lightred",
+        Color::LightGreen => "lightgreen",
+        Color::LightYellow => "lightyellow",
+        Color::LightBlue => "lightblue",
+        Color::LightMagenta => "lightmagenta",
+        Color::LightCyan => "lightcyan",
+        Color::White => "white",
+        Color::Rgb(r, g, b) => {
+            return Value::list(
+                vec![
+                    Value::int(*r as i64, Span::unknown()),
+                    Value::int(*g as i64, Span::unknown()),
+                    Value::int(*b as i64, Span::unknown()),
+                ],
+                Span::unknown(),
+            )
+        }
+        Color::Indexed(i) => return Value::int(*i as i64, Span::unknown()),
+    };
+
+    string(name)
+}
+
+fn fg_bg_colors(colors: &BgFgColorConfig) -> Value {
+    Value::record(
+        record! {
+            "background" => color(&colors.background),
+            "foreground" => color(&colors.foreground),
+        },
+        Span::unknown(),
+    )
+}
+
+/// the inverse of [`super::parsing::try_modifier`]
+fn modifier(modifier: &Modifier) -> Value {
+    let name = if modifier.contains(Modifier::BOLD) {
+        "bold"
+    } else if modifier.contains(Modifier::ITALIC) {
+        "italic"
+    } else if modifier.contains(Modifier::UNDERLINED) {
+        "underline"
+    } else if modifier.contains(Modifier::SLOW_BLINK) {
+        "blink"
+    } else {
+        ""
+    };
+
+    string(name)
+}
+
+/// the inverse of [`super::parsing::try_key`]
+fn key(key: &KeyCode) -> Value {
+    match key {
+        KeyCode::Up => string("up"),
+        KeyCode::Down => string("down"),
+        KeyCode::Left => string("left"),
+        KeyCode::Right => string("right"),
+        KeyCode::Esc => string("escape"),
+        x => string(&repr_keycode(x)),
+    }
+}
+
+impl ColorConfig {
+    /// export the colors as a Nushell record, e.g. to tune them, with the fields of
+    /// [`Config::to_value`]
+    pub(crate) fn to_value(&self) -> Value {
+        let rec = |r| Value::record(r, Span::unknown());
+
+        rec(record! {
+            "normal" => rec(record! {
+                "name" => fg_bg_colors(&self.normal.name),
+                "data" => fg_bg_colors(&self.normal.data),
+                "shape" => fg_bg_colors(&self.normal.shape),
+            }),
+            "selected" => fg_bg_colors(&self.selected),
+            "selected_modifier" => modifier(&self.selected_modifier),
+            "selected_symbol" => string(&self.selected_symbol),
+            "zebra" => color(&self.zebra),
+            "changed" => color(&self.changed),
+            "trailing_whitespace" => color(&self.trailing_whitespace),
+            "status_bar" => rec(record! {
+                "normal" => fg_bg_colors(&self.status_bar.normal),
+                "insert" => fg_bg_colors(&self.status_bar.insert),
+                "peek" => fg_bg_colors(&self.status_bar.peek),
+                "bottom" => fg_bg_colors(&self.status_bar.bottom),
+                "search" => fg_bg_colors(&self.status_bar.search),
+            }),
+            "editor" => rec(record! {
+                "frame" => fg_bg_colors(&self.editor.frame),
+                "buffer" => fg_bg_colors(&self.editor.buffer),
+            }),
+        })
+    }
+}
+
+impl Config {
+    /// export the configuration as a Nushell record, with its fields in a fixed order
+    pub(crate) fn to_value(&self) -> Value {
+        let keys = &self.keybindings;
+        let rec = |r| Value::record(r, Span::unknown());
+
+        rec(record! {
+            "show_cell_path" => Value::bool(self.show_cell_path, Span::unknown()),
+            "show_tabs" => Value::bool(self.show_tabs, Span::unknown()),
+            "show_border" => Value::bool(self.show_border, Span::unknown()),
+            "status_bar_position" => string(match self.status_bar_position {
+                StatusBarPosition::Top => "top",
+                StatusBarPosition::Bottom => "bottom",
+            }),
+            "breadcrumb_style" => string(match self.breadcrumb_style {
+                BreadcrumbStyle::Leading => "leading",
+                BreadcrumbStyle::In => "in",
+                BreadcrumbStyle::Bare => "bare",
+            }),
+            "path_export_style" => string(match self.path_export_style {
+                PathExportStyle::JsonPath => "jsonpath",
+                PathExportStyle::JmesPath => "jmespath",
+            }),
+            "show_table_header" => Value::bool(self.show_table_header, Span::unknown()),
+            "show_column_summary" => Value::bool(self.show_column_summary, Span::unknown()),
+            "layout" => string(match self.layout {
+                Layout::Table => "table",
+                Layout::Compact => "compact",
+                Layout::Tree => "tree",
+            }),
+            "group_records" => Value::bool(self.group_records, Span::unknown()),
+            "group_by_type" => Value::bool(self.group_by_type, Span::unknown()),
+            "indent" => Value::int(self.indent as i64, Span::unknown()),
+            "initial_key" => match &self.initial_key {
+                Some(key) => string(key),
+                None => Value::nothing(Span::unknown()),
+            },
+            "initial_mode" => string(match self.initial_mode {
+                Mode::Peeking => "peeking",
+                _ => "normal",
+            }),
+            "warn_on_type_change" => Value::bool(self.warn_on_type_change, Span::unknown()),
+            "show_preview_pane" => Value::bool(self.show_preview_pane, Span::unknown()),
+            "show_sparkline" => Value::bool(self.show_sparkline, Span::unknown()),
+            "interpret_ansi" => Value::bool(self.interpret_ansi, Span::unknown()),
+            "zebra_stripes" => Value::bool(self.zebra_stripes, Span::unknown()),
+            "collapse_on_center" => Value::bool(self.collapse_on_center, Span::unknown()),
+            "repeat_debounce_ms" => Value::int(self.repeat_debounce_ms as i64, Span::unknown()),
+            "flash_ms" => Value::int(self.flash_ms as i64, Span::unknown()),
+            "prompt_timeout_ms" => Value::int(self.prompt_timeout_ms as i64, Span::unknown()),
+            "operator_pending" => Value::bool(self.operator_pending, Span::unknown()),
+            "left_at_root" => string(match self.left_at_root {
+                LeftAtRoot::Noop => "noop",
+                LeftAtRoot::Quit => "quit",
+            }),
+            "skip_empty_optional" => Value::bool(self.skip_empty_optional, Span::unknown()),
+            "missing_display" => string(match self.missing_display {
+                MissingDisplay::Null => "null",
+                MissingDisplay::Missing => "missing",
+                MissingDisplay::Reconcile => "reconcile",
+            }),
+            "leaf_view" => string(match self.leaf_view {
+                LeafView::Plain => "plain",
+                LeafView::Debug => "debug",
+                LeafView::Json => "json",
+            }),
+            "new_entry_default" => string(match self.new_entry_default {
+                NewEntryDefault::Null => "null",
+                NewEntryDefault::EmptyString => "empty-string",
+                NewEntryDefault::Zero => "zero",
+            }),
+            "max_rows" => match self.max_rows {
+                Some(max) => Value::int(max as i64, Span::unknown()),
+                None => Value::nothing(Span::unknown()),
+            },
+            "tab_width" => Value::int(self.tab_width as i64, Span::unknown()),
+            "thousands_sep" => string(&self.thousands_sep),
+            "null_placeholder" => string(&self.null_placeholder),
+            "history_file" => match &self.history_file {
+                Some(file) => string(file),
+                None => Value::nothing(Span::unknown()),
+            },
+            "preview" => rec(record! {
+                "record_keys" => Value::int(self.preview.record_keys as i64, Span::unknown()),
+                "collapse_over" => Value::int(self.preview.collapse_over as i64, Span::unknown()),
+            }),
+            "peeking" => rec(record! {
+                "quit_returns_to_normal" => Value::bool(self.peeking.quit_returns_to_normal, Span::unknown()),
+                "warn_on_lost_edits" => Value::bool(self.peeking.warn_on_lost_edits, Span::unknown()),
+            }),
+            "scrollbar" => rec(record! {
+                "visibility" => string(match self.scrollbar.visibility {
+                    ScrollbarVisibility::Always => "always",
+                    ScrollbarVisibility::Auto => "auto",
+                    ScrollbarVisibility::Never => "never",
+                }),
+                "thumb" => string(&self.scrollbar.thumb),
+                "track" => string(&self.scrollbar.track),
+            }),
+            "type_glyphs" => rec(self
+                .type_glyphs
+                .iter()
+                .map(|(shape, glyph)| (shape.clone(), string(glyph)))
+                .collect()),
+            "colors" => self.colors.to_value(),
+            "keybindings" => rec(record! {
+                "quit" => key(&keys.quit),
+                "insert" => key(&keys.insert),
+                "form" => key(&keys.form),
+                "normal" => key(&keys.normal),
+                "navigation" => rec(record! {
+                    "left" => key(&keys.navigation.left),
+                    "down" => key(&keys.navigation.down),
+                    "up" => key(&keys.navigation.up),
+                    "right" => key(&keys.navigation.right),
+                }),
+                "peek" => key(&keys.peek),
+                "peeking" => rec(record! {
+                    "all" => key(&keys.peeking.all),
+                    "cell_path" => key(&keys.peeking.cell_path),
+                    "under" => key(&keys.peeking.under),
+                    "view" => key(&keys.peeking.view),
+                    "config" => key(&keys.peeking.config),
+                    "theme" => key(&keys.peeking.theme),
+                    "cycle_format" => key(&keys.peeking.cycle_format),
+                }),
+                "transpose" => key(&keys.transpose),
+                "paste" => key(&keys.paste),
+                "mark" => key(&keys.mark),
+                "jump_to_mark" => key(&keys.jump_to_mark),
+                "type_ahead" => key(&keys.type_ahead),
+                "type_filter" => key(&keys.type_filter),
+                "pin" => key(&keys.pin),
+                "expand_all" => key(&keys.expand_all),
+                "collapse_all" => key(&keys.collapse_all),
+                "collapse_others" => key(&keys.collapse_others),
+                "collapse" => key(&keys.collapse),
+                "center" => key(&keys.center),
+                "search" => key(&keys.search),
+                "next_match" => key(&keys.next_match),
+                "previous_match" => key(&keys.previous_match),
+                "next_edit" => key(&keys.next_edit),
+                "previous_edit" => key(&keys.previous_edit),
+                "history_back" => key(&keys.history_back),
+                "history_forward" => key(&keys.history_forward),
+                "jump_to_clipboard" => key(&keys.jump_to_clipboard),
+                "label_rows" => key(&keys.label_rows),
+                "next_leaf" => key(&keys.next_leaf),
+                "next_container" => key(&keys.next_container),
+                "drill" => key(&keys.drill),
+                "snapshot" => key(&keys.snapshot),
+                "reroot" => key(&keys.reroot),
+                "pop_root" => key(&keys.pop_root),
+                "explore_nested" => key(&keys.explore_nested),
+                "wrap_in_list" => key(&keys.wrap_in_list),
+                "wrap_in_record" => key(&keys.wrap_in_record),
+                "split_string" => key(&keys.split_string),
+                "replace" => key(&keys.replace),
+                "join_list" => key(&keys.join_list),
+                "parse_string" => key(&keys.parse_string),
+                "collapse_string" => key(&keys.collapse_string),
+                "retab" => key(&keys.retab),
+                "cycle_enum" => key(&keys.cycle_enum),
+                "filter_list" => key(&keys.filter_list),
+                "record_to_values" => key(&keys.record_to_values),
+                "table_to_columns" => key(&keys.table_to_columns),
+                "summarize_list" => key(&keys.summarize_list),
+                "search_results" => key(&keys.search_results),
+                "parse_envish" => key(&keys.parse_envish),
+                "flatten" => key(&keys.flatten),
+                "schema" => key(&keys.schema),
+                "sort" => key(&keys.sort),
+                "commit_sort" => key(&keys.commit_sort),
+                "toggle_relative" => key(&keys.toggle_relative),
+                "toggle_reverse" => key(&keys.toggle_reverse),
+                "toggle_scope_lock" => key(&keys.toggle_scope_lock),
+                "jump_to_matching" => key(&keys.jump_to_matching),
+                "toggle_key_focus" => key(&keys.toggle_key_focus),
+                "fold_view" => key(&keys.fold_view),
+                "toggle_fold" => key(&keys.toggle_fold),
+                "toggle_group" => key(&keys.toggle_group),
+                "reload_config" => key(&keys.reload_config),
+                "cycle_breadcrumb" => key(&keys.cycle_breadcrumb),
+                "toggle_wrap" => key(&keys.toggle_wrap),
+                "toggle_chrome" => key(&keys.toggle_chrome),
+                "toggle_legend" => key(&keys.toggle_legend),
+                "toggle_quoted_keys" => key(&keys.toggle_quoted_keys),
+                "toggle_ancestors" => key(&keys.toggle_ancestors),
+                "toggle_minimap" => key(&keys.toggle_minimap),
+                "toggle_histogram" => key(&keys.toggle_histogram),
+                "toggle_compare" => key(&keys.toggle_compare),
+                "toggle_table_view" => key(&keys.toggle_table_view),
+                "columns_left" => key(&keys.columns_left),
+                "columns_right" => key(&keys.columns_right),
+                "scroll_preview_down" => key(&keys.scroll_preview_down),
+                "scroll_preview_up" => key(&keys.scroll_preview_up),
+                "yank_debug" => key(&keys.yank_debug),
+                "yank_container_json" => key(&keys.yank_container_json),
+                "yank_merge" => key(&keys.yank_merge),
+                "yank_markdown" => key(&keys.yank_markdown),
+                "yank_yaml" => key(&keys.yank_yaml),
+                "yank_csv" => key(&keys.yank_csv),
+                "yank_dotted" => key(&keys.yank_dotted),
+                "yank_patch" => key(&keys.yank_patch),
+                "yank_closure" => key(&keys.yank_closure),
+                "yank_update" => key(&keys.yank_update),
+                "yank_where" => key(&keys.yank_where),
+                "yank_path" => key(&keys.yank_path),
+                "yank_relative_path" => key(&keys.yank_relative_path),
+                "yank_type" => key(&keys.yank_type),
+                "duplicate_and_yank" => key(&keys.duplicate_and_yank),
+                "delete_operator" => key(&keys.delete_operator),
+                "append_field" => key(&keys.append_field),
+                "append_item" => key(&keys.append_item),
+                "truncate_list" => key(&keys.truncate_list),
+                "jump_recent" => key(&keys.jump_recent),
+                "lift" => key(&keys.lift),
+                "yank_leaves" => key(&keys.yank_leaves),
+                "yank_rust" => key(&keys.yank_rust),
+                "yank_epoch" => key(&keys.yank_epoch),
+                "yank_thousands" => key(&keys.yank_thousands),
+                "yank_stats" => key(&keys.yank_stats),
+                "yank_script" => key(&keys.yank_script),
+                "yank_with_path" => key(&keys.yank_with_path),
+                "yank_hash" => key(&keys.yank_hash),
+                "yank_summary" => key(&keys.yank_summary),
+                "show_size" => key(&keys.show_size),
+                "open_in_pager" => key(&keys.open_in_pager),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::Value;
+    use ratatui::style::Color;
+
+    use crate::config::{BgFgColorConfig, ColorConfig, Config};
+
+    fn columns(value: &Value) -> Vec<&str> {
+        value
+            .as_record()
+            .unwrap()
+            .cols
+            .iter()
+            .map(|c| c.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn export_key_order() {
+        let value = Config::default().to_value();
+
+        assert_eq!(
+            columns(&value),
+            vec![
+                "show_cell_path",
+                "show_tabs",
+                "show_border",
+                "status_bar_position",
+                "breadcrumb_style",
+                "path_export_style",
+                "show_table_header",
+                "show_column_summary",
+                "layout",
+                "group_records",
+                "group_by_type",
+                "indent",
+                "initial_key",
+                "initial_mode",
+                "warn_on_type_change",
+                "show_preview_pane",
+                "show_sparkline",
+                "interpret_ansi",
+                "zebra_stripes",
+                "collapse_on_center",
+                "repeat_debounce_ms",
+                "flash_ms",
+                "prompt_timeout_ms",
+                "operator_pending",
+                "left_at_root",
+                "skip_empty_optional",
+                "missing_display",
+                "leaf_view",
+                "new_entry_default",
+                "max_rows",
+                "tab_width",
+                "thousands_sep",
+                "null_placeholder",
+                "history_file",
+                "preview",
+                "peeking",
+                "scrollbar",
+                "type_glyphs",
+                "colors",
+                "keybindings",
+            ]
+        );
+        assert_eq!(
+            columns(value.get_data_by_key("colors").as_ref().unwrap()),
+            vec![
+                "normal",
+                "selected",
+                "selected_modifier",
+                "selected_symbol",
+                "zebra",
+                "changed",
+                "trailing_whitespace",
+                "status_bar",
+                "editor",
+            ]
+        );
+        assert_eq!(
+            columns(
+                value
+                    .get_data_by_key("keybindings")
+                    .unwrap()
+                    .get_data_by_key("navigation")
+                    .as_ref()
+                    .unwrap()
+            ),
+            vec!["left", "down", "up", "right"]
+        );
+
+        // NOTE: the order does not depend on the values of the config
+        let config = Config {
+            initial_key: Some("foo".into()),
+            ..Default::default()
+        };
+        assert_eq!(columns(&config.to_value()), columns(&value));
+    }
+
+    #[test]
+    fn export_theme() {
+        let config = Config {
+            colors: ColorConfig {
+                selected: BgFgColorConfig {
+                    background: Color::Rgb(1, 2, 3),
+                    foreground: Color::Black,
+                },
+                ..Config::default().colors
+            },
+            ..Default::default()
+        };
+        let theme = config.colors.to_value();
+
+        assert_eq!(
+            theme
+                .get_data_by_key("selected")
+                .and_then(|selected| selected.get_data_by_key("background")),
+            Some(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2),
+                Value::test_int(3),
+            ]))
+        );
+        // NOTE: the theme is the same as the colors of the full config
+        assert_eq!(config.to_value().get_data_by_key("colors"), Some(theme));
+    }
+
+    #[test]
+    fn export_and_parse_back() {
+        let config = Config::default();
+        assert_eq!(Config::from_value(config.to_value()), Ok(config));
+    }
+}
@@ -4,16 +4,24 @@
 //! 1. holds the data structure of the [`Config`]
 //! 1. gives default values to a [`Config`] with [`Config::default`]
 //! 1. parses a Nushell [`Value`](https://docs.rs/nu-protocol/0.83.1/nu_protocol/enum.Value.html) into a valid [`Config`]
+use std::path::Path;
+
 use crossterm::event::KeyCode;
 use ratatui::style::{Color, Modifier};
 
 use nu_plugin::LabeledError;
-use nu_protocol::Value;
+use nu_protocol::{Span, Value};
+
+use crate::app::Mode;
+use crate::file::load_file;
 
+mod export;
 mod parsing;
 use parsing::{
-    follow_cell_path, invalid_field, invalid_type, try_bool, try_fg_bg_colors, try_key, try_layout,
-    try_modifier, try_string,
+    follow_cell_path, invalid_field, invalid_type, try_bool, try_breadcrumb_style, try_color,
+    try_fg_bg_colors, try_key, try_layout, try_leaf_view, try_left_at_root, try_missing_display,
+    try_mode, try_modifier, try_new_entry_default, try_path_export_style, try_scrollbar_visibility,
+    try_status_bar_position, try_string, try_usize,
 };
 
 /// the configuration for the status bar colors in all [`crate::app::Mode`]s
@@ -23,6 +31,7 @@ pub struct StatusBarColorConfig {
     pub insert: BgFgColorConfig,
     pub peek: BgFgColorConfig,
     pub bottom: BgFgColorConfig,
+    pub search: BgFgColorConfig,
 }
 
 /// the configuration for a row of the data rendering table
@@ -54,6 +63,13 @@ pub struct ColorConfig {
     pub selected_modifier: Modifier,
     /// the symbol to show to the left of the selected row under the cursor
     pub selected_symbol: String,
+    /// the background of every other row of the tables, when [`Config::zebra_stripes`] is set
+    pub zebra: Color,
+    /// the foreground of the rows that differ from the snapshot, see [`crate::app::App::snapshot`]
+    pub changed: Color,
+    /// the background of the spaces and tabs at the end of the strings, at the bottom of the data
+    /// and in the editor
+    pub trailing_whitespace: Color,
     pub status_bar: StatusBarColorConfig,
     /// the color when editing a cell
     pub editor: EditorColorConfig,
@@ -66,7 +82,8 @@ pub struct BgFgColorConfig {
     pub foreground: Color,
 }
 
-/// the bindings in NORMAL mode (see [crate::app::Mode::Normal])
+/// the bindings in NORMAL and PEEKING modes (see [crate::app::Mode::Normal] and
+/// [crate::app::Mode::Peeking])
 #[derive(Clone, PartialEq, Debug)]
 pub struct NavigationBindingsMap {
     /// go one row up in the data
@@ -90,6 +107,13 @@ pub struct PeekingBindingsMap {
     pub under: KeyCode,
     /// peek the current view
     pub view: KeyCode,
+    /// peek the configuration of `explore`
+    pub config: KeyCode,
+    /// peek the colors of `explore`, see [`ColorConfig`]
+    pub theme: KeyCode,
+    /// go to the next format of the peeked values, see
+    /// [`App::peek_format`](crate::app::App::peek_format)
+    pub cycle_format: KeyCode,
 }
 
 /// the keybindings mapping
@@ -98,6 +122,8 @@ pub struct KeyBindingsMap {
     pub quit: KeyCode,
     /// go into INSERT mode (see [crate::app::Mode::Insert])
     pub insert: KeyCode,
+    /// go into FORM mode to edit the record around the cursor (see [crate::app::Mode::Form])
+    pub form: KeyCode,
     /// go back into NORMAL mode (see [crate::app::Mode::Normal])
     pub normal: KeyCode,
     pub navigation: NavigationBindingsMap,
@@ -105,6 +131,278 @@ pub struct KeyBindingsMap {
     pub peek: KeyCode,
     pub peeking: PeekingBindingsMap,
     pub transpose: KeyCode,
+    /// paste the content of the clipboard into the cell under the cursor
+    pub paste: KeyCode,
+    /// mark the current cell with the next letter
+    pub mark: KeyCode,
+    /// jump to the cell marked with the next letter
+    pub jump_to_mark: KeyCode,
+    /// jump to the next sibling field whose key starts with the next key
+    pub type_ahead: KeyCode,
+    /// only stop on the siblings of the type given by the next letter when going up or down, e.g.
+    /// `s` for strings, or stop on all of them again with any other letter
+    pub type_filter: KeyCode,
+    /// expand all the nodes of the tree view, up to a maximum depth
+    pub expand_all: KeyCode,
+    /// collapse all the nodes of the tree view
+    pub collapse_all: KeyCode,
+    /// collapse all the nodes of the tree view that are not on the path to the cursor, with `Alt`
+    /// held
+    pub collapse_others: KeyCode,
+    /// collapse the node under the cursor in the tree view or, if it's not expanded, go to its
+    /// parent
+    pub collapse: KeyCode,
+    /// go to SEARCH mode to search the data
+    pub search: KeyCode,
+    /// jump to the next match of the search
+    pub next_match: KeyCode,
+    /// jump to the previous match of the search
+    pub previous_match: KeyCode,
+    /// jump to the next cell that has been edited, see [`crate::app::App::edited`]
+    pub next_edit: KeyCode,
+    /// jump to the previous cell that has been edited
+    pub previous_edit: KeyCode,
+    /// go back to the previous position in the history of the navigation, with `Alt` held
+    pub history_back: KeyCode,
+    /// go forward to the next position in the history of the navigation, with `Alt` held
+    pub history_forward: KeyCode,
+    /// jump to the cell path in the clipboard, written in Nushell, JSONPath or JMESPath, with `Alt`
+    /// held
+    pub jump_to_clipboard: KeyCode,
+    /// label the visible rows with numbers to jump to one of them by typing its number, with
+    /// `Alt` held, see [`App::row_labels`](crate::app::App::row_labels)
+    pub label_rows: KeyCode,
+    /// explore the matches of the search as a list, or go back to the data
+    pub search_results: KeyCode,
+    /// explore the string under the cursor split as environment variables or a `PATH`, or go
+    /// back to the data
+    pub parse_envish: KeyCode,
+    /// explore the record of tables under the cursor flattened into a single table, or go back to
+    /// the data
+    pub flatten: KeyCode,
+    /// explore the schema of the table or record under the cursor, i.e. the names of the types of
+    /// its values, or go back to the data
+    pub schema: KeyCode,
+    /// explore the list around the cursor sorted in the order given by the next key, or go back to
+    /// the data
+    pub sort: KeyCode,
+    /// sort the list or record around the cursor in the data, by the keys or the values given by
+    /// the next key, with `Alt` held, see [`sort_container`](crate::nu::value::sort_container)
+    pub commit_sort: KeyCode,
+    /// copy the value under the cursor to the clipboard, as a Rust debug string
+    pub yank_debug: KeyCode,
+    /// copy the list or record that contains the cell under the cursor to the clipboard, as JSON
+    pub yank_container_json: KeyCode,
+    /// copy the cell under the cursor to the clipboard, as a record to `merge` into the data
+    pub yank_merge: KeyCode,
+    /// copy the list or record that contains the cell under the cursor to the clipboard, as a
+    /// Markdown table
+    pub yank_markdown: KeyCode,
+    /// copy the value under the cursor to the clipboard, as YAML
+    pub yank_yaml: KeyCode,
+    /// copy the table or the record under the cursor to the clipboard as CSV, with `Alt` held, see
+    /// [`to_csv`](crate::nu::value::to_csv)
+    pub yank_csv: KeyCode,
+    /// copy the list or the record under the cursor to the clipboard as a record of its leaves
+    /// with dotted keys, as JSON, with `Alt` held, see
+    /// [`flatten_to_dotted`](crate::nu::value::flatten_to_dotted)
+    pub yank_dotted: KeyCode,
+    /// copy the edits of the whole session to the clipboard, as a unified diff of the JSON of the
+    /// data, with `Alt` held, see [`to_patch`](crate::nu::value::to_patch)
+    pub yank_patch: KeyCode,
+    /// copy the path of the cell under the cursor to the clipboard, as a closure getting it
+    pub yank_closure: KeyCode,
+    /// copy the value under the cursor to the clipboard, as a Nushell pipeline updating its cell to
+    /// it, with `Alt` held, see [`to_update_pipeline`](crate::nu::value::to_update_pipeline)
+    pub yank_update: KeyCode,
+    /// copy a `where` clause keeping the rows of the table around the cursor with the same value in
+    /// the column of the cell under the cursor, with `Alt` held, see
+    /// [`to_where_clause`](crate::nu::value::to_where_clause)
+    pub yank_where: KeyCode,
+    /// copy the path of the cell under the cursor to the clipboard, for tools outside of Nushell,
+    /// see [`Config::path_export_style`]
+    pub yank_path: KeyCode,
+    /// copy the path of the cell under the cursor relative to the mark given by the next letter,
+    /// see [`KeyBindingsMap::mark`]
+    pub yank_relative_path: KeyCode,
+    /// copy the name of the type of the cell under the cursor to the clipboard, e.g. `list<string>`
+    pub yank_type: KeyCode,
+    /// duplicate the cell under the cursor next to itself and copy it to the clipboard, as JSON
+    ///
+    /// NOTE: this is unreachable when [`Config::operator_pending`] is on and
+    /// [`KeyBindingsMap::delete_operator`] has the same key, as by default
+    pub duplicate_and_yank: KeyCode,
+    /// delete the items of the list around the cursor up to the next motion, e.g. `d` then `2` and
+    /// `down` for the item under the cursor and the two below it, when
+    /// [`Config::operator_pending`] is on, see [`Operator`](crate::app::Operator)
+    pub delete_operator: KeyCode,
+    /// append a field to the record around the cursor, see [`Config::new_entry_default`]
+    pub append_field: KeyCode,
+    /// append an item to the list around the cursor, see [`Config::new_entry_default`]
+    pub append_item: KeyCode,
+    /// remove the last item of the list around the cursor
+    pub truncate_list: KeyCode,
+    /// jump to the next cell path visited in the previous sessions, see [`Config::history_file`]
+    pub jump_recent: KeyCode,
+    /// replace the list or record around the cursor with the value under the cursor
+    pub lift: KeyCode,
+    /// copy all the leaves under the cursor to the clipboard, as a flat JSON list
+    pub yank_leaves: KeyCode,
+    /// copy the value under the cursor to the clipboard, as the Rust code building it in tests
+    pub yank_rust: KeyCode,
+    /// copy the date under the cursor to the clipboard, as the number of seconds since the Unix
+    /// epoch
+    pub yank_epoch: KeyCode,
+    /// copy the number under the cursor to the clipboard, with its digits grouped by three, see
+    /// [`Config::thousands_sep`]
+    pub yank_thousands: KeyCode,
+    /// copy the statistics of the list of numbers or the column of a table under the cursor to the
+    /// clipboard, as JSON
+    pub yank_stats: KeyCode,
+    /// copy a Nushell script reproducing the edits of the session to the clipboard, see
+    /// [`App::edited`](crate::app::App::edited)
+    pub yank_script: KeyCode,
+    /// copy the value under the cursor to the clipboard as a NUON record together with its cell
+    /// path, e.g. `{path: $.r.a, value: 1}`
+    pub yank_with_path: KeyCode,
+    /// copy the SHA-256 digest of the value under the cursor to the clipboard, as hex, see
+    /// [`hash_value`](crate::nu::value::hash_value)
+    pub yank_hash: KeyCode,
+    /// copy a short summary of the value under the cursor to the clipboard, e.g. `list of 3
+    /// strings`, with `Alt` held, see [`describe_container`](crate::nu::value::describe_container)
+    pub yank_summary: KeyCode,
+    /// show the approximate size in bytes of the value under the cursor, with `Alt` held, see
+    /// [`approx_size`](crate::nu::value::approx_size)
+    pub show_size: KeyCode,
+    /// show the value under the cursor in the pager of the system, e.g. `less`, with `Alt` held,
+    /// see [`crate::pager`]
+    pub open_in_pager: KeyCode,
+    /// toggle between absolute and relative indices in lists
+    pub toggle_relative: KeyCode,
+    /// toggle the reverse order of the lists and records in the view, see [`App::reversed`]
+    pub toggle_reverse: KeyCode,
+    /// lock the navigation inside the current container, or unlock it, with `Alt` held, see
+    /// [`App::scope_lock`](crate::app::App::scope_lock)
+    pub toggle_scope_lock: KeyCode,
+    /// jump between the first and the last rows of the current container, like `%` in Vim, with
+    /// `Alt` held
+    pub jump_to_matching: KeyCode,
+    /// move the focus from the values of the fields of a record to their keys, or back, with `Alt`
+    /// held, see [`App::key_focus`](crate::app::App::key_focus)
+    pub toggle_key_focus: KeyCode,
+    /// show the list or the record under the cursor as pretty JSON whose nested lists and records
+    /// can be folded, with `Alt` held, see [`KeyBindingsMap::toggle_fold`]
+    pub fold_view: KeyCode,
+    /// fold the list or the record on the line under the cursor of the view of
+    /// [`KeyBindingsMap::fold_view`], or unfold it
+    pub toggle_fold: KeyCode,
+    /// collapse the group of the field under the cursor, or expand it back, with `Alt` held, see
+    /// [`Config::group_records`]
+    pub toggle_group: KeyCode,
+    /// read the config file given with `--config` again and apply it, with `Alt` held, see
+    /// [`Config::load_from_disk`]
+    pub reload_config: KeyCode,
+    /// cycle through the styles of the cell path, see [`Config::breadcrumb_style`]
+    pub cycle_breadcrumb: KeyCode,
+    /// wrap the value under the cursor over multiple lines instead of truncating it, or not
+    pub toggle_wrap: KeyCode,
+    /// hide the status bar and the cell path, or show them back
+    pub toggle_chrome: KeyCode,
+    /// toggle the legend of the colors
+    pub toggle_legend: KeyCode,
+    /// show the keys of the records that need quotes in a cell path with their quotes, or without
+    pub toggle_quoted_keys: KeyCode,
+    /// toggle a panel with the containers above the cursor, see [`crate::app::App::show_ancestors`]
+    pub toggle_ancestors: KeyCode,
+    /// toggle a minimap of the whole data, see [`crate::app::App::show_minimap`]
+    pub toggle_minimap: KeyCode,
+    /// show how many times each value appears in the column of the table under the cursor, or hide it
+    pub toggle_histogram: KeyCode,
+    /// show the original data to the left of the edited one, or hide it
+    pub toggle_compare: KeyCode,
+    /// switch between a table and a row per record for the lists of records, see
+    /// [`crate::app::App::table_view`]
+    pub toggle_table_view: KeyCode,
+    /// show the previous columns of a table that has more columns than fit in the table view
+    pub columns_left: KeyCode,
+    /// show the next columns of a table that has more columns than fit in the table view
+    pub columns_right: KeyCode,
+    /// scroll the preview pane down, without moving the selection
+    pub scroll_preview_down: KeyCode,
+    /// scroll the preview pane up, without moving the selection
+    pub scroll_preview_up: KeyCode,
+    /// scroll the tree view so that the node under the cursor is in the middle
+    pub center: KeyCode,
+    /// jump to the next scalar leaf of the data, skipping lists and records
+    pub next_leaf: KeyCode,
+    /// jump to the same position in the next sibling of the parent, see
+    /// [`crate::navigation::sibling_in_next_container`]
+    pub next_container: KeyCode,
+    /// pin the field under the cursor to the top of all the records, or unpin it
+    pub pin: KeyCode,
+    /// go down the lists and records as long as they have a single child
+    pub drill: KeyCode,
+    /// take a snapshot of the data to highlight what changes from now on, or drop it
+    pub snapshot: KeyCode,
+    /// make the list or record under the cursor the root of the view
+    pub reroot: KeyCode,
+    /// go back to the previous root of the view
+    pub pop_root: KeyCode,
+    /// explore the list or record under the cursor in a nested session, whose changes are merged
+    /// back into the data when quitting it
+    pub explore_nested: KeyCode,
+    /// replace the value under the cursor with a list containing it
+    pub wrap_in_list: KeyCode,
+    /// replace the value under the cursor with a record containing it
+    pub wrap_in_record: KeyCode,
+    /// replace the string under the cursor with the list of its parts separated by the next key
+    pub split_string: KeyCode,
+    /// replace the list of strings under the cursor with their join, separated by the next key
+    pub join_list: KeyCode,
+    /// replace a text in all the strings of the list or record around the cursor, both typed in
+    /// REPLACE mode
+    pub replace: KeyCode,
+    /// replace the JSON or NUON string under the cursor with the value it describes
+    pub parse_string: KeyCode,
+    /// replace the string under the cursor with its single-line form, without the newlines
+    pub collapse_string: KeyCode,
+    /// convert the indentation of the string under the cursor from tabs to spaces, or back, see
+    /// [`Config::tab_width`]
+    pub retab: KeyCode,
+    /// replace the string under the cursor with the next of its allowed values in the schema
+    pub cycle_enum: KeyCode,
+    /// replace the list under the cursor with its items matching the condition of the `--filter`
+    /// flag, see [`crate::app::where_predicate`]
+    pub filter_list: KeyCode,
+    /// replace the record under the cursor with the list of its values, without the keys
+    pub record_to_values: KeyCode,
+    /// replace the table under the cursor with a record of its columns, each being the list of
+    /// its values, with `Alt` held, see
+    /// [`records_to_columns`](crate::nu::value::records_to_columns)
+    pub table_to_columns: KeyCode,
+    /// replace the list under the cursor with a record of its length and its first and last
+    /// items, with `Alt` held, see [`summarize_list`](crate::nu::value::summarize_list)
+    pub summarize_list: KeyCode,
+}
+
+/// the configuration of the previews of the values
+#[derive(Clone, PartialEq, Debug)]
+pub struct PreviewConfig {
+    /// the number of keys to list in the preview of a record
+    pub record_keys: usize,
+    /// the number of items or fields above which lists and records are collapsed in the preview
+    /// pane, to keep it fast and readable
+    pub collapse_over: usize,
+}
+
+/// the configuration of the PEEKING mode
+#[derive(Clone, PartialEq, Debug)]
+pub struct PeekingConfig {
+    /// go back to NORMAL mode when quitting from PEEKING mode, instead of quitting `explore`
+    pub quit_returns_to_normal: bool,
+    /// warn before peeking at a value that does not contain all the edits of the session, the same
+    /// key having to be pressed again to peek anyway, see [`App::edited`](crate::app::App::edited)
+    pub warn_on_lost_edits: bool,
 }
 
 /// the layout of the application
@@ -114,6 +412,124 @@ pub enum Layout {
     Table,
     /// show each row in compact form, to the left, `"{name}: ({type}) {data}"`
     Compact,
+    /// show the whole data as a tree, where lists and records can be expanded and collapsed
+    Tree,
+}
+
+/// what to do when going left while at the root of the data
+#[derive(Clone, PartialEq, Debug)]
+pub enum LeftAtRoot {
+    /// do nothing
+    Noop,
+    /// quit the application, like going back past the root in a file manager
+    Quit,
+}
+
+/// how to write the cell path above the status bar, e.g. for `r.a`
+#[derive(Clone, PartialEq, Debug)]
+pub enum BreadcrumbStyle {
+    /// `$.r.a`
+    Leading,
+    /// `$in.r.a`
+    In,
+    /// `r.a`
+    Bare,
+}
+
+impl Default for BreadcrumbStyle {
+    fn default() -> Self {
+        Self::Leading
+    }
+}
+
+impl BreadcrumbStyle {
+    /// give the style that comes after this one, to cycle through them all
+    pub(crate) fn next(&self) -> Self {
+        match self {
+            Self::Leading => Self::In,
+            Self::In => Self::Bare,
+            Self::Bare => Self::Leading,
+        }
+    }
+}
+
+/// the language of the paths copied with [`KeyBindingsMap::yank_path`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum PathExportStyle {
+    /// e.g. `$.l[1].a`
+    JsonPath,
+    /// e.g. `l[1].a`
+    JmesPath,
+}
+
+/// how to render the value under the cursor when hitting the bottom of the data
+#[derive(Clone, PartialEq, Debug)]
+pub enum LeafView {
+    /// the value and its shape, e.g. `1 is of shape int`
+    Plain,
+    /// the pretty-printed Rust debug string of the value
+    Debug,
+    /// the value as JSON on a single line
+    Json,
+}
+
+/// where the status bar is drawn, see [`Config::status_bar_position`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum StatusBarPosition {
+    /// on the first line, above the tabs and the data
+    Top,
+    /// on the last line, below the data and the cell path
+    Bottom,
+}
+
+/// what to do with the missing cell under the cursor, i.e. inside an empty list or record
+#[derive(Clone, PartialEq, Debug)]
+pub enum MissingDisplay {
+    /// the cell is `null`, e.g. when peeking at it
+    Null,
+    /// the cell is shown as `(missing)` and can't be peeked at
+    Missing,
+    /// the cursor stays on the empty list or record instead of going inside
+    Reconcile,
+}
+
+/// the value of the fields appended to records with [`KeyBindingsMap::append_field`] and of the
+/// items appended to lists with [`KeyBindingsMap::append_item`]
+#[derive(Clone, PartialEq, Debug)]
+pub enum NewEntryDefault {
+    Null,
+    EmptyString,
+    Zero,
+}
+
+impl NewEntryDefault {
+    /// give the placeholder value of a new field
+    pub(crate) fn placeholder(&self) -> Value {
+        match self {
+            Self::Null => Value::nothing(Span::unknown()),
+            Self::EmptyString => Value::string("", Span::unknown()),
+            Self::Zero => Value::int(0, Span::unknown()),
+        }
+    }
+}
+
+/// when to show the scrollbar of the data
+#[derive(Clone, PartialEq, Debug)]
+pub enum ScrollbarVisibility {
+    Always,
+    /// only when some rows do not fit in the terminal
+    Auto,
+    Never,
+}
+
+/// the configuration of the scrollbar of the data
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScrollbarConfig {
+    pub visibility: ScrollbarVisibility,
+    /// the symbol of the part of the scrollbar showing the visible rows
+    pub thumb: String,
+    /// the symbol of the rest of the scrollbar
+    pub track: String,
 }
 
 /// the configuration of the whole application
@@ -122,8 +538,92 @@ pub struct Config {
     pub colors: ColorConfig,
     pub keybindings: KeyBindingsMap,
     pub show_cell_path: bool,
+    /// show a tab for each container above the cursor at the top, see
+    /// [`crate::app::App::ancestor_tabs`]
+    pub show_tabs: bool,
+    /// draw a border around the data pane, with the path of the container of the cursor as its
+    /// title, see [`crate::app::App::border_width`]
+    pub show_border: bool,
+    /// where to put the status bar, the data pane moving down when it's at the top
+    pub status_bar_position: StatusBarPosition,
+    /// the style of the cell path, which can be changed with [`KeyBindingsMap::cycle_breadcrumb`]
+    pub breadcrumb_style: BreadcrumbStyle,
+    /// the language of the paths copied for tools outside of Nushell
+    pub path_export_style: PathExportStyle,
     pub layout: Layout,
     pub show_table_header: bool,
+    /// summarize the columns of tables and their types on the top border, e.g. `name:string`
+    pub show_column_summary: bool,
+    /// group the fields of records by the first letter of their name
+    pub group_records: bool,
+    /// group the fields of records in sections by the type of their value, the scalars first and
+    /// then the lists and records, which takes precedence over [`Config::group_records`]
+    pub group_by_type: bool,
+    /// the field to focus when starting to explore a record
+    pub initial_key: Option<String>,
+    /// the mode in which to start exploring the data
+    pub initial_mode: Mode,
+    /// how the lists and records are previewed, e.g. `{a, b, …}`
+    pub preview: PreviewConfig,
+    /// the behavior of the PEEKING mode, see [`crate::app::Mode::Peeking`]
+    pub peeking: PeekingConfig,
+    /// what to do when going left at the root of the data, see [`LeftAtRoot`]
+    pub left_at_root: LeftAtRoot,
+    /// stay on the empty lists and records when going deeper into them, instead of stopping inside
+    pub skip_empty_optional: bool,
+    /// what to do with the missing cell inside an empty list or record
+    pub missing_display: MissingDisplay,
+    /// how to render the value under the cursor in BOTTOM mode
+    pub leaf_view: LeafView,
+    /// the value of the fields appended to records
+    pub new_entry_default: NewEntryDefault,
+    /// the maximum number of rows of the lists and records in the "table" layout, the others being
+    /// summarized as `+N more`, whatever the height of the terminal
+    pub max_rows: Option<usize>,
+    /// the number of spaces of a tab when converting the indentation of strings
+    pub tab_width: usize,
+    /// the separator between the groups of three digits when copying numbers for readability
+    pub thousands_sep: String,
+    /// the placeholder shown in the editor of a `null` cell until the first key, nothing being
+    /// shown when empty
+    pub null_placeholder: String,
+    /// the file in which to remember the cell paths visited in the last sessions, nothing being
+    /// written when not set, a leading `~` being the home directory
+    pub history_file: Option<String>,
+    /// warn the user when an edit changes the type of a cell
+    pub warn_on_type_change: bool,
+    /// show the value under the cursor in full in a pane to the right of the data
+    pub show_preview_pane: bool,
+    /// alternate the background of the rows of the tables, see [`ColorConfig::zebra`]
+    pub zebra_stripes: bool,
+    /// collapse the siblings of the node under the cursor when centering the tree view on it
+    pub collapse_on_center: bool,
+    /// the number of spaces per level of nesting, in the tree view and the preview pane
+    pub indent: usize,
+    /// ignore the repetitions of a key that come faster than this while moving around the data, in
+    /// milliseconds, `0` to never ignore any key
+    pub repeat_debounce_ms: usize,
+    /// highlight the cell landed on after a jump, e.g. to a match or a mark, for this many
+    /// milliseconds, 0 to disable
+    pub flash_ms: usize,
+    /// cancel the prompts waiting for their next key, e.g. for the letter of a mark or to confirm
+    /// losing some edits, after this many milliseconds without a key press, 0 to wait forever
+    pub prompt_timeout_ms: usize,
+    /// start a Vim-like operator with [`KeyBindingsMap::delete_operator`], applied to the range of
+    /// items given by the next motion, before the other bindings of the same key
+    ///
+    /// NOTE: the other bindings of the same key can no longer be reached, e.g.
+    /// [`KeyBindingsMap::duplicate_and_yank`] by default, and need a key of their own
+    pub operator_pending: bool,
+    /// summarize the lists of numbers with a sparkline above their rows
+    pub show_sparkline: bool,
+    /// show the strings with ANSI escape sequences, e.g. the captured output of a command, with
+    /// their colors instead of the raw sequences, off by default
+    pub interpret_ansi: bool,
+    pub scrollbar: ScrollbarConfig,
+    /// the glyphs prefixing the values in the [`Layout::Table`] layout, by name of their shape,
+    /// e.g. `#` for `int`, to see the structure of the data without colors
+    pub type_glyphs: Vec<(String, String)>,
 }
 
 impl Default for Config {
@@ -132,8 +632,53 @@ impl Default for Config {
         // black, "black" is not really black which is ugly, whereas "reset" is really black.
         Self {
             show_cell_path: true,
+            show_tabs: false,
+            show_border: false,
+            status_bar_position: StatusBarPosition::Bottom,
+            breadcrumb_style: BreadcrumbStyle::Leading,
+            path_export_style: PathExportStyle::JsonPath,
             show_table_header: true,
+            show_column_summary: true,
             layout: Layout::Table,
+            group_records: false,
+            group_by_type: false,
+            initial_key: None,
+            initial_mode: Mode::Normal,
+            preview: PreviewConfig {
+                record_keys: 3,
+                collapse_over: 100,
+            },
+            peeking: PeekingConfig {
+                quit_returns_to_normal: false,
+                warn_on_lost_edits: true,
+            },
+            left_at_root: LeftAtRoot::Noop,
+            skip_empty_optional: false,
+            missing_display: MissingDisplay::Null,
+            leaf_view: LeafView::Plain,
+            new_entry_default: NewEntryDefault::Null,
+            max_rows: None,
+            tab_width: 4,
+            thousands_sep: ",".into(),
+            null_placeholder: "(null)".into(),
+            history_file: None,
+            warn_on_type_change: true,
+            show_preview_pane: false,
+            zebra_stripes: false,
+            collapse_on_center: false,
+            indent: 2,
+            repeat_debounce_ms: 0,
+            flash_ms: 300,
+            prompt_timeout_ms: 0,
+            operator_pending: false,
+            show_sparkline: true,
+            interpret_ansi: false,
+            scrollbar: ScrollbarConfig {
+                visibility: ScrollbarVisibility::Auto,
+                thumb: "█".into(),
+                track: "│".into(),
+            },
+            type_glyphs: vec![],
             colors: ColorConfig {
                 normal: TableRowColorConfig {
                     name: BgFgColorConfig {
@@ -155,6 +700,9 @@ impl Default for Config {
                 },
                 selected_modifier: Modifier::BOLD,
                 selected_symbol: "".into(),
+                zebra: Color::DarkGray,
+                changed: Color::Yellow,
+                trailing_whitespace: Color::Red,
                 status_bar: StatusBarColorConfig {
                     normal: BgFgColorConfig {
                         background: Color::Black,
@@ -172,6 +720,10 @@ impl Default for Config {
                         background: Color::Black,
                         foreground: Color::LightMagenta,
                     },
+                    search: BgFgColorConfig {
+                        background: Color::Black,
+                        foreground: Color::LightBlue,
+                    },
                 },
                 editor: EditorColorConfig {
                     frame: BgFgColorConfig {
@@ -187,6 +739,7 @@ impl Default for Config {
             keybindings: KeyBindingsMap {
                 quit: KeyCode::Char('q'),
                 insert: KeyCode::Char('i'),
+                form: KeyCode::Char('('),
                 normal: KeyCode::Esc,
                 navigation: NavigationBindingsMap {
                     left: KeyCode::Char('h'),
@@ -200,14 +753,130 @@ impl Default for Config {
                     cell_path: KeyCode::Char('c'),
                     under: KeyCode::Char('p'),
                     view: KeyCode::Char('v'),
+                    config: KeyCode::Char('o'),
+                    theme: KeyCode::Char('t'),
+                    cycle_format: KeyCode::Char('f'),
                 },
                 transpose: KeyCode::Char('t'),
+                paste: KeyCode::Char('P'),
+                mark: KeyCode::Char('m'),
+                jump_to_mark: KeyCode::Char('\''),
+                type_ahead: KeyCode::Char('a'),
+                type_filter: KeyCode::Char('f'),
+                expand_all: KeyCode::Char('E'),
+                collapse_all: KeyCode::Char('C'),
+                collapse_others: KeyCode::Char('C'),
+                collapse: KeyCode::Char('c'),
+                search: KeyCode::Char('/'),
+                next_match: KeyCode::Char('n'),
+                previous_match: KeyCode::Char('N'),
+                next_edit: KeyCode::Char('>'),
+                previous_edit: KeyCode::Char('<'),
+                history_back: KeyCode::Left,
+                history_forward: KeyCode::Right,
+                jump_to_clipboard: KeyCode::Char('P'),
+                label_rows: KeyCode::Char('G'),
+                search_results: KeyCode::Char('S'),
+                parse_envish: KeyCode::Char('V'),
+                flatten: KeyCode::Char('F'),
+                schema: KeyCode::Char(']'),
+                sort: KeyCode::Char('o'),
+                commit_sort: KeyCode::Char('o'),
+                yank_debug: KeyCode::Char('y'),
+                yank_container_json: KeyCode::Char('Y'),
+                yank_merge: KeyCode::Char('M'),
+                yank_markdown: KeyCode::Char('T'),
+                yank_yaml: KeyCode::Char(':'),
+                yank_csv: KeyCode::Char(','),
+                yank_dotted: KeyCode::Char('.'),
+                yank_patch: KeyCode::Char('D'),
+                yank_closure: KeyCode::Char('G'),
+                yank_update: KeyCode::Char('U'),
+                yank_where: KeyCode::Char('W'),
+                yank_path: KeyCode::Char('X'),
+                yank_relative_path: KeyCode::Char('x'),
+                yank_type: KeyCode::Char('I'),
+                duplicate_and_yank: KeyCode::Char('d'),
+                delete_operator: KeyCode::Char('d'),
+                append_field: KeyCode::Char('A'),
+                append_item: KeyCode::Char('='),
+                truncate_list: KeyCode::Char('~'),
+                jump_recent: KeyCode::Char('`'),
+                lift: KeyCode::Char(';'),
+                yank_leaves: KeyCode::Char('L'),
+                yank_rust: KeyCode::Char('U'),
+                yank_epoch: KeyCode::Char('@'),
+                yank_thousands: KeyCode::Char('$'),
+                yank_stats: KeyCode::Char('Z'),
+                yank_script: KeyCode::Char('u'),
+                yank_with_path: KeyCode::Char('Q'),
+                yank_hash: KeyCode::Char('.'),
+                yank_summary: KeyCode::Char('Z'),
+                show_size: KeyCode::Char('S'),
+                open_in_pager: KeyCode::Char('O'),
+                toggle_relative: KeyCode::Char('#'),
+                toggle_reverse: KeyCode::Char('v'),
+                toggle_scope_lock: KeyCode::Char('L'),
+                jump_to_matching: KeyCode::Char('%'),
+                toggle_key_focus: KeyCode::Char('K'),
+                fold_view: KeyCode::Char('J'),
+                toggle_fold: KeyCode::Char('z'),
+                toggle_group: KeyCode::Char('g'),
+                reload_config: KeyCode::Char('R'),
+                cycle_breadcrumb: KeyCode::Char('b'),
+                toggle_wrap: KeyCode::Char('W'),
+                toggle_chrome: KeyCode::Char('H'),
+                toggle_legend: KeyCode::Char('?'),
+                toggle_quoted_keys: KeyCode::Char('\\'),
+                toggle_ancestors: KeyCode::Char('B'),
+                toggle_minimap: KeyCode::Char(')'),
+                toggle_histogram: KeyCode::Char('g'),
+                toggle_compare: KeyCode::Char('|'),
+                toggle_table_view: KeyCode::Char('&'),
+                columns_left: KeyCode::Left,
+                columns_right: KeyCode::Right,
+                scroll_preview_down: KeyCode::Char('J'),
+                scroll_preview_up: KeyCode::Char('K'),
+                center: KeyCode::Char('z'),
+                next_leaf: KeyCode::Char('w'),
+                next_container: KeyCode::Char('}'),
+                pin: KeyCode::Char('*'),
+                drill: KeyCode::Char('D'),
+                snapshot: KeyCode::Char('s'),
+                reroot: KeyCode::Char('r'),
+                pop_root: KeyCode::Char('R'),
+                explore_nested: KeyCode::Char('e'),
+                wrap_in_list: KeyCode::Char('['),
+                wrap_in_record: KeyCode::Char('{'),
+                split_string: KeyCode::Char(','),
+                replace: KeyCode::Char('"'),
+                join_list: KeyCode::Char('+'),
+                parse_string: KeyCode::Char('O'),
+                collapse_string: KeyCode::Char('_'),
+                retab: KeyCode::Char('^'),
+                cycle_enum: KeyCode::Char('!'),
+                filter_list: KeyCode::Char('%'),
+                record_to_values: KeyCode::Char('-'),
+                table_to_columns: KeyCode::Char('T'),
+                summarize_list: KeyCode::Char('M'),
             },
         }
     }
 }
 
 impl Config {
+    /// load the config from a JSON, TOML, YAML or NUON file, with the same fields as the config
+    /// of the plugin, see [`crate::load_file`]
+    pub fn load_from_disk(path: &Path) -> Result<Self, LabeledError> {
+        let value = load_file(path).map_err(|err| LabeledError {
+            label: "could not load the config".into(),
+            msg: err.to_string(),
+            span: None,
+        })?;
+
+        Self::from_value(value)
+    }
+
     pub fn from_value(value: Value) -> Result<Self, LabeledError> {
         let mut config = Config::default();
 
@@ -218,16 +887,269 @@ impl Config {
                         config.show_cell_path = val
                     }
                 }
+                "show_tabs" => {
+                    if let Some(val) = try_bool(&value, &["show_tabs"])? {
+                        config.show_tabs = val
+                    }
+                }
+                "show_border" => {
+                    if let Some(val) = try_bool(&value, &["show_border"])? {
+                        config.show_border = val
+                    }
+                }
+                "status_bar_position" => {
+                    if let Some(val) = try_status_bar_position(&value, &["status_bar_position"])? {
+                        config.status_bar_position = val
+                    }
+                }
+                "breadcrumb_style" => {
+                    if let Some(val) = try_breadcrumb_style(&value, &["breadcrumb_style"])? {
+                        config.breadcrumb_style = val
+                    }
+                }
+                "path_export_style" => {
+                    if let Some(val) = try_path_export_style(&value, &["path_export_style"])? {
+                        config.path_export_style = val
+                    }
+                }
                 "show_table_header" => {
                     if let Some(val) = try_bool(&value, &["show_table_header"])? {
                         config.show_table_header = val
                     }
                 }
+                "show_column_summary" => {
+                    if let Some(val) = try_bool(&value, &["show_column_summary"])? {
+                        config.show_column_summary = val
+                    }
+                }
                 "layout" => {
                     if let Some(val) = try_layout(&value, &["layout"])? {
                         config.layout = val
                     }
                 }
+                "group_records" => {
+                    if let Some(val) = try_bool(&value, &["group_records"])? {
+                        config.group_records = val
+                    }
+                }
+                "group_by_type" => {
+                    if let Some(val) = try_bool(&value, &["group_by_type"])? {
+                        config.group_by_type = val
+                    }
+                }
+                "initial_key" => {
+                    config.initial_key = match follow_cell_path(&value, &["initial_key"]) {
+                        Some(Value::Nothing { .. }) => None,
+                        _ => try_string(&value, &["initial_key"])?,
+                    }
+                }
+                "initial_mode" => {
+                    if let Some(val) = try_mode(&value, &["initial_mode"])? {
+                        config.initial_mode = val
+                    }
+                }
+                "show_preview_pane" => {
+                    if let Some(val) = try_bool(&value, &["show_preview_pane"])? {
+                        config.show_preview_pane = val
+                    }
+                }
+                "indent" => {
+                    if let Some(val) = try_usize(&value, &["indent"])? {
+                        config.indent = val
+                    }
+                }
+                "collapse_on_center" => {
+                    if let Some(val) = try_bool(&value, &["collapse_on_center"])? {
+                        config.collapse_on_center = val
+                    }
+                }
+                "show_sparkline" => {
+                    if let Some(val) = try_bool(&value, &["show_sparkline"])? {
+                        config.show_sparkline = val
+                    }
+                }
+                "interpret_ansi" => {
+                    if let Some(val) = try_bool(&value, &["interpret_ansi"])? {
+                        config.interpret_ansi = val
+                    }
+                }
+                "repeat_debounce_ms" => {
+                    if let Some(val) = try_usize(&value, &["repeat_debounce_ms"])? {
+                        config.repeat_debounce_ms = val
+                    }
+                }
+                "flash_ms" => {
+                    if let Some(val) = try_usize(&value, &["flash_ms"])? {
+                        config.flash_ms = val
+                    }
+                }
+                "prompt_timeout_ms" => {
+                    if let Some(val) = try_usize(&value, &["prompt_timeout_ms"])? {
+                        config.prompt_timeout_ms = val
+                    }
+                }
+                "operator_pending" => {
+                    if let Some(val) = try_bool(&value, &["operator_pending"])? {
+                        config.operator_pending = val
+                    }
+                }
+                "zebra_stripes" => {
+                    if let Some(val) = try_bool(&value, &["zebra_stripes"])? {
+                        config.zebra_stripes = val
+                    }
+                }
+                "warn_on_type_change" => {
+                    if let Some(val) = try_bool(&value, &["warn_on_type_change"])? {
+                        config.warn_on_type_change = val
+                    }
+                }
+                "left_at_root" => {
+                    if let Some(val) = try_left_at_root(&value, &["left_at_root"])? {
+                        config.left_at_root = val
+                    }
+                }
+                "skip_empty_optional" => {
+                    if let Some(val) = try_bool(&value, &["skip_empty_optional"])? {
+                        config.skip_empty_optional = val
+                    }
+                }
+                "missing_display" => {
+                    if let Some(val) = try_missing_display(&value, &["missing_display"])? {
+                        config.missing_display = val
+                    }
+                }
+                "leaf_view" => {
+                    if let Some(val) = try_leaf_view(&value, &["leaf_view"])? {
+                        config.leaf_view = val
+                    }
+                }
+                "new_entry_default" => {
+                    if let Some(val) = try_new_entry_default(&value, &["new_entry_default"])? {
+                        config.new_entry_default = val
+                    }
+                }
+                "max_rows" => {
+                    config.max_rows = match follow_cell_path(&value, &["max_rows"]) {
+                        Some(Value::Nothing { .. }) => None,
+                        _ => try_usize(&value, &["max_rows"])?,
+                    }
+                }
+                "tab_width" => {
+                    if let Some(val) = try_usize(&value, &["tab_width"])? {
+                        config.tab_width = val
+                    }
+                }
+                "thousands_sep" => {
+                    if let Some(val) = try_string(&value, &["thousands_sep"])? {
+                        config.thousands_sep = val
+                    }
+                }
+                "null_placeholder" => {
+                    if let Some(val) = try_string(&value, &["null_placeholder"])? {
+                        config.null_placeholder = val
+                    }
+                }
+                "history_file" => {
+                    config.history_file = match follow_cell_path(&value, &["history_file"]) {
+                        Some(Value::Nothing { .. }) => None,
+                        _ => try_string(&value, &["history_file"])?,
+                    }
+                }
+                "preview" => {
+                    let cell = follow_cell_path(&value, &["preview"]).unwrap();
+                    let columns = match &cell {
+                        Value::Record { val: rec, .. } => &rec.cols,
+                        x => return Err(invalid_type(x, &["preview"], "record")),
+                    };
+
+                    for column in columns {
+                        match column.as_str() {
+                            "record_keys" => {
+                                if let Some(val) = try_usize(&value, &["preview", "record_keys"])? {
+                                    config.preview.record_keys = val
+                                }
+                            }
+                            "collapse_over" => {
+                                if let Some(val) = try_usize(&value, &["preview", "collapse_over"])?
+                                {
+                                    config.preview.collapse_over = val
+                                }
+                            }
+                            x => return Err(invalid_field(&["preview", x], Some(cell.span()))),
+                        }
+                    }
+                }
+                "peeking" => {
+                    let cell = follow_cell_path(&value, &["peeking"]).unwrap();
+                    let columns = match &cell {
+                        Value::Record { val: rec, .. } => &rec.cols,
+                        x => return Err(invalid_type(x, &["peeking"], "record")),
+                    };
+
+                    for column in columns {
+                        match column.as_str() {
+                            "quit_returns_to_normal" => {
+                                if let Some(val) =
+                                    try_bool(&value, &["peeking", "quit_returns_to_normal"])?
+                                {
+                                    config.peeking.quit_returns_to_normal = val
+                                }
+                            }
+                            "warn_on_lost_edits" => {
+                                if let Some(val) =
+                                    try_bool(&value, &["peeking", "warn_on_lost_edits"])?
+                                {
+                                    config.peeking.warn_on_lost_edits = val
+                                }
+                            }
+                            x => return Err(invalid_field(&["peeking", x], Some(cell.span()))),
+                        }
+                    }
+                }
+                "scrollbar" => {
+                    let cell = follow_cell_path(&value, &["scrollbar"]).unwrap();
+                    let columns = match &cell {
+                        Value::Record { val: rec, .. } => &rec.cols,
+                        x => return Err(invalid_type(x, &["scrollbar"], "record")),
+                    };
+
+                    for column in columns {
+                        match column.as_str() {
+                            "visibility" => {
+                                if let Some(val) =
+                                    try_scrollbar_visibility(&value, &["scrollbar", "visibility"])?
+                                {
+                                    config.scrollbar.visibility = val
+                                }
+                            }
+                            "thumb" => {
+                                if let Some(val) = try_string(&value, &["scrollbar", "thumb"])? {
+                                    config.scrollbar.thumb = val
+                                }
+                            }
+                            "track" => {
+                                if let Some(val) = try_string(&value, &["scrollbar", "track"])? {
+                                    config.scrollbar.track = val
+                                }
+                            }
+                            x => return Err(invalid_field(&["scrollbar", x], Some(cell.span()))),
+                        }
+                    }
+                }
+                "type_glyphs" => {
+                    let cell = follow_cell_path(&value, &["type_glyphs"]).unwrap();
+                    let columns = match &cell {
+                        Value::Record { val: rec, .. } => &rec.cols,
+                        x => return Err(invalid_type(x, &["type_glyphs"], "record")),
+                    };
+
+                    config.type_glyphs = vec![];
+                    for column in columns {
+                        if let Some(val) = try_string(&value, &["type_glyphs", column])? {
+                            config.type_glyphs.push((column.clone(), val));
+                        }
+                    }
+                }
                 "colors" => {
                     let cell = follow_cell_path(&value, &["colors"]).unwrap();
                     let columns = match &cell {
@@ -311,6 +1233,23 @@ impl Config {
                                     config.colors.selected_modifier = val
                                 }
                             }
+                            "zebra" => {
+                                if let Some(val) = try_color(&value, &["colors", "zebra"])? {
+                                    config.colors.zebra = val
+                                }
+                            }
+                            "changed" => {
+                                if let Some(val) = try_color(&value, &["colors", "changed"])? {
+                                    config.colors.changed = val
+                                }
+                            }
+                            "trailing_whitespace" => {
+                                if let Some(val) =
+                                    try_color(&value, &["colors", "trailing_whitespace"])?
+                                {
+                                    config.colors.trailing_whitespace = val
+                                }
+                            }
                             "status_bar" => {
                                 let cell =
                                     follow_cell_path(&value, &["colors", "status_bar"]).unwrap();
@@ -363,6 +1302,15 @@ impl Config {
                                                 config.colors.status_bar.bottom = val
                                             }
                                         }
+                                        "search" => {
+                                            if let Some(val) = try_fg_bg_colors(
+                                                &value,
+                                                &["colors", "status_bar", "search"],
+                                                &config.colors.status_bar.search,
+                                            )? {
+                                                config.colors.status_bar.search = val
+                                            }
+                                        }
                                         x => {
                                             return Err(invalid_field(
                                                 &["colors", "status_bar", x],
@@ -437,6 +1385,11 @@ impl Config {
                                     config.keybindings.insert = val
                                 }
                             }
+                            "form" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "form"])? {
+                                    config.keybindings.form = val
+                                }
+                            }
                             "normal" => {
                                 if let Some(val) = try_key(&value, &["keybindings", "normal"])? {
                                     config.keybindings.normal = val
@@ -551,6 +1504,30 @@ impl Config {
                                                 config.keybindings.peeking.view = val
                                             }
                                         }
+                                        "config" => {
+                                            if let Some(val) = try_key(
+                                                &value,
+                                                &["keybindings", "peeking", "config"],
+                                            )? {
+                                                config.keybindings.peeking.config = val
+                                            }
+                                        }
+                                        "theme" => {
+                                            if let Some(val) = try_key(
+                                                &value,
+                                                &["keybindings", "peeking", "theme"],
+                                            )? {
+                                                config.keybindings.peeking.theme = val
+                                            }
+                                        }
+                                        "cycle_format" => {
+                                            if let Some(val) = try_key(
+                                                &value,
+                                                &["keybindings", "peeking", "cycle_format"],
+                                            )? {
+                                                config.keybindings.peeking.cycle_format = val
+                                            }
+                                        }
                                         x => {
                                             return Err(invalid_field(
                                                 &["keybindings", "peeking", x],
@@ -565,49 +1542,690 @@ impl Config {
                                     config.keybindings.transpose = val
                                 }
                             }
-                            x => return Err(invalid_field(&["keybindings", x], Some(cell.span()))),
-                        }
-                    }
-                }
-                x => return Err(invalid_field(&[x], Some(value.span()))),
-            }
-        }
-
-        Ok(config)
-    }
-}
-
-/// represent a [`KeyCode`] as a simple string
-pub fn repr_keycode(keycode: &KeyCode) -> String {
-    match keycode {
-        KeyCode::Char(c) => c.to_string(),
-        KeyCode::Left => char::from_u32(0x2190).unwrap().into(),
-        KeyCode::Up => char::from_u32(0x2191).unwrap().into(),
-        KeyCode::Right => char::from_u32(0x2192).unwrap().into(),
-        KeyCode::Down => char::from_u32(0x2193).unwrap().into(),
-        KeyCode::Esc => "<esc>".into(),
-        KeyCode::Enter => char::from_u32(0x23ce).unwrap().into(),
-        KeyCode::Backspace => char::from_u32(0x232b).unwrap().into(),
-        KeyCode::Delete => char::from_u32(0x2326).unwrap().into(),
-        _ => "??".into(),
-    }
-}
-
-// TODO: add proper assert error messages
-#[cfg(test)]
-mod tests {
-    use crossterm::event::KeyCode;
-    use nu_protocol::{record, Record, Value};
-
-    use super::{repr_keycode, Config};
-
-    #[test]
-    fn keycode_representation() {
-        assert_eq!(repr_keycode(&KeyCode::Char('x')), "x".to_string());
-        assert_eq!(repr_keycode(&KeyCode::Left), "←".to_string());
-        assert_eq!(repr_keycode(&KeyCode::Esc), "<esc>".to_string());
-        assert_eq!(repr_keycode(&KeyCode::Enter), "⏎".to_string());
-        assert_eq!(repr_keycode(&KeyCode::Home), "??".to_string());
+                            "paste" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "paste"])? {
+                                    config.keybindings.paste = val
+                                }
+                            }
+                            "mark" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "mark"])? {
+                                    config.keybindings.mark = val
+                                }
+                            }
+                            "jump_to_mark" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "jump_to_mark"])?
+                                {
+                                    config.keybindings.jump_to_mark = val
+                                }
+                            }
+                            "type_ahead" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "type_ahead"])?
+                                {
+                                    config.keybindings.type_ahead = val
+                                }
+                            }
+                            "type_filter" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "type_filter"])?
+                                {
+                                    config.keybindings.type_filter = val
+                                }
+                            }
+                            "expand_all" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "expand_all"])?
+                                {
+                                    config.keybindings.expand_all = val
+                                }
+                            }
+                            "collapse_all" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "collapse_all"])?
+                                {
+                                    config.keybindings.collapse_all = val
+                                }
+                            }
+                            "collapse_others" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "collapse_others"])?
+                                {
+                                    config.keybindings.collapse_others = val
+                                }
+                            }
+                            "collapse" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "collapse"])? {
+                                    config.keybindings.collapse = val
+                                }
+                            }
+                            "search" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "search"])? {
+                                    config.keybindings.search = val
+                                }
+                            }
+                            "next_match" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "next_match"])?
+                                {
+                                    config.keybindings.next_match = val
+                                }
+                            }
+                            "previous_match" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "previous_match"])?
+                                {
+                                    config.keybindings.previous_match = val
+                                }
+                            }
+                            "next_edit" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "next_edit"])? {
+                                    config.keybindings.next_edit = val
+                                }
+                            }
+                            "previous_edit" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "previous_edit"])?
+                                {
+                                    config.keybindings.previous_edit = val
+                                }
+                            }
+                            "history_back" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "history_back"])?
+                                {
+                                    config.keybindings.history_back = val
+                                }
+                            }
+                            "history_forward" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "history_forward"])?
+                                {
+                                    config.keybindings.history_forward = val
+                                }
+                            }
+                            "jump_to_clipboard" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "jump_to_clipboard"])?
+                                {
+                                    config.keybindings.jump_to_clipboard = val
+                                }
+                            }
+                            "label_rows" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "label_rows"])?
+                                {
+                                    config.keybindings.label_rows = val
+                                }
+                            }
+                            "parse_envish" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "parse_envish"])?
+                                {
+                                    config.keybindings.parse_envish = val
+                                }
+                            }
+                            "flatten" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "flatten"])? {
+                                    config.keybindings.flatten = val
+                                }
+                            }
+                            "schema" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "schema"])? {
+                                    config.keybindings.schema = val
+                                }
+                            }
+                            "sort" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "sort"])? {
+                                    config.keybindings.sort = val
+                                }
+                            }
+                            "commit_sort" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "commit_sort"])?
+                                {
+                                    config.keybindings.commit_sort = val
+                                }
+                            }
+                            "search_results" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "search_results"])?
+                                {
+                                    config.keybindings.search_results = val
+                                }
+                            }
+                            "yank_debug" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_debug"])?
+                                {
+                                    config.keybindings.yank_debug = val
+                                }
+                            }
+                            "yank_container_json" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_container_json"])?
+                                {
+                                    config.keybindings.yank_container_json = val
+                                }
+                            }
+                            "yank_merge" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_merge"])?
+                                {
+                                    config.keybindings.yank_merge = val
+                                }
+                            }
+                            "yank_markdown" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_markdown"])?
+                                {
+                                    config.keybindings.yank_markdown = val
+                                }
+                            }
+                            "yank_yaml" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_yaml"])? {
+                                    config.keybindings.yank_yaml = val
+                                }
+                            }
+                            "yank_csv" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_csv"])? {
+                                    config.keybindings.yank_csv = val
+                                }
+                            }
+                            "yank_dotted" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_dotted"])?
+                                {
+                                    config.keybindings.yank_dotted = val
+                                }
+                            }
+                            "yank_patch" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_patch"])?
+                                {
+                                    config.keybindings.yank_patch = val
+                                }
+                            }
+                            "yank_closure" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_closure"])?
+                                {
+                                    config.keybindings.yank_closure = val
+                                }
+                            }
+                            "yank_update" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_update"])?
+                                {
+                                    config.keybindings.yank_update = val
+                                }
+                            }
+                            "yank_where" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_where"])?
+                                {
+                                    config.keybindings.yank_where = val
+                                }
+                            }
+                            "yank_path" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_path"])? {
+                                    config.keybindings.yank_path = val
+                                }
+                            }
+                            "yank_relative_path" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_relative_path"])?
+                                {
+                                    config.keybindings.yank_relative_path = val
+                                }
+                            }
+                            "yank_type" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_type"])? {
+                                    config.keybindings.yank_type = val
+                                }
+                            }
+                            "duplicate_and_yank" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "duplicate_and_yank"])?
+                                {
+                                    config.keybindings.duplicate_and_yank = val
+                                }
+                            }
+                            "delete_operator" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "delete_operator"])?
+                                {
+                                    config.keybindings.delete_operator = val
+                                }
+                            }
+                            "append_field" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "append_field"])?
+                                {
+                                    config.keybindings.append_field = val
+                                }
+                            }
+                            "append_item" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "append_item"])?
+                                {
+                                    config.keybindings.append_item = val
+                                }
+                            }
+                            "truncate_list" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "truncate_list"])?
+                                {
+                                    config.keybindings.truncate_list = val
+                                }
+                            }
+                            "jump_recent" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "jump_recent"])?
+                                {
+                                    config.keybindings.jump_recent = val
+                                }
+                            }
+                            "lift" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "lift"])? {
+                                    config.keybindings.lift = val
+                                }
+                            }
+                            "yank_leaves" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_leaves"])?
+                                {
+                                    config.keybindings.yank_leaves = val
+                                }
+                            }
+                            "yank_rust" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_rust"])? {
+                                    config.keybindings.yank_rust = val
+                                }
+                            }
+                            "yank_epoch" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_epoch"])?
+                                {
+                                    config.keybindings.yank_epoch = val
+                                }
+                            }
+                            "yank_thousands" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_thousands"])?
+                                {
+                                    config.keybindings.yank_thousands = val
+                                }
+                            }
+                            "yank_stats" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_stats"])?
+                                {
+                                    config.keybindings.yank_stats = val
+                                }
+                            }
+                            "yank_script" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_script"])?
+                                {
+                                    config.keybindings.yank_script = val
+                                }
+                            }
+                            "yank_with_path" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_with_path"])?
+                                {
+                                    config.keybindings.yank_with_path = val
+                                }
+                            }
+                            "yank_hash" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "yank_hash"])? {
+                                    config.keybindings.yank_hash = val
+                                }
+                            }
+                            "yank_summary" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "yank_summary"])?
+                                {
+                                    config.keybindings.yank_summary = val
+                                }
+                            }
+                            "show_size" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "show_size"])? {
+                                    config.keybindings.show_size = val
+                                }
+                            }
+                            "open_in_pager" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "open_in_pager"])?
+                                {
+                                    config.keybindings.open_in_pager = val
+                                }
+                            }
+                            "toggle_relative" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_relative"])?
+                                {
+                                    config.keybindings.toggle_relative = val
+                                }
+                            }
+                            "toggle_reverse" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_reverse"])?
+                                {
+                                    config.keybindings.toggle_reverse = val
+                                }
+                            }
+                            "toggle_scope_lock" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_scope_lock"])?
+                                {
+                                    config.keybindings.toggle_scope_lock = val
+                                }
+                            }
+                            "jump_to_matching" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "jump_to_matching"])?
+                                {
+                                    config.keybindings.jump_to_matching = val
+                                }
+                            }
+                            "toggle_key_focus" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_key_focus"])?
+                                {
+                                    config.keybindings.toggle_key_focus = val
+                                }
+                            }
+                            "fold_view" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "fold_view"])? {
+                                    config.keybindings.fold_view = val
+                                }
+                            }
+                            "toggle_fold" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "toggle_fold"])?
+                                {
+                                    config.keybindings.toggle_fold = val
+                                }
+                            }
+                            "toggle_group" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_group"])?
+                                {
+                                    config.keybindings.toggle_group = val
+                                }
+                            }
+                            "reload_config" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "reload_config"])?
+                                {
+                                    config.keybindings.reload_config = val
+                                }
+                            }
+                            "cycle_breadcrumb" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "cycle_breadcrumb"])?
+                                {
+                                    config.keybindings.cycle_breadcrumb = val
+                                }
+                            }
+                            "toggle_wrap" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "toggle_wrap"])?
+                                {
+                                    config.keybindings.toggle_wrap = val
+                                }
+                            }
+                            "toggle_chrome" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_chrome"])?
+                                {
+                                    config.keybindings.toggle_chrome = val
+                                }
+                            }
+                            "toggle_legend" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_legend"])?
+                                {
+                                    config.keybindings.toggle_legend = val
+                                }
+                            }
+                            "toggle_quoted_keys" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_quoted_keys"])?
+                                {
+                                    config.keybindings.toggle_quoted_keys = val
+                                }
+                            }
+                            "toggle_ancestors" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_ancestors"])?
+                                {
+                                    config.keybindings.toggle_ancestors = val
+                                }
+                            }
+                            "toggle_minimap" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_minimap"])?
+                                {
+                                    config.keybindings.toggle_minimap = val
+                                }
+                            }
+                            "toggle_histogram" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_histogram"])?
+                                {
+                                    config.keybindings.toggle_histogram = val
+                                }
+                            }
+                            "toggle_compare" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_compare"])?
+                                {
+                                    config.keybindings.toggle_compare = val
+                                }
+                            }
+                            "toggle_table_view" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "toggle_table_view"])?
+                                {
+                                    config.keybindings.toggle_table_view = val
+                                }
+                            }
+                            "columns_left" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "columns_left"])?
+                                {
+                                    config.keybindings.columns_left = val
+                                }
+                            }
+                            "columns_right" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "columns_right"])?
+                                {
+                                    config.keybindings.columns_right = val
+                                }
+                            }
+                            "scroll_preview_down" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "scroll_preview_down"])?
+                                {
+                                    config.keybindings.scroll_preview_down = val
+                                }
+                            }
+                            "scroll_preview_up" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "scroll_preview_up"])?
+                                {
+                                    config.keybindings.scroll_preview_up = val
+                                }
+                            }
+                            "center" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "center"])? {
+                                    config.keybindings.center = val
+                                }
+                            }
+                            "next_leaf" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "next_leaf"])? {
+                                    config.keybindings.next_leaf = val
+                                }
+                            }
+                            "next_container" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "next_container"])?
+                                {
+                                    config.keybindings.next_container = val
+                                }
+                            }
+                            "pin" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "pin"])? {
+                                    config.keybindings.pin = val
+                                }
+                            }
+                            "drill" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "drill"])? {
+                                    config.keybindings.drill = val
+                                }
+                            }
+                            "snapshot" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "snapshot"])? {
+                                    config.keybindings.snapshot = val
+                                }
+                            }
+                            "reroot" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "reroot"])? {
+                                    config.keybindings.reroot = val
+                                }
+                            }
+                            "pop_root" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "pop_root"])? {
+                                    config.keybindings.pop_root = val
+                                }
+                            }
+                            "explore_nested" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "explore_nested"])?
+                                {
+                                    config.keybindings.explore_nested = val
+                                }
+                            }
+                            "wrap_in_list" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "wrap_in_list"])?
+                                {
+                                    config.keybindings.wrap_in_list = val
+                                }
+                            }
+                            "wrap_in_record" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "wrap_in_record"])?
+                                {
+                                    config.keybindings.wrap_in_record = val
+                                }
+                            }
+                            "split_string" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "split_string"])?
+                                {
+                                    config.keybindings.split_string = val
+                                }
+                            }
+                            "replace" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "replace"])? {
+                                    config.keybindings.replace = val
+                                }
+                            }
+                            "join_list" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "join_list"])? {
+                                    config.keybindings.join_list = val
+                                }
+                            }
+                            "parse_string" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "parse_string"])?
+                                {
+                                    config.keybindings.parse_string = val
+                                }
+                            }
+                            "collapse_string" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "collapse_string"])?
+                                {
+                                    config.keybindings.collapse_string = val
+                                }
+                            }
+                            "retab" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "retab"])? {
+                                    config.keybindings.retab = val
+                                }
+                            }
+                            "cycle_enum" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "cycle_enum"])?
+                                {
+                                    config.keybindings.cycle_enum = val
+                                }
+                            }
+                            "filter_list" => {
+                                if let Some(val) = try_key(&value, &["keybindings", "filter_list"])?
+                                {
+                                    config.keybindings.filter_list = val
+                                }
+                            }
+                            "record_to_values" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "record_to_values"])?
+                                {
+                                    config.keybindings.record_to_values = val
+                                }
+                            }
+                            "table_to_columns" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "table_to_columns"])?
+                                {
+                                    config.keybindings.table_to_columns = val
+                                }
+                            }
+                            "summarize_list" => {
+                                if let Some(val) =
+                                    try_key(&value, &["keybindings", "summarize_list"])?
+                                {
+                                    config.keybindings.summarize_list = val
+                                }
+                            }
+                            x => return Err(invalid_field(&["keybindings", x], Some(cell.span()))),
+                        }
+                    }
+                }
+                x => return Err(invalid_field(&[x], Some(value.span()))),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// represent a [`KeyCode`] as a simple string
+pub fn repr_keycode(keycode: &KeyCode) -> String {
+    match keycode {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Left => char::from_u32(0x2190).unwrap().into(),
+        KeyCode::Up => char::from_u32(0x2191).unwrap().into(),
+        KeyCode::Right => char::from_u32(0x2192).unwrap().into(),
+        KeyCode::Down => char::from_u32(0x2193).unwrap().into(),
+        KeyCode::Esc => "<esc>".into(),
+        KeyCode::Enter => char::from_u32(0x23ce).unwrap().into(),
+        KeyCode::Backspace => char::from_u32(0x232b).unwrap().into(),
+        KeyCode::Delete => char::from_u32(0x2326).unwrap().into(),
+        KeyCode::Tab => "<tab>".into(),
+        KeyCode::BackTab => "<s-tab>".into(),
+        _ => "??".into(),
+    }
+}
+
+// TODO: add proper assert error messages
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+    use nu_protocol::{record, Record, Value};
+
+    use super::{repr_keycode, Config};
+
+    #[test]
+    fn keycode_representation() {
+        assert_eq!(repr_keycode(&KeyCode::Char('x')), "x".to_string());
+        assert_eq!(repr_keycode(&KeyCode::Left), "←".to_string());
+        assert_eq!(repr_keycode(&KeyCode::Esc), "<esc>".to_string());
+        assert_eq!(repr_keycode(&KeyCode::Enter), "⏎".to_string());
+        assert_eq!(repr_keycode(&KeyCode::BackTab), "<s-tab>".to_string());
+        assert_eq!(repr_keycode(&KeyCode::Home), "??".to_string());
+    }
+
+    #[test]
+    fn load_default_config_file() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/config/default.nuon");
+        assert_eq!(Config::load_from_disk(&path), Ok(Config::default()));
     }
 
     #[test]
@@ -6,7 +6,11 @@ use ratatui::style::{Color, Modifier};
 use nu_plugin::LabeledError;
 use nu_protocol::{ast::PathMember, Span, Value};
 
-use super::{BgFgColorConfig, Layout};
+use super::{
+    BgFgColorConfig, BreadcrumbStyle, Layout, LeafView, LeftAtRoot, MissingDisplay,
+    NewEntryDefault, PathExportStyle, ScrollbarVisibility, StatusBarPosition,
+};
+use crate::app::Mode;
 
 /// return an *invalid field* error
 ///
@@ -79,6 +83,26 @@ pub fn try_bool(value: &Value, cell_path: &[&str]) -> Result<Option<bool>, Label
     }
 }
 
+/// try to parse a non-negative integer in the *value* at the given *cell path*
+pub fn try_usize(value: &Value, cell_path: &[&str]) -> Result<Option<usize>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::Int { val, .. }) => match usize::try_from(val) {
+            Ok(u) => Ok(Some(u)),
+            Err(_) => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    "`$.{}` should be a non-negative integer, found {}",
+                    cell_path.join("."),
+                    val
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "int")),
+        _ => Ok(None),
+    }
+}
+
 /// try to parse a string in the *value* at the given *cell path*
 pub fn try_string(value: &Value, cell_path: &[&str]) -> Result<Option<String>, LabeledError> {
     match follow_cell_path(value, cell_path) {
@@ -269,10 +293,226 @@ pub fn try_layout(value: &Value, cell_path: &[&str]) -> Result<Option<Layout>, L
         Some(Value::String { val, .. }) => match val.as_str() {
             "table" => Ok(Some(Layout::Table)),
             "compact" => Ok(Some(Layout::Compact)),
+            "tree" => Ok(Some(Layout::Tree)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [table, compact, tree] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse the behavior of the left key at the root in the *value* at the given *cell path*
+pub fn try_left_at_root(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<LeftAtRoot>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "noop" => Ok(Some(LeftAtRoot::Noop)),
+            "quit" => Ok(Some(LeftAtRoot::Quit)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [noop, quit] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse the style of the cell path in the *value* at the given *cell path*
+pub fn try_breadcrumb_style(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<BreadcrumbStyle>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "leading" => Ok(Some(BreadcrumbStyle::Leading)),
+            "in" => Ok(Some(BreadcrumbStyle::In)),
+            "bare" => Ok(Some(BreadcrumbStyle::Bare)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [leading, in, bare] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse the language of the exported paths in the *value* at the given *cell path*
+pub fn try_path_export_style(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<PathExportStyle>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "jsonpath" => Ok(Some(PathExportStyle::JsonPath)),
+            "jmespath" => Ok(Some(PathExportStyle::JmesPath)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [jsonpath, jmespath] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse a view of the leaves in the *value* at the given *cell path*
+pub fn try_leaf_view(value: &Value, cell_path: &[&str]) -> Result<Option<LeafView>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "plain" => Ok(Some(LeafView::Plain)),
+            "debug" => Ok(Some(LeafView::Debug)),
+            "json" => Ok(Some(LeafView::Json)),
             x => Err(LabeledError {
                 label: "invalid config".into(),
                 msg: format!(
-                    r#"`$.{}` should be one of [table, compact] , found {}"#,
+                    r#"`$.{}` should be one of [plain, debug, json] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse a way to show the missing cells in the *value* at the given *cell path*
+pub fn try_missing_display(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<MissingDisplay>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "null" => Ok(Some(MissingDisplay::Null)),
+            "missing" => Ok(Some(MissingDisplay::Missing)),
+            "reconcile" => Ok(Some(MissingDisplay::Reconcile)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [null, missing, reconcile] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse a position of the status bar in the *value* at the given *cell path*
+pub fn try_status_bar_position(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<StatusBarPosition>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "top" => Ok(Some(StatusBarPosition::Top)),
+            "bottom" => Ok(Some(StatusBarPosition::Bottom)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [top, bottom] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse a default value of new fields in the *value* at the given *cell path*
+pub fn try_new_entry_default(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<NewEntryDefault>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "null" => Ok(Some(NewEntryDefault::Null)),
+            "empty-string" => Ok(Some(NewEntryDefault::EmptyString)),
+            "zero" => Ok(Some(NewEntryDefault::Zero)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [null, empty-string, zero] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse the visibility of the scrollbar in the *value* at the given *cell path*
+pub fn try_scrollbar_visibility(
+    value: &Value,
+    cell_path: &[&str],
+) -> Result<Option<ScrollbarVisibility>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "always" => Ok(Some(ScrollbarVisibility::Always)),
+            "auto" => Ok(Some(ScrollbarVisibility::Auto)),
+            "never" => Ok(Some(ScrollbarVisibility::Never)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [always, auto, never] , found {}"#,
+                    cell_path.join("."),
+                    x
+                ),
+                span: Some(value.span()),
+            }),
+        },
+        Some(x) => Err(invalid_type(&x, cell_path, "string")),
+        _ => Ok(None),
+    }
+}
+
+/// try to parse an initial mode in the *value* at the given *cell path*
+pub fn try_mode(value: &Value, cell_path: &[&str]) -> Result<Option<Mode>, LabeledError> {
+    match follow_cell_path(value, cell_path) {
+        Some(Value::String { val, .. }) => match val.as_str() {
+            "normal" => Ok(Some(Mode::Normal)),
+            "peeking" => Ok(Some(Mode::Peeking)),
+            x => Err(LabeledError {
+                label: "invalid config".into(),
+                msg: format!(
+                    r#"`$.{}` should be one of [normal, peeking] , found {}"#,
                     cell_path.join("."),
                     x
                 ),
@@ -322,10 +562,18 @@ mod tests {
     use ratatui::style::{Color, Modifier};
 
     use super::{
-        follow_cell_path, try_bool, try_color, try_fg_bg_colors, try_key, try_layout, try_modifier,
-        try_string,
+        follow_cell_path, try_bool, try_breadcrumb_style, try_color, try_fg_bg_colors, try_key,
+        try_layout, try_leaf_view, try_left_at_root, try_missing_display, try_mode, try_modifier,
+        try_new_entry_default, try_path_export_style, try_scrollbar_visibility,
+        try_status_bar_position, try_string, try_usize,
+    };
+    use crate::{
+        app::Mode,
+        config::{
+            BgFgColorConfig, BreadcrumbStyle, Layout, LeafView, LeftAtRoot, MissingDisplay,
+            NewEntryDefault, PathExportStyle, ScrollbarVisibility, StatusBarPosition,
+        },
     };
-    use crate::config::{BgFgColorConfig, Layout};
 
     #[test]
     fn follow_str_cell_path() {
@@ -383,6 +631,24 @@ mod tests {
         assert_eq!(try_bool(&Value::test_nothing(), &["x"]), Ok(None));
     }
 
+    #[test]
+    fn trying_usize() {
+        test_tried_error(
+            try_usize(&Value::test_string("not an int"), &[]),
+            "",
+            "should be a int, found string",
+        );
+        test_tried_error(
+            try_usize(&Value::test_int(-1), &[]),
+            "",
+            "should be a non-negative integer, found -1",
+        );
+
+        assert_eq!(try_usize(&Value::test_int(0), &[]), Ok(Some(0)));
+        assert_eq!(try_usize(&Value::test_int(123), &[]), Ok(Some(123)));
+        assert_eq!(try_usize(&Value::test_nothing(), &["x"]), Ok(None));
+    }
+
     #[test]
     fn trying_string() {
         test_tried_error(
@@ -455,10 +721,14 @@ mod tests {
         test_tried_error(
             try_layout(&Value::test_string("collapsed"), &[]),
             "",
-            "should be one of [table, compact] , found collapsed",
+            "should be one of [table, compact, tree] , found collapsed",
         );
 
-        let cases = vec![("table", Layout::Table), ("compact", Layout::Compact)];
+        let cases = vec![
+            ("table", Layout::Table),
+            ("compact", Layout::Compact),
+            ("tree", Layout::Tree),
+        ];
 
         for (input, expected) in cases {
             assert_eq!(
@@ -468,6 +738,204 @@ mod tests {
         }
     }
 
+    #[test]
+    fn trying_mode() {
+        test_tried_error(
+            try_mode(&Value::test_int(123), &[]),
+            "",
+            "should be a string, found int",
+        );
+        test_tried_error(
+            try_mode(&Value::test_string("insert"), &[]),
+            "",
+            "should be one of [normal, peeking] , found insert",
+        );
+
+        let cases = vec![("normal", Mode::Normal), ("peeking", Mode::Peeking)];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_mode(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_left_at_root() {
+        test_tried_error(
+            try_left_at_root(&Value::test_int(123), &[]),
+            "",
+            "should be a string, found int",
+        );
+        test_tried_error(
+            try_left_at_root(&Value::test_string("exit"), &[]),
+            "",
+            "should be one of [noop, quit] , found exit",
+        );
+
+        let cases = vec![("noop", LeftAtRoot::Noop), ("quit", LeftAtRoot::Quit)];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_left_at_root(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_breadcrumb_style() {
+        test_tried_error(
+            try_breadcrumb_style(&Value::test_string("dollar"), &[]),
+            "",
+            "should be one of [leading, in, bare] , found dollar",
+        );
+
+        let cases = vec![
+            ("leading", BreadcrumbStyle::Leading),
+            ("in", BreadcrumbStyle::In),
+            ("bare", BreadcrumbStyle::Bare),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_breadcrumb_style(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_path_export_style() {
+        test_tried_error(
+            try_path_export_style(&Value::test_string("xpath"), &[]),
+            "",
+            "should be one of [jsonpath, jmespath] , found xpath",
+        );
+
+        let cases = vec![
+            ("jsonpath", PathExportStyle::JsonPath),
+            ("jmespath", PathExportStyle::JmesPath),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_path_export_style(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_leaf_view() {
+        test_tried_error(
+            try_leaf_view(&Value::test_string("yaml"), &[]),
+            "",
+            "should be one of [plain, debug, json] , found yaml",
+        );
+
+        let cases = vec![
+            ("plain", LeafView::Plain),
+            ("debug", LeafView::Debug),
+            ("json", LeafView::Json),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_leaf_view(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_missing_display() {
+        test_tried_error(
+            try_missing_display(&Value::test_string("hide"), &[]),
+            "",
+            "should be one of [null, missing, reconcile] , found hide",
+        );
+
+        let cases = vec![
+            ("null", MissingDisplay::Null),
+            ("missing", MissingDisplay::Missing),
+            ("reconcile", MissingDisplay::Reconcile),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_missing_display(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_status_bar_position() {
+        test_tried_error(
+            try_status_bar_position(&Value::test_string("left"), &[]),
+            "",
+            "should be one of [top, bottom] , found left",
+        );
+
+        let cases = vec![
+            ("top", StatusBarPosition::Top),
+            ("bottom", StatusBarPosition::Bottom),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_status_bar_position(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_new_entry_default() {
+        test_tried_error(
+            try_new_entry_default(&Value::test_string("one"), &[]),
+            "",
+            "should be one of [null, empty-string, zero] , found one",
+        );
+
+        let cases = vec![
+            ("null", NewEntryDefault::Null),
+            ("empty-string", NewEntryDefault::EmptyString),
+            ("zero", NewEntryDefault::Zero),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_new_entry_default(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn trying_scrollbar_visibility() {
+        test_tried_error(
+            try_scrollbar_visibility(&Value::test_string("sometimes"), &[]),
+            "",
+            "should be one of [always, auto, never] , found sometimes",
+        );
+
+        let cases = vec![
+            ("always", ScrollbarVisibility::Always),
+            ("auto", ScrollbarVisibility::Auto),
+            ("never", ScrollbarVisibility::Never),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                try_scrollbar_visibility(&Value::test_string(input), &[]),
+                Ok(Some(expected))
+            );
+        }
+    }
+
     #[test]
     fn trying_modifier() {
         test_tried_error(
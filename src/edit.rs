@@ -1,19 +1,36 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     prelude::Rect,
-    style::Style,
+    style::{Modifier, Style},
+    text::{Line, Text},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use nu_protocol::{Span, Value};
+use nu_protocol::{Span, Type, Value};
 
-use crate::config::Config;
+use crate::{config::Config, nu::value::parse_as_type, ui::highlight_trailing_whitespace};
+
+/// the outcome of a key press in the [`Editor`]
+#[derive(Debug, PartialEq)]
+pub enum EditorEvent {
+    /// the edition is not over yet
+    Continue,
+    /// the edition is over and the cell should be set to the value
+    Commit(Value),
+    /// the edition is over and the cell should be left untouched
+    Cancel,
+}
 
 pub struct Editor {
     pub buffer: String,
     cursor_position: (usize, usize),
     width: usize,
+    /// the type of the cell being edited, used to preview the result of the edit
+    target: Type,
+    /// the edited cell is `null` and no key has been pressed yet, see
+    /// [`Config::null_placeholder`]
+    replacing_null: bool,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -23,6 +40,8 @@ impl Default for Editor {
             buffer: String::new(),
             cursor_position: (0, 0),
             width: 0,
+            target: Type::String,
+            replacing_null: false,
         }
     }
 }
@@ -40,6 +59,8 @@ impl Editor {
             buffer: value.to_expanded_string(" ", &nu_protocol::Config::default()),
             cursor_position: (0, 0),
             width: 0,
+            target: value.get_type(),
+            replacing_null: matches!(value, Value::Nothing { .. }),
         }
     }
 
@@ -117,8 +138,18 @@ impl Editor {
         self.delete_char(0);
     }
 
-    pub(super) fn handle_key(&mut self, key: &KeyCode) -> Option<Option<Value>> {
-        match key {
+    /// handle a key event
+    ///
+    /// <enter> commits the buffer as a string, <ctrl-n> commits `null` and <esc> cancels the
+    /// edition.
+    pub(super) fn handle_key(&mut self, key: &KeyEvent) -> EditorEvent {
+        self.replacing_null = false;
+
+        if key.code == KeyCode::Char('n') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return EditorEvent::Commit(Value::nothing(Span::unknown()));
+        }
+
+        match &key.code {
             KeyCode::Left => self.move_cursor_left(),
             KeyCode::Right => self.move_cursor_right(),
             KeyCode::Up => self.move_cursor_up(),
@@ -128,30 +159,60 @@ impl Editor {
             KeyCode::Delete => self.delete_char_under_cursor(),
             KeyCode::Enter => {
                 let val = Value::string(self.buffer.clone(), Span::unknown());
-                return Some(Some(val));
+                return EditorEvent::Commit(val);
             }
-            KeyCode::Esc => return Some(None),
+            KeyCode::Esc => return EditorEvent::Cancel,
             _ => {}
         }
 
-        None
+        EditorEvent::Continue
+    }
+
+    /// preview the value the buffer would give once parsed as the type of the edited cell
+    ///
+    /// this is either the type and the value, e.g. `int 42`, or the parsing error.
+    pub(super) fn preview(&self) -> String {
+        match parse_as_type(&self.buffer, &self.target) {
+            Ok(value) => format!(
+                "{} {}",
+                value.get_type(),
+                value.to_expanded_string(" ", &nu_protocol::Config::default())
+            ),
+            Err(err) => err,
+        }
     }
 
     pub(super) fn render(&self, frame: &mut Frame, config: &Config) {
         let title = "Editor";
 
-        let block = Paragraph::new(self.buffer.as_str())
+        // NOTE: the stray whitespace at the end of each line is shown
+        let lines: Vec<Line> = if self.replacing_null && !config.null_placeholder.is_empty() {
+            vec![Line::styled(
+                config.null_placeholder.clone(),
+                Style::default().add_modifier(Modifier::DIM),
+            )]
+        } else {
+            self.buffer
+                .split('\n')
+                .map(|line| highlight_trailing_whitespace(line, Style::default(), config))
+                .collect()
+        };
+        let block = Paragraph::new(Text::from(lines))
             .style(
                 Style::default()
                     .fg(config.colors.editor.buffer.foreground)
                     .bg(config.colors.editor.buffer.background),
             )
             .block(
-                Block::default().borders(Borders::ALL).title(title).style(
-                    Style::default()
-                        .fg(config.colors.editor.frame.foreground)
-                        .bg(config.colors.editor.frame.background),
-                ),
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_bottom(format!("→ {}", self.preview()))
+                    .style(
+                        Style::default()
+                            .fg(config.colors.editor.frame.foreground)
+                            .bg(config.colors.editor.frame.background),
+                    ),
             );
 
         let height = if self.buffer.is_empty() {
@@ -178,10 +239,12 @@ impl Editor {
 
 #[cfg(test)]
 mod tests {
-    use crossterm::event::KeyCode;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use nu_protocol::Value;
+    use ratatui::{backend::TestBackend, Terminal};
 
-    use super::Editor;
+    use super::{Editor, EditorEvent};
+    use crate::config::Config;
 
     #[test]
     fn edit_cells() {
@@ -189,68 +252,134 @@ mod tests {
         editor.set_width(10 + 2);
 
         let strokes = vec![
-            (KeyCode::Enter, "", Some(Some(Value::test_string("")))),
-            (KeyCode::Char('a'), "a", None),
-            (KeyCode::Char('b'), "ab", None),
-            (KeyCode::Char('c'), "abc", None),
-            (KeyCode::Char('d'), "abcd", None),
-            (KeyCode::Char('e'), "abcde", None),
-            (KeyCode::Left, "abcde", None),
-            (KeyCode::Char('f'), "abcdfe", None),
-            (KeyCode::Left, "abcdfe", None),
-            (KeyCode::Left, "abcdfe", None),
-            (KeyCode::Char('g'), "abcgdfe", None),
-            (KeyCode::Right, "abcgdfe", None),
-            (KeyCode::Right, "abcgdfe", None),
-            (KeyCode::Right, "abcgdfe", None),
-            (KeyCode::Up, "abcgdfe", None),
-            (KeyCode::Down, "abcgdfe", None),
-            (KeyCode::Char('h'), "abcgdfeh", None),
-            (KeyCode::Char('i'), "abcgdfehi", None),
-            (KeyCode::Char('j'), "abcgdfehij", None),
-            (KeyCode::Char('k'), "abcgdfehijk", None),
-            (KeyCode::Char('l'), "abcgdfehijkl", None),
-            (KeyCode::Up, "abcgdfehijkl", None),
-            (KeyCode::Char('m'), "abmcgdfehijkl", None),
-            (KeyCode::Down, "abmcgdfehijkl", None),
-            (KeyCode::Left, "abmcgdfehijkl", None),
-            (KeyCode::Char('n'), "abmcgdfehijknl", None),
-            (KeyCode::Left, "abmcgdfehijknl", None),
-            (KeyCode::Left, "abmcgdfehijknl", None),
-            (KeyCode::Left, "abmcgdfehijknl", None),
-            (KeyCode::Left, "abmcgdfehijknl", None),
-            (KeyCode::Left, "abmcgdfehijknl", None),
-            (KeyCode::Char('o'), "abmcgdfeohijknl", None),
-            (KeyCode::Right, "abmcgdfeohijknl", None),
-            (KeyCode::Right, "abmcgdfeohijknl", None),
+            (
+                KeyCode::Enter,
+                "",
+                EditorEvent::Commit(Value::test_string("")),
+            ),
+            (KeyCode::Char('a'), "a", EditorEvent::Continue),
+            (KeyCode::Char('b'), "ab", EditorEvent::Continue),
+            (KeyCode::Char('c'), "abc", EditorEvent::Continue),
+            (KeyCode::Char('d'), "abcd", EditorEvent::Continue),
+            (KeyCode::Char('e'), "abcde", EditorEvent::Continue),
+            (KeyCode::Left, "abcde", EditorEvent::Continue),
+            (KeyCode::Char('f'), "abcdfe", EditorEvent::Continue),
+            (KeyCode::Left, "abcdfe", EditorEvent::Continue),
+            (KeyCode::Left, "abcdfe", EditorEvent::Continue),
+            (KeyCode::Char('g'), "abcgdfe", EditorEvent::Continue),
+            (KeyCode::Right, "abcgdfe", EditorEvent::Continue),
+            (KeyCode::Right, "abcgdfe", EditorEvent::Continue),
+            (KeyCode::Right, "abcgdfe", EditorEvent::Continue),
+            (KeyCode::Up, "abcgdfe", EditorEvent::Continue),
+            (KeyCode::Down, "abcgdfe", EditorEvent::Continue),
+            (KeyCode::Char('h'), "abcgdfeh", EditorEvent::Continue),
+            (KeyCode::Char('i'), "abcgdfehi", EditorEvent::Continue),
+            (KeyCode::Char('j'), "abcgdfehij", EditorEvent::Continue),
+            (KeyCode::Char('k'), "abcgdfehijk", EditorEvent::Continue),
+            (KeyCode::Char('l'), "abcgdfehijkl", EditorEvent::Continue),
+            (KeyCode::Up, "abcgdfehijkl", EditorEvent::Continue),
+            (KeyCode::Char('m'), "abmcgdfehijkl", EditorEvent::Continue),
+            (KeyCode::Down, "abmcgdfehijkl", EditorEvent::Continue),
+            (KeyCode::Left, "abmcgdfehijkl", EditorEvent::Continue),
+            (KeyCode::Char('n'), "abmcgdfehijknl", EditorEvent::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorEvent::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorEvent::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorEvent::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorEvent::Continue),
+            (KeyCode::Left, "abmcgdfehijknl", EditorEvent::Continue),
+            (KeyCode::Char('o'), "abmcgdfeohijknl", EditorEvent::Continue),
+            (KeyCode::Right, "abmcgdfeohijknl", EditorEvent::Continue),
+            (KeyCode::Right, "abmcgdfeohijknl", EditorEvent::Continue),
             (
                 KeyCode::Enter,
                 "abmcgdfeohijknl",
-                Some(Some(Value::test_string("abmcgdfeohijknl"))),
+                EditorEvent::Commit(Value::test_string("abmcgdfeohijknl")),
             ),
-            (KeyCode::Right, "abmcgdfeohijknl", None),
-            (KeyCode::Right, "abmcgdfeohijknl", None),
-            (KeyCode::Char('p'), "abmcgdfeohijkpnl", None),
-            (KeyCode::Backspace, "abmcgdfeohijknl", None),
-            (KeyCode::Backspace, "abmcgdfeohijnl", None),
-            (KeyCode::Backspace, "abmcgdfeohinl", None),
-            (KeyCode::Up, "abmcgdfeohinl", None),
-            (KeyCode::Delete, "amcgdfeohinl", None),
-            (KeyCode::Delete, "acgdfeohinl", None),
-            (KeyCode::Delete, "agdfeohinl", None),
-            (KeyCode::Esc, "agdfeohinl", Some(None)),
+            (KeyCode::Right, "abmcgdfeohijknl", EditorEvent::Continue),
+            (KeyCode::Right, "abmcgdfeohijknl", EditorEvent::Continue),
+            (
+                KeyCode::Char('p'),
+                "abmcgdfeohijkpnl",
+                EditorEvent::Continue,
+            ),
+            (KeyCode::Backspace, "abmcgdfeohijknl", EditorEvent::Continue),
+            (KeyCode::Backspace, "abmcgdfeohijnl", EditorEvent::Continue),
+            (KeyCode::Backspace, "abmcgdfeohinl", EditorEvent::Continue),
+            (KeyCode::Up, "abmcgdfeohinl", EditorEvent::Continue),
+            (KeyCode::Delete, "amcgdfeohinl", EditorEvent::Continue),
+            (KeyCode::Delete, "acgdfeohinl", EditorEvent::Continue),
+            (KeyCode::Delete, "agdfeohinl", EditorEvent::Continue),
+            (KeyCode::Esc, "agdfeohinl", EditorEvent::Cancel),
             (
                 KeyCode::Enter,
                 "agdfeohinl",
-                Some(Some(Value::test_string("agdfeohinl"))),
+                EditorEvent::Commit(Value::test_string("agdfeohinl")),
             ),
         ];
 
         for (key, expected_buffer, expected) in strokes {
-            let result = editor.handle_key(&key);
+            let result = editor.handle_key(&KeyEvent::new(key, KeyModifiers::empty()));
 
             assert_eq!(result, expected);
             assert_eq!(editor.buffer, expected_buffer.to_string());
         }
     }
+
+    #[test]
+    fn commit_null() {
+        let mut editor = Editor::default();
+        editor.set_width(10 + 2);
+
+        for c in "abc".chars() {
+            editor.handle_key(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+
+        let result = editor.handle_key(&KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(result, EditorEvent::Commit(Value::test_nothing()));
+    }
+
+    #[test]
+    fn preview_edit() {
+        let mut editor = Editor::from_value(&Value::test_int(0));
+        editor.set_width(10 + 2);
+
+        editor.buffer = "42".into();
+        assert_eq!(editor.preview(), "int 42");
+
+        editor.buffer = "4x".into();
+        assert_eq!(editor.preview(), "could not parse `4x` as int");
+
+        let mut editor = Editor::from_value(&Value::test_string("foo"));
+        editor.set_width(10 + 2);
+        assert_eq!(editor.preview(), "string foo");
+    }
+
+    #[test]
+    fn null_placeholder() {
+        let config = Config::default();
+        let draw = |editor: &Editor| -> String {
+            let mut terminal = Terminal::new(TestBackend::new(20, 8)).unwrap();
+            terminal
+                .draw(|frame| editor.render(frame, &config))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..8)
+                .flat_map(|y| (0..20).map(move |x| (x, y)))
+                .map(|(x, y)| buffer.get(x, y).symbol())
+                .collect()
+        };
+
+        let mut editor = Editor::from_value(&Value::test_nothing());
+        editor.set_width(10 + 2);
+        assert!(draw(&editor).contains("(null)"));
+
+        editor.handle_key(&KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty()));
+        assert!(!draw(&editor).contains("(null)"));
+        assert_eq!(editor.buffer, "a");
+
+        // NOTE: only the `null` cells have a placeholder
+        let mut editor = Editor::from_value(&Value::test_string(""));
+        editor.set_width(10 + 2);
+        assert!(!draw(&editor).contains("(null)"));
+    }
 }
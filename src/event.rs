@@ -1,6 +1,7 @@
 use anyhow::Result;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -17,6 +18,110 @@ pub enum Event {
     Resize(u16, u16),
 }
 
+/// tell whether a key pressed at *now* repeats the same key pressed at *previous* too fast, i.e.
+/// within the *window*
+pub(crate) fn is_too_fast(previous: Instant, now: Instant, window: Duration) -> bool {
+    now.saturating_duration_since(previous) < window
+}
+
+/// ignore the repetitions of a key that come faster than a threshold, see [`is_too_fast`]
+#[derive(Debug)]
+pub(crate) struct Debouncer {
+    window: Duration,
+    last: Option<(KeyEvent, Instant)>,
+}
+
+impl Debouncer {
+    /// a zero *window* never ignores any key
+    pub(crate) fn new(window: Duration) -> Self {
+        Self { window, last: None }
+    }
+
+    /// tell whether the *key* pressed at *now* should be handled
+    pub(crate) fn accept(&mut self, key: KeyEvent, now: Instant) -> bool {
+        if let Some((last, at)) = self.last {
+            if last.code == key.code
+                && last.modifiers == key.modifiers
+                && is_too_fast(at, now, self.window)
+            {
+                return false;
+            }
+        }
+
+        self.last = Some((key, now));
+        true
+    }
+}
+
+/// The side of the [`EventHandler`] of the handshake that stops its thread from reading the events
+/// of the terminal, see [`pause_channel`].
+#[derive(Debug)]
+struct Pause {
+    paused: Arc<AtomicBool>,
+    /// The acknowledgements of the thread that it does not read the terminal anymore.
+    acks: mpsc::Receiver<()>,
+    resumes: mpsc::Sender<()>,
+}
+
+/// The side of the thread of the handshake, see [`pause_channel`].
+#[derive(Debug)]
+struct PauseListener {
+    paused: Arc<AtomicBool>,
+    acks: mpsc::Sender<()>,
+    resumes: mpsc::Receiver<()>,
+}
+
+/// Builds both sides of the handshake to pause the thread reading the events of the terminal.
+///
+/// The handler only goes on once the thread has acknowledged the pause, so that no event meant for
+/// another program, e.g. a pager, can be read by the thread.
+fn pause_channel() -> (Pause, PauseListener) {
+    let paused = Arc::new(AtomicBool::new(false));
+    let (ack_sender, ack_receiver) = mpsc::channel();
+    let (resume_sender, resume_receiver) = mpsc::channel();
+    (
+        Pause {
+            paused: paused.clone(),
+            acks: ack_receiver,
+            resumes: resume_sender,
+        },
+        PauseListener {
+            paused,
+            acks: ack_sender,
+            resumes: resume_receiver,
+        },
+    )
+}
+
+impl Pause {
+    /// Waits for the thread to stop reading the terminal, doing nothing if it's already paused.
+    fn pause(&self) {
+        if !self.paused.swap(true, Ordering::SeqCst) {
+            let _ = self.acks.recv();
+        }
+    }
+
+    /// Lets the thread read the terminal again, doing nothing if it's not paused.
+    fn resume(&self) {
+        if self.paused.swap(false, Ordering::SeqCst) {
+            let _ = self.resumes.send(());
+        }
+    }
+}
+
+impl PauseListener {
+    /// Blocks the thread while it's paused, acknowledging the pause first.
+    ///
+    /// Gives `false` when the other side of the handshake is gone, i.e. when the thread should
+    /// stop.
+    fn wait_while_paused(&self) -> bool {
+        if !self.paused.load(Ordering::SeqCst) {
+            return true;
+        }
+        self.acks.send(()).is_ok() && self.resumes.recv().is_ok()
+    }
+}
+
 /// Terminal event handler.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -27,6 +132,8 @@ pub struct EventHandler {
     receiver: mpsc::Receiver<Event>,
     /// Event handler thread.
     handler: thread::JoinHandle<()>,
+    /// Whether the events of the terminal are left to another program, see [`EventHandler::pause`].
+    pause: Pause,
 }
 
 impl EventHandler {
@@ -34,11 +141,16 @@ impl EventHandler {
     pub fn new(tick_rate: u64) -> Self {
         let tick_rate = Duration::from_millis(tick_rate);
         let (sender, receiver) = mpsc::channel();
+        let (pause, listener) = pause_channel();
         let handler = {
             let sender = sender.clone();
             thread::spawn(move || {
                 let mut last_tick = Instant::now();
                 loop {
+                    if !listener.wait_while_paused() {
+                        return;
+                    }
+
                     let timeout = tick_rate
                         .checked_sub(last_tick.elapsed())
                         .unwrap_or(tick_rate);
@@ -64,9 +176,22 @@ impl EventHandler {
             sender,
             receiver,
             handler,
+            pause,
         }
     }
 
+    /// Stops reading the events of the terminal, e.g. for a pager to read them instead.
+    ///
+    /// This waits for the thread to be done with the event it might be waiting for.
+    pub fn pause(&self) {
+        self.pause.pause();
+    }
+
+    /// Reads the events of the terminal again after [`EventHandler::pause`].
+    pub fn resume(&self) {
+        self.pause.resume();
+    }
+
     /// Receive the next event from the handler thread.
     ///
     /// This function will always block the current thread if
@@ -75,3 +200,92 @@ impl EventHandler {
         Ok(self.receiver.recv()?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{is_too_fast, pause_channel, Debouncer};
+
+    #[test]
+    fn debounce_predicate() {
+        let start = Instant::now();
+        let window = Duration::from_millis(50);
+
+        assert!(is_too_fast(
+            start,
+            start + Duration::from_millis(10),
+            window
+        ));
+        assert!(!is_too_fast(
+            start,
+            start + Duration::from_millis(50),
+            window
+        ));
+        assert!(!is_too_fast(
+            start,
+            start + Duration::from_millis(80),
+            window
+        ));
+        assert!(!is_too_fast(start, start, Duration::ZERO));
+    }
+
+    #[test]
+    fn debounce_repeated_keys() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty());
+        let k = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::empty());
+
+        let mut debouncer = Debouncer::new(Duration::from_millis(50));
+        assert!(debouncer.accept(j, at(0)));
+        assert!(!debouncer.accept(j, at(10)));
+        // NOTE: the window starts again from the last key that has been handled
+        assert!(!debouncer.accept(j, at(40)));
+        assert!(debouncer.accept(j, at(60)));
+        assert!(debouncer.accept(k, at(65)));
+        assert!(debouncer.accept(j, at(70)));
+
+        let mut debouncer = Debouncer::new(Duration::ZERO);
+        assert!(debouncer.accept(j, at(0)));
+        assert!(debouncer.accept(j, at(0)));
+    }
+
+    #[test]
+    fn pause_with_acknowledgement() {
+        let (pause, listener) = pause_channel();
+        let reads = Arc::new(AtomicUsize::new(0));
+        let thread = {
+            let reads = reads.clone();
+            thread::spawn(move || {
+                while listener.wait_while_paused() {
+                    reads.fetch_add(1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+        };
+
+        // NOTE: nothing is read anymore as soon as the pause is over
+        pause.pause();
+        let paused_reads = reads.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(reads.load(Ordering::SeqCst), paused_reads);
+        pause.pause();
+
+        pause.resume();
+        while reads.load(Ordering::SeqCst) == paused_reads {
+            thread::sleep(Duration::from_millis(1));
+        }
+        pause.resume();
+
+        // NOTE: the thread stops with the handler
+        pause.pause();
+        drop(pause);
+        thread.join().unwrap();
+    }
+}
@@ -0,0 +1,437 @@
+//! load structured data from a file on disk, to be explored without piping it through Nushell
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use nu_protocol::{Record, Span, Value};
+
+use crate::nu::{
+    nuon::from_nuon,
+    value::{to_json, to_yaml},
+};
+
+/// the formats that can be loaded by [`load_file`], detected from the extension of the file
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Json,
+    Toml,
+    Yaml,
+    Nuon,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "nuon" => Some(Self::Nuon),
+            _ => None,
+        }
+    }
+}
+
+fn from_json(value: serde_json::Value) -> Value {
+    let span = Span::unknown();
+    match value {
+        serde_json::Value::Null => Value::nothing(span),
+        serde_json::Value::Bool(b) => Value::bool(b, span),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::int(i, span),
+            None => Value::float(n.as_f64().unwrap_or(f64::NAN), span),
+        },
+        serde_json::Value::String(s) => Value::string(s, span),
+        serde_json::Value::Array(vals) => {
+            Value::list(vals.into_iter().map(from_json).collect(), span)
+        }
+        serde_json::Value::Object(map) => Value::record(
+            map.into_iter()
+                .map(|(k, v)| (k, from_json(v)))
+                .collect::<Record>(),
+            span,
+        ),
+    }
+}
+
+fn from_toml(value: toml::Value) -> Value {
+    let span = Span::unknown();
+    match value {
+        toml::Value::Boolean(b) => Value::bool(b, span),
+        toml::Value::Integer(i) => Value::int(i, span),
+        toml::Value::Float(f) => Value::float(f, span),
+        toml::Value::String(s) => Value::string(s, span),
+        // NOTE: TOML dates can be partial, e.g. only a time, so they are kept as strings
+        toml::Value::Datetime(d) => Value::string(d.to_string(), span),
+        toml::Value::Array(vals) => Value::list(vals.into_iter().map(from_toml).collect(), span),
+        toml::Value::Table(table) => Value::record(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, from_toml(v)))
+                .collect::<Record>(),
+            span,
+        ),
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn from_yaml(value: serde_yaml::Value) -> Value {
+    let span = Span::unknown();
+    match value {
+        serde_yaml::Value::Null => Value::nothing(span),
+        serde_yaml::Value::Bool(b) => Value::bool(b, span),
+        serde_yaml::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::int(i, span),
+            None => Value::float(n.as_f64().unwrap_or(f64::NAN), span),
+        },
+        serde_yaml::Value::String(s) => Value::string(s, span),
+        serde_yaml::Value::Sequence(vals) => {
+            Value::list(vals.into_iter().map(from_yaml).collect(), span)
+        }
+        serde_yaml::Value::Mapping(map) => Value::record(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let key = match k {
+                        serde_yaml::Value::String(s) => s,
+                        k => serde_yaml::to_string(&k)
+                            .unwrap_or_default()
+                            .trim_end()
+                            .to_string(),
+                    };
+                    (key, from_yaml(v))
+                })
+                .collect::<Record>(),
+            span,
+        ),
+        serde_yaml::Value::Tagged(tagged) => from_yaml(tagged.value),
+    }
+}
+
+/// parse a string holding a JSON or NUON list or record, e.g. a field of an API response
+///
+/// strings that do not describe a list or a record give nothing, see [`from_nuon`].
+pub(crate) fn parse_structured(s: &str) -> Option<Value> {
+    let s = s.trim();
+    if !(s.starts_with('{') || s.starts_with('[')) {
+        return None;
+    }
+
+    let value = parse(s, Format::Json)
+        .or_else(|_| parse(s, Format::Nuon))
+        .ok()?;
+    match value {
+        Value::List { .. } | Value::Record { .. } => Some(value),
+        _ => None,
+    }
+}
+
+/// the error when loading or saving YAML without the `yaml` feature
+#[cfg(not(feature = "yaml"))]
+const NO_YAML: &str = "YAML is not available, please build `explore` with the `yaml` feature";
+
+/// parse YAML, only available with the `yaml` feature
+#[cfg(feature = "yaml")]
+fn parse_yaml(content: &str) -> Result<Value> {
+    Ok(from_yaml(serde_yaml::from_str(content)?))
+}
+
+/// parse YAML, only available with the `yaml` feature
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_content: &str) -> Result<Value> {
+    Err(anyhow!(NO_YAML))
+}
+
+/// parse the content of a file in the given format
+fn parse(content: &str, format: Format) -> Result<Value> {
+    match format {
+        Format::Json => Ok(from_json(serde_json::from_str(content)?)),
+        Format::Toml => Ok(from_toml(toml::from_str(content)?)),
+        Format::Yaml => parse_yaml(content),
+        Format::Nuon => from_nuon(content).map_err(|err| anyhow!(err)),
+    }
+}
+
+/// load the file at *path* as a Nushell value, detecting its format from its extension
+///
+/// JSON, TOML, YAML and NUON are supported, YAML only with the `yaml` feature.
+pub fn load_file(path: &Path) -> Result<Value> {
+    let format = format_of(path)?;
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("could not read {}: {}", path.display(), e))?;
+
+    parse(&content, format).map_err(|e| anyhow!("could not parse {}: {}", path.display(), e))
+}
+
+/// give the format of the file at *path*, or an error suggesting to use Nushell instead
+fn format_of(path: &Path) -> Result<Format> {
+    Format::from_path(path).ok_or_else(|| {
+        anyhow!(
+            "unknown format, should be one of [json, toml, yaml, nuon], try `open {} | explore`",
+            path.display()
+        )
+    })
+}
+
+/// give the format in which [`save_value`] would save to *path*
+fn save_format(path: &Path) -> Result<Format> {
+    match format_of(path)? {
+        Format::Nuon => Err(anyhow!(
+            "NUON is not supported directly, try to peek the data and `save {}`",
+            path.display()
+        )),
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(anyhow!(NO_YAML)),
+        format => Ok(format),
+    }
+}
+
+/// check that values can be saved to *path* with [`save_value`], e.g. before exploring the data
+/// to save, for a bad extension not to be found only at the end
+pub(crate) fn check_save_path(path: &Path) -> Result<()> {
+    save_format(path).map(|_| ())
+}
+
+/// save *value* to the file at *path*, in the format given by its extension
+///
+/// this is the inverse of [`load_file`] and supports the same formats, the values without an
+/// equivalent in JSON being saved as strings. TOML has no `null` and needs a record at the top.
+pub fn save_value(value: &Value, path: &Path) -> Result<()> {
+    let json = to_json(value);
+    let content = match save_format(path)? {
+        Format::Json => serde_json::to_string_pretty(&json)? + "\n",
+        Format::Toml => toml::to_string_pretty(&json)?,
+        Format::Yaml => to_yaml(value).map_err(|err| anyhow!(err))?,
+        Format::Nuon => unreachable!("NUON is not a format to save to"),
+    };
+
+    std::fs::write(path, content).map_err(|e| anyhow!("could not write {}: {}", path.display(), e))
+}
+
+/// expand a leading `~` of *path* to the home directory of the user, like Nushell does, e.g. for
+/// the paths of the config
+///
+/// the path is left as is when the home directory is not known.
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return PathBuf::from(path),
+    };
+    match std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        Some(home) => PathBuf::from(home).join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use nu_protocol::{record, Value};
+
+    use super::{
+        check_save_path, expand_home, load_file, parse, parse_structured, save_value, Format,
+    };
+
+    #[test]
+    fn detect_format() {
+        let cases = vec![
+            ("foo.json", Some(Format::Json)),
+            ("foo.toml", Some(Format::Toml)),
+            ("foo.yaml", Some(Format::Yaml)),
+            ("foo.YML", Some(Format::Yaml)),
+            ("foo.nuon", Some(Format::Nuon)),
+            ("foo.txt", None),
+            ("foo", None),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(Format::from_path(Path::new(path)), expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn load_json() {
+        let path = std::env::temp_dir().join("nu_plugin_explore_load_json.json");
+        std::fs::write(
+            &path,
+            r#"{"b": [1, 2.5, null], "a": {"c": true, "d": "foo"}}"#,
+        )
+        .unwrap();
+
+        let value = load_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // NOTE: the order of the keys is the one of the file
+        assert_eq!(
+            value,
+            Value::test_record(record! {
+                "b" => Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_float(2.5),
+                    Value::test_nothing(),
+                ]),
+                "a" => Value::test_record(record! {
+                    "c" => Value::test_bool(true),
+                    "d" => Value::test_string("foo"),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn load_toml() {
+        let path = std::env::temp_dir().join("nu_plugin_explore_load_toml.toml");
+        std::fs::write(
+            &path,
+            "name = \"explore\"\nversion = 2\n\n[deps]\nfoo = [\"a\", \"b\"]\n",
+        )
+        .unwrap();
+
+        let value = load_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            value,
+            Value::test_record(record! {
+                "name" => Value::test_string("explore"),
+                "version" => Value::test_int(2),
+                "deps" => Value::test_record(record! {
+                    "foo" => Value::test_list(vec![
+                        Value::test_string("a"),
+                        Value::test_string("b"),
+                    ]),
+                }),
+            })
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn parse_yaml() {
+        assert_eq!(
+            parse("a: 1\nb:\n  - x\n  - y\n", Format::Yaml).unwrap(),
+            Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_list(vec![Value::test_string("x"), Value::test_string("y")]),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_structured_strings() {
+        let cases = vec![
+            (
+                r#"{"a": 1}"#,
+                Some(Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                })),
+            ),
+            (
+                "{a: 1, b: [x, 2]}",
+                Some(Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "b" => Value::test_list(vec![Value::test_string("x"), Value::test_int(2)]),
+                })),
+            ),
+            (
+                " [1, 2] ",
+                Some(Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_int(2),
+                ])),
+            ),
+            (
+                "{size: 1024b, tags: [[name]; [x]]}  # a NUON comment",
+                Some(Value::test_record(record! {
+                    "size" => Value::test_filesize(1024),
+                    "tags" => Value::test_list(vec![Value::test_record(record! {
+                        "name" => Value::test_string("x"),
+                    })]),
+                })),
+            ),
+            ("foo", None),
+            ("1", None),
+            ("{not closed", None),
+            ("{a: 12kg}", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(parse_structured(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn unsupported_files() {
+        assert!(load_file(Path::new("foo.txt"))
+            .unwrap_err()
+            .to_string()
+            .contains("unknown format"));
+        assert!(load_file(Path::new("does/not/exist.json"))
+            .unwrap_err()
+            .to_string()
+            .contains("could not read"));
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn yaml_without_feature() {
+        assert!(parse("a: 1", Format::Yaml)
+            .unwrap_err()
+            .to_string()
+            .contains("`yaml` feature"));
+        assert!(check_save_path(Path::new("foo.yaml"))
+            .unwrap_err()
+            .to_string()
+            .contains("`yaml` feature"));
+    }
+
+    #[test]
+    fn save_and_load_back() {
+        let value = Value::test_record(record! {
+            "b" => Value::test_list(vec![Value::test_int(1), Value::test_float(2.5)]),
+            "a" => Value::test_record(record! {
+                "c" => Value::test_bool(true),
+            }),
+        });
+
+        for ext in ["json", "toml", "yaml"] {
+            // NOTE: YAML needs the `yaml` feature
+            if ext == "yaml" && !cfg!(feature = "yaml") {
+                continue;
+            }
+
+            let path = std::env::temp_dir().join(format!("nu_plugin_explore_save.{}", ext));
+            save_value(&value, &path).unwrap();
+            let loaded = load_file(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(loaded, value, "{}", ext);
+        }
+
+        assert!(save_value(&value, Path::new("foo.nuon"))
+            .unwrap_err()
+            .to_string()
+            .contains("NUON is not supported"));
+        assert!(save_value(&Value::test_int(1), Path::new("foo.toml")).is_err());
+
+        assert!(check_save_path(Path::new("foo.json")).is_ok());
+        assert!(check_save_path(Path::new("foo.nuon")).is_err());
+        assert!(check_save_path(Path::new("foo.txt"))
+            .unwrap_err()
+            .to_string()
+            .contains("unknown format"));
+    }
+
+    #[test]
+    fn expand_home_directory() {
+        let Some(home) = std::env::var_os("HOME") else {
+            return;
+        };
+        let home = Path::new(&home);
+
+        assert_eq!(expand_home("~/.history.json"), home.join(".history.json"));
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(expand_home("~foo/a"), Path::new("~foo/a"));
+        assert_eq!(expand_home("/tmp/~/a"), Path::new("/tmp/~/a"));
+    }
+}
@@ -0,0 +1,196 @@
+//! a pretty JSON view of the list or the record under the cursor, whose nested lists and records
+//! can be folded to scan a big subtree, see [`crate::config::KeyBindingsMap::fold_view`]
+use std::collections::BTreeSet;
+
+use nu_protocol::Value;
+
+use crate::nu::value::repr_json;
+
+/// the marker of the lines opening a list or a record that is folded
+const FOLDED: &str = "▸ ";
+/// the marker of the lines opening a list or a record that is unfolded
+const UNFOLDED: &str = "▾ ";
+/// the marker of the other lines, as wide as the others
+const NO_FOLD: &str = "  ";
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FoldedJson {
+    /// the lines of the pretty JSON of the value
+    lines: Vec<String>,
+    /// the index of the line closing the list or the record opened by each line, if any
+    ends: Vec<Option<usize>>,
+    /// the lines opening a list or a record that is folded
+    folded: BTreeSet<usize>,
+    /// the index of the line under the cursor, never hidden in a fold
+    pub(crate) cursor: usize,
+}
+
+impl FoldedJson {
+    /// build the view of *value*, with nothing folded and the cursor on the first line
+    pub(crate) fn new(value: &Value) -> Self {
+        let lines: Vec<String> = repr_json(value).lines().map(String::from).collect();
+
+        // NOTE: the strings end with a quote, so only the lists and records are opened at the end
+        // of a line and closed at its start
+        let mut ends = vec![None; lines.len()];
+        let mut opened = vec![];
+        for (i, line) in lines.iter().enumerate() {
+            let line = line.trim();
+            if line.starts_with(['}', ']']) {
+                if let Some(start) = opened.pop() {
+                    ends[start] = Some(i);
+                }
+            }
+            if line.ends_with(['{', '[']) {
+                opened.push(i);
+            }
+        }
+
+        Self {
+            lines,
+            ends,
+            folded: BTreeSet::new(),
+            cursor: 0,
+        }
+    }
+
+    /// give the indices of the lines that are not hidden in a fold, in order
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut i = 0;
+        while i < self.lines.len() {
+            visible.push(i);
+            i = match self.ends[i] {
+                Some(end) if self.folded.contains(&i) => end + 1,
+                _ => i + 1,
+            };
+        }
+        visible
+    }
+
+    /// give the lines to show, each with its index and a marker telling whether it opens a folded
+    /// list or record, the folded ones being shown on a single line, e.g. `▸ "r": {…},`
+    pub(crate) fn rows(&self) -> Vec<(usize, String)> {
+        self.visible()
+            .into_iter()
+            .map(|i| {
+                let line = &self.lines[i];
+                let row = match self.ends[i] {
+                    Some(end) if self.folded.contains(&i) => {
+                        format!("{}{}…{}", FOLDED, line, self.lines[end].trim_start())
+                    }
+                    Some(_) => format!("{}{}", UNFOLDED, line),
+                    None => format!("{}{}", NO_FOLD, line),
+                };
+                (i, row)
+            })
+            .collect()
+    }
+
+    /// move the cursor by *step* visible lines, staying inside the view
+    pub(crate) fn move_cursor(&mut self, step: isize) {
+        let visible = self.visible();
+        let current = visible.iter().position(|&i| i == self.cursor).unwrap_or(0) as isize;
+        let next = (current + step).clamp(0, visible.len() as isize - 1);
+        self.cursor = visible[next as usize];
+    }
+
+    /// fold the list or the record opened on the line under the cursor, or unfold it if it's
+    /// already folded
+    ///
+    /// on any other line, the innermost list or record around the cursor is folded and the cursor
+    /// goes to its first line.
+    pub(crate) fn toggle(&mut self) {
+        if self.ends[self.cursor].is_some() {
+            if !self.folded.remove(&self.cursor) {
+                self.folded.insert(self.cursor);
+            }
+            return;
+        }
+
+        let around = (0..self.cursor)
+            .rev()
+            .find(|&i| self.ends[i].is_some_and(|end| end >= self.cursor));
+        if let Some(start) = around {
+            self.folded.insert(start);
+            self.cursor = start;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{record, Value};
+
+    use super::FoldedJson;
+
+    fn test_view() -> FoldedJson {
+        FoldedJson::new(&Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_string("{"),
+            }),
+            "i" => Value::test_int(3),
+        }))
+    }
+
+    fn text(view: &FoldedJson) -> Vec<String> {
+        view.rows().into_iter().map(|(_, row)| row).collect()
+    }
+
+    #[test]
+    fn fold_lines() {
+        let mut view = test_view();
+        assert_eq!(
+            text(&view),
+            vec![
+                "▾ {",
+                "▾   \"l\": [",
+                "      1,",
+                "      2",
+                "    ],",
+                "▾   \"r\": {",
+                "      \"a\": \"{\"",
+                "    },",
+                "    \"i\": 3",
+                "  }",
+            ]
+        );
+
+        view.move_cursor(1);
+        view.toggle();
+        assert_eq!(
+            text(&view),
+            vec![
+                "▾ {",
+                "▸   \"l\": […],",
+                "▾   \"r\": {",
+                "      \"a\": \"{\"",
+                "    },",
+                "    \"i\": 3",
+                "  }",
+            ]
+        );
+
+        // NOTE: the folded lines are skipped by the cursor
+        view.move_cursor(1);
+        assert_eq!(view.cursor, 5);
+
+        // NOTE: a line inside a list or a record folds it
+        view.move_cursor(1);
+        view.toggle();
+        assert_eq!(view.cursor, 5);
+        assert_eq!(text(&view)[2], "▸   \"r\": {…},");
+
+        view.move_cursor(-1);
+        view.toggle();
+        assert_eq!(text(&view).len(), 8);
+
+        view.move_cursor(-10);
+        assert_eq!(view.cursor, 0);
+        view.toggle();
+        assert_eq!(text(&view), vec!["▸ {…}"]);
+        view.move_cursor(10);
+        assert_eq!(view.cursor, 0);
+    }
+}
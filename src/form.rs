@@ -0,0 +1,300 @@
+//! a form to edit all the fields of a record at once, each key being a label next to its value
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    prelude::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use nu_protocol::{ast::CellPath, Record, Type, Value};
+
+use crate::{config::Config, nu::value::parse_as_type};
+
+/// the outcome of a key press in the [`Form`]
+#[derive(Debug, PartialEq)]
+pub enum FormEvent {
+    /// the edition is not over yet
+    Continue,
+    /// the edition is over and the record should be set to the value
+    Commit(Value),
+    /// a field could not be parsed as the type of its value, the form staying open on it
+    Invalid(String),
+}
+
+/// a single field of a [`Form`]
+struct Field {
+    key: String,
+    /// the text of the value, as typed so far
+    buffer: String,
+    /// the value before the edition, kept as is when the field is not changed
+    value: Value,
+    /// only the fields with a simple value can be typed in, not the lists and records
+    editable: bool,
+}
+
+pub struct Form {
+    /// the path of the edited record
+    pub(crate) path: CellPath,
+    fields: Vec<Field>,
+    /// the index of the field being typed in
+    focus: usize,
+}
+
+impl Form {
+    /// build a form for the *record* at *path*, starting on the field named *key* if it can be
+    /// edited, or nothing if *record* is not a record
+    pub(super) fn from_record(record: &Value, path: CellPath, key: Option<&str>) -> Option<Self> {
+        let Value::Record { val: rec, .. } = record else {
+            return None;
+        };
+
+        let fields: Vec<Field> = rec
+            .iter()
+            .map(|(key, value)| {
+                let editable = matches!(
+                    value.get_type(),
+                    Type::String | Type::Nothing | Type::Int | Type::Float | Type::Bool
+                );
+                let buffer = if editable {
+                    value.to_expanded_string(" ", &nu_protocol::Config::default())
+                } else {
+                    value.get_type().to_string()
+                };
+                Field {
+                    key: key.clone(),
+                    buffer,
+                    value: value.clone(),
+                    editable,
+                }
+            })
+            .collect();
+
+        let focus = fields
+            .iter()
+            .position(|f| f.editable && Some(f.key.as_str()) == key)
+            .or_else(|| fields.iter().position(|f| f.editable))
+            .unwrap_or(0);
+
+        Some(Self {
+            path,
+            fields,
+            focus,
+        })
+    }
+
+    /// move the focus to the next editable field in the direction of *step*, wrapping around
+    fn move_focus(&mut self, step: isize) {
+        let len = self.fields.len() as isize;
+        for i in 1..=len {
+            let next = (self.focus as isize + step * i).rem_euclid(len) as usize;
+            if self.fields[next].editable {
+                self.focus = next;
+                return;
+            }
+        }
+    }
+
+    /// build the record from the fields, parsing the changed ones as the type of their value
+    fn commit(&mut self) -> FormEvent {
+        let mut record = Record::new();
+        for (i, field) in self.fields.iter().enumerate() {
+            let original = field
+                .value
+                .to_expanded_string(" ", &nu_protocol::Config::default());
+            let value = if !field.editable || field.buffer == original {
+                field.value.clone()
+            } else {
+                match parse_as_type(&field.buffer, &field.value.get_type()) {
+                    Ok(value) => value,
+                    Err(err) => {
+                        self.focus = i;
+                        return FormEvent::Invalid(err);
+                    }
+                }
+            };
+            record.push(field.key.clone(), value);
+        }
+
+        FormEvent::Commit(Value::record(record, nu_protocol::Span::unknown()))
+    }
+
+    /// handle a key event
+    ///
+    /// <tab> and <down> go to the next field, <shift-tab> and <up> to the previous one and <enter>
+    /// commits the whole record. the edition is cancelled by the handler, with the key of
+    /// [`crate::config::KeyBindingsMap::normal`].
+    pub(super) fn handle_key(&mut self, key: &KeyEvent) -> FormEvent {
+        match &key.code {
+            KeyCode::Tab | KeyCode::Down => self.move_focus(1),
+            KeyCode::BackTab | KeyCode::Up => self.move_focus(-1),
+            KeyCode::Char(c) => {
+                if let Some(field) = self.fields.get_mut(self.focus).filter(|f| f.editable) {
+                    field.buffer.push(*c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(field) = self.fields.get_mut(self.focus).filter(|f| f.editable) {
+                    field.buffer.pop();
+                }
+            }
+            KeyCode::Enter => return self.commit(),
+            _ => {}
+        }
+
+        FormEvent::Continue
+    }
+
+    /// compute the index of the first field shown in a form of *height* lines, for the field being
+    /// typed in to always be visible
+    fn scroll_offset(&self, height: usize) -> usize {
+        self.focus.saturating_sub(height.saturating_sub(1))
+    }
+
+    /// render the form in *area*, one field per line with the keys aligned to the right and the
+    /// field being typed in highlighted
+    ///
+    /// the fields are scrolled when there are more of them than lines in *area*.
+    pub(super) fn render(&self, frame: &mut Frame, area: Rect, config: &Config) {
+        let width = self
+            .fields
+            .iter()
+            .map(|f| f.key.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let name_style = Style::default()
+            .fg(config.colors.normal.name.foreground)
+            .bg(config.colors.normal.name.background);
+        let data_style = Style::default()
+            .fg(config.colors.normal.data.foreground)
+            .bg(config.colors.normal.data.background);
+        let selected_style = Style::default()
+            .fg(config.colors.selected.foreground)
+            .bg(config.colors.selected.background);
+
+        let lines: Vec<Line> = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let style = if i == self.focus {
+                    selected_style
+                } else if field.editable {
+                    data_style
+                } else {
+                    data_style.add_modifier(Modifier::DIM)
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:>width$}", field.key), name_style),
+                    Span::raw(": "),
+                    Span::styled(field.buffer.clone(), style),
+                ])
+            })
+            .collect();
+
+        // NOTE: the top and bottom borders
+        let offset = self.scroll_offset(area.height.saturating_sub(2) as usize);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .scroll((offset as u16, 0))
+                .block(Block::default().borders(Borders::ALL).title("form")),
+            area,
+        );
+
+        if let Some(field) = self.fields.get(self.focus) {
+            let x = area.x + 1 + (width + 2 + field.buffer.chars().count()) as u16;
+            let y = area.y + 1 + (self.focus - offset) as u16;
+            if x < area.right() && y < area.bottom() {
+                frame.set_cursor(x, y);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use nu_protocol::{ast::CellPath, record, Record, Value};
+
+    use super::{Form, FormEvent};
+
+    fn test_form() -> Form {
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "r" => Value::test_record(record! {}),
+            "b" => Value::test_string("foo"),
+        });
+        Form::from_record(&record, CellPath { members: vec![] }, Some("a")).unwrap()
+    }
+
+    fn press(form: &mut Form, code: KeyCode) -> FormEvent {
+        form.handle_key(&KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn edit_fields() {
+        let mut form = test_form();
+
+        press(&mut form, KeyCode::Backspace);
+        press(&mut form, KeyCode::Char('2'));
+        // NOTE: the records cannot be typed in and are skipped
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, 2);
+        press(&mut form, KeyCode::Char('d'));
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(form.focus, 0);
+        press(&mut form, KeyCode::BackTab);
+        assert_eq!(form.focus, 2);
+
+        assert_eq!(
+            press(&mut form, KeyCode::Enter),
+            FormEvent::Commit(Value::test_record(record! {
+                "a" => Value::test_int(2),
+                "r" => Value::test_record(record! {}),
+                "b" => Value::test_string("food"),
+            }))
+        );
+    }
+
+    #[test]
+    fn scroll_long_form() {
+        let record = Value::test_record(
+            (0..10)
+                .map(|i| (format!("f{i}"), Value::test_int(i)))
+                .collect::<Record>(),
+        );
+        let mut form = Form::from_record(&record, CellPath { members: vec![] }, None).unwrap();
+
+        assert_eq!(form.scroll_offset(4), 0);
+        for _ in 0..5 {
+            press(&mut form, KeyCode::Down);
+        }
+        // NOTE: the field being typed in is on the last line
+        assert_eq!(form.scroll_offset(4), 2);
+        assert_eq!(form.scroll_offset(20), 0);
+    }
+
+    #[test]
+    fn invalid_field() {
+        let mut form = test_form();
+
+        press(&mut form, KeyCode::Char('x'));
+        press(&mut form, KeyCode::Tab);
+        assert_eq!(
+            press(&mut form, KeyCode::Enter),
+            FormEvent::Invalid("could not parse `1x` as int".into())
+        );
+        // NOTE: the form goes back to the invalid field
+        assert_eq!(form.focus, 0);
+    }
+
+    #[test]
+    fn form_of_non_records() {
+        let path = CellPath { members: vec![] };
+        assert!(Form::from_record(&Value::test_int(1), path, None).is_none());
+    }
+}
@@ -1,4 +1,7 @@
-use crossterm::event::KeyEvent;
+use std::ops::Range;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use nu_protocol::{
     ast::{CellPath, PathMember},
@@ -6,10 +9,33 @@ use nu_protocol::{
 };
 
 use crate::{
-    app::{App, Mode},
-    config::Config,
-    navigation::{self, Direction},
-    nu::value::transpose,
+    app::{App, HiddenView, Mode, Operator, PeekFormat, PendingKind, PendingOperator},
+    clipboard,
+    config::{repr_keycode, Config, Layout, LeftAtRoot, MissingDisplay, PathExportStyle},
+    edit::EditorEvent,
+    file::parse_structured,
+    fold::FoldedJson,
+    form::{Form, FormEvent},
+    mouse::{select, visible_view_rows},
+    navigation::{self, is_container, Direction},
+    nu::nuon::to_nuon,
+    nu::schema::{enum_values, next_enum_value},
+    nu::strings::{collapse_whitespace, is_tab_indented, retab},
+    nu::value::{
+        approx_size, cell_path_to_jmespath, cell_path_to_jsonpath, collect_leaves,
+        column_histogram, date_to_epoch, describe_container, duplicate_cell, edits_to_script,
+        filter_list, find_matches, flatten_one_level, flatten_to_dotted, format_with_separators,
+        infer_schema, insert_value_cell, is_table, join_list, lift_cell, numeric_stats,
+        parse_as_type, parse_cell_path, parse_envish, path_to_get_closure, path_to_nested_record,
+        pop_list_item, push_list_item, reconcile_cell_path, record_values_to_list,
+        records_to_columns, relative_path, remove_list_items, rename_field, replace_in_subtree,
+        repr_debug, repr_json, repr_type, search_results, sort_container, sort_list, split_string,
+        summarize_list, to_csv, to_markdown_table, to_patch, to_update_pipeline, to_where_clause,
+        to_yaml, transpose, try_hash_value, value_to_rust_literal, value_with_path, wrap_in_list,
+        wrap_in_record, SortBy, SortKind, WRAPPED_FIELD,
+    },
+    pager::pager_text,
+    tree::{self, EXPAND_ALL_MAX_DEPTH},
 };
 
 /// the result of a state transition
@@ -19,6 +45,12 @@ pub enum TransitionResult {
     Continue,
     Return(Value),
     Mutate(Value, CellPath),
+    /// explore the list or record at the cell path in a nested session
+    Explore(CellPath),
+    /// show the text in the pager of the system, see [`crate::pager`]
+    Page(String),
+    /// read the config file again and apply it, see [`reload_config`]
+    ReloadConfig,
     Error(String),
 }
 
@@ -29,18 +61,251 @@ impl TransitionResult {
     }
 }
 
+/// the message shown when trying to change the data in inspect mode, see [`App::inspect`]
+pub(crate) const READ_ONLY: &str = "the data is read-only in inspect mode";
+
+/// refuse to change the data in inspect mode, showing [`READ_ONLY`], see [`App::inspect`]
+///
+/// every key that changes the data checks it up front, before asking for anything else, e.g. a
+/// delimiter or the fields of a form, for the user not to type what would be thrown away. the
+/// changes that would still get through are refused when applied, in [`crate::run`].
+pub(crate) fn reject_if_read_only(app: &mut App) -> Option<TransitionResult> {
+    if !app.inspect {
+        return None;
+    }
+
+    app.message = Some(READ_ONLY.into());
+    Some(TransitionResult::Continue)
+}
+
+/// the message shown when expanding or collapsing all the nodes outside of [`Layout::Tree`]
+const TREE_ONLY: &str = "all the nodes can only be expanded or collapsed in the tree layout";
+
 /// Handles the key events and updates the state of [`App`].
-#[allow(clippy::collapsible_if)]
+///
+/// the positions visited along the way are remembered in [`App::nav_history`], except while
+/// exploring data derived from the original one, e.g. the results of a search.
 pub fn handle_key_events(
     key_event: KeyEvent,
     app: &mut App,
     config: &Config,
 ) -> Result<TransitionResult, ShellError> {
+    let was_hidden = app.hidden_data.is_some();
+    let from = app.absolute_position();
+
+    let result = handle_key(key_event, app, config);
+    // NOTE: some moves go straight to a cell, e.g. the search or the marks
+    app.clamp_to_max_depth();
+    navigation::reset_column_offset(app, &from);
+
+    if !was_hidden && app.hidden_data.is_none() {
+        app.visit(from, app.absolute_position());
+    }
+
+    result
+}
+
+#[allow(clippy::collapsible_if)]
+fn handle_key(
+    key_event: KeyEvent,
+    app: &mut App,
+    config: &Config,
+) -> Result<TransitionResult, ShellError> {
+    app.message = None;
+
     match app.mode {
         Mode::Normal => {
+            if let Some(pending) = app.pending_sequence.take() {
+                app.pending_since = None;
+                return Ok(finish_pending_sequence(pending, key_event, app, config));
+            }
+            if let Some(typed) = app.row_labels.take() {
+                type_row_label(app, config, typed, &key_event);
+                return Ok(TransitionResult::Continue);
+            }
+            if let Some(pending) = app.pending_operator.take() {
+                return Ok(finish_operator(pending, &key_event, app, config));
+            }
+            // NOTE: any other key than a digit ends the number of the match being typed
+            let pending_match = app.pending_match.take();
+
+            if config.operator_pending
+                && !key_event.modifiers.contains(KeyModifiers::ALT)
+                && key_event.code == config.keybindings.delete_operator
+            {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                app.pending_operator = Some(PendingOperator {
+                    operator: Operator::Delete,
+                    count: None,
+                });
+                return Ok(TransitionResult::Continue);
+            }
+
+            if key_event.modifiers.contains(KeyModifiers::ALT) {
+                if key_event.code == config.keybindings.history_back {
+                    move_in_history(app, -1, config);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.history_forward {
+                    move_in_history(app, 1, config);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.collapse_others {
+                    tree::collapse_others(&mut app.expanded, &app.position.members);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.jump_to_clipboard {
+                    return Ok(match clipboard::get() {
+                        Ok(text) => jump_to_path_string(app, &text, config),
+                        Err(err) => TransitionResult::Error(err),
+                    });
+                } else if key_event.code == config.keybindings.label_rows {
+                    match visible_view_rows(app, config) {
+                        Some((_, rows)) if rows > 0 => app.row_labels = Some(0),
+                        _ => app.message = Some("no rows to label in this view".into()),
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.table_to_columns {
+                    return Ok(table_to_columns(app));
+                } else if key_event.code == config.keybindings.summarize_list {
+                    return Ok(summarize_list_under_cursor(app));
+                } else if key_event.code == config.keybindings.yank_csv {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    let csv = match to_csv(&cell) {
+                        Ok(csv) => csv,
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    };
+                    match clipboard::set(csv) {
+                        Ok(()) => app.message = Some("copied the value as CSV".into()),
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.yank_dotted {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    if !is_container(Some(&cell)) {
+                        app.message =
+                            Some("only lists and records can be flattened to dotted keys".into());
+                        return Ok(TransitionResult::Continue);
+                    }
+                    match clipboard::set(repr_json(&flatten_to_dotted(&cell))) {
+                        Ok(()) => app.message = Some("copied the value with dotted keys".into()),
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.yank_patch {
+                    let Some(patch) = session_patch(app) else {
+                        app.message = Some("no edits to export as a patch".into());
+                        return Ok(TransitionResult::Continue);
+                    };
+                    match clipboard::set(patch) {
+                        Ok(()) => app.message = Some("copied the edits as a patch".into()),
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.yank_update {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    let pipeline = to_update_pipeline(&app.absolute_position(), &cell);
+                    match clipboard::set(pipeline) {
+                        Ok(()) => app.message = Some("copied the value as an update".into()),
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.yank_where {
+                    let Some(clause) = where_clause_under_cursor(app) else {
+                        app.message = Some("only the cells of tables can be filtered on".into());
+                        return Ok(TransitionResult::Continue);
+                    };
+                    match clipboard::set(clause) {
+                        Ok(()) => app.message = Some("copied the filter on the cell".into()),
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    }
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.toggle_scope_lock {
+                    app.scope_lock = !app.scope_lock;
+                    app.message = Some(if app.scope_lock {
+                        "locked the navigation in the current container".into()
+                    } else {
+                        "unlocked the navigation".into()
+                    });
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.toggle_group {
+                    navigation::toggle_group(app, config);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.fold_view {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    if !is_container(Some(&cell)) {
+                        app.message = Some("only lists and records can be folded".into());
+                        return Ok(TransitionResult::Continue);
+                    }
+                    app.hit_bottom();
+                    app.folded_json = Some(FoldedJson::new(&cell));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.toggle_key_focus {
+                    toggle_key_focus(app);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.jump_to_matching {
+                    navigation::jump_to_matching_in_data(app, config);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.reload_config {
+                    if app.config_file.is_none() {
+                        app.message =
+                            Some("no config file to reload, give one with `--config`".into());
+                        return Ok(TransitionResult::Continue);
+                    }
+                    return Ok(TransitionResult::ReloadConfig);
+                } else if key_event.code == config.keybindings.show_size {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    app.message = Some(format!(
+                        "about {} bytes, as compact JSON",
+                        approx_size(&cell)
+                    ));
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.commit_sort {
+                    if let Some(result) = reject_if_read_only(app) {
+                        return Ok(result);
+                    }
+                    app.pending_sequence = Some(PendingKind::CommitSort);
+                    return Ok(TransitionResult::Continue);
+                } else if key_event.code == config.keybindings.open_in_pager {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    return Ok(TransitionResult::Page(pager_text(&cell)));
+                } else if key_event.code == config.keybindings.yank_summary {
+                    let cell = app
+                        .value
+                        .clone()
+                        .follow_cell_path(&app.position.members, false)?;
+                    let summary = describe_container(&cell);
+                    match clipboard::set(summary.clone()) {
+                        Ok(()) => app.message = Some(format!("copied {summary}")),
+                        Err(err) => return Ok(TransitionResult::Error(err)),
+                    }
+                    return Ok(TransitionResult::Continue);
+                }
+            }
+
             if key_event.code == config.keybindings.quit {
                 return Ok(TransitionResult::Quit);
             } else if key_event.code == config.keybindings.insert {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
                 match app.enter_editor() {
                     Ok(_) => return Ok(TransitionResult::Continue),
                     Err(err) => return Ok(TransitionResult::Error(err)),
@@ -49,18 +314,376 @@ pub fn handle_key_events(
                 app.mode = Mode::Peeking;
                 return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.navigation.down {
-                navigation::go_up_or_down_in_data(app, Direction::Down);
+                go_up_or_down(app, Direction::Down, config);
                 return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.navigation.up {
-                navigation::go_up_or_down_in_data(app, Direction::Up);
+                go_up_or_down(app, Direction::Up, config);
                 return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.navigation.right {
-                navigation::go_deeper_in_data(app);
+                if app.focused_key().is_some() {
+                    app.message = Some("the keys have nothing below them, focus the values".into());
+                } else if !is_scope_locked(app) {
+                    go_deeper(app, config);
+                }
                 return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.navigation.left {
+                if is_scope_locked(app) {
+                    return Ok(TransitionResult::Continue);
+                }
+                // NOTE: the keys are only focused in the current record
+                app.key_focus = false;
+                if config.left_at_root == LeftAtRoot::Quit && app.position.members.len() <= 1 {
+                    return Ok(TransitionResult::Quit);
+                }
                 navigation::go_back_in_data(app);
                 return Ok(TransitionResult::Continue);
+            } else if (key_event.code == config.keybindings.expand_all
+                || key_event.code == config.keybindings.collapse_all)
+                && config.layout != Layout::Tree
+            {
+                app.message = Some(TREE_ONLY.into());
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.expand_all {
+                // NOTE: the nodes at the maximum depth are not expanded
+                let depth = match app.max_position_depth() {
+                    Some(max) => EXPAND_ALL_MAX_DEPTH.min(max.saturating_sub(1)),
+                    None => EXPAND_ALL_MAX_DEPTH,
+                };
+                app.expanded = tree::expand_all(&app.value, depth);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.collapse_all {
+                if !is_scope_locked(app) {
+                    app.expanded.clear();
+                    // NOTE: the focused node might be hidden inside a collapsed node
+                    app.position.members.truncate(1);
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.collapse {
+                if !is_scope_locked(app) {
+                    match config.layout {
+                        Layout::Tree => tree::collapse_or_go_to_parent(app),
+                        _ => navigation::go_back_in_data(app),
+                    }
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.center {
+                tree::center(app, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.form {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                let mut path = app.position.clone();
+                let key = match path.members.pop() {
+                    Some(PathMember::String { val, .. }) => Some(val),
+                    _ => None,
+                };
+                let record = app.value.clone().follow_cell_path(&path.members, false)?;
+                match Form::from_record(&record, path, key.as_deref()) {
+                    Some(form) => {
+                        app.form = Some(form);
+                        app.mode = Mode::Form;
+                    }
+                    None => app.message = Some("only records can be edited as a form".into()),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.replace {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                app.replace_find = None;
+                app.replace_input.clear();
+                app.mode = Mode::Replace;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.search {
+                app.search_query.clear();
+                app.search_changed(Instant::now());
+                app.mode = Mode::Search;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.next_match {
+                jump_to_match(app, 1, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.previous_match {
+                jump_to_match(app, -1, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.next_edit {
+                jump_to_edit(app, 1, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.previous_edit {
+                jump_to_edit(app, -1, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.next_leaf {
+                match navigation::next_leaf(&app.value, &app.position) {
+                    Some(path) => app.jump_to(&path, config),
+                    None => app.message = Some("no value to jump to in the data".into()),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.drill {
+                let path = navigation::drill_to_leaf(&app.value, &app.position);
+                if path == app.position {
+                    app.message = Some("nothing to drill into".into());
+                } else {
+                    app.jump_to(&path, config);
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.next_container {
+                match navigation::sibling_in_next_container(&app.value, &app.position.members) {
+                    Some(members) => app.jump_to(&CellPath { members }, config),
+                    None => app.message = Some("no other list or record next to this one".into()),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.parse_envish {
+                toggle_envish(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.flatten {
+                toggle_flatten(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.schema {
+                toggle_schema(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.sort {
+                // NOTE: another temporary view would be lost under the sorted list
+                if app.hidden_data.is_none() {
+                    app.pending_sequence = Some(PendingKind::Sort);
+                } else if !app.restore_hidden_view(HiddenView::Sort) {
+                    app.message = Some("only the data can be sorted, not a temporary view".into());
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.search_results {
+                toggle_search_results(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_legend {
+                app.show_legend = !app.show_legend;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_quoted_keys {
+                app.quote_keys = !app.quote_keys;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_ancestors {
+                app.show_ancestors = !app.show_ancestors;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_minimap {
+                app.show_minimap = !app.show_minimap;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_table_view {
+                app.table_view = !app.table_view;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.columns_left {
+                navigation::page_table_columns(app, config, false);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.columns_right {
+                navigation::page_table_columns(app, config, true);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_compare {
+                app.show_compare = !app.show_compare;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_histogram {
+                toggle_histogram(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.scroll_preview_down {
+                app.preview_scroll = app.preview_scroll.saturating_add(1);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.scroll_preview_up {
+                app.preview_scroll = app.preview_scroll.saturating_sub(1);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_relative {
+                app.relative_indices = !app.relative_indices;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_reverse {
+                app.reversed = !app.reversed;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.cycle_breadcrumb {
+                app.breadcrumb_style = app.breadcrumb_style.next();
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_wrap {
+                app.wrap_focused = !app.wrap_focused;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.toggle_chrome {
+                app.show_chrome = !app.show_chrome;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_debug {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                match clipboard::set(repr_debug(&cell)) {
+                    Ok(()) => app.message = Some("copied the debug string of the cell".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_container_json {
+                let mut path = app.position.members.clone();
+                path.pop();
+                let container = app.value.clone().follow_cell_path(&path, false)?;
+                match clipboard::set(repr_json(&container)) {
+                    Ok(()) => app.message = Some("copied the container as JSON".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_markdown {
+                let mut path = app.position.members.clone();
+                path.pop();
+                let view = app.value.clone().follow_cell_path(&path, false)?;
+                match clipboard::set(to_markdown_table(&view)) {
+                    Ok(()) => app.message = Some("copied the view as a Markdown table".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_yaml {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let yaml = match to_yaml(&cell) {
+                    Ok(yaml) => yaml,
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                };
+                match clipboard::set(yaml) {
+                    Ok(()) => app.message = Some("copied the value as YAML".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_closure {
+                match clipboard::set(path_to_get_closure(&app.absolute_position())) {
+                    Ok(()) => app.message = Some("copied the path as a closure".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_relative_path {
+                app.pending_sequence = Some(PendingKind::YankRelativePath);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_path {
+                let position = app.absolute_position();
+                let path = match config.path_export_style {
+                    PathExportStyle::JsonPath => cell_path_to_jsonpath(&position),
+                    PathExportStyle::JmesPath => cell_path_to_jmespath(&position),
+                };
+                match clipboard::set(path.clone()) {
+                    Ok(()) => app.message = Some(format!("copied {}", path)),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_type {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                match clipboard::set(repr_type(&cell)) {
+                    Ok(()) => app.message = Some("copied the type of the cell".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_leaves {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let leaves = Value::list(collect_leaves(&cell), Span::unknown());
+                match clipboard::set(repr_json(&leaves)) {
+                    Ok(()) => app.message = Some("copied the leaves of the cell as a list".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_epoch {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let Some(epoch) = date_to_epoch(&cell) else {
+                    app.message = Some("only dates can be copied as epoch seconds".into());
+                    return Ok(TransitionResult::Continue);
+                };
+                match clipboard::set(epoch.to_string()) {
+                    Ok(()) => app.message = Some(format!("copied {epoch}")),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_thousands {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let Some(number) = format_with_separators(&cell, &config.thousands_sep) else {
+                    app.message = Some("only numbers can be copied with separators".into());
+                    return Ok(TransitionResult::Continue);
+                };
+                match clipboard::set(number.clone()) {
+                    Ok(()) => app.message = Some(format!("copied {number}")),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_rust {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                match clipboard::set(value_to_rust_literal(&cell)) {
+                    Ok(()) => app.message = Some("copied the cell as a Rust literal".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_stats {
+                return Ok(yank_stats(app));
+            } else if key_event.code == config.keybindings.yank_script {
+                if app.edited.is_empty() {
+                    app.message = Some("no edits to export yet".into());
+                    return Ok(TransitionResult::Continue);
+                }
+                match clipboard::set(edits_to_script(&app.full_value(), &app.edited)) {
+                    Ok(()) => app.message = Some("copied the script of the edits".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_with_path {
+                match clipboard::set(value_with_path_under_cursor(app)?) {
+                    Ok(()) => app.message = Some("copied the cell with its path".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.yank_hash {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let digest = match try_hash_value(&cell) {
+                    Ok(digest) => digest,
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                };
+                match clipboard::set(digest) {
+                    Ok(()) => app.message = Some("copied the SHA-256 digest of the cell".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.duplicate_and_yank {
+                return Ok(duplicate_and_yank(app));
+            } else if key_event.code == config.keybindings.append_field {
+                return Ok(append_field(app, config));
+            } else if key_event.code == config.keybindings.append_item {
+                return Ok(append_item(app, config));
+            } else if key_event.code == config.keybindings.truncate_list {
+                return Ok(truncate_list(app));
+            } else if key_event.code == config.keybindings.jump_recent {
+                jump_to_recent(app, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.lift {
+                return Ok(lift(app));
+            } else if key_event.code == config.keybindings.yank_merge {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let Some(record) = path_to_nested_record(&app.absolute_position(), &cell) else {
+                    app.message = Some("the cells inside lists cannot be merged as records".into());
+                    return Ok(TransitionResult::Continue);
+                };
+                match clipboard::set(repr_json(&record)) {
+                    Ok(()) => app.message = Some("copied the cell as a record to merge".into()),
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+                return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.transpose {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
                 let mut path = app.position.clone();
                 path.members.pop();
 
@@ -89,6 +712,177 @@ pub fn handle_key_events(
                     return Ok(TransitionResult::Mutate(transpose, path));
                 }
 
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.pin {
+                match app.position.members.last() {
+                    Some(PathMember::String { val, .. }) if !app.is_at_bottom() => {
+                        if !app.pinned.remove(val) {
+                            app.pinned.insert(val.clone());
+                        }
+                    }
+                    _ => app.message = Some("only the fields of records can be pinned".into()),
+                }
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.wrap_in_list {
+                return Ok(wrap(
+                    app,
+                    wrap_in_list,
+                    PathMember::Int {
+                        val: 0,
+                        span: Span::unknown(),
+                        optional: false,
+                    },
+                ));
+            } else if key_event.code == config.keybindings.wrap_in_record {
+                return Ok(wrap(
+                    app,
+                    wrap_in_record,
+                    PathMember::String {
+                        val: WRAPPED_FIELD.into(),
+                        span: Span::unknown(),
+                        optional: false,
+                    },
+                ));
+            } else if key_event.code == config.keybindings.explore_nested {
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                if !matches!(cell, Value::List { .. } | Value::Record { .. }) {
+                    app.message =
+                        Some("only lists and records can be explored in a nested session".into());
+                    return Ok(TransitionResult::Continue);
+                }
+                return Ok(TransitionResult::Explore(app.position.clone()));
+            } else if key_event.code == config.keybindings.reroot {
+                reroot(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.pop_root {
+                pop_root(app);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.snapshot {
+                app.message = match app.snapshot {
+                    Some(_) => {
+                        app.snapshot = None;
+                        Some("dropped the snapshot".into())
+                    }
+                    None => {
+                        app.snapshot = Some(app.full_value());
+                        Some("took a snapshot of the data".into())
+                    }
+                };
+                app.refresh_changes();
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.mark {
+                app.pending_sequence = Some(PendingKind::Mark);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.type_ahead {
+                app.pending_sequence = Some(PendingKind::TypeAhead);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.jump_to_mark {
+                app.pending_sequence = Some(PendingKind::JumpToMark);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.type_filter {
+                app.pending_sequence = Some(PendingKind::TypeFilter);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.split_string {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                app.pending_sequence = Some(PendingKind::SplitString);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.join_list {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                app.pending_sequence = Some(PendingKind::JoinList);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.parse_string {
+                return Ok(parse_cell(app));
+            } else if key_event.code == config.keybindings.record_to_values {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let Some(list) = record_values_to_list(&cell) else {
+                    app.message = Some("only records can be turned into their values".into());
+                    return Ok(TransitionResult::Continue);
+                };
+                return Ok(TransitionResult::Mutate(list, app.position.clone()));
+            } else if key_event.code == config.keybindings.filter_list {
+                return Ok(filter_cell(app));
+            } else if key_event.code == config.keybindings.cycle_enum {
+                return Ok(cycle_enum(app));
+            } else if key_event.code == config.keybindings.retab {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let Value::String { val, .. } = cell else {
+                    app.message = Some("only strings can be retabbed".into());
+                    return Ok(TransitionResult::Continue);
+                };
+                let to_spaces = is_tab_indented(&val);
+                app.message = Some(if to_spaces {
+                    "converted the tabs to spaces".into()
+                } else {
+                    "converted the spaces to tabs".into()
+                });
+                return Ok(TransitionResult::Mutate(
+                    Value::string(retab(&val, config.tab_width, to_spaces), Span::unknown()),
+                    app.position.clone(),
+                ));
+            } else if key_event.code == config.keybindings.collapse_string {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                let Value::String { val, .. } = cell else {
+                    app.message = Some("only strings can be collapsed".into());
+                    return Ok(TransitionResult::Continue);
+                };
+                return Ok(TransitionResult::Mutate(
+                    Value::string(collapse_whitespace(&val), Span::unknown()),
+                    app.position.clone(),
+                ));
+            } else if key_event.code == config.keybindings.paste {
+                if let Some(result) = reject_if_read_only(app) {
+                    return Ok(result);
+                }
+                let text = match clipboard::get() {
+                    Ok(text) => text,
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                };
+                if text.is_empty() {
+                    app.message = Some("nothing to paste, the clipboard is empty".into());
+                    return Ok(TransitionResult::Continue);
+                }
+
+                let cell = app
+                    .value
+                    .clone()
+                    .follow_cell_path(&app.position.members, false)?;
+                match parse_as_type(&text, &cell.get_type()) {
+                    Ok(value) => {
+                        let path = app.position.clone();
+                        return Ok(commit_edit(app, value, path, config));
+                    }
+                    Err(err) => return Ok(TransitionResult::Error(err)),
+                }
+            } else if let KeyCode::Char(c @ '0'..='9') = key_event.code {
+                if !app.matches.is_empty() {
+                    let digit = c.to_digit(10).unwrap() as usize;
+                    jump_to_match_number(app, pending_match, digit, config);
+                }
                 return Ok(TransitionResult::Continue);
             }
         }
@@ -98,187 +892,3734 @@ pub fn handle_key_events(
                 return Ok(TransitionResult::Continue);
             }
 
-            match app.editor.handle_key(&key_event.code) {
-                Some(Some(v)) => {
+            match app.editor.handle_key(&key_event) {
+                EditorEvent::Commit(v) if app.focused_key().is_some() => {
+                    app.mode = Mode::Normal;
+                    return Ok(rename_key(app, v));
+                }
+                EditorEvent::Commit(v) => {
                     app.mode = Mode::Normal;
-                    return Ok(TransitionResult::Mutate(v, app.position.clone()));
+                    let path = app.position.clone();
+                    return Ok(commit_edit(app, v, path, config));
                 }
-                Some(None) => {
+                EditorEvent::Cancel => {
                     app.mode = Mode::Normal;
                     return Ok(TransitionResult::Continue);
                 }
-                None => return Ok(TransitionResult::Continue),
+                EditorEvent::Continue => return Ok(TransitionResult::Continue),
             }
         }
         Mode::Peeking => {
+            let warned = app.lost_edits_warning.take() == Some(key_event.code);
+
             if key_event.code == config.keybindings.quit {
+                if config.peeking.quit_returns_to_normal {
+                    app.mode = Mode::Normal;
+                    return Ok(TransitionResult::Continue);
+                }
                 return Ok(TransitionResult::Quit);
             } else if key_event.code == config.keybindings.normal {
                 app.mode = Mode::Normal;
                 return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.navigation.down {
+                go_up_or_down(app, Direction::Down, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.navigation.up {
+                go_up_or_down(app, Direction::Up, config);
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.navigation.right {
+                // NOTE: hitting the bottom of the data would leave the PEEKING mode
+                if !is_scope_locked(app) {
+                    go_deeper(app, config);
+                }
+                app.mode = Mode::Peeking;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.navigation.left {
+                if !is_scope_locked(app) {
+                    navigation::go_back_in_data(app);
+                }
+                app.mode = Mode::Peeking;
+                return Ok(TransitionResult::Continue);
+            } else if key_event.code == config.keybindings.peeking.cycle_format {
+                app.peek_format = app.peek_format.next();
+                app.message = Some(format!("peeking as {}", app.peek_format));
+                return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.peeking.all {
-                return Ok(TransitionResult::Return(app.value.clone()));
-            } else if key_event.code == config.keybindings.peeking.view {
-                app.position.members.pop();
-                return Ok(TransitionResult::Return(
-                    app.value
-                        .clone()
-                        .follow_cell_path(&app.position.members, false)?,
+                let value = app.full_value();
+                return Ok(peek_as_format(
+                    app,
+                    value,
+                    Some(CellPath { members: vec![] }),
                 ));
-            } else if key_event.code == config.keybindings.peeking.under {
-                return Ok(TransitionResult::Return(
-                    app.value
-                        .clone()
-                        .follow_cell_path(&app.position.members, false)?,
+            } else if key_event.code == config.keybindings.peeking.config {
+                let value = config.to_value();
+                return Ok(confirm_peek(
+                    app,
+                    config,
+                    key_event.code,
+                    warned,
+                    None,
+                    value,
+                ));
+            } else if key_event.code == config.keybindings.peeking.theme {
+                let value = config.colors.to_value();
+                return Ok(confirm_peek(
+                    app,
+                    config,
+                    key_event.code,
+                    warned,
+                    None,
+                    value,
                 ));
+            } else if key_event.code == config.keybindings.peeking.view {
+                let mut path = app.position.clone();
+                path.members.pop();
+                let value = app.value.clone().follow_cell_path(&path.members, false)?;
+                let result = confirm_peek(app, config, key_event.code, warned, Some(path), value);
+                if matches!(result, TransitionResult::Return(_)) {
+                    app.position.members.pop();
+                }
+                return Ok(result);
+            } else if key_event.code == config.keybindings.peeking.under {
+                return peek_under(app, config, key_event.code, warned);
             } else if key_event.code == config.keybindings.peeking.cell_path {
-                return Ok(TransitionResult::Return(Value::cell_path(
-                    app.position.clone(),
-                    Span::unknown(),
-                )));
+                let value = Value::cell_path(app.absolute_position(), Span::unknown());
+                return Ok(confirm_peek(
+                    app,
+                    config,
+                    key_event.code,
+                    warned,
+                    None,
+                    value,
+                ));
             }
         }
         Mode::Bottom => {
+            let warned = app.lost_edits_warning.take() == Some(key_event.code);
+
             if key_event.code == config.keybindings.quit {
                 return Ok(TransitionResult::Quit);
             } else if key_event.code == config.keybindings.navigation.left {
                 app.mode = Mode::Normal;
+                app.folded_json = None;
                 return Ok(TransitionResult::Continue);
             } else if key_event.code == config.keybindings.peek {
-                return Ok(TransitionResult::Return(
-                    app.value
-                        .clone()
-                        .follow_cell_path(&app.position.members, false)?,
+                let path = app.position.clone();
+                let value = app.value.clone().follow_cell_path(&path.members, false)?;
+                return Ok(confirm_peek(
+                    app,
+                    config,
+                    key_event.code,
+                    warned,
+                    Some(path),
+                    value,
                 ));
             }
+
+            if let Some(folded) = app.folded_json.as_mut() {
+                if key_event.code == config.keybindings.navigation.up {
+                    folded.move_cursor(-1);
+                } else if key_event.code == config.keybindings.navigation.down {
+                    folded.move_cursor(1);
+                } else if key_event.code == config.keybindings.toggle_fold {
+                    folded.toggle();
+                }
+            }
         }
+        Mode::Search => {
+            if key_event.code == config.keybindings.normal {
+                app.mode = Mode::Normal;
+                return Ok(TransitionResult::Continue);
+            }
+
+            match key_event.code {
+                KeyCode::Enter => {
+                    app.mode = Mode::Normal;
+                    app.matches = find_matches(&app.value, &app.search_query);
+                    app.match_index = 0;
+                    match app.matches.first() {
+                        Some(path) => {
+                            let path = path.clone();
+                            app.jump_to(&path, config);
+                            app.message = Some(format!("match 1 of {}", app.matches.len()));
+                        }
+                        None => app.message = Some(format!("no match for `{}`", app.search_query)),
+                    }
+                }
+                KeyCode::Backspace => {
+                    app.search_query.pop();
+                    app.search_changed(Instant::now());
+                }
+                KeyCode::Char(c) => {
+                    app.search_query.push(c);
+                    app.search_changed(Instant::now());
+                }
+                _ => {}
+            }
+            return Ok(TransitionResult::Continue);
+        }
+        Mode::Form => {
+            let Some(form) = app.form.as_mut() else {
+                app.mode = Mode::Normal;
+                return Ok(TransitionResult::Continue);
+            };
+            // NOTE: the edition of the whole record is cancelled
+            if key_event.code == config.keybindings.normal {
+                app.form = None;
+                app.mode = Mode::Normal;
+                return Ok(TransitionResult::Continue);
+            }
+
+            match form.handle_key(&key_event) {
+                FormEvent::Commit(record) => {
+                    let path = form.path.clone();
+                    app.form = None;
+                    app.mode = Mode::Normal;
+                    return Ok(TransitionResult::Mutate(record, path));
+                }
+                FormEvent::Invalid(err) => app.message = Some(err),
+                FormEvent::Continue => app.message = None,
+            }
+            return Ok(TransitionResult::Continue);
+        }
+        Mode::Replace => {
+            if key_event.code == config.keybindings.normal {
+                app.mode = Mode::Normal;
+                return Ok(TransitionResult::Continue);
+            }
+
+            match key_event.code {
+                KeyCode::Enter => match app.replace_find.take() {
+                    None if app.replace_input.is_empty() => {}
+                    None => app.replace_find = Some(std::mem::take(&mut app.replace_input)),
+                    Some(find) => {
+                        app.mode = Mode::Normal;
+                        let replace = std::mem::take(&mut app.replace_input);
+                        return Ok(replace_around_cursor(app, &find, &replace));
+                    }
+                },
+                KeyCode::Backspace => {
+                    app.replace_input.pop();
+                }
+                KeyCode::Char(c) => app.replace_input.push(c),
+                _ => {}
+            }
+            return Ok(TransitionResult::Continue);
+        }
+    }
+
+    Ok(TransitionResult::Continue)
+}
+
+/// commit the edit of the cell at the given *cell path*
+///
+/// when [`Config::warn_on_type_change`] is set, the user is warned if the edit changes the type of
+/// the cell.
+fn commit_edit(app: &mut App, cell: Value, path: CellPath, config: &Config) -> TransitionResult {
+    if config.warn_on_type_change {
+        if let Ok(old) = app.value.clone().follow_cell_path(&path.members, false) {
+            app.message = type_change_warning(&old, &cell);
+        }
+    }
+
+    TransitionResult::Mutate(cell, path)
+}
+
+/// read the config file of the *app* again and replace the active *config* with it, see
+/// [`App::config_file`]
+///
+/// the active config is kept as is when the file cannot be loaded, e.g. while it is being fixed.
+pub(crate) fn reload_config(app: &mut App, config: &mut Config) -> Result<(), String> {
+    let Some(file) = &app.config_file else {
+        return Err("no config file to reload, give one with `--config`".into());
+    };
+
+    *config = Config::load_from_disk(file).map_err(|err| format!("{}: {}", err.label, err.msg))?;
+    app.message = Some(format!("reloaded the config from {}", file.display()));
+
+    Ok(())
+}
+
+/// focus the keys of the fields of the current record, or their values back, see
+/// [`App::key_focus`]
+fn toggle_key_focus(app: &mut App) {
+    if !app.key_focus
+        && (app.is_at_bottom()
+            || !matches!(app.position.members.last(), Some(PathMember::String { .. })))
+    {
+        app.message = Some("only the fields of records have keys to focus".into());
+        return;
+    }
+
+    app.key_focus = !app.key_focus;
+    app.message = Some(if app.key_focus {
+        "focusing the keys".into()
+    } else {
+        "focusing the values".into()
+    });
+}
+
+/// rename the field under the cursor to *name*, the cursor following it, see [`rename_field`]
+fn rename_key(app: &mut App, name: Value) -> TransitionResult {
+    let Value::String { val: name, .. } = name else {
+        return TransitionResult::Error("the keys of records can only be strings".into());
+    };
+
+    let mut path = app.position.clone();
+    let Some(PathMember::String {
+        val: old,
+        span,
+        optional,
+    }) = path.members.pop()
+    else {
+        return TransitionResult::Continue;
+    };
+
+    let record = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(record) => record,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    match rename_field(&record, &old, &name) {
+        Ok(renamed) => {
+            app.position.members = path.members.clone();
+            app.position.members.push(PathMember::String {
+                val: name,
+                span,
+                optional,
+            });
+            TransitionResult::Mutate(renamed, path)
+        }
+        Err(err) => TransitionResult::Error(err),
+    }
+}
+
+/// give a warning if the new value of a cell does not have the same type as the old one
+fn type_change_warning(old: &Value, new: &Value) -> Option<String> {
+    let (old, new) = (old.get_type(), new.get_type());
+    if old == new {
+        None
+    } else {
+        Some(format!(
+            "warning: the type of the cell changed from {old} to {new}"
+        ))
+    }
+}
+
+/// jump to the match of the last search that is *offset* matches away, wrapping around
+fn jump_to_match(app: &mut App, offset: i32, config: &Config) {
+    if app.matches.is_empty() {
+        app.message = Some("no match, search with a query first".into());
+        return;
+    }
+
+    let len = app.matches.len() as i32;
+    app.match_index = ((app.match_index as i32 + offset + len) % len) as usize;
+
+    let path = app.matches[app.match_index].clone();
+    app.jump_to(&path, config);
+    app.message = Some(format!("match {} of {}", app.match_index + 1, len));
+}
+
+/// jump to the cell path written in *text*, see [`parse_cell_path`], if it leads to a cell in the
+/// view
+fn jump_to_path_string(app: &mut App, text: &str, config: &Config) -> TransitionResult {
+    let Some(path) = parse_cell_path(text) else {
+        app.message = Some(format!("`{}` is not a cell path", text.trim()));
+        return TransitionResult::Continue;
+    };
+    let Some(path) = reconcile_cell_path(&app.full_value(), &path) else {
+        app.message = Some(format!("`{}` is not in the data", text.trim()));
+        return TransitionResult::Continue;
+    };
+
+    match app.relative_to_root(&path) {
+        Some(path) if path.members.is_empty() => {
+            app.message = Some(format!("`{}` is the root of the view", text.trim()))
+        }
+        Some(path) => app.jump_to(&path, config),
+        None => app.message = Some(format!("`{}` is not in the view", text.trim())),
+    }
+    TransitionResult::Continue
+}
+
+/// go back or forward in [`App::nav_history`] by *offset* positions, like in a browser
+fn move_in_history(app: &mut App, offset: i32, config: &Config) {
+    let index = app.nav_index as i32 + offset;
+    if index < 0 || index >= app.nav_history.len() as i32 {
+        app.message = Some(if offset < 0 {
+            "already at the oldest position of the history".into()
+        } else {
+            "already at the newest position of the history".into()
+        });
+        return;
+    }
+
+    match app.relative_to_root(&app.nav_history[index as usize]) {
+        Some(path)
+            if app
+                .value
+                .clone()
+                .follow_cell_path(&path.members, false)
+                .is_ok() =>
+        {
+            app.nav_index = index as usize;
+            app.jump_to(&path, config);
+        }
+        _ => app.message = Some("this position is not in the view anymore".into()),
+    }
+}
+
+/// jump to the edited cell at *offset* from the current one in [`App::edited`], cycling through them
+///
+/// the first jump goes to the first or the last edited cell, depending on the sign of *offset*.
+fn jump_to_edit(app: &mut App, offset: i32, config: &Config) {
+    if app.edited.is_empty() {
+        app.message = Some("no cell has been edited yet".into());
+        return;
+    }
+
+    let len = app.edited.len() as i32;
+    let index = match app.edit_index {
+        Some(index) => (index as i32 + offset).rem_euclid(len) as usize,
+        None if offset < 0 => app.edited.len() - 1,
+        None => 0,
+    };
+    app.edit_index = Some(index);
+
+    match app.relative_to_root(&app.edited[index]) {
+        Some(path)
+            if app
+                .value
+                .clone()
+                .follow_cell_path(&path.members, false)
+                .is_ok() =>
+        {
+            app.jump_to(&path, config);
+            app.message = Some(format!("edit {} of {}", index + 1, len));
+        }
+        _ => {
+            app.message = Some(format!(
+                "edit {} of {} is not in the current view",
+                index + 1,
+                len
+            ))
+        }
+    }
+}
+
+/// type the next key of the number of a labelled row, see [`App::row_labels`]
+///
+/// the cursor goes to the row as soon as no other label starts with the *typed* number, or on
+/// `Enter`, and any other key than a digit hides the labels.
+fn type_row_label(app: &mut App, config: &Config, typed: usize, key_event: &KeyEvent) {
+    let Some((pane, rows)) = visible_view_rows(app, config) else {
+        return;
+    };
+
+    let number = match key_event.code {
+        KeyCode::Char(c @ '0'..='9') => typed * 10 + c.to_digit(10).unwrap() as usize,
+        KeyCode::Enter if typed > 0 => typed,
+        _ => return,
+    };
+
+    if number == 0 || number > rows {
+        app.message = Some(format!("no row {}, there are {} rows", number, rows));
+        return;
+    }
+    if key_event.code != KeyCode::Enter && number * 10 <= rows {
+        app.row_labels = Some(number);
+        return;
+    }
+
+    select(app, pane.offset + number - 1, config);
+}
+
+/// jump to the match whose number is being typed, one *digit* at a time
+///
+/// the *digit* is appended to the *pending* number, unless the result is not the number of a
+/// match, in which case a new number is started.
+fn jump_to_match_number(app: &mut App, pending: Option<usize>, digit: usize, config: &Config) {
+    let len = app.matches.len();
+    let number = match pending {
+        Some(n) if n * 10 + digit <= len => n * 10 + digit,
+        _ => digit,
+    };
+
+    if number == 0 || number > len {
+        app.message = Some(format!("no match {}, there are {} matches", number, len));
+        return;
+    }
+
+    app.pending_match = Some(number);
+    app.match_index = number - 1;
+    let path = app.matches[app.match_index].clone();
+    app.jump_to(&path, config);
+    app.message = Some(format!("match {} of {}", number, len));
+}
+
+/// replace the value under the cursor with its wrapped version and go to it, i.e. to *member* of
+/// the wrapper
+fn wrap(app: &mut App, wrapper: fn(&Value) -> Value, member: PathMember) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+
+    let path = app.position.clone();
+    app.position.members.push(member);
+    TransitionResult::Mutate(wrapper(&cell), path)
+}
+
+/// duplicate the cell under the cursor next to itself and copy it to the clipboard as JSON
+///
+/// the data is duplicated even when the clipboard is not available, the error being only shown
+/// to the user.
+fn duplicate_and_yank(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let mut path = app.position.clone();
+    let member = match path.members.pop() {
+        Some(member) => member,
+        None => {
+            app.message =
+                Some("only the items of lists and the fields of records can be duplicated".into());
+            return TransitionResult::Continue;
+        }
+    };
+
+    let container = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(container) => container,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    let Some(duplicated) = duplicate_cell(&container, &member) else {
+        return TransitionResult::Continue;
+    };
+
+    let cell = match container.follow_cell_path(&[member], false) {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    app.message = Some(match clipboard::set(repr_json(&cell)) {
+        Ok(()) => "duplicated the cell and copied it as JSON".into(),
+        Err(err) => format!("duplicated the cell but {}", err),
+    });
+
+    TransitionResult::Mutate(duplicated, path)
+}
+
+/// append a field to the record around the cursor, with the placeholder value of
+/// [`Config::new_entry_default`], see [`insert_value_cell`]
+fn append_field(app: &mut App, config: &Config) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let mut path = app.position.clone();
+    path.members.pop();
+
+    let record = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(record) => record,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    match insert_value_cell(&record, &config.new_entry_default.placeholder()) {
+        Some((record, name)) => {
+            app.message = Some(format!("appended the field `{name}`"));
+            TransitionResult::Mutate(record, path)
+        }
+        None => {
+            app.message = Some("fields can only be appended to records".into());
+            TransitionResult::Continue
+        }
+    }
+}
+
+/// give the number of edits of the session that are not in the value at *peeked*, relative to the
+/// current root, i.e. that would be lost by peeking at it, all of them when *peeked* is not part
+/// of the data, e.g. the config
+///
+/// an edit above *peeked* is only partly lost and is not counted.
+fn lost_edits(app: &App, peeked: Option<&CellPath>) -> usize {
+    let Some(peeked) = peeked else {
+        return app.edited.len();
+    };
+
+    let peeked = tree::to_keys(&absolute_path(app, peeked).members);
+
+    app.edited
+        .iter()
+        .filter(|edit| {
+            let edit = tree::to_keys(&edit.members);
+            !edit.starts_with(&peeked) && !peeked.starts_with(&edit)
+        })
+        .count()
+}
+
+/// peek at the *value* at *peeked*, relative to the current root, with the *key* the user pressed
+///
+/// when [`PeekingConfig::warn_on_lost_edits`](crate::config::PeekingConfig::warn_on_lost_edits)
+/// is set and some edits would be lost, the user is warned instead and has to press *key* again,
+/// i.e. with *warned* set, to peek anyway.
+fn confirm_peek(
+    app: &mut App,
+    config: &Config,
+    key: KeyCode,
+    warned: bool,
+    peeked: Option<CellPath>,
+    value: Value,
+) -> TransitionResult {
+    let lost = lost_edits(app, peeked.as_ref());
+    if config.peeking.warn_on_lost_edits && lost > 0 && !warned {
+        app.lost_edits_warning = Some(key);
+        app.message = Some(format!(
+            "warning: {lost} edit(s) would be lost, press {} again to peek anyway",
+            repr_keycode(&key)
+        ));
+        return TransitionResult::Continue;
+    }
+
+    let absolute = peeked.map(|peeked| absolute_path(app, &peeked));
+    peek_as_format(app, value, absolute)
+}
+
+/// give the cell path of *path*, relative to the current root, from the top of the data
+fn absolute_path(app: &App, path: &CellPath) -> CellPath {
+    let mut members = app
+        .root_stack
+        .last()
+        .map(|root| root.members.clone())
+        .unwrap_or_default();
+    members.extend(path.members.iter().cloned());
+    CellPath { members }
+}
+
+/// peek at the *value* at the *absolute* cell path in the [`App::peek_format`]
+///
+/// the values that are not in the data, i.e. the config and the theme, have no cell path.
+fn peek_as_format(app: &App, value: Value, absolute: Option<CellPath>) -> TransitionResult {
+    let string = |s: String| Value::string(s, Span::unknown());
+    let peeked = match app.peek_format {
+        PeekFormat::Value => Ok(value),
+        PeekFormat::Json => Ok(string(repr_json(&value))),
+        PeekFormat::Nuon => Ok(string(to_nuon(&value))),
+        PeekFormat::Yaml => to_yaml(&value).map(string),
+        PeekFormat::CellPath => match absolute {
+            Some(path) => Ok(Value::cell_path(path, Span::unknown())),
+            None => Err("only the values of the data have a cell path".into()),
+        },
+    };
+
+    match peeked {
+        Ok(value) => TransitionResult::Return(value),
+        Err(err) => TransitionResult::Error(err),
+    }
+}
+
+/// peek at the value under the cursor, the missing ones being handled according to
+/// [`Config::missing_display`]
+fn peek_under(
+    app: &mut App,
+    config: &Config,
+    key: KeyCode,
+    warned: bool,
+) -> Result<TransitionResult, ShellError> {
+    let mut path = app.position.members.clone();
+    if navigation::is_missing(&path) {
+        match config.missing_display {
+            MissingDisplay::Null => {
+                let absolute = app.absolute_position();
+                return Ok(peek_as_format(
+                    app,
+                    Value::nothing(Span::unknown()),
+                    Some(absolute),
+                ));
+            }
+            MissingDisplay::Missing => {
+                app.message = Some("(missing) there is nothing to peek at".into());
+                return Ok(TransitionResult::Continue);
+            }
+            MissingDisplay::Reconcile => {
+                while navigation::is_missing(&path) {
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    let value = app.value.clone().follow_cell_path(&path, false)?;
+    Ok(confirm_peek(
+        app,
+        config,
+        key,
+        warned,
+        Some(CellPath { members: path }),
+        value,
+    ))
+}
+
+/// replace the list under the cursor with its items for which [`App::predicate`] is true
+fn filter_cell(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let Some(predicate) = &app.predicate else {
+        app.message = Some("no condition to filter the lists with, see the `--filter` flag".into());
+        return TransitionResult::Continue;
+    };
+
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    match filter_list(&cell, predicate.as_ref()) {
+        Some(Ok(list)) => TransitionResult::Mutate(list, app.position.clone()),
+        Some(Err(err)) => TransitionResult::Error(err),
+        None => {
+            app.message = Some("only lists can be filtered".into());
+            TransitionResult::Continue
+        }
+    }
+}
+
+/// replace the string under the cursor with the next of its allowed values in [`App::schema`]
+fn cycle_enum(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let values = app
+        .schema
+        .as_ref()
+        .and_then(|schema| enum_values(schema, &app.absolute_position()));
+    let Some(values) = values else {
+        app.message = Some("no allowed values in the schema for this cell".into());
+        return TransitionResult::Continue;
+    };
+
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    let Value::String { val, .. } = cell else {
+        app.message = Some("only strings can be cycled through their allowed values".into());
+        return TransitionResult::Continue;
+    };
+
+    TransitionResult::Mutate(
+        Value::string(next_enum_value(&values, &val), Span::unknown()),
+        app.position.clone(),
+    )
+}
+
+/// append an item to the list around the cursor, with the placeholder value of
+/// [`Config::new_entry_default`], and move the cursor to it, see [`push_list_item`]
+fn append_item(app: &mut App, config: &Config) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let mut path = app.position.clone();
+    if !matches!(path.members.pop(), Some(PathMember::Int { .. })) {
+        app.message = Some("items can only be appended to lists".into());
+        return TransitionResult::Continue;
+    }
+
+    let list = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(list) => list,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    let Some(list) = push_list_item(&list, &config.new_entry_default.placeholder()) else {
+        app.message = Some("items can only be appended to lists".into());
+        return TransitionResult::Continue;
+    };
+
+    let len = list.as_list().map(|vals| vals.len()).unwrap_or_default();
+    app.position.members = path.members.clone();
+    app.position.members.push(PathMember::Int {
+        val: len - 1,
+        span: Span::unknown(),
+        optional: false,
+    });
+    app.message = Some(format!("appended the item {}", len - 1));
+
+    TransitionResult::Mutate(list, path)
+}
+
+/// apply the *pending* operator to the range of items from the cursor to the motion of
+/// *key_event*, like in Vim
+///
+/// the digits before the motion are its count, e.g. `d2j` for the item under the cursor and the two
+/// below it, and the key of the operator again gives the items from the cursor, e.g. `dd` or `d3d`.
+/// any other key cancels the operator.
+fn finish_operator(
+    pending: PendingOperator,
+    key_event: &KeyEvent,
+    app: &mut App,
+    config: &Config,
+) -> TransitionResult {
+    if let KeyCode::Char(c @ '0'..='9') = key_event.code {
+        let digit = c.to_digit(10).unwrap_or_default() as usize;
+        // NOTE: a leading zero is not a count, like in Vim
+        if digit > 0 || pending.count.is_some() {
+            app.pending_operator = Some(PendingOperator {
+                count: Some(
+                    pending
+                        .count
+                        .unwrap_or_default()
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                ),
+                ..pending
+            });
+            return TransitionResult::Continue;
+        }
+    }
+
+    let operator_key = match pending.operator {
+        Operator::Delete => config.keybindings.delete_operator,
+    };
+    let mut path = app.position.clone();
+    let Some(PathMember::Int { val: index, .. }) = path.members.pop() else {
+        app.message = Some("only the items of lists can be operated on".into());
+        return TransitionResult::Continue;
+    };
+
+    let count = pending.count.unwrap_or(1);
+    let range = if key_event.code == config.keybindings.navigation.down {
+        index..index.saturating_add(count).saturating_add(1)
+    } else if key_event.code == config.keybindings.navigation.up {
+        index.saturating_sub(count)..index + 1
+    } else if key_event.code == operator_key {
+        index..index.saturating_add(count)
+    } else {
+        return TransitionResult::Continue;
+    };
+
+    match pending.operator {
+        Operator::Delete => delete_list_items(app, path, range),
+    }
+}
+
+/// remove the items in *range* of the list at *path*, moving the cursor to the first item after
+/// them, or to a missing item if the list is now empty, see [`remove_list_items`]
+fn delete_list_items(app: &mut App, path: CellPath, range: Range<usize>) -> TransitionResult {
+    let list = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(list) => list,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    let before = list.as_list().map(|vals| vals.len()).unwrap_or_default();
+    let Some(list) = remove_list_items(&list, range.clone()) else {
+        app.message = Some("only the items of lists can be deleted".into());
+        return TransitionResult::Continue;
+    };
+
+    let len = list.as_list().map(|vals| vals.len()).unwrap_or_default();
+    app.position.members = path.members.clone();
+    // NOTE: the cursor stays inside an empty list, on a missing item, like in `App::from_value`
+    app.position.members.push(PathMember::Int {
+        val: range.start.min(len.saturating_sub(1)),
+        span: Span::unknown(),
+        optional: len == 0,
+    });
+    app.message = Some(match before - len {
+        1 => "deleted 1 item".into(),
+        n => format!("deleted {} items", n),
+    });
+
+    TransitionResult::Mutate(list, path)
+}
+
+/// remove the last item of the list around the cursor, moving the cursor up if it was on it, or to
+/// a missing item if the list is now empty, see [`pop_list_item`]
+fn truncate_list(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let mut path = app.position.clone();
+    let Some(PathMember::Int { val: index, .. }) = path.members.pop() else {
+        app.message = Some("only lists can be truncated".into());
+        return TransitionResult::Continue;
+    };
+
+    let list = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(list) => list,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    let Some(list) = pop_list_item(&list) else {
+        app.message = Some("only lists can be truncated".into());
+        return TransitionResult::Continue;
+    };
+
+    let len = list.as_list().map(|vals| vals.len()).unwrap_or_default();
+    if index >= len {
+        app.position.members = path.members.clone();
+        // NOTE: the cursor stays inside an empty list, on a missing item, like in `App::from_value`
+        app.position.members.push(PathMember::Int {
+            val: len.saturating_sub(1),
+            span: Span::unknown(),
+            optional: len == 0,
+        });
+    }
+    app.message = Some(format!("removed the item {}", len));
+
+    TransitionResult::Mutate(list, path)
+}
+
+/// replace *find* with *replace* in all the strings of the list or record around the cursor, see
+/// [`replace_in_subtree`]
+fn replace_around_cursor(app: &mut App, find: &str, replace: &str) -> TransitionResult {
+    let mut path = app.position.clone();
+    path.members.pop();
+
+    let container = match app.value.clone().follow_cell_path(&path.members, false) {
+        Ok(container) => container,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    match replace_in_subtree(&container, find, replace) {
+        (_, 0) => {
+            app.message = Some(format!("no match for `{}`", find));
+            TransitionResult::Continue
+        }
+        (replaced, n) => {
+            let values = if n == 1 { "value" } else { "values" };
+            app.message = Some(format!("replaced `{}` in {} {}", find, n, values));
+            TransitionResult::Mutate(replaced, path)
+        }
+    }
+}
+
+/// jump to the next cell path of [`App::history`] after the last one jumped to, skipping the ones
+/// that are not in the data, e.g. when exploring another file than in the previous sessions
+fn jump_to_recent(app: &mut App, config: &Config) {
+    let paths = app.history.paths.clone();
+    let start = app.history_index.map(|i| i + 1).unwrap_or(0);
+
+    for i in (0..paths.len()).map(|step| (start + step) % paths.len()) {
+        let Some(path) = app.relative_to_root(&paths[i]) else {
+            continue;
+        };
+        // NOTE: the cursor is always on a cell inside the root, never on the root itself
+        if path.members.is_empty()
+            || app
+                .value
+                .clone()
+                .follow_cell_path(&path.members, false)
+                .is_err()
+        {
+            continue;
+        }
+
+        app.history_index = Some(i);
+        app.jump_to(&path, config);
+        app.message = Some(format!("recent path {} of {}", i + 1, paths.len()));
+        return;
+    }
+
+    app.message = Some("no recent path in the data".into());
+}
+
+/// replace the list or record around the cursor with the value under the cursor, see [`lift_cell`]
+///
+/// the cursor moves up to the lifted value, where its parent was. the root cannot be replaced.
+fn lift(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let members = &app.position.members;
+    let Some(split) = members.len().checked_sub(2) else {
+        app.message = Some("only the cells inside a nested list or record can be lifted".into());
+        return TransitionResult::Continue;
+    };
+
+    // NOTE: only the container around the parent of the cell is lifted, not the whole data
+    let (outer, inner) = members.split_at(split);
+    let container = match app.value.clone().follow_cell_path(outer, false) {
+        Ok(container) => container,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    let Some(lifted) = lift_cell(
+        &container,
+        &CellPath {
+            members: inner.to_vec(),
+        },
+    ) else {
+        app.message = Some("only the cells inside a nested list or record can be lifted".into());
+        return TransitionResult::Continue;
+    };
+    let cell = match lifted.follow_cell_path(&inner[..1], false) {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+
+    let mut parent = app.position.clone();
+    parent.members.pop();
+    app.position = parent.clone();
+
+    TransitionResult::Mutate(cell, parent)
+}
+
+/// replace the table under the cursor with the record of its columns, see [`records_to_columns`]
+fn table_to_columns(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    if !is_table(&cell) {
+        app.message = Some("only tables can be turned into columns".into());
+        return TransitionResult::Continue;
+    }
+
+    TransitionResult::Mutate(records_to_columns(&cell), app.position.clone())
+}
+
+/// give the `where` clause filtering the table around the cursor on the column and the value of the
+/// cell under it, see [`to_where_clause`], or nothing if the cursor is not in a table
+fn where_clause_under_cursor(app: &App) -> Option<String> {
+    let members = &app.position.members;
+    let [table @ .., PathMember::Int { .. }, PathMember::String { val: column, .. }] =
+        members.as_slice()
+    else {
+        return None;
+    };
+
+    let table = app.value.clone().follow_cell_path(table, false).ok()?;
+    if !is_table(&table) {
+        return None;
+    }
+    let cell = app.value.clone().follow_cell_path(members, false).ok()?;
+
+    Some(to_where_clause(column, &cell))
+}
+
+/// give the cell under the cursor together with its path in the full data, see
+/// [`value_with_path`]
+fn value_with_path_under_cursor(app: &App) -> Result<String, ShellError> {
+    let cell = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)?;
+
+    Ok(value_with_path(&app.absolute_position(), &cell))
+}
+
+/// give the patch from the data before the first edit to the current data, see [`to_patch`], or
+/// nothing when nothing has been edited
+///
+/// the data hidden by a temporary view is compared, not the view, see [`App::unhidden_value`].
+fn session_patch(app: &App) -> Option<String> {
+    to_patch(app.original.as_ref()?, &app.unhidden_value())
+}
+
+/// replace the list under the cursor with its summary, see [`summarize_list`]
+fn summarize_list_under_cursor(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+    if !matches!(cell, Value::List { .. }) {
+        app.message = Some("only lists can be summarized".into());
+        return TransitionResult::Continue;
+    }
+
+    TransitionResult::Mutate(summarize_list(&cell), app.position.clone())
+}
+
+/// replace the string under the cursor with the list of its parts separated by *delimiter*
+fn split_cell(app: &mut App, delimiter: char) -> TransitionResult {
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+
+    match split_string(&cell, &delimiter.to_string()) {
+        Ok(list) => {
+            app.message = Some(format!("split the string on '{delimiter}'"));
+            TransitionResult::Mutate(list, app.position.clone())
+        }
+        Err(err) => TransitionResult::Error(err),
+    }
+}
+
+/// replace the list of strings under the cursor with their join, separated by *separator*
+fn join_cell(app: &mut App, separator: char) -> TransitionResult {
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell) => cell,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+
+    match join_list(&cell, &separator.to_string()) {
+        Ok(string) => {
+            app.message = Some(format!("joined the list with '{separator}'"));
+            TransitionResult::Mutate(string, app.position.clone())
+        }
+        Err(err) => TransitionResult::Error(err),
+    }
+}
+
+/// jump to the next field of the current record whose key starts with *letter*, see
+/// [`navigation::next_key_starting_with`]
+fn jump_to_key(app: &mut App, letter: char, config: &Config) {
+    let mut path = app.position.members.clone();
+    let current = match path.pop() {
+        Some(PathMember::String { val, .. }) => val,
+        _ => {
+            app.message = Some("only the fields of records can be typed ahead".into());
+            return;
+        }
+    };
+    let cols = match app.value.clone().follow_cell_path(&path, false) {
+        Ok(Value::Record { val: rec, .. }) => {
+            let mut cols = navigation::visible_columns(&rec, config, app);
+            if app.reversed {
+                cols.reverse();
+            }
+            cols
+        }
+        _ => return,
+    };
+
+    match navigation::next_key_starting_with(&cols, &current, letter) {
+        Some(col) => {
+            path.push(PathMember::String {
+                val: col,
+                span: Span::unknown(),
+                optional: false,
+            });
+            app.position.members = path;
+        }
+        None => app.message = Some(format!("no key starting with '{letter}'")),
+    }
+}
+
+/// copy the path of the cell under the cursor relative to the mark *letter*, see
+/// [`relative_path`]
+///
+/// the leading `$` of JSONPath is dropped, e.g. `.a` instead of `$.a`.
+fn yank_relative_path(app: &mut App, letter: char, style: &PathExportStyle) -> TransitionResult {
+    let mark = match app.marks.get(&letter) {
+        Some(mark) => mark,
+        None => return TransitionResult::Error(format!("no mark '{letter}'")),
+    };
+    let relative = match relative_path(mark, &app.position) {
+        Some(path) if path.members.is_empty() => {
+            app.message = Some(format!("the current cell is the mark '{letter}' itself"));
+            return TransitionResult::Continue;
+        }
+        Some(path) => path,
+        None => {
+            return TransitionResult::Error(format!(
+                "the current cell is not under the mark '{letter}'"
+            ))
+        }
+    };
+
+    let path = match style {
+        PathExportStyle::JsonPath => cell_path_to_jsonpath(&relative).replacen('$', "", 1),
+        PathExportStyle::JmesPath => cell_path_to_jmespath(&relative),
+    };
+    match clipboard::set(path.clone()) {
+        Ok(()) => app.message = Some(format!("copied {}", path)),
+        Err(err) => return TransitionResult::Error(err),
+    }
+    TransitionResult::Continue
+}
+
+/// replace the JSON or NUON string under the cursor with the value it describes, see
+/// [`parse_structured`]
+fn parse_cell(app: &mut App) -> TransitionResult {
+    if let Some(result) = reject_if_read_only(app) {
+        return result;
+    }
+
+    let parsed = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(Value::String { val, .. }) => parse_structured(&val),
+        Ok(_) => None,
+        Err(err) => return TransitionResult::Error(err.to_string()),
+    };
+
+    match parsed {
+        Some(value) => {
+            app.message = Some("parsed the string".into());
+            TransitionResult::Mutate(value, app.position.clone())
+        }
+        None => {
+            app.message = Some("not a JSON or NUON list or record".into());
+            TransitionResult::Continue
+        }
+    }
+}
+
+/// make the list or record under the cursor the new root of the view, hiding its ancestors
+///
+/// the cursor goes to the first item or field of the new root. because they are relative to the
+/// root, the matches of the search, the marks and the expanded nodes of the tree are forgotten.
+fn reroot(app: &mut App) {
+    if app.hidden_data.is_some() {
+        app.message = Some("the results of a search cannot be re-rooted".into());
+        return;
+    }
+
+    let cell = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(cell @ (Value::List { .. } | Value::Record { .. })) => cell,
+        _ => {
+            app.message = Some("only lists and records can be the root of the view".into());
+            return;
+        }
+    };
+
+    let first = match &cell {
+        Value::List { vals, .. } if !vals.is_empty() => PathMember::Int {
+            val: 0,
+            span: Span::unknown(),
+            optional: false,
+        },
+        Value::Record { val: rec, .. } if !rec.is_empty() => PathMember::String {
+            val: rec.cols[0].clone(),
+            span: Span::unknown(),
+            optional: false,
+        },
+        _ => {
+            app.message = Some("an empty list or record cannot be the root of the view".into());
+            return;
+        }
+    };
+
+    let full = app.full_value();
+    let root = app.absolute_position();
+    app.full_data = Some(full);
+    // NOTE: the snapshot is compared with the data under the new root
+    app.root_stack.push(root);
+    app.set_value(cell);
+    app.position = CellPath {
+        members: vec![first],
+    };
+    app.matches.clear();
+    app.marks.clear();
+    app.expanded.clear();
+}
+
+/// go back to the previous root of the view, see [`reroot`]
+///
+/// the changes made under the current root are kept and the cursor goes back to it. a temporary
+/// view is closed first, for the view not to replace the data under the root.
+fn pop_root(app: &mut App) {
+    app.restore_hidden_data();
+    let full = app.full_value();
+    let Some(root) = app.root_stack.pop() else {
+        app.message = Some("already at the root of the data".into());
+        return;
+    };
+
+    let parent = app
+        .root_stack
+        .last()
+        .map(|r| r.members.clone())
+        .unwrap_or_default();
+    app.set_value(
+        full.clone()
+            .follow_cell_path(&parent, false)
+            .expect("the parent of a root should be in the data"),
+    );
+    app.position = CellPath {
+        members: root.members[parent.len()..].to_vec(),
+    };
+    app.full_data = if app.root_stack.is_empty() {
+        None
+    } else {
+        Some(full)
+    };
+    app.mode = Mode::Normal;
+    app.matches.clear();
+    app.marks.clear();
+    app.expanded.clear();
+}
+
+/// explore the string under the cursor split into a list or a record, see [`parse_envish`]
+///
+/// like with [`toggle_search_results`], the data is hidden while exploring the parsed string and
+/// calling this again brings it back.
+///
+/// > :bulb: **Note**
+/// > the changes made to the parsed string are not applied to the data
+fn toggle_envish(app: &mut App) {
+    if app.restore_hidden_data() {
+        return;
+    }
+
+    let parsed = match app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+    {
+        Ok(Value::String { val, .. }) => parse_envish(&val),
+        _ => None,
+    };
+
+    let (parsed, first) = match parsed {
+        Some(Value::Record { val: rec, .. }) => {
+            let first = PathMember::String {
+                val: rec.cols[0].clone(),
+                span: Span::unknown(),
+                optional: false,
+            };
+            (Value::record(rec, Span::unknown()), first)
+        }
+        Some(list) => {
+            let first = PathMember::Int {
+                val: 0,
+                span: Span::unknown(),
+                optional: false,
+            };
+            (list, first)
+        }
+        None => {
+            app.message = Some(
+                "only strings like `KEY=val;KEY2=val2` or `/bin:/usr/bin` can be split".into(),
+            );
+            return;
+        }
+    };
+
+    app.hide_data(
+        HiddenView::Envish,
+        parsed,
+        CellPath {
+            members: vec![first],
+        },
+    );
+    app.mode = Mode::Normal;
+}
+
+/// explore the record of tables under the cursor as a single table, see [`flatten_one_level`]
+///
+/// like with [`toggle_search_results`], the data is hidden while exploring the flattened table and
+/// calling this again brings it back.
+///
+/// > :bulb: **Note**
+/// > the changes made to the flattened table are not applied to the data
+fn toggle_flatten(app: &mut App) {
+    if app.restore_hidden_data() {
+        return;
+    }
+
+    let cell = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+        .unwrap_or_else(|_| Value::nothing(Span::unknown()));
+    let flat = flatten_one_level(&cell);
+    if flat == cell {
+        app.message = Some("only records of tables with the same columns can be flattened".into());
+        return;
+    }
+
+    app.hide_data(
+        HiddenView::Flatten,
+        flat,
+        CellPath {
+            members: vec![PathMember::Int {
+                val: 0,
+                span: Span::unknown(),
+                optional: false,
+            }],
+        },
+    );
+    app.mode = Mode::Normal;
+}
+
+/// explore the schema of the list or record under the cursor, see [`infer_schema`]
+///
+/// like with [`toggle_search_results`], the data is hidden while exploring the schema and calling
+/// this again brings it back.
+///
+/// > :bulb: **Note**
+/// > the changes made to the schema are not applied to the data
+fn toggle_schema(app: &mut App) {
+    if app.restore_hidden_data() {
+        return;
+    }
+
+    let cell = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+        .unwrap_or_else(|_| Value::nothing(Span::unknown()));
+    let (schema, first) = match infer_schema(&cell) {
+        Value::Record { val: rec, .. } if !rec.is_empty() => {
+            let first = PathMember::String {
+                val: rec.cols[0].clone(),
+                span: Span::unknown(),
+                optional: false,
+            };
+            (Value::record(rec, Span::unknown()), first)
+        }
+        list @ Value::List { .. } => {
+            let first = PathMember::Int {
+                val: 0,
+                span: Span::unknown(),
+                optional: false,
+            };
+            (list, first)
+        }
+        _ => {
+            app.message = Some("only tables and non-empty records have a schema".into());
+            return;
+        }
+    };
+
+    app.hide_data(
+        HiddenView::Schema,
+        schema,
+        CellPath {
+            members: vec![first],
+        },
+    );
+    app.mode = Mode::Normal;
+}
+
+/// copy the statistics of the numbers under the cursor, see [`numeric_stats`]
+///
+/// the numbers are the items of the list under the cursor or, in a field of a row of a table, the
+/// cells of its column.
+fn yank_stats(app: &mut App) -> TransitionResult {
+    let values = match app.position.members.as_slice() {
+        [table @ .., PathMember::Int { .. }, PathMember::String { val, .. }] => {
+            match app.value.clone().follow_cell_path(table, false) {
+                Ok(table) if is_table(&table) => table
+                    .as_list()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|row| row.get_data_by_key(val))
+                    .collect::<Vec<Value>>(),
+                _ => vec![],
+            }
+        }
+        _ => vec![],
+    };
+    let values = if values.is_empty() {
+        match app
+            .value
+            .clone()
+            .follow_cell_path(&app.position.members, false)
+        {
+            Ok(Value::List { vals, .. }) => vals,
+            _ => vec![],
+        }
+    } else {
+        values
+    };
+
+    if !values
+        .iter()
+        .any(|v| matches!(v, Value::Int { .. } | Value::Float { .. }))
+    {
+        app.message = Some("no number to compute the statistics of".into());
+        return TransitionResult::Continue;
+    }
+
+    match clipboard::set(repr_json(&numeric_stats(&values))) {
+        Ok(()) => app.message = Some("copied the statistics of the numbers".into()),
+        Err(err) => return TransitionResult::Error(err),
+    }
+    TransitionResult::Continue
+}
+
+/// show the histogram of the column under the cursor, see [`column_histogram`], or hide it
+///
+/// the cursor needs to be on a field of a row of a table, the column being the one of the field.
+fn toggle_histogram(app: &mut App) {
+    if app.histogram.take().is_some() {
+        return;
+    }
+
+    let members = &app.position.members;
+    let column = match members.as_slice() {
+        [table @ .., PathMember::Int { .. }, PathMember::String { val, .. }] => app
+            .value
+            .clone()
+            .follow_cell_path(table, false)
+            .ok()
+            .map(|table| (val.clone(), column_histogram(&table, val))),
+        _ => None,
+    };
+
+    match column {
+        Some((name, counts)) if !counts.is_empty() => app.histogram = Some((name, counts)),
+        _ => app.message = Some("only the columns of tables have a histogram".into()),
+    }
+}
+
+/// explore the list around the cursor sorted in the given [`SortKind`], see [`sort_list`]
+///
+/// like with [`toggle_search_results`], the data is hidden while exploring the sorted list and
+/// calling [`crate::config::KeyBindingsMap::sort`] again brings it back.
+///
+/// > :bulb: **Note**
+/// > the changes made to the sorted list are not applied to the data
+fn sort_view(app: &mut App, kind: SortKind) {
+    let mut path = app.position.members.clone();
+    path.pop();
+
+    let sorted = app
+        .value
+        .clone()
+        .follow_cell_path(&path, false)
+        .ok()
+        .and_then(|view| sort_list(&view, kind));
+    let Some(sorted) = sorted else {
+        app.message = Some("only lists can be sorted".into());
+        return;
+    };
+
+    app.hide_data(
+        HiddenView::Sort,
+        sorted,
+        CellPath {
+            members: vec![PathMember::Int {
+                val: 0,
+                span: Span::unknown(),
+                optional: false,
+            }],
+        },
+    );
+    app.mode = Mode::Normal;
+}
+
+/// sort the list or record around the cursor in the data, by key with `k` or by value with `v`,
+/// the uppercase letters sorting in descending order, see [`sort_container`]
+///
+/// unlike [`sort_view`], the sorted container replaces the original one in the data.
+fn commit_sort(app: &mut App, letter: char) -> TransitionResult {
+    let (by, descending) = match letter {
+        'k' => (SortBy::Key, false),
+        'K' => (SortBy::Key, true),
+        'v' => (SortBy::Value, false),
+        'V' => (SortBy::Value, true),
+        _ => {
+            app.message = Some("containers can only be sorted with k, K, v or V".into());
+            return TransitionResult::Continue;
+        }
+    };
+
+    let mut path = app.position.clone();
+    path.members.pop();
+
+    let sorted = app
+        .value
+        .clone()
+        .follow_cell_path(&path.members, false)
+        .ok()
+        .and_then(|container| sort_container(&container, by, descending));
+    match sorted {
+        Some(sorted) => TransitionResult::Mutate(sorted, path),
+        None => {
+            app.message = Some(match by {
+                SortBy::Key => "only records can be sorted by key".into(),
+                SortBy::Value => "only lists and records can be sorted".into(),
+            });
+            TransitionResult::Continue
+        }
+    }
+}
+
+/// explore the matches of the last search as a list of `{path, value}` records
+///
+/// the data is hidden while exploring the results and calling this again brings it back, along
+/// with the position and the matches.
+///
+/// > :bulb: **Note**
+/// > the changes made to the results are not applied to the data
+fn toggle_search_results(app: &mut App) {
+    if app.restore_hidden_data() {
+        return;
+    }
+
+    if app.matches.is_empty() {
+        app.message = Some("no search results to explore, search with a query first".into());
+        return;
+    }
+
+    let results = search_results(&app.value, &app.matches);
+    app.hide_data(
+        HiddenView::SearchResults,
+        results,
+        CellPath {
+            members: vec![PathMember::Int {
+                val: 0,
+                span: Span::unknown(),
+                optional: false,
+            }],
+        },
+    );
+    app.mode = Mode::Normal;
+}
+
+/// go up or down in the data, in the way of the current [`Layout`]
+pub(crate) fn go_up_or_down(app: &mut App, direction: Direction, config: &Config) {
+    app.preview_scroll = 0;
+    let from = app.position.clone();
+    match config.layout {
+        // NOTE: the rows of the tree view go through all the containers
+        Layout::Tree if app.scope_lock => tree::go_up_or_down_in_container(app, direction),
+        Layout::Tree => tree::go_up_or_down(app, direction),
+        _ => navigation::go_up_or_down_in_data(app, direction, config),
+    }
+
+    let container =
+        |members: &[PathMember]| tree::to_keys(&members[..members.len().saturating_sub(1)]);
+    if app.scope_lock
+        && (app.position.members.len() != from.members.len()
+            || container(&app.position.members) != container(&from.members))
+    {
+        app.position = from;
+    }
+}
+
+/// tell whether the navigation is locked inside the current container, see [`App::scope_lock`],
+/// telling the user about it
+fn is_scope_locked(app: &mut App) -> bool {
+    if app.scope_lock {
+        app.message = Some("the navigation is locked in the current container".into());
+    }
+    app.scope_lock
+}
+
+/// go one level deeper in the data, in the way of the current [`Layout`]
+pub(crate) fn go_deeper(app: &mut App, config: &Config) {
+    app.preview_scroll = 0;
+    match config.layout {
+        Layout::Tree => tree::go_deeper(app, config),
+        _ => navigation::go_deeper_in_data(app, config),
+    }
+}
+
+/// finish a key sequence with the key that was waited for
+///
+/// any key that is not a character cancels the sequence. the delimiters of [`split_cell`] and
+/// [`join_cell`] and the first character of a key to jump to can be any character, the other
+/// sequences are cancelled by anything but a letter. when filtering by type, the letters that are
+/// not a type, see [`navigation::type_of_letter`], remove the filter.
+fn finish_pending_sequence(
+    pending: PendingKind,
+    key_event: KeyEvent,
+    app: &mut App,
+    config: &Config,
+) -> TransitionResult {
+    let letter = match key_event.code {
+        KeyCode::Char(c) => c,
+        _ => return TransitionResult::Continue,
+    };
+
+    match pending {
+        // NOTE: any character can separate the parts of a string, not only letters
+        PendingKind::SplitString => return split_cell(app, letter),
+        PendingKind::JoinList => return join_cell(app, letter),
+        // NOTE: keys can start with any character
+        PendingKind::TypeAhead => jump_to_key(app, letter, config),
+        _ if !letter.is_ascii_alphabetic() => return TransitionResult::Continue,
+        PendingKind::Mark => {
+            app.marks.insert(letter, app.position.clone());
+            app.message = Some(format!("marked the current cell as '{letter}'"));
+        }
+        PendingKind::YankRelativePath => {
+            return yank_relative_path(app, letter, &config.path_export_style)
+        }
+        PendingKind::JumpToMark => match app.marks.get(&letter) {
+            Some(path) => {
+                if app
+                    .value
+                    .clone()
+                    .follow_cell_path(&path.members, false)
+                    .is_err()
+                {
+                    return TransitionResult::Error(format!(
+                        "mark '{letter}' does not point to the data anymore"
+                    ));
+                }
+                let path = path.clone();
+                app.jump_to(&path, config);
+            }
+            None => return TransitionResult::Error(format!("no mark '{letter}'")),
+        },
+        PendingKind::Sort => match letter {
+            'n' => sort_view(app, SortKind::Numeric),
+            'l' => sort_view(app, SortKind::Lexical),
+            _ => app.message = Some("lists can only be sorted with n or l".into()),
+        },
+        PendingKind::CommitSort => return commit_sort(app, letter),
+        PendingKind::TypeFilter => {
+            app.nav_type_filter = navigation::type_of_letter(letter);
+            app.message = Some(match &app.nav_type_filter {
+                Some(ty) => format!("only stopping on the values of type {ty}"),
+                None => "stopping on all the values again".into(),
+            });
+        }
+    }
+
+    TransitionResult::Continue
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use nu_protocol::{
+        ast::{CellPath, PathMember},
+        record, Span, Value,
+    };
+
+    use super::{
+        commit_edit, duplicate_and_yank, handle_key_events, jump_to_path_string, reload_config,
+        value_with_path_under_cursor, App, TransitionResult, READ_ONLY, TREE_ONLY,
+    };
+    use crate::{
+        app::{where_predicate, Mode, Operator, PeekFormat, PendingOperator},
+        config::{
+            repr_keycode, Config, Layout, LeftAtRoot, MissingDisplay, NewEntryDefault,
+            PeekingConfig,
+        },
+        navigation::MAX_DEPTH,
+        nu::{
+            cell_path::{to_path_member_vec, PM},
+            value::mutate_value_cell,
+        },
+        tree::to_keys,
+    };
+
+    /// {
+    ///     l: ["my", "list", "elements"],
+    ///     r: {a: 1, b: 2},
+    ///     s: "some string",
+    ///     i: 123,
+    /// }
+    fn test_value() -> Value {
+        Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }
+            ),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        })
+    }
+
+    #[test]
+    fn switch_modes() {
+        let config = Config::default();
+        let keybindings = config.clone().keybindings;
+
+        let value = Value::test_string("foo");
+        let mut app = App::from_value(value);
+
+        assert!(app.mode == Mode::Normal);
+
+        // INSERT -> PEEKING: not allowed
+        // PEEKING -> INSERT: not allowed
+        let transitions = vec![
+            (keybindings.normal, Mode::Normal),
+            (keybindings.insert, Mode::Insert),
+            (keybindings.normal, Mode::Normal),
+            (keybindings.peek, Mode::Peeking),
+            (keybindings.normal, Mode::Normal),
+        ];
+
+        for (key, expected_mode) in transitions {
+            let mode = app.mode.clone();
+
+            let result =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                    .unwrap();
+
+            assert!(
+                !result.is_quit(),
+                "unexpected exit after pressing {} in {}",
+                repr_keycode(&key),
+                mode,
+            );
+            assert!(
+                app.mode == expected_mode,
+                "expected to be in {} after pressing {} in {}, found {}",
+                expected_mode,
+                repr_keycode(&key),
+                mode,
+                app.mode
+            );
+        }
+    }
+
+    #[test]
+    fn quit_from_peeking() {
+        for (quit_returns_to_normal, expected) in [
+            (false, vec![(Mode::Peeking, true)]),
+            (true, vec![(Mode::Normal, false), (Mode::Normal, true)]),
+        ] {
+            let config = Config {
+                peeking: PeekingConfig {
+                    quit_returns_to_normal,
+                    ..Config::default().peeking
+                },
+                ..Default::default()
+            };
+            let kmap = config.clone().keybindings;
+
+            let mut app = App::from_value(test_value());
+            handle_key_events(
+                KeyEvent::new(kmap.peek, KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+            .unwrap();
+
+            for (mode, exit) in expected {
+                let result = handle_key_events(
+                    KeyEvent::new(kmap.quit, KeyModifiers::empty()),
+                    &mut app,
+                    &config,
+                )
+                .unwrap();
+                assert_eq!(app.mode, mode);
+                assert_eq!(result.is_quit(), exit);
+            }
+        }
+    }
+
+    #[test]
+    fn quit() {
+        let config = Config::default();
+        let keybindings = config.clone().keybindings;
+
+        let value = test_value();
+        let mut app = App::from_value(value);
+
+        let transitions = vec![
+            (keybindings.insert, false),
+            (keybindings.quit, true),
+            (keybindings.normal, false),
+            (keybindings.quit, true),
+            (keybindings.peek, false),
+            (keybindings.quit, true),
+        ];
+
+        for (key, exit) in transitions {
+            let mode = app.mode.clone();
+
+            let result =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                    .unwrap();
+
+            if exit {
+                assert!(
+                    result.is_quit(),
+                    "expected to quit after pressing {} in {} mode",
+                    repr_keycode(&key),
+                    mode
+                );
+            } else {
+                assert!(
+                    !result.is_quit(),
+                    "expected NOT to quit after pressing {} in {} mode",
+                    repr_keycode(&key),
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn left_at_root() {
+        let mut config = Config::default();
+        let left = KeyEvent::new(config.keybindings.navigation.left, KeyModifiers::empty());
+
+        let mut app = App::from_value(test_value());
+        let result = handle_key_events(left, &mut app, &config).unwrap();
+        assert_eq!(result, TransitionResult::Continue);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("l")]));
+
+        config.left_at_root = LeftAtRoot::Quit;
+
+        let mut app = App::from_value(test_value());
+        let result = handle_key_events(left, &mut app, &config).unwrap();
+        assert_eq!(result, TransitionResult::Quit);
+
+        // going left when not at the root still goes back in the data
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+        let result = handle_key_events(left, &mut app, &config).unwrap();
+        assert_eq!(result, TransitionResult::Continue);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r")]));
+    }
+
+    #[test]
+    fn warn_on_type_change() {
+        let mut config = Config::default();
+
+        // editing a string with the editor keeps its type
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        for key in [
+            config.keybindings.insert,
+            KeyCode::Char('x'),
+            KeyCode::Enter,
+        ] {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
+            // NOTE: the editor is given its width by the terminal in the real application
+            app.editor.set_width(20);
+        }
+        assert_eq!(app.message, None);
+
+        let mut app = App::from_value(test_value());
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("i")]),
+        };
+
+        let result = commit_edit(&mut app, Value::test_int(0), path.clone(), &config);
+        assert_eq!(
+            result,
+            TransitionResult::Mutate(Value::test_int(0), path.clone())
+        );
+        assert_eq!(app.message, None);
+
+        let result = commit_edit(&mut app, Value::test_string("0"), path.clone(), &config);
+        assert_eq!(
+            result,
+            TransitionResult::Mutate(Value::test_string("0"), path.clone())
+        );
+        assert_eq!(
+            app.message,
+            Some("warning: the type of the cell changed from int to string".into())
+        );
+
+        config.warn_on_type_change = false;
+        app.message = None;
+        commit_edit(&mut app, Value::test_string("0"), path, &config);
+        assert_eq!(app.message, None);
+    }
+
+    #[test]
+    fn cancel_or_commit_edit() {
+        let config = Config::default();
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("s")]),
+        };
+
+        let edit = |keys: &[KeyCode]| {
+            let mut app = App::from_value(test_value());
+            app.position = path.clone();
+            let mut result = TransitionResult::Continue;
+            for key in keys {
+                result = handle_key_events(
+                    KeyEvent::new(*key, KeyModifiers::empty()),
+                    &mut app,
+                    &config,
+                )
+                .unwrap();
+                // NOTE: the editor is given its width by the terminal in the real application
+                app.editor.set_width(20);
+            }
+            (app.mode, result)
+        };
+
+        // NOTE: the cell is left untouched when cancelling the edition
+        assert_eq!(
+            edit(&[config.keybindings.insert, KeyCode::Char('x'), KeyCode::Esc]),
+            (Mode::Normal, TransitionResult::Continue)
+        );
+        assert_eq!(
+            edit(&[config.keybindings.insert, KeyCode::Char('x')]),
+            (Mode::Insert, TransitionResult::Continue)
+        );
+
+        let (mode, result) = edit(&[config.keybindings.insert, KeyCode::Enter]);
+        assert_eq!(mode, Mode::Normal);
+        assert_eq!(
+            result,
+            TransitionResult::Mutate(Value::test_string("some string"), path)
+        );
+    }
+
+    #[test]
+    fn show_node_count_until_first_key() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+
+        app.show_node_count();
+        assert_eq!(app.message, Some("loaded: 10 nodes".into()));
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.navigation.down, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(app.message, None);
+    }
+
+    #[test]
+    fn jump_to_nth_match() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(Value::test_list(
+            (0..12).map(|_| Value::test_string("x")).collect(),
+        ));
+
+        let mut press = |key| {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
+            app.position.members.clone()
+        };
+
+        // NOTE: digits do nothing before searching
+        assert_eq!(press(KeyCode::Char('2')), to_path_member_vec(&[PM::I(0)]));
+
+        for key in [kmap.search, KeyCode::Char('x'), KeyCode::Enter] {
+            press(key);
+        }
+
+        let transitions = vec![
+            (KeyCode::Char('2'), PM::I(1)),
+            // NOTE: there is no match 21, so a new number is started
+            (KeyCode::Char('1'), PM::I(0)),
+            (KeyCode::Char('1'), PM::I(10)),
+            (KeyCode::Char('2'), PM::I(1)),
+            (KeyCode::Char('1'), PM::I(0)),
+            (KeyCode::Char('2'), PM::I(11)),
+            (KeyCode::Char('5'), PM::I(4)),
+            (kmap.navigation.down, PM::I(5)),
+            (KeyCode::Char('3'), PM::I(2)),
+        ];
+
+        for (key, expected) in transitions {
+            assert_eq!(
+                press(key),
+                to_path_member_vec(&[expected]),
+                "after pressing {:?}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn reroot_and_pop_back() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key| {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, &config).unwrap()
+        };
+
+        press(&mut app, kmap.navigation.down);
+        press(&mut app, kmap.reroot);
+        assert_eq!(app.value, test_value().get_data_by_key("r").unwrap());
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("a")]));
+        assert_eq!(
+            app.absolute_position().members,
+            to_path_member_vec(&[PM::S("r"), PM::S("a")])
+        );
+
+        // NOTE: the ancestors of the root cannot be reached anymore
+        press(&mut app, kmap.navigation.left);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("a")]));
+        press(&mut app, kmap.navigation.down);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("b")]));
+
+        // NOTE: the edits are relative to the root and end up in the full data
+        let position = app.position.clone();
+        let TransitionResult::Mutate(cell, path) =
+            commit_edit(&mut app, Value::test_int(20), position, &config)
+        else {
+            panic!("the edit should mutate the data");
+        };
+        app.value = mutate_value_cell(&app.value, &path, &cell);
+
+        let expected = mutate_value_cell(
+            &test_value(),
+            &CellPath {
+                members: to_path_member_vec(&[PM::S("r"), PM::S("b")]),
+            },
+            &Value::test_int(20),
+        );
+        assert_eq!(app.full_value(), expected);
+
+        // NOTE: the copied path goes through the root
+        assert_eq!(
+            value_with_path_under_cursor(&app).unwrap(),
+            "{path: $.r.b, value: 20}"
+        );
+
+        press(&mut app, kmap.pop_root);
+        assert!(app.root_stack.is_empty());
+        assert_eq!(app.value, expected);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r")]));
+
+        press(&mut app, kmap.pop_root);
+        assert_eq!(app.message, Some("already at the root of the data".into()));
+
+        press(&mut app, kmap.navigation.down);
+        press(&mut app, kmap.reroot);
+        assert_eq!(
+            app.message,
+            Some("only lists and records can be the root of the view".into())
+        );
+
+        // NOTE: a temporary view is closed before going back, without changing the data
+        let mut app = App::from_value(test_value());
+        press(&mut app, kmap.reroot);
+        press(&mut app, kmap.sort);
+        press(&mut app, KeyCode::Char('l'));
+        assert!(app.hidden_data.is_some());
+        press(&mut app, kmap.pop_root);
+        assert!(app.hidden_data.is_none());
+        assert_eq!(app.value, test_value());
+        assert_eq!(app.final_value(), test_value());
+    }
+
+    #[test]
+    fn reroot_with_snapshot() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key| {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, &config).unwrap()
+        };
+
+        press(&mut app, kmap.snapshot);
+        press(&mut app, kmap.navigation.down);
+        press(&mut app, kmap.reroot);
+        assert!(app.changes.is_empty(), "{:?}", app.changes);
+
+        app.apply_edit(
+            &CellPath {
+                members: to_path_member_vec(&[PM::S("b")]),
+            },
+            &Value::test_int(20),
+        );
+        assert_eq!(
+            app.changes,
+            vec![CellPath {
+                members: to_path_member_vec(&[PM::S("b")]),
+            }]
+        );
+
+        press(&mut app, kmap.pop_root);
+        assert_eq!(
+            app.changes,
+            vec![CellPath {
+                members: to_path_member_vec(&[PM::S("r"), PM::S("b")]),
+            }]
+        );
+
+        // NOTE: a temporary view is not a change of the data
+        let mut app = App::from_value(test_value());
+        press(&mut app, kmap.snapshot);
+        press(&mut app, kmap.reroot);
+        press(&mut app, kmap.sort);
+        press(&mut app, KeyCode::Char('l'));
+        assert!(app.hidden_data.is_some());
+        assert!(app.changes.is_empty(), "{:?}", app.changes);
+    }
+
+    #[test]
+    fn append_fields() {
+        let cases = vec![
+            (NewEntryDefault::Null, Value::test_nothing()),
+            (NewEntryDefault::EmptyString, Value::test_string("")),
+            (NewEntryDefault::Zero, Value::test_int(0)),
+        ];
+
+        for (default, expected) in cases {
+            let config = Config {
+                new_entry_default: default,
+                ..Default::default()
+            };
+            let mut app = App::from_value(test_value());
+            app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+
+            let transition = handle_key_events(
+                KeyEvent::new(config.keybindings.append_field, KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+            .unwrap();
+            let TransitionResult::Mutate(cell, path) = transition else {
+                panic!("expected a mutation, found {:?}", transition)
+            };
+            app.apply_edit(&path, &cell);
+
+            assert_eq!(
+                app.value
+                    .clone()
+                    .follow_cell_path(&to_path_member_vec(&[PM::S("r"), PM::S("new")]), false)
+                    .ok(),
+                Some(expected)
+            );
+        }
+
+        // NOTE: only records can be appended to
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+        assert_eq!(
+            handle_key_events(
+                KeyEvent::new(config.keybindings.append_field, KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+            .unwrap(),
+            TransitionResult::Continue
+        );
+    }
+
+    #[test]
+    fn append_and_truncate_items() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+
+        let press = |app: &mut App, key| {
+            let transition =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, &config).unwrap();
+            let TransitionResult::Mutate(cell, path) = transition else {
+                panic!("expected a mutation, found {:?}", transition)
+            };
+            app.apply_edit(&path, &cell);
+        };
+        let len = |app: &App| {
+            app.value
+                .clone()
+                .follow_cell_path(&to_path_member_vec(&[PM::S("l")]), false)
+                .unwrap()
+                .as_list()
+                .unwrap()
+                .len()
+        };
+
+        press(&mut app, config.keybindings.append_item);
+        assert_eq!(len(&app), 4);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(3)])
+        );
+        assert_eq!(
+            app.value
+                .clone()
+                .follow_cell_path(&app.position.members, false)
+                .ok(),
+            Some(Value::test_nothing())
+        );
+
+        // NOTE: the cursor moves up when its item is removed
+        press(&mut app, config.keybindings.truncate_list);
+        press(&mut app, config.keybindings.truncate_list);
+        assert_eq!(len(&app), 2);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(1)])
+        );
+
+        // NOTE: the cursor stays where it is when it's above the removed item
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+        press(&mut app, config.keybindings.truncate_list);
+        assert_eq!(len(&app), 1);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(0)])
+        );
+
+        // NOTE: the cursor stays inside the list when it's empty
+        press(&mut app, config.keybindings.truncate_list);
+        assert_eq!(len(&app), 0);
+        let missing = vec![
+            PathMember::String {
+                val: "l".into(),
+                span: Span::unknown(),
+                optional: false,
+            },
+            PathMember::Int {
+                val: 0,
+                span: Span::unknown(),
+                optional: true,
+            },
+        ];
+        assert_eq!(app.position.members, missing);
+
+        // NOTE: a top-level list can be emptied and navigated
+        let mut top = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        press(&mut top, config.keybindings.truncate_list);
+        assert_eq!(top.value, Value::test_list(vec![]));
+        assert_eq!(top.position.members, missing[1..]);
+        handle_key_events(
+            KeyEvent::new(config.keybindings.navigation.down, KeyModifiers::empty()),
+            &mut top,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(top.position.members, missing[1..]);
+
+        // NOTE: only lists can be appended to and truncated
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+        for key in [
+            config.keybindings.append_item,
+            config.keybindings.truncate_list,
+        ] {
+            assert_eq!(
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                    .unwrap(),
+                TransitionResult::Continue
+            );
+        }
+    }
+
+    #[test]
+    fn replace_in_container() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(1)]);
+
+        let press = |app: &mut App, code| {
+            handle_key_events(KeyEvent::new(code, KeyModifiers::empty()), app, &config).unwrap()
+        };
+
+        press(&mut app, config.keybindings.replace);
+        assert_eq!(app.mode, Mode::Replace);
+        for c in "my".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        for c in "MY".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        let TransitionResult::Mutate(cell, path) = press(&mut app, KeyCode::Enter) else {
+            panic!("expected a mutation")
+        };
+        app.apply_edit(&path, &cell);
+
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(
+            app.value.get_data_by_key("l"),
+            Some(Value::test_list(vec![
+                Value::test_string("MY"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]))
+        );
+        assert_eq!(app.message, Some("replaced `my` in 1 value".into()));
+    }
+
+    #[test]
+    fn no_replace_in_inspect_mode() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(1)]);
+        app.replace_input = "my".into();
+        app.inspect = true;
+
+        let result = handle_key_events(
+            KeyEvent::new(config.keybindings.replace, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(result, TransitionResult::Continue);
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.replace_input, "my");
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+    }
+
+    #[test]
+    fn jump_to_recent_paths() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.history.paths = vec![
+            CellPath {
+                members: to_path_member_vec(&[PM::S("r"), PM::S("b")]),
+            },
+            CellPath {
+                members: to_path_member_vec(&[PM::S("x"), PM::S("y")]),
+            },
+            CellPath {
+                members: to_path_member_vec(&[PM::S("l"), PM::I(2)]),
+            },
+        ];
+        let press = |app: &mut App| {
+            handle_key_events(
+                KeyEvent::new(config.keybindings.jump_recent, KeyModifiers::empty()),
+                app,
+                &config,
+            )
+            .unwrap();
+        };
+
+        press(&mut app);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("r"), PM::S("b")])
+        );
+        // NOTE: the paths that are not in the data are skipped
+        press(&mut app);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(2)])
+        );
+        assert_eq!(app.message, Some("recent path 3 of 3".into()));
+        press(&mut app);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("r"), PM::S("b")])
+        );
+
+        app.history.paths.clear();
+        press(&mut app);
+        assert_eq!(app.message, Some("no recent path in the data".into()));
+    }
+
+    #[test]
+    fn lift_cell_under_cursor() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+
+        let transition = handle_key_events(
+            KeyEvent::new(config.keybindings.lift, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let TransitionResult::Mutate(cell, path) = transition else {
+            panic!("expected a mutation, found {:?}", transition)
+        };
+        app.apply_edit(&path, &cell);
+
+        assert_eq!(app.value.get_data_by_key("r"), Some(Value::test_int(1)));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r")]));
+
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(1)]);
+        assert_eq!(
+            handle_key_events(
+                KeyEvent::new(config.keybindings.lift, KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+            .unwrap(),
+            TransitionResult::Mutate(
+                Value::test_string("list"),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("l")])
+                }
+            )
+        );
+
+        // NOTE: the top-level cells have no parent to replace
+        assert_eq!(
+            handle_key_events(
+                KeyEvent::new(config.keybindings.lift, KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+            .unwrap(),
+            TransitionResult::Continue
+        );
+    }
+
+    #[test]
+    fn duplicate_and_yank_cells() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(1)]);
+
+        let transition = handle_key_events(
+            KeyEvent::new(kmap.duplicate_and_yank, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let TransitionResult::Mutate(cell, path) = transition else {
+            panic!("expected a mutation, found {:?}", transition)
+        };
+        app.apply_edit(&path, &cell);
+
+        assert_eq!(
+            app.value.get_data_by_key("l"),
+            Some(Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]))
+        );
+        // NOTE: the clipboard is only there with the `clipboard` feature
+        assert!(app
+            .message
+            .as_ref()
+            .is_some_and(|msg| msg.starts_with("duplicated the cell")));
+    }
+
+    #[test]
+    fn wrap_values() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+
+        let transition = handle_key_events(
+            KeyEvent::new(kmap.wrap_in_list, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("i")]),
+        };
+        assert_eq!(
+            transition,
+            TransitionResult::Mutate(Value::test_list(vec![Value::test_int(123)]), path)
+        );
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("i"), PM::I(0)])
+        );
+
+        let TransitionResult::Mutate(cell, path) = transition else {
+            unreachable!()
+        };
+        app.value = mutate_value_cell(&app.value, &path, &cell);
+
+        handle_key_events(
+            KeyEvent::new(kmap.wrap_in_record, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("i"), PM::I(0), PM::S("value")])
+        );
+    }
+
+    #[test]
+    fn read_only_in_inspect_mode() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        app.inspect = true;
+
+        let result = handle_key_events(
+            KeyEvent::new(config.keybindings.insert, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(result, TransitionResult::Continue);
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+
+        // NOTE: the changes are refused up front, not only when applied
+        let kmap = config.clone().keybindings;
+        for key in [
+            kmap.paste,
+            kmap.retab,
+            kmap.collapse_string,
+            kmap.parse_string,
+            kmap.record_to_values,
+            kmap.cycle_enum,
+            kmap.filter_list,
+            kmap.append_field,
+        ] {
+            app.message = None;
+            let result =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                    .unwrap();
+            assert_eq!(result, TransitionResult::Continue, "after pressing {key:?}");
+            assert_eq!(
+                app.message,
+                Some(READ_ONLY.into()),
+                "after pressing {key:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn no_side_effect_in_inspect_mode() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        // NOTE: these would move the cursor before changing the data
+        for key in [kmap.wrap_in_list, kmap.wrap_in_record, kmap.transpose] {
+            let mut app = App::from_value(test_value());
+            app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+            app.inspect = true;
+
+            let result =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                    .unwrap();
+            assert_eq!(result, TransitionResult::Continue, "after pressing {key:?}");
+            assert_eq!(
+                app.position.members,
+                to_path_member_vec(&[PM::S("r"), PM::S("a")]),
+                "after pressing {key:?}"
+            );
+            assert_eq!(app.message, Some(READ_ONLY.into()));
+        }
+
+        // NOTE: this would copy the value to the clipboard before changing the data
+        let mut app = App::from_value(test_value());
+        app.inspect = true;
+        assert_eq!(duplicate_and_yank(&mut app), TransitionResult::Continue);
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+    }
+
+    #[test]
+    fn scope_lock() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key, modifiers| {
+            handle_key_events(KeyEvent::new(key, modifiers), app, &config).unwrap();
+        };
+        let path = |members: &[PM]| to_path_member_vec(members);
+
+        press(&mut app, kmap.navigation.down, KeyModifiers::empty());
+        press(&mut app, kmap.navigation.right, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("r"), PM::S("a")]));
+
+        press(&mut app, kmap.toggle_scope_lock, KeyModifiers::ALT);
+        assert!(app.scope_lock);
+
+        // NOTE: going left from the container is a no-op
+        press(&mut app, kmap.navigation.left, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("r"), PM::S("a")]));
+        assert_eq!(
+            app.message,
+            Some("the navigation is locked in the current container".into())
+        );
+
+        press(&mut app, kmap.navigation.down, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("r"), PM::S("b")]));
+        press(&mut app, kmap.navigation.right, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("r"), PM::S("b")]));
+        assert_eq!(app.mode, Mode::Normal);
+
+        // NOTE: the tree view does not go to the rows of the other containers either
+        let tree = Config {
+            layout: Layout::Tree,
+            ..config.clone()
+        };
+        handle_key_events(
+            KeyEvent::new(kmap.navigation.down, KeyModifiers::empty()),
+            &mut app,
+            &tree,
+        )
+        .unwrap();
+        assert_eq!(app.position.members, path(&[PM::S("r"), PM::S("b")]));
+
+        // NOTE: the rows of the expanded siblings are skipped
+        let mut root = App::from_value(test_value());
+        root.scope_lock = true;
+        root.expanded.insert(to_keys(&path(&[PM::S("r")])));
+        root.position.members = path(&[PM::S("r")]);
+        handle_key_events(
+            KeyEvent::new(kmap.navigation.down, KeyModifiers::empty()),
+            &mut root,
+            &tree,
+        )
+        .unwrap();
+        assert_eq!(root.position.members, path(&[PM::S("s")]));
+
+        // NOTE: collapsing does not leave the container either
+        for layout in [Layout::Table, Layout::Tree] {
+            let config = Config {
+                layout,
+                ..config.clone()
+            };
+            for key in [kmap.collapse, kmap.collapse_all] {
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                    .unwrap();
+                assert_eq!(
+                    app.position.members,
+                    path(&[PM::S("r"), PM::S("b")]),
+                    "after pressing {key:?} in {:?}",
+                    config.layout
+                );
+            }
+        }
+
+        press(&mut app, kmap.toggle_scope_lock, KeyModifiers::ALT);
+        press(&mut app, kmap.navigation.left, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("r")]));
+    }
+
+    #[test]
+    fn expand_and_collapse_all_outside_of_the_tree() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("b")]);
+
+        for key in [kmap.expand_all, kmap.collapse_all] {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
+            assert_eq!(
+                app.position.members,
+                to_path_member_vec(&[PM::S("r"), PM::S("b")]),
+                "after pressing {key:?}"
+            );
+            assert!(app.expanded.is_empty(), "after pressing {key:?}");
+            assert_eq!(app.message, Some(TREE_ONLY.into()));
+        }
+    }
+
+    #[test]
+    fn jump_to_matching() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key, modifiers| {
+            handle_key_events(KeyEvent::new(key, modifiers), app, &config).unwrap();
+        };
+        let path = |members: &[PM]| to_path_member_vec(members);
+
+        press(&mut app, kmap.navigation.right, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("l"), PM::I(0)]));
+
+        press(&mut app, kmap.jump_to_matching, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("l"), PM::I(2)]));
+        press(&mut app, kmap.jump_to_matching, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("l"), PM::I(0)]));
+
+        // NOTE: any row in the middle goes back to the first one
+        press(&mut app, kmap.navigation.down, KeyModifiers::empty());
+        press(&mut app, kmap.jump_to_matching, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("l"), PM::I(0)]));
+
+        // NOTE: the rows of the records are their fields
+        press(&mut app, kmap.navigation.left, KeyModifiers::empty());
+        press(&mut app, kmap.jump_to_matching, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("i")]));
+        press(&mut app, kmap.jump_to_matching, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("l")]));
+    }
+
+    #[test]
+    fn rename_keys() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key, modifiers| {
+            let result = handle_key_events(KeyEvent::new(key, modifiers), app, &config).unwrap();
+            if let TransitionResult::Mutate(cell, path) = &result {
+                app.apply_edit(path, cell);
+            }
+            // NOTE: the editor is given its width by the terminal in the real application
+            app.editor.set_width(20);
+            result
+        };
+
+        // NOTE: the items of lists have no keys
+        press(&mut app, kmap.navigation.right, KeyModifiers::empty());
+        press(&mut app, kmap.toggle_key_focus, KeyModifiers::ALT);
+        assert!(!app.key_focus);
+        press(&mut app, kmap.navigation.left, KeyModifiers::empty());
+
+        press(&mut app, kmap.navigation.down, KeyModifiers::empty());
+        press(&mut app, kmap.toggle_key_focus, KeyModifiers::ALT);
+        assert!(app.key_focus);
+        assert_eq!(app.message, Some("focusing the keys".into()));
+
+        // NOTE: the cursor stays on the keys
+        press(&mut app, kmap.navigation.right, KeyModifiers::empty());
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r")]));
+
+        press(&mut app, kmap.insert, KeyModifiers::empty());
+        assert_eq!(app.editor.buffer, "r");
+        // NOTE: the cursor of the editor starts at the beginning of the key
+        press(&mut app, KeyCode::Right, KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('2'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r2")]));
+        assert_eq!(
+            app.value.get_data_by_key("r2"),
+            Some(Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }))
+        );
+        assert_eq!(app.value.get_data_by_key("r"), None);
+
+        // NOTE: another field cannot be overwritten
+        press(&mut app, kmap.insert, KeyModifiers::empty());
+        press(&mut app, KeyCode::Delete, KeyModifiers::empty());
+        press(&mut app, KeyCode::Delete, KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::empty());
+        assert_eq!(
+            press(&mut app, KeyCode::Enter, KeyModifiers::empty()),
+            TransitionResult::Error("there is already a field named `s`".into())
+        );
+
+        press(&mut app, kmap.toggle_key_focus, KeyModifiers::ALT);
+        assert!(!app.key_focus);
+        press(&mut app, kmap.navigation.right, KeyModifiers::empty());
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("r2"), PM::S("a")])
+        );
+    }
+
+    #[test]
+    fn delete_with_motion() {
+        let config = Config {
+            operator_pending: true,
+            ..Config::default()
+        };
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(Value::test_list((0..5).map(Value::test_int).collect()));
+        app.position.members = to_path_member_vec(&[PM::I(1)]);
+        let press = |app: &mut App, key| {
+            let result =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, &config).unwrap();
+            if let TransitionResult::Mutate(cell, path) = &result {
+                app.apply_edit(path, cell);
+            }
+        };
+
+        press(&mut app, kmap.delete_operator);
+        press(&mut app, KeyCode::Char('2'));
+        assert_eq!(
+            app.pending_operator,
+            Some(PendingOperator {
+                operator: Operator::Delete,
+                count: Some(2),
+            })
+        );
+        press(&mut app, kmap.navigation.down);
+        assert_eq!(
+            app.value,
+            Value::test_list(vec![Value::test_int(0), Value::test_int(4)])
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(1)]));
+        assert_eq!(app.message, Some("deleted 3 items".into()));
+        assert_eq!(app.pending_operator, None);
+
+        // NOTE: the key of the operator again deletes the item under the cursor
+        press(&mut app, kmap.delete_operator);
+        press(&mut app, kmap.delete_operator);
+        assert_eq!(app.value, Value::test_list(vec![Value::test_int(0)]));
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+
+        // NOTE: any other key cancels the operator
+        press(&mut app, kmap.delete_operator);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.pending_operator, None);
+        assert_eq!(app.value, Value::test_list(vec![Value::test_int(0)]));
+
+        // NOTE: a huge count does not overflow and deletes up to the end of the list
+        let mut app = App::from_value(Value::test_list((0..3).map(Value::test_int).collect()));
+        app.position.members = to_path_member_vec(&[PM::I(1)]);
+        press(&mut app, kmap.delete_operator);
+        for _ in 0..25 {
+            press(&mut app, KeyCode::Char('9'));
+        }
+        assert_eq!(
+            app.pending_operator,
+            Some(PendingOperator {
+                operator: Operator::Delete,
+                count: Some(usize::MAX),
+            })
+        );
+        press(&mut app, kmap.navigation.down);
+        assert_eq!(app.value, Value::test_list(vec![Value::test_int(0)]));
+
+        // NOTE: the cursor stays inside the list when it's emptied and can still move
+        press(&mut app, kmap.delete_operator);
+        press(&mut app, kmap.delete_operator);
+        assert_eq!(app.value, Value::test_list(vec![]));
+        let missing = vec![PathMember::Int {
+            val: 0,
+            span: Span::unknown(),
+            optional: true,
+        }];
+        assert_eq!(app.position.members, missing);
+        press(&mut app, kmap.navigation.down);
+        press(&mut app, kmap.navigation.up);
+        assert_eq!(app.position.members, missing);
+    }
+
+    #[test]
+    fn where_clause() {
+        let value = Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("foo"),
+                "size" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("bar"),
+                "size" => Value::test_int(2),
+            }),
+        ]);
+        let mut app = App::from_value(value);
+        app.position.members = to_path_member_vec(&[PM::I(0), PM::S("name")]);
+        assert_eq!(
+            super::where_clause_under_cursor(&app),
+            Some(r#"where name == "foo""#.into())
+        );
+
+        // NOTE: the cells outside of tables cannot be filtered on
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+        assert_eq!(super::where_clause_under_cursor(&app), None);
+    }
+
+    #[test]
+    fn session_patch() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let result = handle_key_events(
+            KeyEvent::new(kmap.yank_patch, KeyModifiers::ALT),
+            &mut app,
+            &config,
+        );
+        assert!(matches!(result, Ok(TransitionResult::Continue)));
+        assert_eq!(app.message, Some("no edits to export as a patch".into()));
+
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("i")]),
+        };
+        app.apply_edit(&path, &Value::test_int(124));
+
+        let patch = super::session_patch(&app).unwrap();
+        let changed: Vec<&str> = patch
+            .lines()
+            .skip(2)
+            .filter(|l| l.starts_with('-') || l.starts_with('+'))
+            .collect();
+        assert_eq!(changed, vec!["-  \"i\": 123", "+  \"i\": 124"]);
+    }
+
+    #[test]
+    fn commit_sort() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+        let press = |app: &mut App, key, modifiers| {
+            let result = handle_key_events(KeyEvent::new(key, modifiers), app, &config);
+            if let TransitionResult::Mutate(cell, path) = result.unwrap() {
+                app.apply_edit(&path, &cell);
+            }
+            app.value.clone()
+        };
+
+        press(&mut app, kmap.commit_sort, KeyModifiers::ALT);
+        let value = press(&mut app, KeyCode::Char('v'), KeyModifiers::empty());
+        assert_eq!(
+            value.get_data_by_key("l"),
+            Some(Value::test_list(vec![
+                Value::test_string("elements"),
+                Value::test_string("list"),
+                Value::test_string("my"),
+            ]))
+        );
+
+        // NOTE: the lists have no key to be sorted by
+        press(&mut app, kmap.commit_sort, KeyModifiers::ALT);
+        let unchanged = press(&mut app, KeyCode::Char('K'), KeyModifiers::empty());
+        assert_eq!(unchanged, value);
+        assert_eq!(
+            app.message,
+            Some("only records can be sorted by key".into())
+        );
+
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+        press(&mut app, kmap.commit_sort, KeyModifiers::ALT);
+        let value = press(&mut app, KeyCode::Char('K'), KeyModifiers::empty());
+        assert_eq!(
+            value.get_data_by_key("r"),
+            Some(Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "a" => Value::test_int(1),
+            }))
+        );
+    }
+
+    #[test]
+    fn reload_the_config() {
+        let file = std::env::temp_dir()
+            .join(format!("nu_plugin_explore_reload_{}", std::process::id()))
+            .join("config.json");
+        let _ = std::fs::remove_dir_all(file.parent().unwrap());
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+
+        let mut config = Config::default();
+        let reload = config.keybindings.reload_config;
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, config: &Config, key, modifiers| {
+            handle_key_events(KeyEvent::new(key, modifiers), app, config).unwrap()
+        };
+        let path = |members: &[PM]| to_path_member_vec(members);
+
+        // NOTE: there is nothing to reload without a config file
+        assert_eq!(
+            press(&mut app, &config, reload, KeyModifiers::ALT),
+            TransitionResult::Continue
+        );
+        assert_eq!(
+            app.message,
+            Some("no config file to reload, give one with `--config`".into())
+        );
+
+        app.config_file = Some(file.clone());
+        press(&mut app, &config, KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("l")]));
+
+        std::fs::write(
+            &file,
+            r#"{"keybindings": {"navigation": {"down": "down"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            press(&mut app, &config, reload, KeyModifiers::ALT),
+            TransitionResult::ReloadConfig
+        );
+        reload_config(&mut app, &mut config).unwrap();
+        assert_eq!(
+            app.message,
+            Some(format!("reloaded the config from {}", file.display()))
+        );
+
+        press(&mut app, &config, KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(app.position.members, path(&[PM::S("r")]));
+
+        // NOTE: a broken config file leaves the active config untouched
+        std::fs::write(&file, "{").unwrap();
+        assert!(reload_config(&mut app, &mut config).is_err());
+        std::fs::remove_dir_all(file.parent().unwrap()).unwrap();
+        assert_eq!(config.keybindings.navigation.down, KeyCode::Down);
+    }
+
+    #[test]
+    fn navigation_history() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key, modifiers| {
+            handle_key_events(KeyEvent::new(key, modifiers), app, &config).unwrap();
+        };
+        let path = |members: &[PM]| to_path_member_vec(members);
+
+        // NOTE: the moves between siblings replace each other in the history
+        for key in [
+            kmap.navigation.right,
+            kmap.navigation.down,
+            kmap.navigation.left,
+            kmap.navigation.down,
+            kmap.navigation.right,
+        ] {
+            press(&mut app, key, KeyModifiers::empty());
+        }
+        assert_eq!(app.position.members, path(&[PM::S("r"), PM::S("a")]));
+
+        for (key, expected) in [
+            (kmap.history_back, path(&[PM::S("r")])),
+            (kmap.history_back, path(&[PM::S("l"), PM::I(1)])),
+            (kmap.history_forward, path(&[PM::S("r")])),
+            (kmap.history_back, path(&[PM::S("l"), PM::I(1)])),
+            (kmap.history_back, path(&[PM::S("l")])),
+        ] {
+            press(&mut app, key, KeyModifiers::ALT);
+            assert_eq!(app.position.members, expected);
+        }
+
+        press(&mut app, kmap.history_back, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("l")]));
+        assert_eq!(
+            app.message,
+            Some("already at the oldest position of the history".into())
+        );
+
+        // NOTE: navigating forgets the positions after the current one, like in a browser
+        press(&mut app, kmap.navigation.down, KeyModifiers::empty());
+        press(&mut app, kmap.history_forward, KeyModifiers::ALT);
+        assert_eq!(app.position.members, path(&[PM::S("r")]));
+        assert_eq!(
+            app.message,
+            Some("already at the newest position of the history".into())
+        );
+    }
+
+    #[test]
+    fn cycle_through_edits() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        let press = |app: &mut App, key| {
+            let transition =
+                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, &config).unwrap();
+            if let TransitionResult::Mutate(cell, path) = transition {
+                app.apply_edit(&path, &cell);
+            }
+            app.editor.set_width(20);
+        };
+
+        press(&mut app, kmap.next_edit);
+        assert_eq!(app.message, Some("no cell has been edited yet".into()));
+
+        // NOTE: edit `s`, then `l.1` twice, which is only remembered once
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        for key in [kmap.insert, KeyCode::Char('x'), KeyCode::Enter] {
+            press(&mut app, key);
+        }
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(1)]);
+        for _ in 0..2 {
+            for key in [kmap.insert, KeyCode::Char('x'), KeyCode::Enter] {
+                press(&mut app, key);
+            }
+        }
+        assert_eq!(app.edited.len(), 2);
+
+        let s = to_path_member_vec(&[PM::S("s")]);
+        let l = to_path_member_vec(&[PM::S("l"), PM::I(1)]);
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+        for (key, expected) in [
+            (kmap.next_edit, &s),
+            (kmap.next_edit, &l),
+            (kmap.next_edit, &s),
+            (kmap.previous_edit, &l),
+        ] {
+            press(&mut app, key);
+            assert_eq!(&app.position.members, expected);
+        }
+        assert_eq!(app.message, Some("edit 2 of 2".into()));
+    }
+
+    #[test]
+    fn filter_siblings_by_type() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_string("foo"),
+            "c" => Value::test_int(2),
+            "d" => Value::test_string("bar"),
+        }));
+
+        let transitions = vec![
+            (kmap.type_filter, "a"),
+            (KeyCode::Char('s'), "a"),
+            (kmap.navigation.down, "b"),
+            (kmap.navigation.down, "d"),
+            (kmap.navigation.down, "b"),
+            (kmap.navigation.up, "d"),
+            // NOTE: a letter that is not a type stops the filtering
+            (kmap.type_filter, "d"),
+            (KeyCode::Char('x'), "d"),
+            (kmap.navigation.down, "a"),
+        ];
+
+        for (key, expected) in transitions {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
+            assert_eq!(app.position.members, to_path_member_vec(&[PM::S(expected)]));
+        }
+        assert_eq!(app.nav_type_filter, None);
+    }
+
+    #[test]
+    fn split_string_cell() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(Value::test_record(record! {
+            "s" => Value::test_string("a,b,c"),
+            "i" => Value::test_int(1),
+        }));
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        handle_key_events(key(kmap.split_string), &mut app, &config).unwrap();
+        assert_eq!(
+            handle_key_events(key(KeyCode::Char(',')), &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_list(vec![
+                    Value::test_string("a"),
+                    Value::test_string("b"),
+                    Value::test_string("c"),
+                ]),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("s")])
+                }
+            )
+        );
+
+        handle_key_events(key(kmap.navigation.down), &mut app, &config).unwrap();
+        handle_key_events(key(kmap.split_string), &mut app, &config).unwrap();
+        assert!(matches!(
+            handle_key_events(key(KeyCode::Char(',')), &mut app, &config).unwrap(),
+            TransitionResult::Error(_)
+        ));
+
+        // NOTE: no delimiter is asked for in inspect mode
+        app.inspect = true;
+        handle_key_events(key(kmap.split_string), &mut app, &config).unwrap();
+        assert_eq!(app.pending_sequence, None);
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+    }
+
+    #[test]
+    fn join_list_cell() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l")]);
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        handle_key_events(key(kmap.join_list), &mut app, &config).unwrap();
+        assert_eq!(
+            handle_key_events(key(KeyCode::Char(',')), &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_string("my,list,elements"),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("l")])
+                }
+            )
+        );
+
+        // NOTE: no separator is asked for in inspect mode
+        app.inspect = true;
+        handle_key_events(key(kmap.join_list), &mut app, &config).unwrap();
+        assert_eq!(app.pending_sequence, None);
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+    }
+
+    #[test]
+    fn type_ahead() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        handle_key_events(key(kmap.type_ahead), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('s')), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("s")]));
+        assert_eq!(app.pending_sequence, None);
+
+        handle_key_events(key(kmap.type_ahead), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('x')), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("s")]));
+        assert_eq!(app.message, Some("no key starting with 'x'".into()));
+    }
+
+    #[test]
+    fn record_to_values_cell() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        assert_eq!(
+            handle_key_events(key(kmap.record_to_values), &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("r")])
+                }
+            )
+        );
+
+        handle_key_events(key(kmap.navigation.down), &mut app, &config).unwrap();
+        assert_eq!(
+            handle_key_events(key(kmap.record_to_values), &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+    }
+
+    #[test]
+    fn filter_list_cell() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l")]);
+        let key = KeyEvent::new(config.keybindings.filter_list, KeyModifiers::empty());
+
+        // NOTE: nothing to filter with without a predicate
+        assert_eq!(
+            handle_key_events(key, &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+        assert!(app.message.is_some());
+
+        app.predicate = Some(where_predicate("!= my").unwrap());
+        assert_eq!(
+            handle_key_events(key, &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_list(vec![
+                    Value::test_string("list"),
+                    Value::test_string("elements"),
+                ]),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("l")])
+                }
+            )
+        );
+
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
+        assert_eq!(
+            handle_key_events(key, &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+    }
+
+    #[test]
+    fn cycle_enum_cell() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "status" => Value::test_string("open"),
+        }));
+        let key = KeyEvent::new(config.keybindings.cycle_enum, KeyModifiers::empty());
+
+        // NOTE: nothing to cycle through without a schema
+        assert_eq!(
+            handle_key_events(key, &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+        assert!(app.message.is_some());
+
+        app.schema = Some(Value::test_record(record! {
+            "properties" => Value::test_record(record! {
+                "status" => Value::test_record(record! {
+                    "enum" => Value::test_list(vec![
+                        Value::test_string("open"),
+                        Value::test_string("closed"),
+                    ]),
+                }),
+            }),
+        }));
+        for expected in ["closed", "open", "closed"] {
+            let TransitionResult::Mutate(cell, path) =
+                handle_key_events(key, &mut app, &config).unwrap()
+            else {
+                panic!("cycling should mutate the data");
+            };
+            app.apply_edit(&path, &cell);
+            assert_eq!(
+                app.value.get_data_by_key("status"),
+                Some(Value::test_string(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn retab_cell() {
+        let config = Config {
+            tab_width: 2,
+            ..Default::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "s" => Value::test_string("a\n\tb"),
+        }));
+        let key = KeyEvent::new(config.keybindings.retab, KeyModifiers::empty());
+
+        for expected in ["a\n  b", "a\n\tb"] {
+            let TransitionResult::Mutate(cell, path) =
+                handle_key_events(key, &mut app, &config).unwrap()
+            else {
+                panic!("retabbing should mutate the data");
+            };
+            app.apply_edit(&path, &cell);
+            assert_eq!(
+                app.value.get_data_by_key("s"),
+                Some(Value::test_string(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn collapse_string_cell() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(Value::test_record(record! {
+            "s" => Value::test_string("a\n b\n"),
+            "i" => Value::test_int(1),
+        }));
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        assert_eq!(
+            handle_key_events(key(kmap.collapse_string), &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_string("a b"),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("s")])
+                }
+            )
+        );
+
+        handle_key_events(key(kmap.navigation.down), &mut app, &config).unwrap();
+        assert_eq!(
+            handle_key_events(key(kmap.collapse_string), &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.message, Some("only strings can be collapsed".into()));
+    }
+
+    #[test]
+    fn parse_string_cell() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(Value::test_record(record! {
+            "j" => Value::test_string(r#"{"a":1}"#),
+            "s" => Value::test_string("foo"),
+        }));
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        assert_eq!(
+            handle_key_events(key(kmap.parse_string), &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                }),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("j")])
+                }
+            )
+        );
+
+        handle_key_events(key(kmap.navigation.down), &mut app, &config).unwrap();
+        assert_eq!(
+            handle_key_events(key(kmap.parse_string), &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(
+            app.message,
+            Some("not a JSON or NUON list or record".into())
+        );
+    }
+
+    #[test]
+    fn sort_view_and_go_back() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let strings =
+            |vals: &[&str]| Value::test_list(vals.iter().map(|v| Value::test_string(*v)).collect());
+        let value = strings(&["10", "2", "1"]);
+        let mut app = App::from_value(value.clone());
+
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
+
+        handle_key_events(key(kmap.sort), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('n')), &mut app, &config).unwrap();
+        assert_eq!(app.value, strings(&["1", "2", "10"]));
+
+        // NOTE: sorting again goes back to the data, which has not changed
+        handle_key_events(key(kmap.sort), &mut app, &config).unwrap();
+        assert_eq!(app.value, value);
+
+        handle_key_events(key(kmap.sort), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('l')), &mut app, &config).unwrap();
+        assert_eq!(app.value, strings(&["1", "10", "2"]));
     }
 
-    Ok(TransitionResult::Continue)
-}
+    #[test]
+    fn edit_under_sort_view() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
 
-#[cfg(test)]
-mod tests {
-    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-    use nu_protocol::{
-        ast::{CellPath, PathMember},
-        record, Span, Value,
-    };
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+        handle_key_events(key(kmap.sort), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('l')), &mut app, &config).unwrap();
 
-    use super::{handle_key_events, App, TransitionResult};
-    use crate::{
-        app::Mode,
-        config::{repr_keycode, Config},
-        nu::cell_path::{to_path_member_vec, PM},
-    };
+        let position = app.position.clone();
+        let TransitionResult::Mutate(cell, path) =
+            commit_edit(&mut app, Value::test_string("x"), position, &config)
+        else {
+            panic!("the edit should mutate the view");
+        };
+        app.apply_edit(&path, &cell);
 
-    /// {
-    ///     l: ["my", "list", "elements"],
-    ///     r: {a: 1, b: 2},
-    ///     s: "some string",
-    ///     i: 123,
-    /// }
-    fn test_value() -> Value {
-        Value::test_record(record! {
-            "l" => Value::test_list(vec![
-                Value::test_string("my"),
-                Value::test_string("list"),
-                Value::test_string("elements"),
-            ]),
-            "r" => Value::test_record(record! {
-                "a" => Value::test_int(1),
-                "b" => Value::test_int(2),
-            }
-            ),
-            "s" => Value::test_string("some string"),
-            "i" => Value::test_int(123),
-        })
+        // NOTE: the edit is lost with the view and the data has no edits
+        handle_key_events(key(kmap.sort), &mut app, &config).unwrap();
+        assert_eq!(app.value, test_value());
+        assert!(app.edited.is_empty());
+        assert_eq!(super::session_patch(&app), None);
     }
 
     #[test]
-    fn switch_modes() {
+    fn sort_only_restores_its_view() {
         let config = Config::default();
-        let keybindings = config.clone().keybindings;
+        let kmap = config.clone().keybindings;
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
 
-        let value = Value::test_string("foo");
-        let mut app = App::from_value(value);
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
 
-        assert!(app.mode == Mode::Normal);
+        // NOTE: the schema is left open and not sorted
+        handle_key_events(key(kmap.schema), &mut app, &config).unwrap();
+        let schema = app.value.clone();
+        handle_key_events(key(kmap.sort), &mut app, &config).unwrap();
+        assert_eq!(app.value, schema);
+        assert!(app.pending_sequence.is_none());
+        assert!(app.message.is_some());
 
-        // INSERT -> PEEKING: not allowed
-        // PEEKING -> INSERT: not allowed
-        let transitions = vec![
-            (keybindings.normal, Mode::Normal),
-            (keybindings.insert, Mode::Insert),
-            (keybindings.normal, Mode::Normal),
-            (keybindings.peek, Mode::Peeking),
-            (keybindings.normal, Mode::Normal),
-        ];
+        handle_key_events(key(kmap.schema), &mut app, &config).unwrap();
+        assert_eq!(app.value, test_value());
+    }
 
-        for (key, expected_mode) in transitions {
-            let mode = app.mode.clone();
+    #[test]
+    fn jump_up_to_max_depth() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+        let key = |k| KeyEvent::new(k, KeyModifiers::empty());
 
-            let result =
-                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
-                    .unwrap();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+        handle_key_events(key(kmap.mark), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('x')), &mut app, &config).unwrap();
 
-            assert!(
-                !result.is_quit(),
-                "unexpected exit after pressing {} in {}",
-                repr_keycode(&key),
-                mode,
-            );
-            assert!(
-                app.mode == expected_mode,
-                "expected to be in {} after pressing {} in {}, found {}",
-                expected_mode,
-                repr_keycode(&key),
-                mode,
-                app.mode
-            );
-        }
+        // NOTE: the mark is deeper than the limit, the cursor stops at the limit
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        app.max_depth = Some(1);
+        handle_key_events(key(kmap.jump_to_mark), &mut app, &config).unwrap();
+        handle_key_events(key(KeyCode::Char('x')), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r")]));
+        assert_eq!(app.message, Some(MAX_DEPTH.into()));
     }
 
     #[test]
-    fn quit() {
+    fn mark_and_jump() {
         let config = Config::default();
-        let keybindings = config.clone().keybindings;
+        let kmap = config.clone().keybindings;
 
-        let value = test_value();
-        let mut app = App::from_value(value);
+        let mut app = App::from_value(test_value());
 
         let transitions = vec![
-            (keybindings.insert, false),
-            (keybindings.quit, true),
-            (keybindings.normal, false),
-            (keybindings.quit, true),
-            (keybindings.peek, false),
-            (keybindings.quit, true),
+            (kmap.navigation.down, vec![PM::S("r")]),
+            (kmap.navigation.right, vec![PM::S("r"), PM::S("a")]),
+            (kmap.mark, vec![PM::S("r"), PM::S("a")]),
+            (KeyCode::Char('x'), vec![PM::S("r"), PM::S("a")]),
+            (kmap.navigation.left, vec![PM::S("r")]),
+            (kmap.navigation.down, vec![PM::S("s")]),
+            (kmap.jump_to_mark, vec![PM::S("s")]),
+            (KeyCode::Char('x'), vec![PM::S("r"), PM::S("a")]),
+            // a key that is not a letter cancels the sequence
+            (kmap.jump_to_mark, vec![PM::S("r"), PM::S("a")]),
+            (KeyCode::Esc, vec![PM::S("r"), PM::S("a")]),
+            (kmap.navigation.down, vec![PM::S("r"), PM::S("b")]),
         ];
 
-        for (key, exit) in transitions {
-            let mode = app.mode.clone();
+        for (key, cell_path) in transitions {
+            let expected = to_path_member_vec(&cell_path);
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
 
-            let result =
-                handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
-                    .unwrap();
+            assert_eq!(
+                app.position.members,
+                expected,
+                "expected to be at {:?}, found {:?}",
+                repr_path_member_vec(&expected),
+                repr_path_member_vec(&app.position.members)
+            );
+        }
 
-            if exit {
-                assert!(
-                    result.is_quit(),
-                    "expected to quit after pressing {} in {} mode",
-                    repr_keycode(&key),
-                    mode
-                );
-            } else {
-                assert!(
-                    !result.is_quit(),
-                    "expected NOT to quit after pressing {} in {} mode",
-                    repr_keycode(&key),
-                    mode
-                );
-            }
+        let result = handle_key_events(
+            KeyEvent::new(kmap.jump_to_mark, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .and_then(|_| {
+            handle_key_events(
+                KeyEvent::new(KeyCode::Char('y'), KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+        })
+        .unwrap();
+        assert_eq!(result, TransitionResult::Error("no mark 'y'".into()));
+    }
+
+    #[test]
+    fn search_and_explore_results() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+
+        let mut app = App::from_value(test_value());
+
+        let mut keys = vec![kmap.search];
+        keys.extend("list".chars().map(KeyCode::Char));
+        keys.push(KeyCode::Enter);
+        for key in keys {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
         }
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(1)])
+        );
+
+        handle_key_events(
+            KeyEvent::new(kmap.search_results, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let expected = Value::test_list(vec![Value::test_record(record! {
+            "path" => Value::test_cell_path(CellPath {
+                members: to_path_member_vec(&[PM::S("l"), PM::I(1)]),
+            }),
+            "value" => Value::test_string("list"),
+        })]);
+        assert_eq!(app.value, expected);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+
+        handle_key_events(
+            KeyEvent::new(kmap.search_results, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(app.value, test_value());
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(1)])
+        );
+    }
+
+    #[test]
+    fn jump_to_path_strings() {
+        let mut app = App::from_value(test_value());
+
+        jump_to_path_string(&mut app, "$.r.a", &Config::default());
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("r"), PM::S("a")])
+        );
+        jump_to_path_string(&mut app, " l[2]\n", &Config::default());
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(2)])
+        );
+
+        jump_to_path_string(&mut app, "$.r.c", &Config::default());
+        assert_eq!(app.message, Some("`$.r.c` is not in the data".into()));
+        jump_to_path_string(&mut app, "$.r[", &Config::default());
+        assert_eq!(app.message, Some("`$.r[` is not a cell path".into()));
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(2)])
+        );
+    }
+
+    #[test]
+    fn explore_schema() {
+        let config = Config::default();
+        let schema = KeyEvent::new(config.keybindings.schema, KeyModifiers::empty());
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
+
+        handle_key_events(schema, &mut app, &config).unwrap();
+        assert_eq!(
+            app.value,
+            Value::test_record(record! {
+                "a" => Value::test_string("int"),
+                "b" => Value::test_string("int"),
+            })
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("a")]));
+
+        handle_key_events(schema, &mut app, &config).unwrap();
+        assert_eq!(app.value, test_value());
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("r")]));
+
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        handle_key_events(schema, &mut app, &config).unwrap();
+        assert_eq!(app.value, test_value());
+        assert!(app.message.is_some());
     }
 
     fn repr_path_member_vec(members: &[PathMember]) -> String {
@@ -451,6 +4792,240 @@ mod tests {
         }
     }
 
+    #[test]
+    fn peek_missing_cells() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![]),
+        });
+        let cases = vec![
+            (
+                MissingDisplay::Null,
+                TransitionResult::Return(Value::test_nothing()),
+            ),
+            (MissingDisplay::Missing, TransitionResult::Continue),
+            (
+                MissingDisplay::Reconcile,
+                TransitionResult::Return(Value::test_list(vec![])),
+            ),
+        ];
+
+        for (missing_display, expected) in cases {
+            let config = Config {
+                missing_display: missing_display.clone(),
+                ..Default::default()
+            };
+            let mut app = App::from_value(value.clone());
+            app.position.members = vec![
+                PathMember::String {
+                    val: "l".into(),
+                    span: Span::unknown(),
+                    optional: false,
+                },
+                PathMember::Int {
+                    val: 0,
+                    span: Span::unknown(),
+                    optional: true,
+                },
+            ];
+            app.mode = Mode::Peeking;
+
+            assert_eq!(
+                handle_key_events(
+                    KeyEvent::new(config.keybindings.peeking.under, KeyModifiers::empty()),
+                    &mut app,
+                    &config,
+                )
+                .unwrap(),
+                expected,
+                "peeking with {:?}",
+                missing_display
+            );
+            if missing_display == MissingDisplay::Missing {
+                assert!(app.message.is_some_and(|m| m.starts_with("(missing)")));
+            }
+        }
+    }
+
+    #[test]
+    fn warn_on_lost_edits() {
+        let mut config = Config::default();
+        let under = config.keybindings.peeking.under;
+        let press = |app: &mut App, config: &Config, key: KeyCode| {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, config).unwrap()
+        };
+        let edited_app = || {
+            let mut app = App::from_value(test_value());
+            let path = CellPath {
+                members: to_path_member_vec(&[PM::S("r"), PM::S("a")]),
+            };
+            app.apply_edit(&path, &Value::test_int(10));
+            app.mode = Mode::Peeking;
+            app
+        };
+
+        // NOTE: the edit is in the peeked value
+        let mut app = edited_app();
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
+        assert_eq!(
+            press(&mut app, &config, under),
+            TransitionResult::Return(Value::test_record(record! {
+                "a" => Value::test_int(10),
+                "b" => Value::test_int(2),
+            }))
+        );
+
+        // NOTE: the edit is outside of the peeked value, the same key has to be pressed again
+        let mut app = edited_app();
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        assert_eq!(press(&mut app, &config, under), TransitionResult::Continue);
+        assert_eq!(
+            app.message,
+            Some("warning: 1 edit(s) would be lost, press p again to peek anyway".into())
+        );
+        assert_eq!(app.mode, Mode::Peeking);
+        assert_eq!(
+            press(&mut app, &config, under),
+            TransitionResult::Return(Value::test_string("some string"))
+        );
+
+        // NOTE: another key cancels the warning
+        let mut app = edited_app();
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        press(&mut app, &config, under);
+        press(&mut app, &config, config.keybindings.navigation.down);
+        assert_eq!(press(&mut app, &config, under), TransitionResult::Continue);
+
+        // NOTE: the config never contains the edits
+        let mut app = edited_app();
+        assert_eq!(
+            press(&mut app, &config, config.keybindings.peeking.config),
+            TransitionResult::Continue
+        );
+
+        config.peeking.warn_on_lost_edits = false;
+        let mut app = edited_app();
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        assert_eq!(
+            press(&mut app, &config, under),
+            TransitionResult::Return(Value::test_string("some string"))
+        );
+    }
+
+    #[test]
+    fn cancel_form_with_normal_key() {
+        let mut config = Config::default();
+        config.keybindings.normal = KeyCode::Char('q');
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+        let press = |code, app: &mut App| {
+            handle_key_events(KeyEvent::new(code, KeyModifiers::empty()), app, &config).unwrap()
+        };
+
+        press(config.keybindings.form, &mut app);
+        assert_eq!(app.mode, Mode::Form);
+
+        // NOTE: the default key to go back to the NORMAL mode is not hardcoded in the form
+        press(KeyCode::Esc, &mut app);
+        assert_eq!(app.mode, Mode::Form);
+        assert_eq!(
+            press(KeyCode::Char('q'), &mut app),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.form.is_none());
+    }
+
+    #[test]
+    fn no_form_in_inspect_mode() {
+        let config = Config::default();
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+        app.inspect = true;
+
+        let result = handle_key_events(
+            KeyEvent::new(config.keybindings.form, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(result, TransitionResult::Continue);
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.form.is_none());
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+    }
+
+    #[test]
+    fn cycle_peek_formats() {
+        let config = Config::default();
+        let peeking = config.keybindings.peeking.clone();
+        let press = |app: &mut App, key: KeyCode| {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), app, &config).unwrap()
+        };
+
+        let mut app = App::from_value(test_value());
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
+        app.mode = Mode::Peeking;
+
+        let expected = [
+            (
+                PeekFormat::Json,
+                Ok(Value::test_string("{\n  \"a\": 1,\n  \"b\": 2\n}")),
+            ),
+            (PeekFormat::Nuon, Ok(Value::test_string("{a: 1, b: 2}"))),
+            (
+                PeekFormat::Yaml,
+                // NOTE: YAML needs the `yaml` feature
+                if cfg!(feature = "yaml") {
+                    Ok(Value::test_string("a: 1\nb: 2\n"))
+                } else {
+                    Err(
+                        "YAML is not available, please build `explore` with the `yaml` feature"
+                            .to_string(),
+                    )
+                },
+            ),
+            (
+                PeekFormat::CellPath,
+                Ok(Value::test_cell_path(CellPath {
+                    members: to_path_member_vec(&[PM::S("r")]),
+                })),
+            ),
+            (
+                PeekFormat::Value,
+                Ok(Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "b" => Value::test_int(2),
+                })),
+            ),
+        ];
+        for (format, value) in expected {
+            assert_eq!(
+                press(&mut app, peeking.cycle_format),
+                TransitionResult::Continue
+            );
+            assert_eq!(app.peek_format, format);
+            assert_eq!(app.message, Some(format!("peeking as {}", format)));
+            assert_eq!(app.mode, Mode::Peeking);
+            assert_eq!(
+                press(&mut app, peeking.under),
+                match value {
+                    Ok(value) => TransitionResult::Return(value),
+                    Err(err) => TransitionResult::Error(err),
+                },
+                "peeking as {}",
+                format
+            );
+            app.mode = Mode::Peeking;
+        }
+
+        // NOTE: the config is not in the data and has no cell path
+        app.peek_format = PeekFormat::CellPath;
+        assert!(matches!(
+            press(&mut app, peeking.config),
+            TransitionResult::Error(_)
+        ));
+    }
+
     #[test]
     fn peek_data() {
         let config = Config::default();
@@ -532,6 +5107,54 @@ mod tests {
         run_peeking_scenario(peek_at_the_bottom, &config, value);
     }
 
+    #[test]
+    fn navigate_while_peeking() {
+        let config = Config::default();
+        let keybindings = config.clone().keybindings;
+
+        let navigate_while_peeking_and_peek_under = vec![
+            (keybindings.peek, false, None),
+            (keybindings.navigation.down, false, None), // on {r: {a: 1, b: 2}}
+            (keybindings.navigation.right, false, None), // on {a: 1}
+            (keybindings.navigation.right, false, None), // still on {a: 1}
+            (keybindings.navigation.down, false, None), // on {b: 2}
+            (keybindings.peeking.under, true, Some(Value::test_int(2))),
+            (keybindings.navigation.left, false, None), // on {r: {a: 1, b: 2}}
+            (keybindings.navigation.up, false, None),   // on {l: ["my", "list", "elements"]}
+            (
+                keybindings.peeking.under,
+                true,
+                Some(Value::test_list(vec![
+                    Value::test_string("my"),
+                    Value::test_string("list"),
+                    Value::test_string("elements"),
+                ])),
+            ),
+        ];
+        run_peeking_scenario(navigate_while_peeking_and_peek_under, &config, test_value());
+    }
+
+    #[test]
+    fn stay_in_peeking_mode_while_navigating() {
+        let config = Config::default();
+        let nav = config.clone().keybindings.navigation;
+
+        let mut app = App::from_value(test_value());
+        app.mode = Mode::Peeking;
+
+        for key in [nav.down, nav.right, nav.right, nav.up, nav.left, nav.left] {
+            handle_key_events(KeyEvent::new(key, KeyModifiers::empty()), &mut app, &config)
+                .unwrap();
+            assert!(
+                app.mode == Mode::Peeking,
+                "expected to stay in {} after pressing {}, found {}",
+                Mode::Peeking,
+                repr_keycode(&key),
+                app.mode
+            );
+        }
+    }
+
     #[test]
     fn transpose_the_data() {
         let config = Config::default();
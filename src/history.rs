@@ -0,0 +1,195 @@
+//! the cell paths visited in the previous sessions, persisted in a small state file to jump back
+//! to them, see [`crate::config::Config::history_file`]
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+use nu_protocol::{
+    ast::{CellPath, PathMember},
+    Span,
+};
+
+use crate::tree::to_keys;
+
+/// the number of cell paths kept in the history
+pub(crate) const HISTORY_LEN: usize = 20;
+
+/// the last visited cell paths, the most recent first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct History {
+    pub paths: Vec<CellPath>,
+}
+
+impl History {
+    /// load the history from the JSON list of cell paths at *path*, each being the list of its
+    /// members, e.g. `[["r", "a"], ["l", 1]]`
+    ///
+    /// a missing file gives an empty history, e.g. the very first time.
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let Some(entries) = json.as_array() else {
+            return Err(anyhow!("the history is not a list, found {}", json));
+        };
+
+        let mut paths = vec![];
+        for entry in entries {
+            let Some(members) = entry.as_array() else {
+                return Err(anyhow!(
+                    "a cell path of the history is not a list: {}",
+                    entry
+                ));
+            };
+            let members = members
+                .iter()
+                .map(|member| match member {
+                    serde_json::Value::String(val) => Ok(PathMember::String {
+                        val: val.clone(),
+                        span: Span::unknown(),
+                        optional: false,
+                    }),
+                    serde_json::Value::Number(n) if n.is_u64() => Ok(PathMember::Int {
+                        val: n.as_u64().unwrap_or_default() as usize,
+                        span: Span::unknown(),
+                        optional: false,
+                    }),
+                    x => Err(anyhow!("invalid member in the history: {}", x)),
+                })
+                .collect::<Result<Vec<PathMember>>>()?;
+            paths.push(CellPath { members });
+        }
+
+        Ok(Self { paths })
+    }
+
+    /// save the history to *path* in the format of [`History::load`], creating its directory if
+    /// needed
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::Value::Array(
+            self.paths
+                .iter()
+                .map(|path| {
+                    serde_json::Value::Array(
+                        path.members
+                            .iter()
+                            .map(|member| match member {
+                                PathMember::String { val, .. } => val.clone().into(),
+                                PathMember::Int { val, .. } => (*val).into(),
+                            })
+                            .collect(),
+                    )
+                })
+                .collect(),
+        );
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string(&json)?)?;
+
+        Ok(())
+    }
+
+    /// put *path* first in the history, removing its previous visit if any and forgetting the
+    /// oldest paths after [`HISTORY_LEN`]
+    pub(crate) fn push(&mut self, path: CellPath) {
+        let keys = to_keys(&path.members);
+        self.paths.retain(|p| to_keys(&p.members) != keys);
+        self.paths.insert(0, path);
+        self.paths.truncate(HISTORY_LEN);
+    }
+
+    /// remember the paths *visited* during a session, in order, and the *last* one, where the
+    /// session ended, which ends up first, see [`crate::app::App::nav_history`]
+    pub(crate) fn push_session(&mut self, visited: &[CellPath], last: CellPath) {
+        for path in visited {
+            self.push(path.clone());
+        }
+        self.push(last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::ast::CellPath;
+
+    use super::{History, HISTORY_LEN};
+    use crate::nu::cell_path::{to_path_member_vec, PM};
+
+    fn path(members: &[PM]) -> CellPath {
+        CellPath {
+            members: to_path_member_vec(members),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let file = std::env::temp_dir().join("nu_plugin_explore_history/history.json");
+        let _ = std::fs::remove_dir_all(file.parent().unwrap());
+
+        // NOTE: there is no history before the first session
+        assert_eq!(History::load(&file).unwrap(), History::default());
+
+        let history = History {
+            paths: vec![
+                path(&[PM::S("r"), PM::S("a")]),
+                path(&[PM::S("l"), PM::I(1)]),
+                path(&[]),
+            ],
+        };
+        history.save(&file).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            r#"[["r","a"],["l",1],[]]"#
+        );
+
+        let loaded = History::load(&file).unwrap();
+        std::fs::remove_dir_all(file.parent().unwrap()).unwrap();
+        assert_eq!(loaded, history);
+    }
+
+    #[test]
+    fn push_paths() {
+        let mut history = History::default();
+        history.push(path(&[PM::S("a")]));
+        history.push(path(&[PM::S("b")]));
+        history.push(path(&[PM::S("a")]));
+        assert_eq!(
+            history.paths,
+            vec![path(&[PM::S("a")]), path(&[PM::S("b")])]
+        );
+
+        for i in 0..HISTORY_LEN {
+            history.push(path(&[PM::I(i)]));
+        }
+        assert_eq!(history.paths.len(), HISTORY_LEN);
+        assert_eq!(history.paths[0], path(&[PM::I(HISTORY_LEN - 1)]));
+    }
+
+    #[test]
+    fn push_visited_paths() {
+        let mut history = History {
+            paths: vec![path(&[PM::S("old")])],
+        };
+        history.push_session(
+            &[
+                path(&[]),
+                path(&[PM::S("r")]),
+                path(&[PM::S("r"), PM::S("a")]),
+            ],
+            path(&[PM::S("r")]),
+        );
+        assert_eq!(
+            history.paths,
+            vec![
+                path(&[PM::S("r")]),
+                path(&[PM::S("r"), PM::S("a")]),
+                path(&[]),
+                path(&[PM::S("old")]),
+            ]
+        );
+    }
+}
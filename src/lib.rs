@@ -1,83 +1,507 @@
 #![doc = include_str!("../README.md")]
+mod ansi;
 mod app;
+mod clipboard;
 mod config;
 mod edit;
 mod event;
+mod file;
+mod fold;
+mod form;
 mod handler;
+mod history;
+mod mouse;
 mod navigation;
 mod nu;
+mod pager;
+mod tree;
 mod tui;
 mod ui;
 
 use anyhow::Result;
 use crossterm::event::KeyEventKind;
-use ratatui::backend::CrosstermBackend;
+use crossterm::{
+    execute,
+    terminal::{Clear, ClearType},
+};
+use ratatui::backend::{Backend, CrosstermBackend};
 use ratatui::Terminal;
 use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use nu_protocol::{Record, Span, Value};
+use nu_plugin::LabeledError;
+use nu_protocol::{ast::CellPath, PipelineData, Record, ShellError, Span, Value};
 
-use app::{App, Mode};
+use app::{cell_path_prompt, where_predicate, App, Mode};
 use config::Config;
-use event::{Event, EventHandler};
-use handler::{handle_key_events, TransitionResult};
+use event::{Debouncer, Event, EventHandler};
+use file::{check_save_path, expand_home};
+pub use file::{load_file, save_value};
+use handler::{handle_key_events, reload_config, TransitionResult, READ_ONLY};
+use history::History;
+use mouse::handle_mouse_events;
+use nu::custom::restore_custom_values;
 use tui::Tui;
 
-pub fn explore(config: &Option<Value>, input: Value) -> Result<Value> {
-    let config = Config::from_value(
-        config
-            .clone()
-            .unwrap_or(Value::record(Record::new(), Span::unknown())),
-    )
-    .expect("Could not convert config value to an actual config");
+/// the options given to the command as flags, as opposed to the configuration
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// render inline instead of in the alternate screen of the terminal
+    pub no_alt_screen: bool,
+    /// explore the data read-only, with the types of all the values shown
+    pub inspect: bool,
+    /// save the data to this file when quitting, instead of giving nothing back
+    pub output: Option<PathBuf>,
+    /// the maximum depth to which the data can be explored, see [`App::max_depth`]
+    pub max_depth: Option<usize>,
+    /// a JSON Schema of the data, see [`App::schema`]
+    pub schema: Option<Value>,
+    /// read the config from this file instead of the config of the plugin, see
+    /// [`App::config_file`]
+    pub config_file: Option<PathBuf>,
+    /// the cell path of the value under the cursor to show to the right of the status bar, see
+    /// [`app::cell_path_prompt`]
+    pub right_prompt: Option<CellPath>,
+    /// a condition to filter the lists with, see [`app::where_predicate`]
+    pub filter: Option<String>,
+}
+
+/// the time between two updates of the progress of the collection of the input
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// the time after which the progress of the collection of the input is shown, for the inputs
+/// collected at once not to flash it
+const PROGRESS_DELAY: Duration = Duration::from_millis(500);
+
+/// the indicator of the progress of the collection of the input, see [`PROGRESS_DELAY`]
+struct Progress {
+    start: Instant,
+    /// when the indicator has been updated last, if ever
+    last: Option<Instant>,
+    /// whether the indicator has been shown, and thus has to be cleared
+    shown: bool,
+}
+
+impl Progress {
+    fn new(start: Instant) -> Self {
+        Self {
+            start,
+            last: None,
+            shown: false,
+        }
+    }
+
+    /// give the text of the indicator for *rows* collected at *now*, once the collection takes
+    /// long enough and at most every [`PROGRESS_INTERVAL`]
+    fn update(&mut self, rows: usize, now: Instant) -> Option<String> {
+        if now.duration_since(self.start) < PROGRESS_DELAY
+            || self
+                .last
+                .is_some_and(|last| now.duration_since(last) < PROGRESS_INTERVAL)
+        {
+            return None;
+        }
+
+        self.last = Some(now);
+        self.shown = true;
+        Some(format!("\rcollecting… {} rows", rows))
+    }
+}
+
+/// collect the *rows* of a stream, calling *progress* with the number of rows collected so far
+/// after each of them, for the progress to be updated even when some rows are slow to come
+fn collect_rows(rows: impl Iterator<Item = Value>, mut progress: impl FnMut(usize)) -> Vec<Value> {
+    let mut collected = vec![];
+    for row in rows {
+        collected.push(row);
+        progress(collected.len());
+    }
+
+    collected
+}
+
+/// collect the *input* of the command into a single value to explore
+///
+/// the lazy lists, e.g. `ls **/*`, can take a while to collect, so the number of rows collected so
+/// far is shown in the terminal until the exploration can start, when it takes more than
+/// [`PROGRESS_DELAY`].
+pub fn collect_input(input: PipelineData, head: Span) -> Value {
+    match input {
+        PipelineData::ListStream(stream, _) => {
+            let mut progress = Progress::new(Instant::now());
+            let rows = collect_rows(stream, |n| {
+                if let Some(indicator) = progress.update(n, Instant::now()) {
+                    eprint!("{}", indicator);
+                }
+            });
+            // NOTE: the TUI might be rendered inline, below the indicator
+            if progress.shown {
+                let _ = execute!(io::stderr(), Clear(ClearType::CurrentLine));
+                eprint!("\r");
+            }
+            Value::list(rows, head)
+        }
+        input => {
+            let span = input.span().unwrap_or(head);
+            input.into_value(span)
+        }
+    }
+}
+
+/// prepare a value peeked by the user to be given back to Nushell
+///
+/// plugins can neither attach metadata to the values they return nor print anything once they are
+/// done, so the value is given the span of the call to `explore`, for Nushell to point errors
+/// about it to where it has been extracted.
+fn peeked(value: Value, head: Span) -> Value {
+    restore_custom_values(value).with_span(head)
+}
+
+/// check the file of [`Options::output`], if any, before exploring the data to save into it
+fn check_output(options: &Options, head: Span) -> Result<()> {
+    if let Some(path) = &options.output {
+        check_save_path(path).map_err(|err| {
+            ShellError::from(LabeledError {
+                label: "invalid output".into(),
+                msg: err.to_string(),
+                span: Some(head),
+            })
+        })?;
+    }
+
+    Ok(())
+}
+
+/// give back the data explored by the user when they quit, see [`Options::output`]
+///
+/// when there is an output file, the data is saved to it and nothing is given back.
+fn quit(value: &Value, head: Span, options: &Options) -> Result<Value> {
+    if let Some(path) = &options.output {
+        save_value(value, path).map_err(|err| {
+            ShellError::from(LabeledError {
+                label: "could not save the data".into(),
+                msg: err.to_string(),
+                span: Some(head),
+            })
+        })?;
+    }
+
+    Ok(Value::nothing(Span::unknown()))
+}
+
+/// explore the *input* value interactively in a TUI
+///
+/// *head* is the span of the call to the plugin, used to report errors such as having nothing to
+/// explore.
+pub fn explore(
+    config: &Option<Value>,
+    input: Value,
+    head: Span,
+    options: &Options,
+) -> Result<Value> {
+    if matches!(input, Value::Nothing { .. }) {
+        return Err(ShellError::from(LabeledError {
+            label: "nothing to explore".into(),
+            msg: "the input is empty".into(),
+            span: Some(head),
+        })
+        .into());
+    }
+
+    let mut config = match &options.config_file {
+        Some(file) => Config::load_from_disk(file).map_err(|err| {
+            ShellError::from(LabeledError {
+                span: Some(head),
+                ..err
+            })
+        })?,
+        None => Config::from_value(
+            config
+                .clone()
+                .unwrap_or(Value::record(Record::new(), Span::unknown())),
+        )
+        .expect("Could not convert config value to an actual config"),
+    };
+
+    check_output(options, head)?;
+
+    let predicate = match &options.filter {
+        Some(condition) => Some(where_predicate(condition).map_err(|err| {
+            ShellError::from(LabeledError {
+                label: "invalid filter".into(),
+                msg: err,
+                span: Some(head),
+            })
+        })?),
+        None => None,
+    };
 
     let mut tui = Tui::new(
         Terminal::new(CrosstermBackend::new(io::stderr()))?,
         EventHandler::new(250),
+        !options.no_alt_screen,
     );
     tui.init()?;
 
-    let mut app = App::from_value(input);
+    let mut app = App::new(&input, &config);
+    app.inspect = options.inspect;
+    app.max_depth = options.max_depth;
+    app.schema = options.schema.clone();
+    app.config_file = options.config_file.clone();
+    app.right_prompt = options.right_prompt.clone().map(cell_path_prompt);
+    app.predicate = predicate;
+    if let Some(file) = &config.history_file {
+        // NOTE: a broken history should not prevent from exploring the data
+        app.history = History::load(&expand_home(file)).unwrap_or_default();
+    }
+    app.show_node_count();
+
+    let exit = run(&mut tui, &mut app, &mut config)?;
+
+    tui.exit()?;
+
+    if let Some(file) = &config.history_file {
+        app.history
+            .push_session(&app.nav_history, app.absolute_position());
+        let _ = app.history.save(&expand_home(file));
+    }
+
+    match exit {
+        Exit::Quit => quit(&app.final_value(), head, options),
+        Exit::Peek(value) => Ok(peeked(value, head)),
+    }
+}
+
+/// how a session of exploration ended
+enum Exit {
+    /// the user quit the session
+    Quit,
+    /// the user peeked this value out of the data
+    Peek(Value),
+}
+
+/// run the loop of events of a session of exploration of the *app*, until the user quits or peeks
+///
+/// the lists and records can be explored in a nested session, whose changes are merged back into
+/// the data when it is quit, see [`App::merge_nested`]. peeking from a nested session ends all the
+/// sessions at once.
+fn run<B: Backend>(tui: &mut Tui<B>, app: &mut App, config: &mut Config) -> Result<Exit> {
+    let mut debouncer = Debouncer::new(Duration::from_millis(config.repeat_debounce_ms as u64));
 
     loop {
+        app.height = tui.size()?.height;
+        app.width = tui.size()?.width;
         if app.mode == Mode::Insert {
             app.editor.set_width(tui.size()?.width as usize)
         }
 
-        tui.draw(&mut app, &config, None)?;
-
-        match tui.events.next()? {
-            Event::Tick => app.tick(),
-            Event::Key(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    match handle_key_events(key_event, &mut app, &config)? {
-                        TransitionResult::Quit => break,
-                        TransitionResult::Continue => {}
-                        TransitionResult::Mutate(cell, path) => {
-                            app.value =
-                                crate::nu::value::mutate_value_cell(&app.value, &path, &cell)
-                        }
-                        TransitionResult::Error(error) => {
-                            tui.draw(&mut app, &config, Some(&error))?;
-                            loop {
-                                if let Event::Key(_) = tui.events.next()? {
-                                    break;
-                                }
-                            }
+        tui.set_cursor_style(&app.mode)?;
+        tui.draw(app, config, None)?;
+
+        let transition = match tui.events.next()? {
+            Event::Tick => {
+                app.tick();
+                continue;
+            }
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                // NOTE: the same letter can be typed twice in a row, e.g. in INSERT mode
+                if app.is_navigating() && !debouncer.accept(key_event, Instant::now()) {
+                    continue;
+                }
+                handle_key_events(key_event, app, config)?
+            }
+            Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, app, config)?,
+            Event::Key(_) | Event::Resize(_, _) => continue,
+        };
+
+        match transition {
+            TransitionResult::Quit => return Ok(Exit::Quit),
+            TransitionResult::Continue => {}
+            // NOTE: the keys refuse the changes up front, this is only a last resort, see
+            // `handler::reject_if_read_only`
+            TransitionResult::Mutate(_, _) if app.inspect => {
+                app.message = Some(READ_ONLY.into());
+            }
+            TransitionResult::Mutate(cell, path) => app.apply_edit(&path, &cell),
+            TransitionResult::Explore(path) => {
+                let cell = app.value.clone().follow_cell_path(&path.members, false)?;
+                let mut nested = App::new(&cell, config);
+                nested.inspect = app.inspect;
+                nested.config_file = app.config_file.clone();
+                nested.right_prompt = app.right_prompt.clone();
+                nested.predicate = app.predicate.clone();
+                // NOTE: the depths of the nested session start at the explored list or record
+                nested.max_depth = app.max_depth.map(|max| {
+                    max.saturating_sub(app.absolute_position().members.len())
+                        .max(1)
+                });
+
+                match run(tui, &mut nested, config)? {
+                    Exit::Quit => app.merge_nested(&path, &mut nested),
+                    peek => return Ok(peek),
+                }
+            }
+            TransitionResult::Error(error) => {
+                tui.draw(app, config, Some(&error))?;
+                loop {
+                    if let Event::Key(_) = tui.events.next()? {
+                        break;
+                    }
+                }
+            }
+            TransitionResult::ReloadConfig => {
+                if let Err(error) = reload_config(app, config) {
+                    tui.draw(app, config, Some(&error))?;
+                    loop {
+                        if let Event::Key(_) = tui.events.next()? {
+                            break;
                         }
-                        TransitionResult::Return(value) => {
-                            tui.exit()?;
-                            return Ok(value);
+                    }
+                }
+            }
+            TransitionResult::Page(text) => {
+                tui.suspend()?;
+                let paged = pager::open(&text);
+                tui.resume()?;
+                if let Err(error) = paged {
+                    tui.draw(app, config, Some(&error))?;
+                    loop {
+                        if let Event::Key(_) = tui.events.next()? {
+                            break;
                         }
                     }
                 }
             }
-            Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
+            TransitionResult::Return(value) => return Ok(Exit::Peek(value)),
         }
     }
+}
 
-    tui.exit()?;
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
 
-    Ok(Value::nothing(Span::unknown()))
+    use nu_plugin::LabeledError;
+    use nu_protocol::{record, ShellError, Span, Value};
+
+    use super::{
+        check_output, collect_rows, explore, peeked, quit, Options, Progress, PROGRESS_DELAY,
+        PROGRESS_INTERVAL,
+    };
+
+    #[test]
+    fn explore_nothing() {
+        let err = explore(
+            &None,
+            Value::test_nothing(),
+            Span::test_data(),
+            &Options::default(),
+        )
+        .unwrap_err();
+        let err = LabeledError::from(err.downcast_ref::<ShellError>().unwrap().clone());
+
+        assert_eq!(err.label, "nothing to explore");
+        assert_eq!(err.msg, "the input is empty");
+        assert_eq!(err.span, Some(Span::test_data()));
+    }
+
+    #[test]
+    fn collect_rows_with_progress() {
+        let mut progress = vec![];
+        let rows = collect_rows((0..2500).map(Value::test_int), |n| progress.push(n));
+
+        assert_eq!(rows.len(), 2500);
+        assert_eq!(rows[2499], Value::test_int(2499));
+        assert_eq!(progress, (1..=2500).collect::<Vec<usize>>());
+
+        let mut progress = vec![];
+        assert!(collect_rows(std::iter::empty(), |n| progress.push(n)).is_empty());
+        assert!(progress.is_empty());
+    }
+
+    #[test]
+    fn progress_after_delay() {
+        let start = Instant::now();
+        let mut progress = Progress::new(start);
+
+        // NOTE: the quick collections show nothing
+        assert_eq!(progress.update(1000, start), None);
+        assert!(!progress.shown);
+
+        assert_eq!(
+            progress.update(2000, start + PROGRESS_DELAY),
+            Some("\rcollecting… 2000 rows".into())
+        );
+        assert!(progress.shown);
+
+        // NOTE: the indicator is updated with the time, whatever the number of rows
+        let later = start + PROGRESS_DELAY + PROGRESS_INTERVAL / 2;
+        assert_eq!(progress.update(2500, later), None);
+        assert_eq!(
+            progress.update(3000, start + PROGRESS_DELAY + PROGRESS_INTERVAL),
+            Some("\rcollecting… 3000 rows".into())
+        );
+    }
+
+    #[test]
+    fn peeked_value() {
+        let head = Span::new(10, 17);
+        let value = Value::test_record(record! {
+            "a" => Value::test_int(1),
+        });
+
+        let peeked = peeked(value.clone(), head);
+        assert_eq!(peeked, value);
+        assert_eq!(peeked.span(), head);
+        // NOTE: only the value itself is given the span, not its content
+        assert_eq!(
+            peeked.get_data_by_key("a").unwrap().span(),
+            Span::test_data()
+        );
+    }
+
+    #[test]
+    fn save_on_quit() {
+        let value = Value::test_record(record! {
+            "a" => Value::test_int(1),
+        });
+        let path = std::env::temp_dir().join("nu_plugin_explore_save_on_quit.json");
+        let options = Options {
+            output: Some(path.clone()),
+            ..Default::default()
+        };
+
+        let result = quit(&value, Span::test_data(), &options).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, Value::test_nothing());
+        assert_eq!(content, "{\n  \"a\": 1\n}\n");
+
+        let options = Options {
+            output: Some("does/not/exist.json".into()),
+            ..Default::default()
+        };
+        let err = quit(&value, Span::test_data(), &options).unwrap_err();
+        let err = LabeledError::from(err.downcast_ref::<ShellError>().unwrap().clone());
+        assert_eq!(err.label, "could not save the data");
+        assert!(err.msg.contains("could not write"), "{}", err.msg);
+    }
+
+    #[test]
+    fn check_output_before_exploring() {
+        let options = |output: &str| Options {
+            output: Some(output.into()),
+            ..Default::default()
+        };
+
+        assert!(check_output(&Options::default(), Span::test_data()).is_ok());
+        assert!(check_output(&options("foo.json"), Span::test_data()).is_ok());
+
+        let err = check_output(&options("foo.nuon"), Span::test_data()).unwrap_err();
+        let err = LabeledError::from(err.downcast_ref::<ShellError>().unwrap().clone());
+        assert_eq!(err.label, "invalid output");
+        assert!(err.msg.contains("NUON is not supported"), "{}", err.msg);
+    }
 }
@@ -1,21 +1,98 @@
-use nu_plugin::{serve_plugin, EvaluatedCall, LabeledError, MsgPackSerializer, Plugin};
-use nu_plugin_explore::explore;
-use nu_protocol::{Category, PluginExample, PluginSignature, ShellError, Type, Value};
+use std::path::Path;
+
+use nu_plugin::{serve_plugin, EvaluatedCall, LabeledError, MsgPackSerializer, StreamingPlugin};
+use nu_plugin_explore::{collect_input, explore, load_file, Options};
+use nu_protocol::{
+    ast::CellPath, Category, PipelineData, PluginExample, PluginSignature, ShellError, Spanned,
+    SyntaxShape, Type, Value,
+};
 
 /// the main structure of the [Nushell](https://nushell.sh) plugin
 struct Explore;
 
-impl Plugin for Explore {
+// NOTE: the input is streamed to show the progress of the collection of the lazy lists
+impl StreamingPlugin for Explore {
     fn signature(&self) -> Vec<PluginSignature> {
         vec![PluginSignature::build("nu_plugin_explore")
             .usage("interactively explore Nushell structured data")
             .input_output_type(Type::Any, Type::Any)
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "a JSON, TOML, YAML or NUON file to explore instead of the input",
+            )
+            .switch(
+                "no-alt-screen",
+                "render inline instead of in the alternate screen of the terminal",
+                None,
+            )
+            .named(
+                "output",
+                SyntaxShape::Filepath,
+                "a JSON, TOML or YAML file to save the data to when quitting",
+                Some('o'),
+            )
+            .switch(
+                "inspect",
+                "explore the data read-only, with the types of all the values",
+                None,
+            )
+            .named(
+                "max-depth",
+                SyntaxShape::Int,
+                "the number of levels of the data that can be explored, at least 1, the deeper ones being elided",
+                None,
+            )
+            .named(
+                "schema",
+                SyntaxShape::Filepath,
+                "a JSON Schema of the data, to cycle the strings through their allowed values",
+                None,
+            )
+            .named(
+                "config",
+                SyntaxShape::Filepath,
+                "a JSON, TOML, YAML or NUON file with the config, instead of the one of the plugin, to reload it while exploring",
+                None,
+            )
+            .named(
+                "right-prompt",
+                SyntaxShape::CellPath,
+                "a cell path into the value under the cursor, to show it to the right of the status bar",
+                None,
+            )
+            .named(
+                "filter",
+                SyntaxShape::String,
+                "a condition like the ones of `where`, e.g. `size > 1kb`, to filter the lists with",
+                None,
+            )
             .plugin_examples(vec![
                 PluginExample {
                     example: "open Cargo.toml | explore".into(),
                     description: "explore the Cargo.toml file of this project".into(),
                     result: None,
                 },
+                PluginExample {
+                    example: "explore Cargo.toml".into(),
+                    description: "explore the same file, without piping it".into(),
+                    result: None,
+                },
+                PluginExample {
+                    example: "explore Cargo.toml --output Cargo.toml".into(),
+                    description: "edit the same file and save the changes when quitting".into(),
+                    result: None,
+                },
+                PluginExample {
+                    example: "open big.json | explore --max-depth 3".into(),
+                    description: "explore a huge file, without going deeper than 3 levels".into(),
+                    result: None,
+                },
+                PluginExample {
+                    example: "ls | explore --filter 'size > 1kb'".into(),
+                    description: "explore the files, keeping the big ones when filtering the list with `%`".into(),
+                    result: None,
+                },
                 PluginExample {
                     example: r#"$nu | explore {show_cell_path: false, layout: "compact"}"#.into(),
                     description: "explore `$nu` and set some config options".into(),
@@ -30,22 +107,68 @@ impl Plugin for Explore {
         name: &str,
         config: &Option<Value>,
         call: &EvaluatedCall,
-        input: &Value,
-    ) -> Result<Value, LabeledError> {
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
         match name {
-            "nu_plugin_explore" => match explore(config, input.clone()) {
-                Ok(value) => Ok(value),
-                Err(err) => {
-                    match err.downcast_ref::<ShellError>() {
+            "nu_plugin_explore" => {
+                let input = match call.opt::<Spanned<String>>(0)? {
+                    Some(path) => load_file(Path::new(&path.item)).map_err(|err| LabeledError {
+                        label: "could not load file".into(),
+                        msg: err.to_string(),
+                        span: Some(path.span),
+                    })?,
+                    None => collect_input(input, call.head),
+                };
+
+                let schema = match call.get_flag::<Spanned<String>>("schema")? {
+                    Some(path) => Some(load_file(Path::new(&path.item)).map_err(|err| {
+                        LabeledError {
+                            label: "could not load schema".into(),
+                            msg: err.to_string(),
+                            span: Some(path.span),
+                        }
+                    })?),
+                    None => None,
+                };
+
+                let max_depth = match call.get_flag::<Spanned<i64>>("max-depth")? {
+                    Some(depth) if depth.item < 1 => {
+                        return Err(LabeledError {
+                            label: "invalid max depth".into(),
+                            msg: format!("should be at least 1, found {}", depth.item),
+                            span: Some(depth.span),
+                        })
+                    }
+                    depth => depth.map(|depth| depth.item as usize),
+                };
+
+                let options = Options {
+                    no_alt_screen: call.has_flag("no-alt-screen")?,
+                    inspect: call.has_flag("inspect")?,
+                    output: call
+                        .get_flag::<String>("output")?
+                        .map(std::path::PathBuf::from),
+                    max_depth,
+                    schema,
+                    config_file: call
+                        .get_flag::<String>("config")?
+                        .map(std::path::PathBuf::from),
+                    right_prompt: call.get_flag::<CellPath>("right-prompt")?,
+                    filter: call.get_flag::<String>("filter")?,
+                };
+
+                match explore(config, input, call.head, &options) {
+                    Ok(value) => Ok(PipelineData::Value(value, None)),
+                    Err(err) => match err.downcast_ref::<ShellError>() {
                         Some(shell_error) => Err(LabeledError::from(shell_error.clone())),
                         None => Err(LabeledError {
                             label: "unexpected internal error".into(),
                             msg: "could not transform error into ShellError, there was another kind of crash...".into(),
                             span: Some(call.head),
                         }),
-                    }
+                    },
                 }
-            },
+            }
             _ => Err(LabeledError {
                 label: "Plugin call with wrong name signature".into(),
                 msg: "the signature used to call the plugin does not match any name in the plugin signature vector".into(),
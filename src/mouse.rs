@@ -0,0 +1,689 @@
+//! map the events of the mouse to actions in the data
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use nu_protocol::{ast::PathMember, ShellError, Span, Value};
+
+use crate::{
+    app::{App, Mode},
+    config::{Config, Layout},
+    handler::{go_deeper, go_up_or_down, reject_if_read_only, TransitionResult},
+    navigation::{reset_column_offset, visible_columns, Direction},
+    nu::value::is_numeric_list,
+    tree::{to_keys, visible_rows},
+    ui::{focused_row_height, is_single_column, is_table_view},
+};
+
+/// the actions that the mouse can trigger
+#[derive(Debug, PartialEq)]
+pub(crate) enum MouseAction {
+    /// add one to the number under the cursor
+    Increment,
+    /// subtract one from the number under the cursor
+    Decrement,
+    /// move the cursor one row up or down, like the navigation keys
+    Scroll(Direction),
+    /// put the cursor on the row at the given index of the current view
+    Select(usize),
+    /// go back to the container of the tab at the given depth, see [`App::ancestor_tabs`]
+    SelectTab(usize),
+}
+
+/// give the index of the tab drawn at the column *x* of the first line, mirroring the layout of
+/// [`ratatui::widgets::Tabs`], i.e. a space on each side of the titles and a divider between them
+fn tab_at(titles: &[String], x: u16) -> Option<usize> {
+    let mut start = 0;
+    for (i, title) in titles.iter().enumerate() {
+        let end = start + title.chars().count() as u16 + 2;
+        if x < end {
+            return Some(i);
+        }
+        // NOTE: the divider between two tabs belongs to none of them
+        if x == end {
+            return None;
+        }
+        start = end + 1;
+    }
+
+    None
+}
+
+/// the data at the current level and the index of the selected row in it
+fn current_view(app: &App, config: &Config) -> (Value, usize) {
+    let mut path = app.position.members.clone();
+    let current = path.pop();
+    let view = app
+        .value
+        .clone()
+        .follow_cell_path(&path, false)
+        .unwrap_or_else(|_| Value::nothing(Span::unknown()));
+
+    let (selected, len) = match (&view, current) {
+        (Value::List { vals, .. }, Some(PathMember::Int { val, .. })) => (val, vals.len()),
+        (Value::Record { val: rec, .. }, Some(PathMember::String { val, .. })) => {
+            let columns = visible_columns(rec, config, app);
+            let selected = columns.iter().position(|c| c == &val).unwrap_or(0);
+            (selected, columns.len())
+        }
+        _ => (0, 0),
+    };
+
+    // NOTE: the rows are shown in reverse order, see [`App::reversed`]
+    if app.reversed && len > 0 {
+        (view, len - 1 - selected)
+    } else {
+        (view, selected)
+    }
+}
+
+/// where the rows of the current view are drawn in the terminal
+#[derive(Debug, PartialEq)]
+pub(crate) struct Pane {
+    /// the line of the first visible row
+    pub origin: u16,
+    /// the number of visible rows
+    pub rows: u16,
+    /// the index of the first visible row in the view
+    pub offset: usize,
+    /// the index in the view of the row taking more than one line and its height, i.e. the
+    /// selected row when it's wrapped, see [`App::wrap_focused`]
+    pub tall: Option<(usize, u16)>,
+}
+
+impl Pane {
+    /// the number of lines taken by the row at *index* in the view
+    fn height_of(&self, index: usize) -> u16 {
+        match self.tall {
+            Some((tall, height)) if tall == index => height.max(1),
+            _ => 1,
+        }
+    }
+
+    /// give the index of the row shown on the line *y* of the terminal, if any
+    pub(crate) fn row_at(&self, y: u16) -> Option<usize> {
+        if y < self.origin || y >= self.origin + self.rows {
+            return None;
+        }
+
+        let mut line = y - self.origin;
+        let mut index = self.offset;
+        while line >= self.height_of(index) {
+            line -= self.height_of(index);
+            index += 1;
+        }
+
+        Some(index)
+    }
+
+    /// give the line of the terminal where the *i*-th visible row starts
+    pub(crate) fn line_of(&self, i: usize) -> u16 {
+        self.origin + (0..i).map(|j| self.height_of(self.offset + j)).sum::<u16>()
+    }
+
+    /// give the number of rows that fit entirely in the pane, from the first visible one
+    fn capacity(&self) -> usize {
+        let mut lines = 0;
+        let mut count = 0;
+        while lines + self.height_of(self.offset + count) <= self.rows {
+            lines += self.height_of(self.offset + count);
+            count += 1;
+        }
+
+        count
+    }
+}
+
+/// compute the [`Pane`] of the rows of the current view
+///
+/// this mirrors the layout of [`crate::ui`]: rows start below the border and the header of the
+/// tables, unless the terminal is so narrow that the data is shown in a single column, and the
+/// view is assumed to be scrolled just enough for the selected row to be visible. grouped records
+/// are not supported because of the rows of the groups.
+pub(crate) fn data_pane(app: &App, config: &Config) -> Option<Pane> {
+    if app.is_at_bottom() {
+        return None;
+    }
+
+    let border = app.border_width(config);
+    let height = app
+        .height
+        .saturating_sub(app.bars_height(config) + 2 * border);
+
+    if config.layout == Layout::Tree {
+        return Some(Pane {
+            origin: app.top_bars_height(config) + border,
+            rows: height,
+            offset: app.tree_offset,
+            tall: None,
+        });
+    }
+
+    let (view, selected) = current_view(app, config);
+    if (config.group_records || config.group_by_type) && matches!(view, Value::Record { .. }) {
+        return None;
+    }
+
+    // NOTE: the sparkline of the lists of numbers takes the first line
+    let sparkline = if config.show_sparkline && is_numeric_list(&view) {
+        1
+    } else {
+        0
+    };
+    let height = height.saturating_sub(sparkline);
+
+    // NOTE: a single column is a compact list, even for the tables
+    let single_column = is_single_column(app.width, config);
+    let is_table_view = is_table_view(app, &view, single_column);
+    // NOTE: the tables are shown as lists when the table view is toggled off, like in the renderer
+    let (origin, rows) = if is_table_view || (!single_column && config.layout == Layout::Table) {
+        let header = if is_table_view || config.show_table_header {
+            1
+        } else {
+            0
+        };
+        // NOTE: the top and bottom borders of the table, the rows being capped by the config
+        let rows = height.saturating_sub(2 + header);
+        let rows = config
+            .max_rows
+            .map_or(rows, |max| rows.min(max.max(1) as u16));
+        (1 + header, rows)
+    } else {
+        (0, height)
+    };
+
+    // NOTE: only the rows of the table layout are wrapped, and the view is scrolled for the whole
+    // selected row to be visible
+    let height = if !single_column && config.layout == Layout::Table && !is_table_view {
+        focused_row_height(app, config)
+    } else {
+        1
+    };
+
+    Some(Pane {
+        origin: app.top_bars_height(config) + border + origin + sparkline,
+        rows,
+        offset: selected.saturating_sub((rows as usize).saturating_sub(height as usize)),
+        tall: (height > 1).then_some((selected, height)),
+    })
+}
+
+/// give the [`Pane`] of the current view together with the number of rows visible in it, i.e. the
+/// rows that can be labelled, see [`App::row_labels`]
+pub(crate) fn visible_view_rows(app: &App, config: &Config) -> Option<(Pane, usize)> {
+    let pane = data_pane(app, config)?;
+    let len = if config.layout == Layout::Tree {
+        visible_rows(&app.value, &app.expanded).len()
+    } else {
+        match current_view(app, config).0 {
+            Value::List { vals, .. } => vals.len(),
+            Value::Record { val: rec, .. } => visible_columns(&rec, config, app).len(),
+            _ => 0,
+        }
+    };
+    let visible = len.saturating_sub(pane.offset).min(pane.capacity());
+
+    Some((pane, visible))
+}
+
+/// give the index of the selected row in the [`Pane`] of the current view, if it's in the view
+fn selected_row(app: &App, config: &Config) -> Option<usize> {
+    if config.layout == Layout::Tree {
+        let current = to_keys(&app.position.members);
+        return visible_rows(&app.value, &app.expanded)
+            .iter()
+            .position(|r| to_keys(&r.path) == current);
+    }
+
+    Some(current_view(app, config).1)
+}
+
+/// compute the action of a mouse event, if any
+///
+/// the wheel changes the number under the cursor when the mouse is over it in NORMAL mode, or
+/// moves the cursor otherwise, and a left click selects the row under the mouse, going into it if
+/// it's a list or a record.
+pub(crate) fn mouse_action(event: &MouseEvent, app: &App, config: &Config) -> Option<MouseAction> {
+    if !matches!(app.mode, Mode::Normal | Mode::Bottom) {
+        return None;
+    }
+
+    let is_number = app.mode == Mode::Normal
+        && data_pane(app, config)
+            .and_then(|pane| pane.row_at(event.row))
+            .is_some_and(|row| Some(row) == selected_row(app, config))
+        && matches!(
+            app.value
+                .clone()
+                .follow_cell_path(&app.position.members, false),
+            Ok(Value::Int { .. } | Value::Float { .. })
+        );
+
+    match event.kind {
+        MouseEventKind::ScrollUp if is_number => Some(MouseAction::Increment),
+        MouseEventKind::ScrollDown if is_number => Some(MouseAction::Decrement),
+        MouseEventKind::ScrollUp => Some(MouseAction::Scroll(Direction::Up)),
+        MouseEventKind::ScrollDown => Some(MouseAction::Scroll(Direction::Down)),
+        // NOTE: the tabs are the last of the bars above the data
+        MouseEventKind::Down(MouseButton::Left)
+            if app.tabs_height(config) > 0 && event.row + 1 == app.top_bars_height(config) =>
+        {
+            tab_at(&app.ancestor_tabs(), event.column).map(MouseAction::SelectTab)
+        }
+        MouseEventKind::Down(MouseButton::Left) => data_pane(app, config)?
+            .row_at(event.row)
+            .map(MouseAction::Select),
+        _ => None,
+    }
+}
+
+/// add *step* to the number under the cursor
+fn step_number(app: &mut App, step: i64) -> Result<TransitionResult, ShellError> {
+    if let Some(result) = reject_if_read_only(app) {
+        return Ok(result);
+    }
+
+    let cell = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)?;
+    let new = match cell {
+        Value::Int { val, .. } => Value::int(val.saturating_add(step), Span::unknown()),
+        Value::Float { val, .. } => Value::float(val + step as f64, Span::unknown()),
+        _ => return Ok(TransitionResult::Continue),
+    };
+
+    Ok(TransitionResult::Mutate(new, app.position.clone()))
+}
+
+/// put the cursor on the row at *index* in the current view, if there is such a row
+pub(crate) fn select(app: &mut App, index: usize, config: &Config) {
+    if config.layout == Layout::Tree {
+        if let Some(row) = visible_rows(&app.value, &app.expanded).get(index) {
+            app.position.members = row.path.clone();
+        }
+        return;
+    }
+
+    let (view, _) = current_view(app, config);
+    let index = match &view {
+        Value::List { vals, .. } if app.reversed => vals.len().checked_sub(1 + index),
+        Value::Record { val: rec, .. } if app.reversed => visible_columns(rec, config, app)
+            .len()
+            .checked_sub(1 + index),
+        _ => Some(index),
+    };
+    let Some(index) = index else {
+        return;
+    };
+    let member = match view {
+        Value::List { vals, .. } if index < vals.len() => PathMember::Int {
+            val: index,
+            span: Span::unknown(),
+            optional: false,
+        },
+        Value::Record { val: rec, .. } => match visible_columns(&rec, config, app).get(index) {
+            Some(col) => PathMember::String {
+                val: col.clone(),
+                span: Span::unknown(),
+                optional: false,
+            },
+            None => return,
+        },
+        _ => return,
+    };
+
+    // NOTE: the position always points to a row of the current view
+    *app.position.members.last_mut().unwrap() = member;
+}
+
+/// handle the mouse events and update the state of [`App`]
+pub fn handle_mouse_events(
+    event: MouseEvent,
+    app: &mut App,
+    config: &Config,
+) -> Result<TransitionResult, ShellError> {
+    let from = app.absolute_position();
+    let result = match mouse_action(&event, app, config) {
+        Some(MouseAction::Increment) => step_number(app, 1),
+        Some(MouseAction::Decrement) => step_number(app, -1),
+        Some(MouseAction::Scroll(direction)) => {
+            go_up_or_down(app, direction, config);
+            Ok(TransitionResult::Continue)
+        }
+        Some(MouseAction::Select(index)) => {
+            select(app, index, config);
+
+            let cell = app
+                .value
+                .clone()
+                .follow_cell_path(&app.position.members, false)?;
+            if matches!(cell, Value::List { .. } | Value::Record { .. }) {
+                go_deeper(app, config);
+            }
+            Ok(TransitionResult::Continue)
+        }
+        Some(MouseAction::SelectTab(depth)) => {
+            app.position.members.truncate(depth + 1);
+            app.mode = Mode::Normal;
+            Ok(TransitionResult::Continue)
+        }
+        None => Ok(TransitionResult::Continue),
+    };
+    // NOTE: the rows of the tree view can be deeper than the maximum depth
+    app.clamp_to_max_depth();
+    reset_column_offset(app, &from);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use nu_protocol::{ast::CellPath, record, Value};
+
+    use super::{data_pane, handle_mouse_events, mouse_action, tab_at, MouseAction, Pane};
+    use crate::{
+        app::App,
+        config::{Config, Layout},
+        handler::{TransitionResult, READ_ONLY},
+        navigation::Direction,
+        nu::cell_path::{to_path_member_vec, PM},
+    };
+
+    fn mouse(kind: MouseEventKind, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 5,
+            row,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    fn test_app() -> App {
+        let mut app = App::from_value(Value::test_record(record! {
+            "i" => Value::test_int(1),
+            "s" => Value::test_string("foo"),
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+        app.height = 20;
+        app.width = 80;
+        app
+    }
+
+    #[test]
+    fn wheel_on_numbers() {
+        let config = Config::default();
+        let mut app = test_app();
+
+        let up = mouse(MouseEventKind::ScrollUp, 2);
+        let down = mouse(MouseEventKind::ScrollDown, 2);
+
+        assert_eq!(
+            mouse_action(&up, &app, &config),
+            Some(MouseAction::Increment)
+        );
+        assert_eq!(
+            mouse_action(&down, &app, &config),
+            Some(MouseAction::Decrement)
+        );
+        assert_eq!(
+            handle_mouse_events(up, &mut app, &config).unwrap(),
+            TransitionResult::Mutate(
+                Value::test_int(2),
+                CellPath {
+                    members: to_path_member_vec(&[PM::S("i")])
+                }
+            )
+        );
+
+        // NOTE: the number is not changed in inspect mode
+        app.inspect = true;
+        assert_eq!(
+            handle_mouse_events(up, &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.message, Some(READ_ONLY.into()));
+        app.inspect = false;
+
+        app.position.members = to_path_member_vec(&[PM::S("s")]);
+        assert_eq!(
+            mouse_action(&up, &app, &config),
+            Some(MouseAction::Scroll(Direction::Up))
+        );
+        handle_mouse_events(down, &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("l")]));
+    }
+
+    #[test]
+    fn wheel_off_the_selected_number() {
+        let config = Config::default();
+        let mut app = test_app();
+
+        // NOTE: the mouse is over the string below the selected number
+        let up = mouse(MouseEventKind::ScrollUp, 3);
+        assert_eq!(
+            mouse_action(&up, &app, &config),
+            Some(MouseAction::Scroll(Direction::Up))
+        );
+        assert_eq!(
+            handle_mouse_events(up, &mut app, &config).unwrap(),
+            TransitionResult::Continue
+        );
+        assert_eq!(app.value, test_app().value);
+    }
+
+    #[test]
+    fn click_on_row() {
+        let config = Config::default();
+        let mut app = test_app();
+
+        // NOTE: the rows of the table start below its border and header
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+        assert_eq!(mouse_action(&click(1), &app, &config), None);
+        assert_eq!(
+            mouse_action(&click(4), &app, &config),
+            Some(MouseAction::Select(2))
+        );
+
+        handle_mouse_events(click(3), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("s")]));
+    }
+
+    #[test]
+    fn click_on_reversed_row() {
+        let config = Config::default();
+        let mut app = test_app();
+        app.reversed = true;
+
+        // NOTE: the first row is the last field of the record
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+        handle_mouse_events(click(3), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("s")]));
+
+        handle_mouse_events(click(4), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("i")]));
+    }
+
+    #[test]
+    fn click_on_container() {
+        let config = Config::default();
+        let mut app = test_app();
+
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), 4);
+        handle_mouse_events(click, &mut app, &config).unwrap();
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("l"), PM::I(0)])
+        );
+    }
+
+    #[test]
+    fn pane_origin() {
+        let pane = Pane {
+            origin: 2,
+            rows: 5,
+            offset: 10,
+            tall: None,
+        };
+        assert_eq!(pane.row_at(1), None);
+        assert_eq!(pane.row_at(2), Some(10));
+        assert_eq!(pane.row_at(6), Some(14));
+        assert_eq!(pane.row_at(7), None);
+
+        let mut app = App::from_value(Value::test_list((0..50).map(Value::test_int).collect()));
+        app.height = 12;
+        app.width = 80;
+        app.position.members = to_path_member_vec(&[PM::I(20)]);
+
+        // NOTE: 12 lines, minus the two bars, the two borders and the header
+        let config = Config {
+            show_sparkline: false,
+            ..Default::default()
+        };
+        let pane = data_pane(&app, &config).unwrap();
+        assert_eq!(
+            pane,
+            Pane {
+                origin: 2,
+                rows: 7,
+                offset: 14,
+                tall: None,
+            }
+        );
+        assert_eq!(pane.row_at(8), Some(20));
+
+        let config = Config {
+            layout: Layout::Compact,
+            show_sparkline: false,
+            ..Default::default()
+        };
+        let pane = data_pane(&app, &config).unwrap();
+        assert_eq!(pane.origin, 0);
+        assert_eq!(pane.row_at(3), Some(14));
+
+        // NOTE: the sparkline of the numbers is above the table
+        let pane = data_pane(&app, &Config::default()).unwrap();
+        assert_eq!(
+            pane,
+            Pane {
+                origin: 3,
+                rows: 6,
+                offset: 15,
+                tall: None,
+            }
+        );
+    }
+
+    #[test]
+    fn click_below_wrapped_row() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_string("foo ".repeat(50)),
+            "b" => Value::test_int(1),
+        }));
+        app.height = 20;
+        app.width = 80;
+        app.wrap_focused = true;
+        app.position.members = to_path_member_vec(&[PM::S("a")]);
+
+        let pane = data_pane(&app, &config).unwrap();
+        let (_, height) = pane.tall.unwrap();
+        assert!(height > 1);
+
+        // NOTE: the next row is below all the lines of the wrapped one
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+        assert_eq!(
+            mouse_action(&click(pane.origin + height - 1), &app, &config),
+            Some(MouseAction::Select(0))
+        );
+        assert_eq!(
+            mouse_action(&click(pane.origin + height), &app, &config),
+            Some(MouseAction::Select(1))
+        );
+        assert_eq!(pane.line_of(1), pane.origin + height);
+    }
+
+    #[test]
+    fn click_in_single_column() {
+        let config = Config::default();
+        let mut app = test_app();
+        app.width = 30;
+
+        // NOTE: the rows are a compact list, without the border and the header of the table
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+        assert_eq!(
+            mouse_action(&click(1), &app, &config),
+            Some(MouseAction::Select(1))
+        );
+
+        handle_mouse_events(click(1), &mut app, &config).unwrap();
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("s")]));
+    }
+
+    #[test]
+    fn click_in_table_shown_as_list() {
+        let config = Config {
+            layout: Layout::Compact,
+            ..Default::default()
+        };
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_record(record! { "a" => Value::test_int(1) }),
+            Value::test_record(record! { "a" => Value::test_int(2) }),
+        ]));
+        app.height = 20;
+        app.width = 80;
+
+        // NOTE: the rows of the table are below its border and its header
+        let origin = data_pane(&app, &config).unwrap().origin;
+
+        app.table_view = false;
+        let pane = data_pane(&app, &config).unwrap();
+        assert_eq!(pane.origin + 2, origin);
+        let click = |row| mouse(MouseEventKind::Down(MouseButton::Left), row);
+        assert_eq!(
+            mouse_action(&click(pane.origin + 1), &app, &config),
+            Some(MouseAction::Select(1))
+        );
+    }
+
+    #[test]
+    fn click_on_tab() {
+        let config = Config {
+            show_tabs: true,
+            ..Default::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            }),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("l"), PM::I(1)]);
+        app.height = 20;
+        app.width = 80;
+
+        // NOTE: ` root │ r │ l `
+        let titles = app.ancestor_tabs();
+        assert_eq!(titles, vec!["root", "r", "l"]);
+        assert_eq!(tab_at(&titles, 0), Some(0));
+        assert_eq!(tab_at(&titles, 5), Some(0));
+        assert_eq!(tab_at(&titles, 6), None);
+        assert_eq!(tab_at(&titles, 7), Some(1));
+        assert_eq!(tab_at(&titles, 11), Some(2));
+        assert_eq!(tab_at(&titles, 20), None);
+
+        // NOTE: the rows are below the tabs, the border, the sparkline and the header
+        assert_eq!(data_pane(&app, &config).unwrap().origin, 4);
+
+        let mut click = mouse(MouseEventKind::Down(MouseButton::Left), 0);
+        click.column = 8;
+        assert_eq!(
+            mouse_action(&click, &app, &config),
+            Some(MouseAction::SelectTab(1))
+        );
+        handle_mouse_events(click, &mut app, &config).unwrap();
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("r"), PM::S("l")])
+        );
+    }
+}
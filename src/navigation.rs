@@ -1,9 +1,24 @@
 //! navigate in the data in all directions
-use nu_protocol::{ast::PathMember, Span, Value};
+use std::collections::HashSet;
 
-use crate::app::{App, Mode};
+use nu_protocol::{
+    ast::{CellPath, PathMember},
+    Record, Span, Type, Value,
+};
+
+use crate::{
+    app::{App, Mode},
+    config::{Config, MissingDisplay},
+    nu::value::{infer_columns, is_table},
+    tree::to_keys,
+    ui::data_area,
+};
+
+/// the minimum width of a column in the table view
+pub(crate) const TABLE_COLUMN_WIDTH: u16 = 25;
 
 /// specify a vertical direction in which to go in the data
+#[derive(Debug, PartialEq)]
 pub enum Direction {
     /// go one row down in the data
     Down,
@@ -11,6 +26,237 @@ pub enum Direction {
     Up,
 }
 
+/// give the number of columns of a table that fit in a table view of the given *width*, at least
+/// one
+pub(crate) fn table_columns_fit(width: u16, config: &Config) -> usize {
+    // NOTE: the table has borders, the symbol of the selected row and a space between the columns
+    let symbol = config.colors.selected_symbol.chars().count() as u16;
+    let width = width.saturating_sub(2 + symbol) + 1;
+    ((width / (TABLE_COLUMN_WIDTH + 1)) as usize).max(1)
+}
+
+/// show the next or previous page of columns of the table around the cursor, as many as fit in the
+/// width of the table view, see [`App::column_offset`]
+pub(crate) fn page_table_columns(app: &mut App, config: &Config, forward: bool) {
+    let mut path = app.position.members.clone();
+    path.pop();
+    let columns = match app.value.clone().follow_cell_path(&path, false) {
+        Ok(view) if app.table_view && is_table(&view) => infer_columns(&view).len(),
+        _ => {
+            app.message = Some("only tables have columns to page through".into());
+            return;
+        }
+    };
+
+    // NOTE: the table view shares the width with the preview pane, see `ui::render_data`
+    let fit = table_columns_fit(data_area(app, config).width, config);
+    let last = columns.saturating_sub(fit);
+    let offset = app.column_offset.min(last);
+    app.column_offset = if forward {
+        (offset + fit).min(last)
+    } else {
+        offset.saturating_sub(fit)
+    };
+}
+
+/// show the first columns again when the cursor leaves the table it was in, coming *from* the
+/// given absolute position, see [`App::column_offset`]
+pub(crate) fn reset_column_offset(app: &mut App, from: &CellPath) {
+    let to = app.absolute_position();
+    if from.members.split_last().map(|(_, table)| table)
+        != to.members.split_last().map(|(_, table)| table)
+    {
+        app.column_offset = 0;
+    }
+}
+
+/// compute the names of the fields of a record, in the order they are shown to the user
+///
+/// when [`Config::group_records`] is set, the fields are sorted alphabetically to match the
+/// grouping of the UI, and with [`Config::group_by_type`], the scalars come before the lists and
+/// records, otherwise they are kept in their original order. in all cases, the pinned fields of
+/// *app* come first.
+///
+/// only the first field of the collapsed groups is kept, to stand for its group, see
+/// [`App::collapsed_groups`].
+pub(crate) fn visible_columns(rec: &Record, config: &Config, app: &App) -> Vec<String> {
+    let pinned = &app.pinned;
+    let mut cols = rec.cols.clone();
+    if config.group_by_type {
+        cols.sort_by_key(|col| is_container(rec.get(col)));
+    } else if config.group_records {
+        cols.sort_by_key(|col| col.to_lowercase());
+    }
+    // NOTE: the sort is stable, so the order is kept among the pinned fields and the others
+    cols.sort_by_key(|col| !pinned.contains(col));
+
+    if config.group_records && !config.group_by_type && !app.collapsed_groups.is_empty() {
+        let mut seen = HashSet::new();
+        cols.retain(|col| {
+            let group = record_group(col);
+            pinned.contains(col) || !app.collapsed_groups.contains(&group) || seen.insert(group)
+        });
+    }
+    cols
+}
+
+/// give the group of the field named *key* when grouping records, i.e. its lowercase first letter,
+/// see [`Config::group_records`]
+pub(crate) fn record_group(key: &str) -> String {
+    key.chars()
+        .next()
+        .map(|c| c.to_lowercase().to_string())
+        .unwrap_or_default()
+}
+
+/// collapse the group of the field under the cursor, the cursor going to its first field which
+/// stands for it, or expand it back, see [`App::collapsed_groups`]
+pub(crate) fn toggle_group(app: &mut App, config: &Config) {
+    if !config.group_records || config.group_by_type {
+        app.message = Some("only the groups of `group_records` can be collapsed".into());
+        return;
+    }
+    let Some(PathMember::String { val: key, .. }) = app.position.members.last() else {
+        app.message = Some("only the fields of records are grouped".into());
+        return;
+    };
+    if app.pinned.contains(key) {
+        app.message = Some("the pinned fields cannot be collapsed".into());
+        return;
+    }
+
+    let group = record_group(key);
+    if app.collapsed_groups.remove(&group) {
+        return;
+    }
+    app.collapsed_groups.insert(group.clone());
+
+    let mut path = app.position.members.clone();
+    path.pop();
+    if let Ok(Value::Record { val: rec, .. }) = app.value.clone().follow_cell_path(&path, false) {
+        let first = visible_columns(&rec, config, app)
+            .into_iter()
+            .find(|col| !app.pinned.contains(col) && record_group(col) == group);
+        if let Some(first) = first {
+            path.push(PathMember::String {
+                val: first,
+                span: Span::unknown(),
+                optional: false,
+            });
+            app.position.members = path;
+        }
+    }
+}
+
+/// expand back the collapsed groups hiding a field on the path of the cursor, e.g. after a jump,
+/// see [`App::collapsed_groups`]
+///
+/// only the first field of a collapsed group is visible, see [`visible_columns`], so the cursor
+/// could not move from the others. nothing is hidden when the records are not grouped by their
+/// first letter.
+pub(crate) fn expand_hidden_groups(app: &mut App, config: &Config) {
+    if app.collapsed_groups.is_empty() || !config.group_records || config.group_by_type {
+        return;
+    }
+
+    let mut hidden = vec![];
+    let mut current = &app.value;
+    for member in &app.position.members {
+        current = match (member, current) {
+            (PathMember::String { val: key, .. }, Value::Record { val: rec, .. }) => {
+                let group = record_group(key);
+                let first = rec
+                    .cols
+                    .iter()
+                    .filter(|col| !app.pinned.contains(*col) && record_group(col) == group)
+                    .min_by_key(|col| col.to_lowercase());
+                if !app.pinned.contains(key)
+                    && app.collapsed_groups.contains(&group)
+                    && first.is_some_and(|first| first != key)
+                {
+                    hidden.push(group);
+                }
+                match rec.get(key) {
+                    Some(val) => val,
+                    None => break,
+                }
+            }
+            (PathMember::Int { val: index, .. }, Value::List { vals, .. }) => {
+                match vals.get(*index) {
+                    Some(val) => val,
+                    None => break,
+                }
+            }
+            _ => break,
+        };
+    }
+
+    for group in hidden {
+        app.collapsed_groups.remove(&group);
+    }
+}
+
+/// find the next key among *cols* that starts with *letter*, ignoring the case, after *current*
+///
+/// the search wraps around the keys, so *current* itself is found last.
+pub(crate) fn next_key_starting_with(
+    cols: &[String],
+    current: &str,
+    letter: char,
+) -> Option<String> {
+    let start = cols
+        .iter()
+        .position(|col| col == current)
+        .map_or(0, |i| i + 1);
+    let letter = letter.to_lowercase().to_string();
+
+    cols.iter()
+        .cycle()
+        .skip(start)
+        .take(cols.len())
+        .find(|col| col.to_lowercase().starts_with(&letter))
+        .cloned()
+}
+
+/// tell whether a value is a list or a record, as opposed to the scalars
+pub(crate) fn is_container(value: Option<&Value>) -> bool {
+    matches!(value, Some(Value::List { .. } | Value::Record { .. }))
+}
+
+/// give the type of the values to stop on for the *letter* typed by the user, see
+/// [`App::nav_type_filter`]
+pub(crate) fn type_of_letter(letter: char) -> Option<Type> {
+    match letter {
+        's' => Some(Type::String),
+        'i' => Some(Type::Int),
+        'f' => Some(Type::Float),
+        'b' => Some(Type::Bool),
+        'd' => Some(Type::Date),
+        'n' => Some(Type::Nothing),
+        'l' => Some(Type::List(Box::new(Type::Any))),
+        'r' => Some(Type::Record(vec![])),
+        _ => None,
+    }
+}
+
+/// tell whether a value is of the same kind of type as the *filter*, see [`App::nav_type_filter`]
+fn matches_type_filter(value: &Value, filter: &Option<Type>) -> bool {
+    match filter {
+        Some(ty) => std::mem::discriminant(&value.get_type()) == std::mem::discriminant(ty),
+        None => true,
+    }
+}
+
+/// give the index of the next item, in *direction*, among *len* items, wrapping around and
+/// skipping the ones that are not *accepted*, or *index* if none is
+fn next_index(index: usize, len: usize, direction: i32, accept: impl Fn(usize) -> bool) -> usize {
+    let len = len as i32;
+    (1..=len)
+        .map(|step| (index as i32 + step * direction).rem_euclid(len) as usize)
+        .find(|i| accept(*i))
+        .unwrap_or(index)
+}
+
 /// go up or down in the data
 ///
 /// depending on the direction (see [`Direction`]), this function will
@@ -18,19 +264,21 @@ pub enum Direction {
 /// situation: you are at the bottom of the data, looking at one item in a list, without this early
 /// return, you'd be able to scroll the list without seeing it as a whole... confusing, right?
 /// - cycle the list indices or the record column names => the index / column will wrap around
+/// - skip the siblings that are not of the type of [`App::nav_type_filter`], if any
 ///
 /// > :bulb: **Note**  
 /// > this function will only modify the last element of the state's *cell path* either by
 /// > - not doing anything
 /// > - poping the last element to know where we are and then pushing back the new element
-pub(super) fn go_up_or_down_in_data(app: &mut App, direction: Direction) {
+pub(super) fn go_up_or_down_in_data(app: &mut App, direction: Direction, config: &Config) {
     if app.is_at_bottom() {
         return;
     }
 
-    let direction = match direction {
-        Direction::Up => -1,
-        Direction::Down => 1,
+    // NOTE: the rows are shown in reverse order, see [`App::reversed`]
+    let direction = match (direction, app.reversed) {
+        (Direction::Up, false) | (Direction::Down, true) => -1,
+        (Direction::Down, false) | (Direction::Up, true) => 1,
     };
 
     let current = app
@@ -63,10 +311,9 @@ pub(super) fn go_up_or_down_in_data(app: &mut App, direction: Direction) {
                     val: if vals.is_empty() {
                         val
                     } else {
-                        let len = vals.len() as i32;
-                        let new_index = (val as i32 + direction + len) % len;
-
-                        new_index as usize
+                        next_index(val, vals.len(), direction, |i| {
+                            matches_type_filter(&vals[i], &app.nav_type_filter)
+                        })
                     },
                     span,
                     optional,
@@ -85,11 +332,23 @@ pub(super) fn go_up_or_down_in_data(app: &mut App, direction: Direction) {
                     val: if rec.cols.is_empty() {
                         "".into()
                     } else {
-                        let index = rec.cols.iter().position(|x| x == &val).unwrap() as i32;
-                        let len = rec.cols.len() as i32;
-                        let new_index = (index + direction + len) % len;
+                        let cols = visible_columns(&rec, config, app);
+                        // NOTE: a field hidden in a collapsed group moves from the first field of
+                        // its group, which stands for it
+                        let index = cols
+                            .iter()
+                            .position(|x| x == &val)
+                            .or_else(|| {
+                                cols.iter()
+                                    .position(|x| record_group(x) == record_group(&val))
+                            })
+                            .unwrap_or_default();
+                        let new_index = next_index(index, cols.len(), direction, |i| {
+                            rec.get(&cols[i])
+                                .is_some_and(|v| matches_type_filter(v, &app.nav_type_filter))
+                        });
 
-                        rec.cols[new_index as usize].clone()
+                        cols[new_index].clone()
                     },
                     span,
                     optional,
@@ -102,13 +361,87 @@ pub(super) fn go_up_or_down_in_data(app: &mut App, direction: Direction) {
     }
 }
 
+/// jump to the "matching" row of the current container, like `%` in Vim: from the first row to
+/// the last one and from any other row back to the first one
+///
+/// the rows of records are their visible columns, see [`visible_columns`].
+pub(super) fn jump_to_matching_in_data(app: &mut App, config: &Config) {
+    if app.is_at_bottom() {
+        return;
+    }
+    let Some(current) = app.position.members.pop() else {
+        return;
+    };
+
+    let container = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+        .ok();
+    let new = match (container, current) {
+        (
+            Some(Value::List { vals, .. }),
+            PathMember::Int {
+                val,
+                span,
+                optional,
+            },
+        ) if !vals.is_empty() => PathMember::Int {
+            val: if val == 0 { vals.len() - 1 } else { 0 },
+            span,
+            optional,
+        },
+        (
+            Some(Value::Record { val: rec, .. }),
+            PathMember::String {
+                val,
+                span,
+                optional,
+            },
+        ) => {
+            let cols = visible_columns(&rec, config, app);
+            let val = match cols.first() {
+                Some(first) if first != &val => first.clone(),
+                _ => cols.last().cloned().unwrap_or(val),
+            };
+            PathMember::String {
+                val,
+                span,
+                optional,
+            }
+        }
+        (_, current) => current,
+    };
+    app.position.members.push(new);
+}
+
+/// the message shown when trying to go deeper than [`App::max_depth`]
+pub(crate) const MAX_DEPTH: &str = "cannot go deeper, the maximum depth has been reached";
+
+/// the message shown when stopping in an empty list or record, see [`Config::skip_empty_optional`]
+const EMPTY_CONTAINER: &str = "nothing to go into, this is empty";
+
+/// tell whether the end of *path* is missing, i.e. it points inside an empty list or record
+pub(crate) fn is_missing(path: &[PathMember]) -> bool {
+    matches!(
+        path.last(),
+        Some(PathMember::Int { optional: true, .. } | PathMember::String { optional: true, .. })
+    )
+}
+
 /// go one level deeper in the data
 ///
 /// > :bulb: **Note**  
 /// > this function will
 /// > - push a new *cell path* member to the state if there is more depth ahead
 /// > - mark the state as *at the bottom* if the value at the new depth is of a simple type
-pub(super) fn go_deeper_in_data(app: &mut App) {
+pub(super) fn go_deeper_in_data(app: &mut App, config: &Config) {
+    // NOTE: the member in an empty list or record is optional and there is nothing below it
+    if is_missing(&app.position.members) {
+        app.message = Some(EMPTY_CONTAINER.into());
+        return;
+    }
+
     let cell = app
         .value
         .clone()
@@ -122,6 +455,25 @@ pub(super) fn go_deeper_in_data(app: &mut App) {
             )
         });
 
+    let is_empty = match &cell {
+        Value::List { vals, .. } => vals.is_empty(),
+        Value::Record { val: rec, .. } => rec.is_empty(),
+        _ => false,
+    };
+    if is_container(Some(&cell)) && app.is_at_max_depth() {
+        app.message = Some(MAX_DEPTH.into());
+        return;
+    }
+    if is_empty {
+        if config.skip_empty_optional {
+            return;
+        }
+        app.message = Some(EMPTY_CONTAINER.into());
+        if config.missing_display == MissingDisplay::Reconcile {
+            return;
+        }
+    }
+
     match cell {
         Value::List { vals, .. } => app.position.members.push(PathMember::Int {
             val: 0,
@@ -129,7 +481,10 @@ pub(super) fn go_deeper_in_data(app: &mut App) {
             optional: vals.is_empty(),
         }),
         Value::Record { val: rec, .. } => app.position.members.push(PathMember::String {
-            val: rec.cols.first().unwrap_or(&"".to_string()).into(),
+            val: visible_columns(&rec, config, app)
+                .first()
+                .unwrap_or(&"".to_string())
+                .into(),
             span: Span::unknown(),
             optional: rec.cols.is_empty(),
         }),
@@ -149,12 +504,220 @@ pub(super) fn go_back_in_data(app: &mut App) {
     app.mode = Mode::Normal;
 }
 
+/// give the cell path members to go from one node to its children, with the children
+fn members_of(value: &Value) -> Vec<(PathMember, &Value)> {
+    match value {
+        Value::List { vals, .. } => vals
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let member = PathMember::Int {
+                    val: i,
+                    span: Span::unknown(),
+                    optional: false,
+                };
+                (member, v)
+            })
+            .collect(),
+        Value::Record { val: rec, .. } => rec
+            .iter()
+            .map(|(c, v)| {
+                let member = PathMember::String {
+                    val: c.clone(),
+                    span: Span::unknown(),
+                    optional: false,
+                };
+                (member, v)
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// list all the nodes of the data in depth-first order, with whether they are scalar leaves
+fn push_nodes(value: &Value, path: &[PathMember], nodes: &mut Vec<(Vec<PathMember>, bool)>) {
+    for (member, child) in members_of(value) {
+        let mut child_path = path.to_vec();
+        child_path.push(member);
+
+        let is_leaf = !matches!(child, Value::List { .. } | Value::Record { .. });
+        nodes.push((child_path.clone(), is_leaf));
+        push_nodes(child, &child_path, nodes);
+    }
+}
+
+/// find the same position as *members* in the next sibling of its parent that is a list or a
+/// record
+///
+/// the search wraps around the siblings and skips the empty ones. in the new parent, the same
+/// column or index is used if there is one, otherwise the first one.
+///
+/// # Example
+/// in `{l: [my list elements], r: {a: 1, b: 2}, s: "some string"}`, the sibling of `$.r.a` is
+/// `$.l.0`.
+pub(crate) fn sibling_in_next_container(
+    value: &Value,
+    members: &[PathMember],
+) -> Option<Vec<PathMember>> {
+    let (current, parent_path) = members.split_last()?;
+    let (parent, grandparent_path) = parent_path.split_last()?;
+
+    let grandparent = value
+        .clone()
+        .follow_cell_path(grandparent_path, false)
+        .ok()?;
+    let siblings = members_of(&grandparent);
+    let index = siblings.iter().position(|(m, _)| m == parent)?;
+
+    let (member, sibling) = siblings[index + 1..]
+        .iter()
+        .chain(siblings[..index].iter())
+        .find(|(_, v)| !members_of(v).is_empty())?;
+
+    let children = members_of(sibling);
+    let child = children
+        .iter()
+        .map(|(m, _)| m)
+        .find(|m| *m == current)
+        .unwrap_or(&children[0].0);
+
+    let mut path = grandparent_path.to_vec();
+    path.push(member.clone());
+    path.push(child.clone());
+    Some(path)
+}
+
+/// go down from *cell_path* as long as the lists and records have a single item or field
+///
+/// this stops on the first leaf, empty container or container with several children, which
+/// makes it possible to skip the long chains of nested records of some configurations.
+pub(crate) fn drill_to_leaf(value: &Value, cell_path: &CellPath) -> CellPath {
+    let mut members = cell_path.members.clone();
+    let mut node = match value.clone().follow_cell_path(&members, false) {
+        Ok(node) => node,
+        Err(_) => return cell_path.clone(),
+    };
+
+    loop {
+        let mut children = members_of(&node);
+        if children.len() != 1 {
+            break;
+        }
+
+        let (member, child) = children.remove(0);
+        members.push(member);
+        node = child.clone();
+    }
+
+    CellPath { members }
+}
+
+/// find the next scalar leaf after *cell_path* in the data, in depth-first order
+///
+/// lists and records are skipped, even empty ones, and the search wraps around at the end of the
+/// data. this gives [`None`] only when there are no leaves at all.
+pub(crate) fn next_leaf(value: &Value, cell_path: &CellPath) -> Option<CellPath> {
+    let mut nodes = vec![];
+    push_nodes(value, &[], &mut nodes);
+
+    let current = to_keys(&cell_path.members);
+    let start = nodes
+        .iter()
+        .position(|(path, _)| to_keys(path) == current)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    nodes[start..]
+        .iter()
+        .chain(nodes[..start].iter())
+        .find(|(_, is_leaf)| *is_leaf)
+        .map(|(path, _)| CellPath {
+            members: path.clone(),
+        })
+}
+
 // TODO: add proper assert error messages
 #[cfg(test)]
 mod tests {
-    use super::{go_back_in_data, go_deeper_in_data, go_up_or_down_in_data, Direction};
-    use crate::app::App;
-    use nu_protocol::{ast::PathMember, record, Span, Value};
+    use super::{
+        drill_to_leaf, go_back_in_data, go_deeper_in_data, go_up_or_down_in_data,
+        next_key_starting_with, next_leaf, page_table_columns, reset_column_offset,
+        sibling_in_next_container, toggle_group, Direction, EMPTY_CONTAINER, MAX_DEPTH,
+    };
+    use crate::{
+        app::App,
+        config::{Config, MissingDisplay},
+        nu::cell_path::{to_path_member_vec, PM},
+    };
+    use nu_protocol::{
+        ast::{CellPath, PathMember},
+        record, Span, Value,
+    };
+
+    /// a table with two rows and the given number of `int` columns, `c0`, `c1`, ...
+    fn wide_table(columns: usize) -> Value {
+        let row = |i: i64| {
+            let mut rec = nu_protocol::Record::new();
+            for c in 0..columns {
+                rec.push(format!("c{c}"), Value::test_int(i * 100 + c as i64));
+            }
+            Value::test_record(rec)
+        };
+        Value::test_list(vec![row(0), row(1)])
+    }
+
+    #[test]
+    fn page_columns() {
+        let config = Config::default();
+        let mut app = App::from_value(wide_table(10));
+        app.position.members = to_path_member_vec(&[PM::I(0)]);
+        // NOTE: 3 columns of 25 characters fit in 80 characters
+        app.width = 80;
+
+        let mut offsets = vec![];
+        for _ in 0..4 {
+            page_table_columns(&mut app, &config, true);
+            offsets.push(app.column_offset);
+        }
+        // NOTE: the last page is full, with the last 3 columns
+        assert_eq!(offsets, vec![3, 6, 7, 7]);
+
+        let mut offsets = vec![];
+        for _ in 0..4 {
+            page_table_columns(&mut app, &config, false);
+            offsets.push(app.column_offset);
+        }
+        assert_eq!(offsets, vec![4, 1, 0, 0]);
+
+        // NOTE: the offset is kept in the table and forgotten outside of it
+        page_table_columns(&mut app, &config, true);
+        let from = app.absolute_position();
+        app.position.members = to_path_member_vec(&[PM::I(1)]);
+        reset_column_offset(&mut app, &from);
+        assert_eq!(app.column_offset, 3);
+        let from = app.absolute_position();
+        app.position.members = to_path_member_vec(&[PM::I(1), PM::S("c0")]);
+        reset_column_offset(&mut app, &from);
+        assert_eq!(app.column_offset, 0);
+        app.position.members = to_path_member_vec(&[PM::I(0)]);
+
+        // NOTE: fewer columns fit next to the preview pane
+        let preview = Config {
+            show_preview_pane: true,
+            ..Default::default()
+        };
+        page_table_columns(&mut app, &preview, true);
+        assert_eq!(app.column_offset, 1);
+        app.column_offset = 0;
+
+        app.table_view = false;
+        page_table_columns(&mut app, &config, true);
+        assert_eq!(app.column_offset, 0);
+        assert_eq!(
+            app.message,
+            Some("only tables have columns to page through".into())
+        );
+    }
 
     fn test_string_pathmember(val: impl Into<String>) -> PathMember {
         PathMember::String {
@@ -190,7 +753,58 @@ mod tests {
             (Direction::Up, 0),
         ];
         for (direction, id) in sequence {
-            go_up_or_down_in_data(&mut app, direction);
+            go_up_or_down_in_data(&mut app, direction, &Config::default());
+            let expected = vec![test_int_pathmember(id)];
+            assert_eq!(app.position.members, expected);
+        }
+    }
+
+    #[test]
+    fn type_ahead() {
+        let cols: Vec<String> = ["name", "size", "Status", "type"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        assert_eq!(
+            next_key_starting_with(&cols, "name", 's'),
+            Some("size".into())
+        );
+        assert_eq!(
+            next_key_starting_with(&cols, "size", 's'),
+            Some("Status".into())
+        );
+        // NOTE: the search wraps around and ignores the case
+        assert_eq!(
+            next_key_starting_with(&cols, "Status", 'S'),
+            Some("size".into())
+        );
+        assert_eq!(
+            next_key_starting_with(&cols, "type", 't'),
+            Some("type".into())
+        );
+        assert_eq!(next_key_starting_with(&cols, "name", 'x'), None);
+    }
+
+    #[test]
+    fn go_up_and_down_in_reversed_list() {
+        let value = Value::test_list(vec![
+            Value::test_nothing(),
+            Value::test_nothing(),
+            Value::test_nothing(),
+        ]);
+        let mut app = App::from_value(value);
+        app.reversed = true;
+
+        // NOTE: going down in the view goes up in the data
+        let sequence = vec![
+            (Direction::Down, 2),
+            (Direction::Down, 1),
+            (Direction::Up, 2),
+            (Direction::Up, 0),
+        ];
+        for (direction, id) in sequence {
+            go_up_or_down_in_data(&mut app, direction, &Config::default());
             let expected = vec![test_int_pathmember(id)];
             assert_eq!(app.position.members, expected);
         }
@@ -214,12 +828,118 @@ mod tests {
             (Direction::Up, "a"),
         ];
         for (direction, id) in sequence {
-            go_up_or_down_in_data(&mut app, direction);
+            go_up_or_down_in_data(&mut app, direction, &Config::default());
+            let expected = vec![test_string_pathmember(id)];
+            assert_eq!(app.position.members, expected);
+        }
+    }
+
+    #[test]
+    fn go_up_and_down_in_grouped_record() {
+        let value = Value::test_record(record! {
+            "b1" => Value::test_nothing(),
+            "a2" => Value::test_nothing(),
+            "a1" => Value::test_nothing(),
+        });
+        let mut app = App::from_value(value);
+        let config = Config {
+            group_records: true,
+            ..Default::default()
+        };
+
+        let sequence = vec![
+            (Direction::Down, "a1"),
+            (Direction::Down, "a2"),
+            (Direction::Down, "b1"),
+            (Direction::Up, "a2"),
+        ];
+        for (direction, id) in sequence {
+            go_up_or_down_in_data(&mut app, direction, &config);
             let expected = vec![test_string_pathmember(id)];
             assert_eq!(app.position.members, expected);
         }
     }
 
+    #[test]
+    fn collapse_groups() {
+        let value = Value::test_record(record! {
+            "b1" => Value::test_nothing(),
+            "a2" => Value::test_nothing(),
+            "a1" => Value::test_nothing(),
+            "a3" => Value::test_nothing(),
+        });
+        let mut app = App::from_value(value);
+        app.position.members = vec![test_string_pathmember("a2")];
+        let config = Config {
+            group_records: true,
+            ..Default::default()
+        };
+
+        toggle_group(&mut app, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("a1")]);
+        go_up_or_down_in_data(&mut app, Direction::Down, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("b1")]);
+
+        go_up_or_down_in_data(&mut app, Direction::Up, &config);
+        toggle_group(&mut app, &config);
+        go_up_or_down_in_data(&mut app, Direction::Down, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("a2")]);
+
+        // NOTE: only the groups by first letter can be collapsed
+        toggle_group(&mut app, &Config::default());
+        assert!(app.collapsed_groups.is_empty());
+    }
+
+    #[test]
+    fn land_in_collapsed_group() {
+        let value = Value::test_record(record! {
+            "b1" => Value::test_nothing(),
+            "a2" => Value::test_nothing(),
+            "a1" => Value::test_nothing(),
+        });
+        let config = Config {
+            group_records: true,
+            ..Default::default()
+        };
+
+        // NOTE: a field hidden in its group moves like the first field of the group
+        let mut app = App::from_value(value.clone());
+        app.collapsed_groups.insert("a".into());
+        app.position.members = vec![test_string_pathmember("a2")];
+        go_up_or_down_in_data(&mut app, Direction::Down, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("b1")]);
+
+        // NOTE: jumping to a hidden field expands its group, but not to the first one
+        let mut app = App::from_value(value);
+        app.collapsed_groups.insert("a".into());
+        app.jump_to(
+            &CellPath {
+                members: vec![test_string_pathmember("a1")],
+            },
+            &config,
+        );
+        assert!(app.collapsed_groups.contains("a"));
+        app.jump_to(
+            &CellPath {
+                members: vec![test_string_pathmember("a2")],
+            },
+            &config,
+        );
+        assert!(app.collapsed_groups.is_empty());
+        go_up_or_down_in_data(&mut app, Direction::Down, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("b1")]);
+
+        // NOTE: the groups are kept when the records are not grouped by their first letter
+        app.collapsed_groups.insert("a".into());
+        app.jump_to(
+            &CellPath {
+                members: vec![test_string_pathmember("a2")],
+            },
+            &Config::default(),
+        );
+        assert!(app.collapsed_groups.contains("a"));
+    }
+
     #[test]
     fn go_deeper() {
         let value = Value::test_list(vec![Value::test_record(record! {
@@ -230,15 +950,97 @@ mod tests {
         let mut expected = vec![test_int_pathmember(0)];
         assert_eq!(app.position.members, expected);
 
-        go_deeper_in_data(&mut app);
+        go_deeper_in_data(&mut app, &Config::default());
         expected.push(test_string_pathmember("a"));
         assert_eq!(app.position.members, expected);
 
-        go_deeper_in_data(&mut app);
+        go_deeper_in_data(&mut app, &Config::default());
         expected.push(test_int_pathmember(0));
         assert_eq!(app.position.members, expected);
     }
 
+    #[test]
+    fn go_deeper_in_empty_list() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![]),
+        });
+
+        // NOTE: by default, the navigation stops in the empty list, and only there
+        let mut app = App::from_value(value.clone());
+        let config = Config::default();
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(
+            app.position.members,
+            vec![
+                test_string_pathmember("l"),
+                PathMember::Int {
+                    val: 0,
+                    span: Span::test_data(),
+                    optional: true,
+                },
+            ]
+        );
+        assert_eq!(app.message, Some(EMPTY_CONTAINER.into()));
+
+        app.message = None;
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(app.position.members.len(), 2);
+        assert!(!app.is_at_bottom());
+        assert_eq!(app.message, Some(EMPTY_CONTAINER.into()));
+
+        // NOTE: otherwise, the empty list is skipped
+        let mut app = App::from_value(value);
+        let config = Config {
+            skip_empty_optional: true,
+            ..Default::default()
+        };
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("l")]);
+        assert!(!app.is_at_bottom());
+        assert_eq!(app.message, None);
+
+        // NOTE: or the cursor stays on it with a message
+        let mut app = App::from_value(Value::test_record(record! {
+            "l" => Value::test_list(vec![]),
+        }));
+        let config = Config {
+            missing_display: MissingDisplay::Reconcile,
+            ..Default::default()
+        };
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(app.position.members, vec![test_string_pathmember("l")]);
+        assert_eq!(app.message, Some(EMPTY_CONTAINER.into()));
+    }
+
+    #[test]
+    fn go_deeper_up_to_max_depth() {
+        let value = Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "l" => Value::test_list(vec![Value::test_int(1)]),
+            }),
+        });
+        let mut app = App::from_value(value);
+        app.max_depth = Some(2);
+        let config = Config::default();
+
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(
+            app.position.members,
+            vec![test_string_pathmember("r"), test_string_pathmember("l")]
+        );
+        assert_eq!(app.message, None);
+
+        // NOTE: the list is at the maximum depth, its items are too deep
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(app.position.members.len(), 2);
+        assert!(!app.is_at_bottom());
+        assert_eq!(app.message, Some(MAX_DEPTH.into()));
+
+        app.max_depth = None;
+        go_deeper_in_data(&mut app, &config);
+        assert_eq!(app.position.members.len(), 3);
+    }
+
     #[test]
     fn hit_bottom() {
         let value = Value::test_nothing();
@@ -246,7 +1048,7 @@ mod tests {
 
         assert!(!app.is_at_bottom());
 
-        go_deeper_in_data(&mut app);
+        go_deeper_in_data(&mut app, &Config::default());
         assert!(app.is_at_bottom());
     }
 
@@ -279,4 +1081,131 @@ mod tests {
         go_back_in_data(&mut app);
         assert_eq!(app.position.members, expected);
     }
+
+    #[test]
+    fn traverse_leaves() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "e" => Value::test_list(vec![]),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        });
+
+        let mut cell_path = CellPath {
+            members: to_path_member_vec(&[PM::S("l")]),
+        };
+        let expected = vec![
+            vec![PM::S("l"), PM::I(0)],
+            vec![PM::S("l"), PM::I(1)],
+            vec![PM::S("l"), PM::I(2)],
+            vec![PM::S("r"), PM::S("a")],
+            vec![PM::S("r"), PM::S("b")],
+            vec![PM::S("s")],
+            vec![PM::S("i")],
+            // NOTE: the traversal wraps around
+            vec![PM::S("l"), PM::I(0)],
+        ];
+        for members in expected {
+            let next = next_leaf(&value, &cell_path).unwrap();
+            assert_eq!(
+                next.members,
+                to_path_member_vec(&members),
+                "expected {}",
+                PM::as_cell_path(&members)
+            );
+            cell_path = next;
+        }
+
+        let no_leaves = Value::test_list(vec![Value::test_list(vec![]), Value::test_list(vec![])]);
+        assert_eq!(next_leaf(&no_leaves, &cell_path), None);
+    }
+
+    #[test]
+    fn jump_to_sibling_container() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "e" => Value::test_record(record! {}),
+            "s" => Value::test_string("some string"),
+            "r2" => Value::test_record(record! {
+                "b" => Value::test_int(3),
+            }),
+        });
+
+        let jump = |members: &[PM]| sibling_in_next_container(&value, &to_path_member_vec(members));
+
+        // NOTE: the empty record and the string are skipped and the siblings wrap around
+        assert_eq!(
+            jump(&[PM::S("r"), PM::S("a")]),
+            Some(to_path_member_vec(&[PM::S("r2"), PM::S("b")]))
+        );
+        assert_eq!(
+            jump(&[PM::S("r2"), PM::S("b")]),
+            Some(to_path_member_vec(&[PM::S("l"), PM::I(0)]))
+        );
+        assert_eq!(
+            jump(&[PM::S("l"), PM::I(2)]),
+            Some(to_path_member_vec(&[PM::S("r"), PM::S("a")]))
+        );
+        // NOTE: there is no parent at the top of the data
+        assert_eq!(jump(&[PM::S("r")]), None);
+    }
+
+    #[test]
+    fn drill_through_single_children() {
+        let value = Value::test_record(record! {
+            "a" => Value::test_record(record! {
+                "b" => Value::test_list(vec![
+                    Value::test_record(record! {
+                        "c" => Value::test_string("leaf"),
+                    }),
+                ]),
+            }),
+            "x" => Value::test_record(record! {
+                "y" => Value::test_record(record! {
+                    "z1" => Value::test_int(1),
+                    "z2" => Value::test_int(2),
+                }),
+            }),
+        });
+
+        let drill = |members: &[PM]| {
+            drill_to_leaf(
+                &value,
+                &CellPath {
+                    members: to_path_member_vec(members),
+                },
+            )
+            .members
+        };
+
+        assert_eq!(
+            drill(&[PM::S("a")]),
+            to_path_member_vec(&[PM::S("a"), PM::S("b"), PM::I(0), PM::S("c")])
+        );
+        // NOTE: the drill stops on records with several fields
+        assert_eq!(
+            drill(&[PM::S("x")]),
+            to_path_member_vec(&[PM::S("x"), PM::S("y")])
+        );
+        assert_eq!(
+            drill(&[PM::S("x"), PM::S("y"), PM::S("z1")]),
+            to_path_member_vec(&[PM::S("x"), PM::S("y"), PM::S("z1")])
+        );
+    }
 }
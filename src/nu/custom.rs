@@ -0,0 +1,200 @@
+//! custom values, e.g. dataframes, whose conversion to a base value is done once, when the data is
+//! loaded, instead of every time they are rendered
+use std::cmp::Ordering;
+
+use nu_protocol::{ast::Operator, CustomValue, Record, ShellError, Span, Value};
+use serde::{Deserialize, Serialize};
+
+/// a custom value along with its base value, converted once
+///
+/// everything but the conversion is delegated to the wrapped custom value.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Converted {
+    inner: Box<dyn CustomValue>,
+    /// the base value of the custom value, if it can be converted
+    base: Option<Value>,
+}
+
+impl Converted {
+    /// the wrapped custom value
+    pub(crate) fn inner(&self) -> &dyn CustomValue {
+        self.inner.as_ref()
+    }
+
+    /// the base value of the custom value, without converting it again
+    pub(crate) fn base(&self) -> Option<&Value> {
+        self.base.as_ref()
+    }
+}
+
+#[typetag::serde]
+impl CustomValue for Converted {
+    fn clone_value(&self, span: Span) -> Value {
+        match self.inner.clone_value(span) {
+            Value::CustomValue { val, .. } => Value::custom_value(
+                Box::new(Converted {
+                    inner: val,
+                    base: self.base.clone(),
+                }),
+                span,
+            ),
+            // NOTE: a custom value that does not clone into a custom value is kept as is
+            value => value,
+        }
+    }
+
+    fn value_string(&self) -> String {
+        self.inner.value_string()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        match &self.base {
+            Some(base) => Ok(base.clone()),
+            None => self.inner.to_base_value(span),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn follow_path_int(&self, count: usize, span: Span) -> Result<Value, ShellError> {
+        self.inner.follow_path_int(count, span)
+    }
+
+    fn follow_path_string(&self, column_name: String, span: Span) -> Result<Value, ShellError> {
+        self.inner.follow_path_string(column_name, span)
+    }
+
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        match other {
+            Value::CustomValue { val, .. } => match val.as_any().downcast_ref::<Converted>() {
+                Some(other) => self
+                    .inner
+                    .partial_cmp(&other.inner.clone_value(Span::unknown())),
+                None => self.inner.partial_cmp(other),
+            },
+            _ => self.inner.partial_cmp(other),
+        }
+    }
+
+    fn operation(
+        &self,
+        lhs_span: Span,
+        operator: Operator,
+        op: Span,
+        right: &Value,
+    ) -> Result<Value, ShellError> {
+        self.inner.operation(lhs_span, operator, op, right)
+    }
+}
+
+/// convert all the custom values of *value* to their base values, once and for all
+pub(crate) fn convert_custom_values(value: Value) -> Value {
+    map_custom_values(value, &|val, span| {
+        if val.as_any().is::<Converted>() {
+            return Value::custom_value(val, span);
+        }
+        let base = val.to_base_value(span).ok();
+        Value::custom_value(Box::new(Converted { inner: val, base }), span)
+    })
+}
+
+/// give back the original custom values of *value*, see [`convert_custom_values`]
+pub(crate) fn restore_custom_values(value: Value) -> Value {
+    map_custom_values(
+        value,
+        &|val, span| match val.as_any().downcast_ref::<Converted>() {
+            Some(converted) => converted.inner.clone_value(span),
+            None => Value::custom_value(val, span),
+        },
+    )
+}
+
+fn map_custom_values(value: Value, f: &impl Fn(Box<dyn CustomValue>, Span) -> Value) -> Value {
+    let span = value.span();
+    match value {
+        Value::List { vals, .. } => Value::list(
+            vals.into_iter().map(|v| map_custom_values(v, f)).collect(),
+            span,
+        ),
+        Value::Record { val: rec, .. } => Value::record(
+            rec.into_iter()
+                .map(|(k, v)| (k, map_custom_values(v, f)))
+                .collect::<Record>(),
+            span,
+        ),
+        Value::CustomValue { val, .. } => f(val, span),
+        x => x,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use nu_protocol::{record, CustomValue, ShellError, Span, Value};
+    use serde::{Deserialize, Serialize};
+
+    use super::{convert_custom_values, restore_custom_values, Converted};
+
+    /// the number of times a [`Counted`] value has been converted to a base value
+    static CONVERSIONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct Counted;
+
+    #[typetag::serde]
+    impl CustomValue for Counted {
+        fn clone_value(&self, span: Span) -> Value {
+            Value::custom_value(Box::new(self.clone()), span)
+        }
+
+        fn value_string(&self) -> String {
+            "Counted".into()
+        }
+
+        fn to_base_value(&self, _span: Span) -> Result<Value, ShellError> {
+            CONVERSIONS.fetch_add(1, Ordering::SeqCst);
+            Ok(Value::test_int(42))
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn convert_once() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_custom_value(Box::new(Counted))]),
+            "c" => Value::test_custom_value(Box::new(Counted)),
+        });
+
+        let converted = convert_custom_values(value);
+        assert_eq!(CONVERSIONS.load(Ordering::SeqCst), 2);
+
+        // NOTE: the base values are cloned along with the custom values and converting again
+        // does nothing
+        let cloned = convert_custom_values(converted.clone());
+        let cell = cloned.get_data_by_key("c").unwrap();
+        let Value::CustomValue { val, .. } = &cell else {
+            panic!("expected a custom value, found {cell:?}");
+        };
+        assert_eq!(
+            val.to_base_value(Span::unknown()).unwrap(),
+            Value::test_int(42)
+        );
+        assert_eq!(CONVERSIONS.load(Ordering::SeqCst), 2);
+
+        let converted_cell = val.as_any().downcast_ref::<Converted>().unwrap();
+        assert_eq!(converted_cell.inner().value_string(), "Counted");
+        assert_eq!(converted_cell.base(), Some(&Value::test_int(42)));
+
+        let restored = restore_custom_values(cloned);
+        let Some(Value::CustomValue { val, .. }) = restored.get_data_by_key("c") else {
+            panic!("expected a custom value in {restored:?}");
+        };
+        assert!(val.as_any().is::<Counted>());
+    }
+}
@@ -1,4 +1,7 @@
 #[cfg(test)]
 pub(super) mod cell_path;
+pub(super) mod custom;
+pub(super) mod nuon;
+pub(super) mod schema;
 pub(super) mod strings;
 pub(super) mod value;
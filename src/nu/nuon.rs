@@ -0,0 +1,665 @@
+//! read and write NUON, the object notation of Nushell, without the parser of Nushell itself
+//!
+//! the lists, records, tables, strings, numbers, file sizes, durations, dates and binaries are
+//! supported, with the comments and the optional commas, but not the ranges nor the closures.
+//!
+//! the parser of NUON lives in the commands of Nushell, which are not a dependency of the plugin,
+//! and NUON is not YAML as soon as it has quoted keys, dates, durations or binaries. the dates are
+//! built with `chrono`, which `nu-protocol` already depends on for its own dates.
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDate};
+use nu_protocol::{Record, Span, Value};
+
+use super::value::repr_nu_key;
+
+/// the units of the file sizes, with their number of bytes
+const FILESIZE_UNITS: [(&str, i64); 13] = [
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("eb", 1_000_000_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+    ("eib", 1 << 60),
+];
+
+/// the units of the durations, with their number of nanoseconds
+const DURATION_UNITS: [(&str, i64); 9] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ms", 1_000_000),
+    ("sec", 1_000_000_000),
+    ("min", 60 * 1_000_000_000),
+    ("hr", 3_600 * 1_000_000_000),
+    ("day", 86_400 * 1_000_000_000),
+    ("wk", 7 * 86_400 * 1_000_000_000),
+];
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn rest_starts_with(&self, prefix: &str) -> bool {
+        prefix
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    /// build an error at the current position, with the line it's on
+    fn error(&self, msg: impl std::fmt::Display) -> String {
+        let line = self.chars[..self.pos.min(self.chars.len())]
+            .iter()
+            .filter(|c| **c == '\n')
+            .count();
+        format!("{} at line {}", msg, line + 1)
+    }
+
+    /// skip the whitespace, the commas and the comments
+    fn skip(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == ',' {
+                self.pos += 1;
+            } else if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("expected `{}`, found the end", expected))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip();
+        match self.peek() {
+            Some('{') => self.record(),
+            Some('[') => self.list(),
+            Some('"') => self.quoted().map(|s| Value::string(s, Span::unknown())),
+            Some('\'' | '`') => self.raw().map(|s| Value::string(s, Span::unknown())),
+            Some(_) if self.rest_starts_with("r#") => {
+                self.raw_hashed().map(|s| Value::string(s, Span::unknown()))
+            }
+            Some(_) if self.rest_starts_with("0x[") => self.binary(),
+            Some(_) => {
+                let token = self.bare(false);
+                if token.is_empty() {
+                    return Err(self.error(format!("unexpected `{}`", self.peek().unwrap())));
+                }
+                parse_token(&token).map_err(|e| self.error(e))
+            }
+            None => Err(self.error("expected a value, found the end")),
+        }
+    }
+
+    fn record(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut record = Record::new();
+        loop {
+            self.skip();
+            match self.peek() {
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Value::record(record, Span::unknown()));
+                }
+                Some('"') => {
+                    let key = self.quoted()?;
+                    self.field(&mut record, key)?;
+                }
+                Some('\'' | '`') => {
+                    let key = self.raw()?;
+                    self.field(&mut record, key)?;
+                }
+                Some(_) => {
+                    let key = self.bare(true);
+                    if key.is_empty() {
+                        return Err(self.error(format!("unexpected `{}`", self.peek().unwrap())));
+                    }
+                    self.field(&mut record, key)?;
+                }
+                None => return Err(self.error("expected `}`, found the end")),
+            }
+        }
+    }
+
+    /// parse the value of the field *key* of a record, after its colon
+    fn field(&mut self, record: &mut Record, key: String) -> Result<(), String> {
+        self.skip();
+        self.expect(':')?;
+        let value = self.value()?;
+        record.push(key, value);
+        Ok(())
+    }
+
+    /// parse a list, or a table when the first list is followed by a `;`, e.g.
+    /// `[[a, b]; [1, 2], [3, 4]]`
+    fn list(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut vals = vec![];
+        loop {
+            self.skip();
+            match self.peek() {
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::list(vals, Span::unknown()));
+                }
+                Some(';') if vals.len() == 1 => {
+                    self.pos += 1;
+                    let header = vals.pop().unwrap();
+                    return self.table(header);
+                }
+                Some(_) => vals.push(self.value()?),
+                None => return Err(self.error("expected `]`, found the end")),
+            }
+        }
+    }
+
+    /// parse the rows of a table whose columns are given by the *header*, after the `;`
+    fn table(&mut self, header: Value) -> Result<Value, String> {
+        let columns = match header {
+            Value::List { vals, .. } => vals
+                .into_iter()
+                .map(|v| match v {
+                    Value::String { val, .. } => val,
+                    x => x.to_expanded_string(" ", &nu_protocol::Config::default()),
+                })
+                .collect::<Vec<String>>(),
+            _ => return Err(self.error("the header of a table should be a list")),
+        };
+
+        let mut rows = vec![];
+        loop {
+            self.skip();
+            match self.peek() {
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::list(rows, Span::unknown()));
+                }
+                Some(_) => {
+                    let Value::List { vals, .. } = self.value()? else {
+                        return Err(self.error("the rows of a table should be lists"));
+                    };
+                    if vals.len() != columns.len() {
+                        return Err(self.error(format!(
+                            "expected {} cells in the row of the table, found {}",
+                            columns.len(),
+                            vals.len()
+                        )));
+                    }
+                    rows.push(Value::record(
+                        columns.iter().cloned().zip(vals).collect::<Record>(),
+                        Span::unknown(),
+                    ));
+                }
+                None => return Err(self.error("expected `]`, found the end")),
+            }
+        }
+    }
+
+    /// parse a string between double quotes, with its escapes
+    fn quoted(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unclosed string"));
+            };
+            self.pos += 1;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let Some(escaped) = self.peek() else {
+                        return Err(self.error("unclosed string"));
+                    };
+                    self.pos += 1;
+                    match escaped {
+                        '"' | '\\' | '/' | '\'' => s.push(escaped),
+                        'n' => s.push('\n'),
+                        't' => s.push('\t'),
+                        'r' => s.push('\r'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'e' => s.push('\u{1b}'),
+                        '0' => s.push('\0'),
+                        'u' => s.push(self.unicode()?),
+                        x => return Err(self.error(format!("unknown escape `\\{}`", x))),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// parse the code point of a `\u` escape, either `\u{1F600}` or `\u00e9`
+    fn unicode(&mut self) -> Result<char, String> {
+        let hex: String = if self.peek() == Some('{') {
+            self.pos += 1;
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c != '}') {
+                self.pos += 1;
+            }
+            let hex = self.chars[start..self.pos].iter().collect();
+            self.expect('}')?;
+            hex
+        } else {
+            let end = (self.pos + 4).min(self.chars.len());
+            let hex = self.chars[self.pos..end].iter().collect();
+            self.pos = end;
+            hex
+        };
+
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error(format!("invalid unicode escape `{}`", hex)))
+    }
+
+    /// parse a string between single quotes or backticks, without escapes
+    fn raw(&mut self) -> Result<String, String> {
+        let quote = self.peek().unwrap();
+        self.pos += 1;
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != quote) {
+            self.pos += 1;
+        }
+        let s = self.chars[start..self.pos].iter().collect();
+        self.expect(quote)
+            .map_err(|_| self.error("unclosed string"))?;
+        Ok(s)
+    }
+
+    /// parse a raw string like `r#'it's'#`, with as many `#` on both sides
+    fn raw_hashed(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut hashes = 0;
+        while self.peek() == Some('#') {
+            hashes += 1;
+            self.pos += 1;
+        }
+        self.expect('\'')?;
+
+        let end = format!("'{}", "#".repeat(hashes));
+        let start = self.pos;
+        while !self.rest_starts_with(&end) {
+            if self.peek().is_none() {
+                return Err(self.error("unclosed string"));
+            }
+            self.pos += 1;
+        }
+        let s = self.chars[start..self.pos].iter().collect();
+        self.pos += end.chars().count();
+        Ok(s)
+    }
+
+    /// parse a binary like `0x[0A FF]`
+    fn binary(&mut self) -> Result<Value, String> {
+        self.pos += "0x[".len();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != ']') {
+            self.pos += 1;
+        }
+        let hex: String = self.chars[start..self.pos]
+            .iter()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        self.expect(']')?;
+
+        // NOTE: the pairs of digits are sliced by bytes below, which needs them all to be ASCII
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(self.error(format!("invalid binary `0x[{}]`", hex)));
+        }
+        if hex.len() % 2 != 0 {
+            return Err(self.error("odd number of digits in binary"));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map(|bytes| Value::binary(bytes, Span::unknown()))
+            .map_err(|_| self.error(format!("invalid binary `0x[{}]`", hex)))
+    }
+
+    /// read a bare word, up to the next space or delimiter, and the next colon for the *key* of a
+    /// field
+    fn bare(&mut self, key: bool) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, ',' | '[' | ']' | '{' | '}') || (key && c == ':') {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+}
+
+/// parse a bare word, i.e. a number, a file size, a duration, a date, a boolean, `null` or a
+/// string
+fn parse_token(token: &str) -> Result<Value, String> {
+    let span = Span::unknown();
+    match token {
+        "true" => return Ok(Value::bool(true, span)),
+        "false" => return Ok(Value::bool(false, span)),
+        "null" => return Ok(Value::nothing(span)),
+        "inf" | "+inf" => return Ok(Value::float(f64::INFINITY, span)),
+        "-inf" => return Ok(Value::float(f64::NEG_INFINITY, span)),
+        "NaN" => return Ok(Value::float(f64::NAN, span)),
+        _ => {}
+    }
+
+    // NOTE: anything else starting like a number has to be one, e.g. not a mistyped unit
+    let unsigned = token.trim_start_matches(['-', '+']);
+    if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(Value::string(token, span));
+    }
+
+    if let Some(int) = parse_int(token) {
+        return Ok(Value::int(int, span));
+    }
+    if let Some(float) = parse_float(token) {
+        return Ok(Value::float(float, span));
+    }
+    if let Some(value) = parse_with_unit(token) {
+        return Ok(value);
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(token) {
+        return Ok(Value::date(date, span));
+    }
+    if let Some(date) = NaiveDate::parse_from_str(token, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    {
+        return Ok(Value::date(date.and_utc().fixed_offset(), span));
+    }
+
+    Err(format!("invalid value `{}`", token))
+}
+
+fn parse_int(token: &str) -> Option<i64> {
+    let token = token.replace('_', "");
+    let (negative, unsigned) = match token.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, token.strip_prefix('+').unwrap_or(&token)),
+    };
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+
+    let int = i64::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -int } else { int })
+}
+
+fn parse_float(token: &str) -> Option<f64> {
+    let token = token.replace('_', "");
+    // NOTE: the names of the special floats are bare words of their own
+    if token
+        .chars()
+        .any(|c| c.is_ascii_alphabetic() && c != 'e' && c != 'E')
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// parse a number followed by the unit of a file size or of a duration, e.g. `1.5kb` or `2sec`
+fn parse_with_unit(token: &str) -> Option<Value> {
+    let split = token.find(|c: char| c.is_alphabetic())?;
+    let (number, unit) = token.split_at(split);
+    let number = parse_float(number)?;
+
+    let scale = |factor: i64| {
+        let scaled = (number * factor as f64).round();
+        (scaled.abs() < i64::MAX as f64).then_some(scaled as i64)
+    };
+    let unit = unit.to_lowercase();
+    if let Some((_, factor)) = FILESIZE_UNITS.iter().find(|(u, _)| *u == unit) {
+        return scale(*factor).map(|size| Value::filesize(size, Span::unknown()));
+    }
+    if let Some((_, factor)) = DURATION_UNITS.iter().find(|(u, _)| *u == unit) {
+        return scale(*factor).map(|ns| Value::duration(ns, Span::unknown()));
+    }
+
+    None
+}
+
+/// write *s* as a NUON string, between double quotes
+pub(crate) fn quote(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// write *value* as NUON, on a single line, e.g. `{a: 1024b, b: [2024-01-02T03:04:05+00:00]}`
+///
+/// this is the inverse of [`from_nuon`]. the values that have no NUON equivalent, e.g. the ranges
+/// and the closures, are written as strings.
+pub(crate) fn to_nuon(value: &Value) -> String {
+    match value {
+        Value::Nothing { .. } => "null".into(),
+        Value::Bool { val, .. } => val.to_string(),
+        Value::Int { val, .. } => val.to_string(),
+        Value::Float { val, .. } if val.is_nan() => "NaN".into(),
+        Value::Float { val, .. } if val.is_infinite() && *val > 0.0 => "inf".into(),
+        Value::Float { val, .. } if val.is_infinite() => "-inf".into(),
+        // NOTE: the debug representation keeps the decimal point of round floats, e.g. `1.0`
+        Value::Float { val, .. } => format!("{:?}", val),
+        Value::Filesize { val, .. } => format!("{}b", val),
+        Value::Duration { val, .. } => format!("{}ns", val),
+        Value::Date { val, .. } => val.to_rfc3339(),
+        Value::String { val, .. } => quote(val),
+        Value::Binary { val, .. } => {
+            let hex = val.iter().fold(String::new(), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            });
+            format!("0x[{}]", hex)
+        }
+        Value::List { vals, .. } => format!(
+            "[{}]",
+            vals.iter().map(to_nuon).collect::<Vec<String>>().join(", ")
+        ),
+        Value::Record { val: rec, .. } => format!(
+            "{{{}}}",
+            rec.iter()
+                .map(|(col, val)| format!("{}: {}", repr_nu_key(col), to_nuon(val)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Value::CustomValue { val, internal_span } => match val.to_base_value(*internal_span) {
+            Ok(base) => to_nuon(&base),
+            Err(_) => quote(&val.value_string()),
+        },
+        x => quote(&x.to_expanded_string(" ", &nu_protocol::Config::default())),
+    }
+}
+
+/// parse the NUON string *s* into a value
+pub(crate) fn from_nuon(s: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: s.chars().collect(),
+        pos: 0,
+    };
+
+    let value = parser.value()?;
+    parser.skip();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.error("unexpected content after the value"));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use nu_protocol::{record, Value};
+
+    use super::{from_nuon, to_nuon};
+
+    #[test]
+    fn parse_nuon() {
+        let cases = vec![
+            ("null", Value::test_nothing()),
+            ("true", Value::test_bool(true)),
+            ("-12", Value::test_int(-12)),
+            ("0xff", Value::test_int(255)),
+            ("1_000", Value::test_int(1000)),
+            ("2.5", Value::test_float(2.5)),
+            ("-inf", Value::test_float(f64::NEG_INFINITY)),
+            ("1024b", Value::test_filesize(1024)),
+            ("1.5kib", Value::test_filesize(1536)),
+            ("2sec", Value::test_duration(2_000_000_000)),
+            ("-1hr", Value::test_duration(-3_600_000_000_000)),
+            (
+                "2024-01-02T03:04:05+01:00",
+                Value::test_date(
+                    DateTime::parse_from_rfc3339("2024-01-02T03:04:05+01:00").unwrap(),
+                ),
+            ),
+            (
+                "2024-01-02",
+                Value::test_date(
+                    DateTime::parse_from_rfc3339("2024-01-02T00:00:00+00:00").unwrap(),
+                ),
+            ),
+            ("0x[0A ff]", Value::test_binary(vec![10, 255])),
+            ("foo", Value::test_string("foo")),
+            (r#""a \"b\"\n\u{e9}""#, Value::test_string("a \"b\"\né")),
+            ("'c:\\path'", Value::test_string("c:\\path")),
+            ("r#'it's'#", Value::test_string("it's")),
+            (
+                "[1 two, \"three\"]",
+                Value::test_list(vec![
+                    Value::test_int(1),
+                    Value::test_string("two"),
+                    Value::test_string("three"),
+                ]),
+            ),
+            (
+                "[[a, b]; [1, 2], [3, 4]]",
+                Value::test_list(vec![
+                    Value::test_record(record! {
+                        "a" => Value::test_int(1),
+                        "b" => Value::test_int(2),
+                    }),
+                    Value::test_record(record! {
+                        "a" => Value::test_int(3),
+                        "b" => Value::test_int(4),
+                    }),
+                ]),
+            ),
+            ("{}", Value::test_record(record! {})),
+            ("[]", Value::test_list(vec![])),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(from_nuon(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse_nuon_record_with_comments() {
+        let nuon = r#"{
+            a: 1  # the first field
+            "b c": [x, y],
+            # a whole line of comment
+            d: {e: green}
+        }"#;
+        assert_eq!(
+            from_nuon(nuon),
+            Ok(Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b c" => Value::test_list(vec![Value::test_string("x"), Value::test_string("y")]),
+                "d" => Value::test_record(record! {
+                    "e" => Value::test_string("green"),
+                }),
+            }))
+        );
+    }
+
+    #[test]
+    fn write_nuon() {
+        let date = DateTime::parse_from_rfc3339("2024-01-02T03:04:05+01:00").unwrap();
+        let value = Value::test_record(record! {
+            "a" => Value::test_nothing(),
+            "b c" => Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_float(2.0),
+                Value::test_float(f64::INFINITY),
+            ]),
+            "size" => Value::test_filesize(1024),
+            "time" => Value::test_duration(2_000_000_000),
+            "date" => Value::test_date(date),
+            "bytes" => Value::test_binary(vec![10, 255]),
+            "s" => Value::test_string("say \"hi\"\n"),
+            "e" => Value::test_record(record! {}),
+        });
+
+        let nuon = to_nuon(&value);
+        assert_eq!(
+            nuon,
+            r#"{a: null, "b c": [1, 2.0, inf], size: 1024b, time: 2000000000ns, date: 2024-01-02T03:04:05+01:00, bytes: 0x[0aff], s: "say \"hi\"\n", e: {}}"#
+        );
+        assert_eq!(from_nuon(&nuon), Ok(value));
+    }
+
+    #[test]
+    fn invalid_nuon() {
+        let cases = vec![
+            ("{a: 1", "expected `}`, found the end at line 1"),
+            ("[1, 2", "expected `]`, found the end at line 1"),
+            ("{a 1}", "expected `:`, found `1` at line 1"),
+            ("\"foo", "unclosed string at line 1"),
+            ("12kg", "invalid value `12kg` at line 1"),
+            (
+                "[[a]; [1, 2]]",
+                "expected 1 cells in the row of the table, found 2 at line 1",
+            ),
+            ("{a: 1}\n}", "unexpected content after the value at line 2"),
+            ("0x[aé]", "invalid binary `0x[aé]` at line 1"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(from_nuon(input), Err(expected.into()), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! a small subset of [JSON Schema](https://json-schema.org), to know more about the data
+//!
+//! only the `properties` of the objects, the `items` of the arrays and the `enum` of the values
+//! are understood, the rest of the schema being ignored.
+use nu_protocol::{
+    ast::{CellPath, PathMember},
+    Value,
+};
+
+/// give the part of *schema* describing the value at *path*, if any
+fn sub_schema(schema: &Value, path: &CellPath) -> Option<Value> {
+    let mut schema = schema.clone();
+    for member in &path.members {
+        schema = match member {
+            PathMember::String { val, .. } => {
+                schema.get_data_by_key("properties")?.get_data_by_key(val)?
+            }
+            PathMember::Int { .. } => schema.get_data_by_key("items")?,
+        };
+    }
+    Some(schema)
+}
+
+/// give the strings allowed by *schema* at *path*, i.e. its `enum`, if any
+pub(crate) fn enum_values(schema: &Value, path: &CellPath) -> Option<Vec<String>> {
+    let values = sub_schema(schema, path)?.get_data_by_key("enum")?;
+    let strings: Vec<String> = values
+        .as_list()
+        .ok()?
+        .iter()
+        .filter_map(|v| v.as_str().ok().map(String::from))
+        .collect();
+
+    if strings.is_empty() {
+        None
+    } else {
+        Some(strings)
+    }
+}
+
+/// give the value after *current* in *values*, going back to the first one after the last one or
+/// when *current* is not one of them
+pub(crate) fn next_enum_value(values: &[String], current: &str) -> String {
+    match values.iter().position(|v| v == current) {
+        Some(i) => values[(i + 1) % values.len()].clone(),
+        None => values[0].clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{ast::CellPath, record, Value};
+
+    use super::{enum_values, next_enum_value};
+    use crate::nu::cell_path::{to_path_member_vec, PM};
+
+    fn strings(values: &[&str]) -> Value {
+        Value::test_list(values.iter().map(|v| Value::test_string(*v)).collect())
+    }
+
+    #[test]
+    fn schema_enums() {
+        let schema = Value::test_record(record! {
+            "type" => Value::test_string("object"),
+            "properties" => Value::test_record(record! {
+                "status" => Value::test_record(record! {
+                    "type" => Value::test_string("string"),
+                    "enum" => strings(&["open", "closed"]),
+                }),
+                "tags" => Value::test_record(record! {
+                    "type" => Value::test_string("array"),
+                    "items" => Value::test_record(record! {
+                        "enum" => strings(&["bug", "feature"]),
+                    }),
+                }),
+                "title" => Value::test_record(record! {
+                    "type" => Value::test_string("string"),
+                }),
+            }),
+        });
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            enum_values(&schema, &path(&[PM::S("status")])),
+            Some(vec!["open".into(), "closed".into()])
+        );
+        assert_eq!(
+            enum_values(&schema, &path(&[PM::S("tags"), PM::I(3)])),
+            Some(vec!["bug".into(), "feature".into()])
+        );
+        assert_eq!(enum_values(&schema, &path(&[PM::S("title")])), None);
+        assert_eq!(enum_values(&schema, &path(&[PM::S("foo")])), None);
+        assert_eq!(enum_values(&schema, &path(&[])), None);
+    }
+
+    #[test]
+    fn next_enum_values() {
+        let values = vec!["open".to_string(), "closed".to_string()];
+
+        assert_eq!(next_enum_value(&values, "open"), "closed");
+        assert_eq!(next_enum_value(&values, "closed"), "open");
+        assert_eq!(next_enum_value(&values, "foo"), "open");
+    }
+}
@@ -32,9 +32,146 @@ impl SpecialString {
     }
 }
 
+/// the first characters of the base64 encoding of some image formats, i.e. their magic numbers
+const BASE64_IMAGE_PREFIXES: [(&str, &str); 3] =
+    [("iVBORw0KGgo", "png"), ("/9j/", "jpeg"), ("R0lGOD", "gif")];
+
+/// give the number of bytes encoded by a base64 *payload*, if it's valid base64
+fn base64_len(payload: &str) -> Option<usize> {
+    let data = payload.trim_end_matches('=');
+    if payload.len() - data.len() > 2
+        || !data
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_'))
+    {
+        return None;
+    }
+
+    Some(data.len() * 3 / 4)
+}
+
+/// describe a base64 data URI or a base64 image with its format and its size in bytes, instead of
+/// showing the whole blob
+///
+/// # Examples
+/// - `data:image/png;base64,iVBORw0KGgo...` gives `png image, 1234 bytes`
+/// - `data:text/plain;base64,Zm9v` gives `text/plain, 3 bytes`
+/// - `R0lGODlhAQABAAAAACw=` gives `gif image, 14 bytes`
+pub(crate) fn describe_data_uri(s: &str) -> Option<String> {
+    let (format, payload) = match s.strip_prefix("data:") {
+        Some(uri) => {
+            let (header, payload) = uri.split_once(',')?;
+            let mime = header.strip_suffix(";base64")?;
+            let format = match mime.strip_prefix("image/") {
+                Some(image) => format!("{} image", image.trim_end_matches("+xml")),
+                None if mime.is_empty() => "data".into(),
+                None => mime.to_string(),
+            };
+            (format, payload)
+        }
+        // NOTE: raw base64 is required to be padded, not to mistake paths like `/9j/foo` for images
+        None if s.len() % 4 != 0 => return None,
+        None => {
+            let (_, image) = BASE64_IMAGE_PREFIXES
+                .iter()
+                .find(|(prefix, _)| s.starts_with(prefix))?;
+            (format!("{} image", image), s)
+        }
+    };
+
+    Some(format!("{}, {} bytes", format, base64_len(payload)?))
+}
+
+/// put a multi-line string on a single line, the runs of whitespace becoming single spaces
+///
+/// # Examples
+/// - `"a\n  b\n"` gives `"a b"`
+/// - `"  a\tb  "` gives `"a b"`
+pub(crate) fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// convert the indentation of each line of *s* to spaces, or back to tabs, with a tab stop every
+/// *width* columns
+///
+/// only the leading whitespace of the lines is converted. when going back to tabs, the spaces that
+/// do not make a full tab are kept after the tabs.
+pub(crate) fn retab(s: &str, width: usize, to_spaces: bool) -> String {
+    s.split('\n')
+        .map(|line| {
+            let content = line.trim_start_matches([' ', '\t']);
+            // NOTE: a tab goes to the next tab stop, e.g. a space followed by a tab is a single tab
+            let columns =
+                line[..line.len() - content.len()]
+                    .chars()
+                    .fold(0, |columns, c| match c {
+                        '\t' if width > 0 => (columns / width + 1) * width,
+                        '\t' => columns,
+                        _ => columns + 1,
+                    });
+
+            let indent = if to_spaces || width == 0 {
+                " ".repeat(columns)
+            } else {
+                format!(
+                    "{}{}",
+                    "\t".repeat(columns / width),
+                    " ".repeat(columns % width)
+                )
+            };
+            format!("{indent}{content}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// tell whether some lines of *s* are indented with tabs, see [`retab`]
+pub(crate) fn is_tab_indented(s: &str) -> bool {
+    s.split('\n')
+        .any(|line| line.trim_start_matches(' ').starts_with('\t'))
+}
+
 #[cfg(test)]
 mod special_strings_tests {
-    use super::SpecialString;
+    use super::{collapse_whitespace, describe_data_uri, is_tab_indented, retab, SpecialString};
+
+    #[test]
+    fn retab_strings() {
+        let tabs = "fn main() {\n\tif true {\n\t\tfoo();\n\t}\n}";
+        let spaces = "fn main() {\n    if true {\n        foo();\n    }\n}";
+
+        assert_eq!(retab(tabs, 4, true), spaces);
+        assert_eq!(retab(spaces, 4, false), tabs);
+        assert_eq!(
+            retab(tabs, 2, true),
+            "fn main() {\n  if true {\n    foo();\n  }\n}"
+        );
+
+        // NOTE: only the indentation is converted and the spaces left over are kept
+        assert_eq!(retab("\ta\tb", 4, true), "    a\tb");
+        assert_eq!(retab("      a", 4, false), "\t  a");
+        assert_eq!(retab(" \ta", 4, true), "    a");
+        assert_eq!(retab("  \t \ta", 4, true), "        a");
+        assert_eq!(retab("    \ta", 4, false), "\t\ta");
+
+        assert!(is_tab_indented(tabs));
+        assert!(!is_tab_indented(spaces));
+        assert!(!is_tab_indented("a\tb"));
+    }
+
+    #[test]
+    fn collapse_strings() {
+        let cases = vec![
+            ("a\n b\n", "a b"),
+            ("  a\tb  ", "a b"),
+            ("one line", "one line"),
+            ("\n\n", ""),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(collapse_whitespace(input), expected, "{:?}", input);
+        }
+    }
 
     #[test]
     fn parse_strings() {
@@ -59,4 +196,33 @@ mod special_strings_tests {
             );
         }
     }
+
+    #[test]
+    fn describe_data_uris() {
+        let cases = vec![
+            (
+                "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==",
+                Some("png image, 70 bytes"),
+            ),
+            ("data:image/svg+xml;base64,PHN2Zy8+", Some("svg image, 6 bytes")),
+            ("data:text/plain;base64,Zm9v", Some("text/plain, 3 bytes")),
+            ("data:;base64,Zm9vYg==", Some("data, 4 bytes")),
+            ("R0lGODlhAQABAAAAACw=", Some("gif image, 14 bytes")),
+            ("/9j/4AAQ", Some("jpeg image, 6 bytes")),
+            // NOTE: only base64 is supported and the payload has to be valid
+            ("data:text/plain,foo", None),
+            ("data:image/png;base64,not base64!", None),
+            ("/9j/ is not an image", None),
+            ("/9j/foo", None),
+            ("foo", None),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                describe_data_uri(input).as_deref(),
+                expected,
+                "on input {input}"
+            );
+        }
+    }
 }
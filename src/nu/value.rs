@@ -1,10 +1,13 @@
 use std::collections::HashMap;
+use std::ops::Range;
 
 use nu_protocol::{
     ast::{CellPath, PathMember},
     record, Record, Span, Type, Value,
 };
 
+use super::nuon::{quote, to_nuon};
+
 pub(crate) fn mutate_value_cell(value: &Value, cell_path: &CellPath, cell: &Value) -> Value {
     if cell_path.members.is_empty() {
         return cell.clone();
@@ -68,219 +71,2289 @@ pub(crate) fn mutate_value_cell(value: &Value, cell_path: &CellPath, cell: &Valu
     }
 }
 
-pub(crate) fn is_table(value: &Value) -> bool {
+/// parse some raw text into a value of the given type
+///
+/// strings and `null`s accept any input, the other simple types are parsed from the text and
+/// containers cannot be built from raw text.
+pub(crate) fn parse_as_type(input: &str, ty: &Type) -> Result<Value, String> {
+    let invalid = || format!("could not parse `{input}` as {ty}");
+
+    match ty {
+        Type::String | Type::Nothing => Ok(Value::string(input, Span::unknown())),
+        Type::Int => input
+            .trim()
+            .parse::<i64>()
+            .map(|val| Value::int(val, Span::unknown()))
+            .map_err(|_| invalid()),
+        Type::Float => input
+            .trim()
+            .parse::<f64>()
+            .map(|val| Value::float(val, Span::unknown()))
+            .map_err(|_| invalid()),
+        Type::Bool => input
+            .trim()
+            .parse::<bool>()
+            .map(|val| Value::bool(val, Span::unknown()))
+            .map_err(|_| invalid()),
+        _ => Err(format!("cannot set a {ty} cell from raw text")),
+    }
+}
+
+/// the maximum number of nodes counted by [`count_nodes`] when loading the data
+pub(crate) const NODE_COUNT_CAP: usize = 1_000_000;
+
+/// count the nodes of a value, i.e. the value itself and all of its descendants
+///
+/// the count stops as soon as `cap` nodes have been seen, to keep huge values cheap to load.
+pub(crate) fn count_nodes(value: &Value, cap: usize) -> usize {
+    let mut count = 0;
+    let mut stack = vec![value];
+
+    while let Some(value) = stack.pop() {
+        if count >= cap {
+            return cap;
+        }
+        count += 1;
+
+        match value {
+            Value::List { vals, .. } => stack.extend(vals.iter()),
+            Value::Record { val: rec, .. } => stack.extend(rec.values()),
+            _ => {}
+        }
+    }
+
+    count
+}
+
+/// collect all the leaves of a value, i.e. the values that are not lists nor records, in the order
+/// in which they appear in the data
+pub(crate) fn collect_leaves(value: &Value) -> Vec<Value> {
     match value {
-        Value::List { vals, .. } => {
-            if vals.is_empty() {
-                return false;
-            }
+        Value::List { vals, .. } => vals.iter().flat_map(collect_leaves).collect(),
+        Value::Record { val: rec, .. } => rec.values().flat_map(collect_leaves).collect(),
+        leaf => vec![leaf.clone()],
+    }
+}
 
-            // extract the columns of each row as hashmaps for easier access
-            let mut rows = Vec::new();
-            for val in vals {
-                match val.get_type() {
-                    Type::Record(fields) => {
-                        rows.push(fields.into_iter().collect::<HashMap<String, Type>>())
-                    }
-                    _ => return false,
-                };
-            }
+/// find all the leaves of a value whose representation contains the query, ignoring the case
+///
+/// the matches are given as *cell paths* to the leaves, in the order in which they appear in the
+/// data. the value itself is never a match, even when it is a leaf.
+pub(crate) fn find_matches(value: &Value, query: &str) -> Vec<CellPath> {
+    let query = query.to_lowercase();
+    let mut matches = vec![];
 
-            // check the number of columns for each row
-            let n = rows[0].keys().len();
-            for row in rows.iter().skip(1) {
-                if row.keys().len() != n {
-                    return false;
+    let mut stack = vec![(vec![], value)];
+    while let Some((members, value)) = stack.pop() {
+        match value {
+            Value::List { vals, .. } => {
+                for (i, val) in vals.iter().enumerate().rev() {
+                    let mut members = members.clone();
+                    members.push(PathMember::Int {
+                        val: i,
+                        span: Span::unknown(),
+                        optional: false,
+                    });
+                    stack.push((members, val));
                 }
             }
-
-            // check the actual types for each column
-            // - if a row has a null, it doesn't count as "not a table"
-            // - if two rows are numeric, then the check can continue
-            for (key, val) in rows[0].iter() {
-                let mut ty = val;
-
-                for row in rows.iter().skip(1) {
-                    match row.get(key) {
-                        Some(v) => match ty {
-                            Type::Nothing => ty = v,
-                            _ => {
-                                if !matches!(v, Type::Nothing) {
-                                    if v.is_numeric() && ty.is_numeric() {
-                                    } else if (!v.is_numeric() && ty.is_numeric())
-                                        | (v.is_numeric() && !ty.is_numeric())
-                                        // NOTE: this might need a bit more work to include more
-                                        // tables
-                                        | (v != ty)
-                                    {
-                                        return false;
-                                    }
-                                }
-                            }
-                        },
-                        None => return false,
-                    }
+            Value::Record { val: rec, .. } => {
+                for (col, val) in rec.iter().rev() {
+                    let mut members = members.clone();
+                    members.push(PathMember::String {
+                        val: col.clone(),
+                        span: Span::unknown(),
+                        optional: false,
+                    });
+                    stack.push((members, val));
+                }
+            }
+            value => {
+                let repr = value.to_expanded_string(" ", &nu_protocol::Config::default());
+                if !members.is_empty() && repr.to_lowercase().contains(&query) {
+                    matches.push(CellPath { members });
                 }
             }
-
-            true
         }
-        _ => false,
     }
+
+    matches
 }
 
-/// this effectively implements the following idempotent `transpose` command written in Nushell
-/// ```nushell
-/// alias "core transpose" = transpose
-///
-/// def transpose []: [table -> any, record -> table] {
-///     let data = $in
+/// gather the matches of a search into a list of `{path: cell-path, value: any}` records
 ///
-///     if ($data | columns) == (seq 1 ($data | columns | length) | into string) {
-///         if ($data | columns | length) == 2 {
-///             return ($data | core transpose --header-row | into record)
-///         } else {
-///             return ($data | core transpose --header-row)
-///         }
-///     }
+/// > :bulb: **Note**
+/// > the matches that do not point to the data anymore are skipped
+pub(crate) fn search_results(value: &Value, matches: &[CellPath]) -> Value {
+    let results = matches
+        .iter()
+        .filter_map(|path| {
+            let cell = value.clone().follow_cell_path(&path.members, false).ok()?;
+            Some(Value::record(
+                record! {
+                    "path" => Value::cell_path(path.clone(), Span::unknown()),
+                    "value" => cell,
+                },
+                Span::unknown(),
+            ))
+        })
+        .collect();
+
+    Value::list(results, Span::unknown())
+}
+
+/// split a string that looks like environment variables or a `PATH`, if it does
 ///
-///     $data | core transpose | rename --block {
-///         ($in | str replace "column" "" | into int) + 1 | into string
-///     }
-/// }
+/// - `KEY=val;KEY2=val2`, or with one pair per line, gives a record
+/// - `/bin:/usr/bin`, or `C:\bin;D:\bin` on Windows, gives a list
 ///
-/// #[test]
-/// def transposition [] {
-///     use std assert
+/// > see the tests for detailed examples
+pub(crate) fn parse_envish(s: &str) -> Option<Value> {
+    let s = s.trim();
+
+    if s.contains('=') {
+        let mut rec = Record::new();
+        for pair in s
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            let (key, val) = pair.split_once('=')?;
+            rec.push(key.trim(), Value::string(val.trim(), Span::unknown()));
+        }
+        return if rec.is_empty() {
+            None
+        } else {
+            Some(Value::record(rec, Span::unknown()))
+        };
+    }
+
+    // NOTE: Windows paths have colons, so semicolons are tried first
+    let separator = if s.contains(';') { ';' } else { ':' };
+    let items: Vec<Value> = s
+        .split(separator)
+        .filter(|p| !p.is_empty())
+        .map(|p| Value::string(p, Span::unknown()))
+        .collect();
+    if items.len() < 2 {
+        return None;
+    }
+
+    Some(Value::list(items, Span::unknown()))
+}
+
+/// give the paths to the cells that differ between *old* and *new*
 ///
-///     assert equal (ls | transpose explore | transpose) (ls)
-///     assert equal (open Cargo.toml | transpose | transpose) (open Cargo.toml)
-/// }
-/// ```
-pub(crate) fn transpose(value: &Value) -> Value {
-    if is_table(value) {
-        let value_rows = match value {
-            Value::List { vals, .. } => vals,
-            _ => return value.clone(),
+/// lists and records are compared item by item and field by field, so that only the deepest
+/// differences are given, and an item or field that exists on one side only is a difference.
+pub(crate) fn diff_values(old: &Value, new: &Value) -> Vec<CellPath> {
+    fn diff(old: Option<&Value>, new: Option<&Value>, path: &mut Vec<PathMember>) -> Vec<CellPath> {
+        let member = |val: PathMember, path: &mut Vec<PathMember>, old, new| {
+            path.push(val);
+            let diffs = diff(old, new, path);
+            path.pop();
+            diffs
         };
 
-        let first_row = value_rows[0].as_record().unwrap();
+        match (old, new) {
+            (Some(Value::Record { val: old, .. }), Some(Value::Record { val: new, .. })) => {
+                let mut cols: Vec<&String> = old.cols.iter().collect();
+                cols.extend(new.cols.iter().filter(|c| !old.cols.contains(c)));
+                cols.into_iter()
+                    .flat_map(|col| {
+                        let val = PathMember::String {
+                            val: col.clone(),
+                            span: Span::unknown(),
+                            optional: false,
+                        };
+                        member(val, path, old.get(col), new.get(col))
+                    })
+                    .collect()
+            }
+            (Some(Value::List { vals: old, .. }), Some(Value::List { vals: new, .. })) => {
+                (0..old.len().max(new.len()))
+                    .flat_map(|i| {
+                        let val = PathMember::Int {
+                            val: i,
+                            span: Span::unknown(),
+                            optional: false,
+                        };
+                        member(val, path, old.get(i), new.get(i))
+                    })
+                    .collect()
+            }
+            (old, new) if old == new => vec![],
+            _ => vec![CellPath {
+                members: path.clone(),
+            }],
+        }
+    }
 
-        let full_columns = (1..=(first_row.len()))
-            .map(|i| format!("{i}"))
-            .collect::<Vec<String>>();
+    diff(Some(old), Some(new), &mut vec![])
+}
 
-        if first_row.cols == full_columns {
-            if first_row.len() == 2 {
-                let cols: Vec<String> = value_rows
-                    .iter()
-                    .map(|row| row.get_data_by_key("1").unwrap().as_str().unwrap().into())
-                    .collect();
+/// the name of the field of the record made by [`wrap_in_record`]
+pub(crate) const WRAPPED_FIELD: &str = "value";
 
-                let vals: Vec<Value> = value_rows
-                    .iter()
-                    .map(|row| row.get_data_by_key("2").unwrap())
-                    .collect();
+/// wrap *value* in a list, i.e. `[value]`
+pub(crate) fn wrap_in_list(value: &Value) -> Value {
+    Value::list(vec![value.clone()], Span::unknown())
+}
 
-                return Value::record(
-                    Record::from_raw_cols_vals(cols, vals, Span::unknown(), Span::unknown())
-                        .unwrap(),
-                    Span::unknown(),
-                );
-            } else {
-                let mut rows = vec![];
-                let cols: Vec<String> = value_rows
-                    .iter()
-                    .map(|v| v.get_data_by_key("1").unwrap().as_str().unwrap().into())
-                    .collect();
+/// wrap *value* in a record, i.e. `{value: value}`, see [`WRAPPED_FIELD`]
+pub(crate) fn wrap_in_record(value: &Value) -> Value {
+    Value::record(
+        Record::from_iter([(WRAPPED_FIELD.to_string(), value.clone())]),
+        Span::unknown(),
+    )
+}
 
-                for i in 0..(first_row.len() - 1) {
-                    rows.push(Value::record(
-                        Record::from_raw_cols_vals(
-                            cols.clone(),
-                            value_rows
-                                .iter()
-                                .map(|v| v.get_data_by_key(&format!("{}", i + 2)).unwrap())
-                                .collect(),
-                            Span::unknown(),
-                            Span::unknown(),
-                        )
-                        .unwrap(),
-                        Span::unknown(),
-                    ));
-                }
+/// split the string *value* into the list of its parts separated by *delimiter*, like `split row`
+///
+/// as in Nushell, consecutive delimiters give empty parts.
+pub(crate) fn split_string(value: &Value, delimiter: &str) -> Result<Value, String> {
+    match value {
+        Value::String { val, .. } => Ok(Value::list(
+            val.split(delimiter)
+                .map(|part| Value::string(part, Span::unknown()))
+                .collect(),
+            Span::unknown(),
+        )),
+        x => Err(format!("only strings can be split, found {}", x.get_type())),
+    }
+}
 
-                return Value::list(rows, Span::unknown());
-            }
+/// replace *find* with *replace* in all the strings of *value*, however deep, and give back the
+/// new value along with the number of strings that changed
+///
+/// the other scalars are left untouched, and so are the keys of the records.
+pub(crate) fn replace_in_subtree(value: &Value, find: &str, replace: &str) -> (Value, usize) {
+    match value {
+        Value::String { val, .. } if !find.is_empty() && val.contains(find) => (
+            Value::string(val.replace(find, replace), Span::unknown()),
+            1,
+        ),
+        Value::List { vals, .. } => {
+            let mut count = 0;
+            let vals = vals
+                .iter()
+                .map(|v| {
+                    let (v, n) = replace_in_subtree(v, find, replace);
+                    count += n;
+                    v
+                })
+                .collect();
+            (Value::list(vals, Span::unknown()), count)
         }
+        Value::Record { val: rec, .. } => {
+            let mut count = 0;
+            let rec = rec
+                .iter()
+                .map(|(c, v)| {
+                    let (v, n) = replace_in_subtree(v, find, replace);
+                    count += n;
+                    (c.clone(), v)
+                })
+                .collect::<Record>();
+            (Value::record(rec, Span::unknown()), count)
+        }
+        v => (v.clone(), 0),
+    }
+}
 
-        let mut rows = vec![];
-        for col in value_rows[0].columns() {
-            let mut cols = vec!["1".into()];
-            let mut vs = vec![Value::string(col, Span::unknown())];
+/// join the list of strings *value* into a single string with *separator*, the inverse of
+/// [`split_string`]
+pub(crate) fn join_list(value: &Value, separator: &str) -> Result<Value, String> {
+    let Value::List { vals, .. } = value else {
+        return Err(format!(
+            "only lists can be joined, found {}",
+            value.get_type()
+        ));
+    };
 
-            for (i, v) in value_rows.iter().enumerate() {
-                cols.push(format!("{}", i + 2));
-                vs.push(v.get_data_by_key(col).unwrap());
-            }
+    let parts = vals
+        .iter()
+        .map(|v| match v {
+            Value::String { val, .. } => Ok(val.as_str()),
+            x => Err(format!(
+                "only lists of strings can be joined, found an item of type {}",
+                x.get_type()
+            )),
+        })
+        .collect::<Result<Vec<&str>, String>>()?;
 
-            rows.push(Value::record(
-                Record::from_raw_cols_vals(cols, vs, Span::unknown(), Span::unknown()).unwrap(),
-                Span::unknown(),
-            ));
-        }
+    Ok(Value::string(parts.join(separator), Span::unknown()))
+}
 
-        return Value::list(rows, Span::unknown());
+/// compute the `min`, `max`, `sum` and `mean` of the numbers among *values*, as a record
+///
+/// the values that are not numbers are ignored. the statistics stay `int`s when all the numbers
+/// are, except for the mean, and without any number, only the sum, `0`, is not `null`.
+///
+/// the `int`s are summed exactly, the sum being a `float` only when it does not fit in an `int`.
+pub(crate) fn numeric_stats(values: &[Value]) -> Value {
+    let all_ints = values.iter().all(|v| !matches!(v, Value::Float { .. }));
+    if all_ints {
+        return int_stats(
+            &values
+                .iter()
+                .filter_map(|v| match v {
+                    Value::Int { val, .. } => Some(*val),
+                    _ => None,
+                })
+                .collect::<Vec<i64>>(),
+        );
     }
 
-    match value {
-        Value::Record { val: rec, .. } => {
-            let mut rows = vec![];
-            for (col, val) in rec.iter() {
-                rows.push(Value::record(
-                    record! {
-                        "1" => Value::string(col, Span::unknown()),
-                        "2" => val.clone(),
-                    },
-                    Span::unknown(),
-                ));
-            }
+    let floats = values
+        .iter()
+        .filter_map(|v| match v {
+            Value::Int { val, .. } => Some(*val as f64),
+            Value::Float { val, .. } => Some(*val),
+            _ => None,
+        })
+        .collect::<Vec<f64>>();
 
-            Value::list(rows, Span::unknown())
+    let number = |x: f64| Value::float(x, Span::unknown());
+    let or_null = |x: Option<Value>| x.unwrap_or_else(|| Value::nothing(Span::unknown()));
+
+    let sum = floats.iter().sum::<f64>();
+    Value::record(
+        record! {
+            "min" => or_null(floats.iter().copied().reduce(f64::min).map(number)),
+            "max" => or_null(floats.iter().copied().reduce(f64::max).map(number)),
+            "sum" => number(sum),
+            "mean" => or_null(
+                (!floats.is_empty()).then(|| Value::float(sum / floats.len() as f64, Span::unknown()))
+            ),
+        },
+        Span::unknown(),
+    )
+}
+
+/// compute the statistics of [`numeric_stats`] when all the numbers are `int`s
+fn int_stats(ints: &[i64]) -> Value {
+    let or_null = |x: Option<Value>| x.unwrap_or_else(|| Value::nothing(Span::unknown()));
+
+    // NOTE: the sum of `i64`s only overflows an `i128` with more than 2^64 of them
+    let sum = ints
+        .iter()
+        .try_fold(0i128, |sum, &x| sum.checked_add(x as i128));
+    let sum_value = match sum {
+        Some(sum) => match i64::try_from(sum) {
+            Ok(sum) => Value::int(sum, Span::unknown()),
+            Err(_) => Value::float(sum as f64, Span::unknown()),
+        },
+        None => Value::nothing(Span::unknown()),
+    };
+
+    Value::record(
+        record! {
+            "min" => or_null(ints.iter().min().map(|x| Value::int(*x, Span::unknown()))),
+            "max" => or_null(ints.iter().max().map(|x| Value::int(*x, Span::unknown()))),
+            "sum" => sum_value,
+            "mean" => or_null(sum.filter(|_| !ints.is_empty()).map(|sum| {
+                Value::float(sum as f64 / ints.len() as f64, Span::unknown())
+            })),
+        },
+        Span::unknown(),
+    )
+}
+
+/// count how many times each value appears in the column *key* of the table *value*
+///
+/// the values are given by their text, from the most frequent to the least frequent one, the ties
+/// being in the order of their first appearance. values that are not tables have no histogram.
+pub(crate) fn column_histogram(value: &Value, key: &str) -> Vec<(String, usize)> {
+    if !is_table(value) {
+        return vec![];
+    }
+    let Value::List { vals, .. } = value else {
+        return vec![];
+    };
+
+    // NOTE: the index of each text in the counts, to keep the order of their first appearance
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut counts: Vec<(String, usize)> = vec![];
+    for cell in vals.iter().filter_map(|row| row.get_data_by_key(key)) {
+        let text = match cell {
+            Value::String { val, .. } => val,
+            x => x.to_expanded_string(" ", &nu_protocol::Config::default()),
+        };
+        match indices.get(&text) {
+            Some(&i) => counts[i].1 += 1,
+            None => {
+                indices.insert(text.clone(), counts.len());
+                counts.push((text, 1));
+            }
         }
-        _ => value.clone(),
     }
+
+    // NOTE: the sort is stable, so the ties keep the order of their first appearance
+    counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+    counts
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{is_table, mutate_value_cell};
-    use crate::nu::{
-        cell_path::{to_path_member_vec, PM},
-        value::transpose,
+/// the ways to compare the items of a list when sorting it, see [`sort_list`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SortKind {
+    /// compare the numbers and the strings of numbers by their value, e.g. `2` before `10`
+    Numeric,
+    /// compare the items by their text, e.g. `10` before `2`
+    Lexical,
+}
+
+/// sort the items of the list *value* in the given [`SortKind`], or give `None` for other values
+///
+/// in a numeric sort, the items that are not numbers come last. both sorts are stable.
+pub(crate) fn sort_list(value: &Value, kind: SortKind) -> Option<Value> {
+    let Value::List { vals, .. } = value else {
+        return None;
     };
-    use nu_protocol::{ast::CellPath, record, Config, Value};
 
-    fn default_value_repr(value: &Value) -> String {
-        value.to_expanded_string(" ", &Config::default())
+    let text = |v: &Value| match v {
+        Value::String { val, .. } => val.clone(),
+        x => x.to_expanded_string(" ", &nu_protocol::Config::default()),
+    };
+    let number = |v: &Value| match v {
+        Value::Int { val, .. } => Some(*val as f64),
+        Value::Float { val, .. } => Some(*val),
+        Value::String { val, .. } => val.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+
+    let mut vals = vals.clone();
+    match kind {
+        SortKind::Numeric => vals.sort_by(|a, b| match (number(a), number(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }),
+        SortKind::Lexical => vals.sort_by_key(text),
     }
 
-    #[test]
-    fn value_mutation() {
-        let list = Value::test_list(vec![
-            Value::test_int(1),
-            Value::test_int(2),
-            Value::test_int(3),
-        ]);
-        let record = Value::test_record(record! {
-            "a" => Value::test_int(1),
-            "b" => Value::test_int(2),
-            "c" => Value::test_int(3),
-        });
+    Some(Value::list(vals, Span::unknown()))
+}
 
-        let cases = vec![
-            // simple value -> simple value
-            (
+/// rename the field *old* of the *record* to *new*, keeping its place among the other fields
+///
+/// the name of another field cannot be taken.
+pub(crate) fn rename_field(record: &Value, old: &str, new: &str) -> Result<Value, String> {
+    let Value::Record { val: rec, .. } = record else {
+        return Err(format!(
+            "only the fields of records can be renamed, found {}",
+            record.get_type()
+        ));
+    };
+    if old != new && rec.contains(new) {
+        return Err(format!("there is already a field named `{new}`"));
+    }
+
+    Ok(Value::record(
+        rec.iter()
+            .map(|(k, v)| {
+                let k = if k == old { new.to_string() } else { k.clone() };
+                (k, v.clone())
+            })
+            .collect(),
+        Span::unknown(),
+    ))
+}
+
+/// what to compare when sorting a list or a record, see [`sort_container`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SortBy {
+    /// the names of the fields of a record
+    Key,
+    /// the items of a list or the values of the fields of a record
+    Value,
+}
+
+/// sort the items of a list or the fields of a record *by* their keys or their values, in
+/// *descending* order or not, or give `None` when the value cannot be sorted this way
+///
+/// the lists have no keys and can only be sorted by value. the values are compared like in
+/// Nushell, e.g. the numbers before the strings, and the sort is stable.
+pub(crate) fn sort_container(value: &Value, by: SortBy, descending: bool) -> Option<Value> {
+    let order = |ordering: std::cmp::Ordering| {
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+    let compare =
+        |a: &Value, b: &Value| order(a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    match (value, by) {
+        (Value::List { vals, .. }, SortBy::Value) => {
+            let mut vals = vals.clone();
+            vals.sort_by(compare);
+            Some(Value::list(vals, Span::unknown()))
+        }
+        (Value::Record { val: rec, .. }, by) => {
+            let mut fields: Vec<(String, Value)> =
+                rec.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            match by {
+                SortBy::Key => fields.sort_by(|(a, _), (b, _)| order(a.cmp(b))),
+                SortBy::Value => fields.sort_by(|(_, a), (_, b)| compare(a, b)),
+            }
+            Some(Value::record(fields.into_iter().collect(), Span::unknown()))
+        }
+        _ => None,
+    }
+}
+
+/// duplicate the item or field at *member* in the *container* around it, right next to it
+///
+/// the copy of a field is named after it, e.g. `foo_copy`, then `foo_copy_2`, ..., so as not to
+/// overwrite any other field. gives nothing if *member* is not in the *container*.
+pub(crate) fn duplicate_cell(container: &Value, member: &PathMember) -> Option<Value> {
+    match (container, member) {
+        (Value::List { vals, .. }, PathMember::Int { val, .. }) if *val < vals.len() => {
+            let mut vals = vals.clone();
+            vals.insert(*val + 1, vals[*val].clone());
+            Some(Value::list(vals, Span::unknown()))
+        }
+        (Value::Record { val: rec, .. }, PathMember::String { val: key, .. }) => {
+            let cell = rec.get(key)?;
+
+            let mut name = format!("{}_copy", key);
+            let mut i = 2;
+            while rec.contains(&name) {
+                name = format!("{}_copy_{}", key, i);
+                i += 1;
+            }
+
+            let mut duplicated = Record::new();
+            for (col, val) in rec.iter() {
+                duplicated.push(col, val.clone());
+                if col == key {
+                    duplicated.push(name.clone(), cell.clone());
+                }
+            }
+            Some(Value::record(duplicated, Span::unknown()))
+        }
+        _ => None,
+    }
+}
+
+/// append a field holding *cell* to a record, named `new`, then `new_2`, ..., so as not to
+/// overwrite any other field
+///
+/// gives the new record and the name of the field, or nothing if *record* is not a record.
+pub(crate) fn insert_value_cell(record: &Value, cell: &Value) -> Option<(Value, String)> {
+    let Value::Record { val: rec, .. } = record else {
+        return None;
+    };
+
+    let mut name = "new".to_string();
+    let mut i = 2;
+    while rec.contains(&name) {
+        name = format!("new_{}", i);
+        i += 1;
+    }
+
+    let mut inserted = rec.clone();
+    inserted.push(name.clone(), cell.clone());
+    Some((Value::record(inserted, Span::unknown()), name))
+}
+
+/// append *item* at the end of a list, or nothing if *list* is not a list
+pub(crate) fn push_list_item(list: &Value, item: &Value) -> Option<Value> {
+    let Value::List { vals, .. } = list else {
+        return None;
+    };
+
+    let mut pushed = vals.clone();
+    pushed.push(item.clone());
+    Some(Value::list(pushed, Span::unknown()))
+}
+
+/// replace the list or record around *cell_path* with the cell at *cell_path*, e.g. lifting `r.a`
+/// in `{r: {a: 1}}` gives `{r: 1}`
+///
+/// gives nothing if the cell does not exist or if its parent is the root of *value*, which cannot be
+/// replaced.
+pub(crate) fn lift_cell(value: &Value, cell_path: &CellPath) -> Option<Value> {
+    if cell_path.members.len() < 2 {
+        return None;
+    }
+
+    let cell = value
+        .clone()
+        .follow_cell_path(&cell_path.members, false)
+        .ok()?;
+    let parent = CellPath {
+        members: cell_path.members[..cell_path.members.len() - 1].to_vec(),
+    };
+    Some(mutate_value_cell(value, &parent, &cell))
+}
+
+/// remove the last item of a list, or nothing if *list* is not a list or is empty
+pub(crate) fn pop_list_item(list: &Value) -> Option<Value> {
+    let Value::List { vals, .. } = list else {
+        return None;
+    };
+    if vals.is_empty() {
+        return None;
+    }
+
+    let mut popped = vals.clone();
+    popped.pop();
+    Some(Value::list(popped, Span::unknown()))
+}
+
+/// remove the items of a list in *range*, clamped to its length, or nothing if *list* is not a list
+pub(crate) fn remove_list_items(list: &Value, range: Range<usize>) -> Option<Value> {
+    let Value::List { vals, .. } = list else {
+        return None;
+    };
+
+    let end = range.end.min(vals.len());
+    let start = range.start.min(end);
+    let mut vals = vals.clone();
+    vals.drain(start..end);
+    Some(Value::list(vals, Span::unknown()))
+}
+
+/// keep only the items of a list for which *predicate* is true
+///
+/// gives nothing if *list* is not a list, and the first error of *predicate* if any.
+pub(crate) fn filter_list<E>(
+    list: &Value,
+    predicate: impl Fn(&Value) -> Result<bool, E>,
+) -> Option<Result<Value, E>> {
+    let Value::List { vals, .. } = list else {
+        return None;
+    };
+
+    let mut kept = vec![];
+    for val in vals {
+        match predicate(val) {
+            Ok(true) => kept.push(val.clone()),
+            Ok(false) => {}
+            Err(err) => return Some(Err(err)),
+        }
+    }
+    Some(Ok(Value::list(kept, Span::unknown())))
+}
+
+/// give the list of the values of a record, without its keys, or nothing if *value* is not a record
+pub(crate) fn record_values_to_list(value: &Value) -> Option<Value> {
+    match value {
+        Value::Record { val: rec, .. } => Some(Value::list(
+            rec.values().cloned().collect(),
+            Span::unknown(),
+        )),
+        _ => None,
+    }
+}
+
+/// turn a table into a record of its columns, each column being the list of its values, e.g.
+/// `[[a, b]; [1, 2] [3, 4]]` becomes `{a: [1, 3], b: [2, 4]}`
+///
+/// the values that are not tables are given back as is.
+pub(crate) fn records_to_columns(value: &Value) -> Value {
+    if !is_table(value) {
+        return value.clone();
+    }
+    let Value::List { vals, .. } = value else {
+        return value.clone();
+    };
+
+    let mut columns = Record::new();
+    for (col, _) in infer_columns(value) {
+        let cells = vals
+            .iter()
+            .map(|row| {
+                row.get_data_by_key(&col)
+                    .unwrap_or_else(|| Value::nothing(Span::unknown()))
+            })
+            .collect();
+        columns.push(col, Value::list(cells, Span::unknown()));
+    }
+
+    Value::record(columns, Span::unknown())
+}
+
+/// summarize a list as a record of its number of items and its first and last items, e.g.
+/// `[1, 2, 3]` becomes `{count: 3, first: 1, last: 3}`
+///
+/// the first and last items of an empty list are `null` and the values that are not lists are
+/// given back as is.
+pub(crate) fn summarize_list(value: &Value) -> Value {
+    let Value::List { vals, .. } = value else {
+        return value.clone();
+    };
+
+    let nothing = || Value::nothing(Span::unknown());
+    Value::record(
+        record! {
+            "count" => Value::int(vals.len() as i64, Span::unknown()),
+            "first" => vals.first().cloned().unwrap_or_else(nothing),
+            "last" => vals.last().cloned().unwrap_or_else(nothing),
+        },
+        Span::unknown(),
+    )
+}
+
+/// give the number of seconds between the Unix epoch and a date, or nothing if *value* is not a date
+pub(crate) fn date_to_epoch(value: &Value) -> Option<i64> {
+    match value {
+        Value::Date { val, .. } => Some(val.timestamp()),
+        _ => None,
+    }
+}
+
+/// format an int or a float with *separator* between the groups of three digits of its integral
+/// part, e.g. `1,234,567.5`, or give nothing if *value* is not a number
+pub(crate) fn format_with_separators(value: &Value, separator: &str) -> Option<String> {
+    let repr = match value {
+        Value::Int { val, .. } => val.to_string(),
+        Value::Float { val, .. } if val.is_finite() => val.to_string(),
+        Value::Float { val, .. } => return Some(val.to_string()),
+        _ => return None,
+    };
+
+    let (sign, unsigned) = match repr.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", repr.as_str()),
+    };
+    let (integral, fractional) = match unsigned.find('.') {
+        Some(dot) => unsigned.split_at(dot),
+        None => (unsigned, ""),
+    };
+
+    Some(format!(
+        "{sign}{}{fractional}",
+        group_digits(integral, separator)
+    ))
+}
+
+/// put *separator* between the groups of three *digits*, from the right, e.g. `12,345`
+pub(crate) fn group_digits(digits: &str, separator: &str) -> String {
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// represent a value as its pretty-printed Rust debug string, e.g. to share its structure
+pub(crate) fn repr_debug(value: &Value) -> String {
+    format!("{:#?}", value)
+}
+
+/// represent a value as the Rust code building it in tests, e.g. `Value::test_int(1)`
+///
+/// lists and records are written over multiple lines, with the `record!` macro for the latter, and
+/// the values without a simple `Value::test_*` constructor are given as their string.
+pub(crate) fn value_to_rust_literal(value: &Value) -> String {
+    fn literal(value: &Value, indent: usize) -> String {
+        let inner = " ".repeat(indent + 4);
+        let outer = " ".repeat(indent);
+
+        match value {
+            Value::Nothing { .. } => "Value::test_nothing()".into(),
+            Value::Bool { val, .. } => format!("Value::test_bool({val})"),
+            Value::Int { val, .. } => format!("Value::test_int({val})"),
+            Value::Float { val, .. } => format!("Value::test_float({val:?})"),
+            Value::Filesize { val, .. } => format!("Value::test_filesize({val})"),
+            Value::Duration { val, .. } => format!("Value::test_duration({val})"),
+            Value::Date { val, .. } => {
+                format!("Value::test_date({:?}.parse().unwrap())", val.to_rfc3339())
+            }
+            Value::String { val, .. } => format!("Value::test_string({val:?})"),
+            Value::Glob { val, .. } => format!("Value::test_glob({val:?})"),
+            Value::Binary { val, .. } => format!("Value::test_binary(vec!{val:?})"),
+            Value::List { vals, .. } if vals.is_empty() => "Value::test_list(vec![])".into(),
+            Value::List { vals, .. } => format!(
+                "Value::test_list(vec![\n{}{outer}])",
+                vals.iter()
+                    .map(|v| format!("{inner}{},\n", literal(v, indent + 4)))
+                    .collect::<Vec<String>>()
+                    .concat()
+            ),
+            Value::Record { val: rec, .. } if rec.is_empty() => {
+                "Value::test_record(Record::new())".into()
+            }
+            Value::Record { val: rec, .. } => format!(
+                "Value::test_record(record! {{\n{}{outer}}})",
+                rec.iter()
+                    .map(|(k, v)| format!("{inner}{k:?} => {},\n", literal(v, indent + 4)))
+                    .collect::<Vec<String>>()
+                    .concat()
+            ),
+            x => format!(
+                "Value::test_string({:?})",
+                x.to_expanded_string(" ", &nu_protocol::Config::default())
+            ),
+        }
+    }
+
+    literal(value, 0)
+}
+
+/// give the name of the type of a value, as Nushell shows it, e.g. `list<string>`
+pub(crate) fn repr_type(value: &Value) -> String {
+    value.get_type().to_string()
+}
+
+/// give a short summary of the size of a container, e.g. `list of 3 strings` or
+/// `record of 4 fields`
+///
+/// the items of a list are named after their type when they all have the same one, and the
+/// values that are not containers are only described by their type.
+pub(crate) fn describe_container(value: &Value) -> String {
+    fn short_type(value: &Value) -> String {
+        match value {
+            Value::List { .. } => "list".into(),
+            Value::Record { .. } => "record".into(),
+            _ => value.get_type().to_string(),
+        }
+    }
+
+    fn count(n: usize, name: &str) -> String {
+        if n == 1 {
+            format!("{n} {name}")
+        } else {
+            format!("{n} {name}s")
+        }
+    }
+
+    match value {
+        Value::List { vals, .. } if vals.is_empty() => "empty list".into(),
+        Value::List { vals, .. } => {
+            let ty = short_type(&vals[0]);
+            if vals.iter().all(|v| short_type(v) == ty) {
+                format!("list of {}", count(vals.len(), &ty))
+            } else {
+                format!("list of {}", count(vals.len(), "item"))
+            }
+        }
+        Value::Record { val, .. } if val.is_empty() => "empty record".into(),
+        Value::Record { val, .. } => format!("record of {}", count(val.len(), "field")),
+        _ => short_type(value),
+    }
+}
+
+/// convert a value to JSON, the values that have no JSON equivalent being kept as strings
+pub(crate) fn to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Nothing { .. } => serde_json::Value::Null,
+        Value::Bool { val, .. } => serde_json::Value::Bool(*val),
+        Value::Int { val, .. } => serde_json::Value::from(*val),
+        Value::Float { val, .. } => serde_json::Value::from(*val),
+        Value::String { val, .. } => serde_json::Value::String(val.clone()),
+        Value::List { vals, .. } => serde_json::Value::Array(vals.iter().map(to_json).collect()),
+        Value::Record { val: rec, .. } => {
+            serde_json::Value::Object(rec.iter().map(|(c, v)| (c.clone(), to_json(v))).collect())
+        }
+        x => serde_json::Value::String(x.to_expanded_string(" ", &nu_protocol::Config::default())),
+    }
+}
+
+/// wrap *value* into records, one per member of *cell_path*, e.g. `{r: {a: 9}}` for `r.a` and `9`
+///
+/// the result can be `merge`d deeply into the original data to override a single cell. gives
+/// nothing when *cell_path* goes through a list, because `merge` replaces the lists as a whole.
+pub(crate) fn path_to_nested_record(cell_path: &CellPath, value: &Value) -> Option<Value> {
+    cell_path
+        .members
+        .iter()
+        .rev()
+        .try_fold(value.clone(), |acc, member| match member {
+            PathMember::String { val, .. } => Some(Value::record(
+                Record::from_iter([(val.clone(), acc)]),
+                Span::unknown(),
+            )),
+            PathMember::Int { .. } => None,
+        })
+}
+
+/// represent *cell_path* as the source of a closure getting it from its input, e.g. `{|| get r.a}`
+///
+/// the keys that would not be parsed as is, e.g. with spaces or only digits, are quoted.
+pub(crate) fn path_to_get_closure(cell_path: &CellPath) -> String {
+    if cell_path.members.is_empty() {
+        return "{|| $in}".into();
+    }
+
+    format!("{{|| get {}}}", repr_nu_path(cell_path))
+}
+
+/// represent the setting of the cell at *cell_path* to *value* as a Nushell pipeline, e.g.
+/// `$in | update r.a { 9 }`, to apply the same edit elsewhere
+///
+/// the value is written as NUON and the root, which cannot be updated, is replaced as a whole, e.g.
+/// `$in | do { 9 }`.
+pub(crate) fn to_update_pipeline(cell_path: &CellPath, value: &Value) -> String {
+    let block = format!("{{ {} }}", to_nuon(value));
+    if cell_path.members.is_empty() {
+        return format!("$in | do {}", block);
+    }
+
+    format!("$in | {}", repr_update(cell_path, &block))
+}
+
+/// represent the `update` command setting the cell at *cell_path* to the already written *value*,
+/// e.g. `update r.a 9`
+fn repr_update(cell_path: &CellPath, value: &str) -> String {
+    format!("update {} {}", repr_nu_path(cell_path), value)
+}
+
+/// represent a filter keeping the rows of a table whose *column* is *value* as a Nushell clause,
+/// e.g. `where name == "foo"`, with the value as NUON, see [`to_nuon`]
+pub(crate) fn to_where_clause(column: &str, value: &Value) -> String {
+    format!("where {} == {}", repr_nu_key(column), to_nuon(value))
+}
+
+/// represent *cell_path* as it would be written in Nushell, e.g. `r.a` or `l.1`, without a leading
+/// `$`
+///
+/// the keys that would not be parsed as is, e.g. with spaces or only digits, are quoted.
+fn repr_nu_path(cell_path: &CellPath) -> String {
+    let members: Vec<String> = cell_path
+        .members
+        .iter()
+        .map(|member| match member {
+            PathMember::Int { val, .. } => val.to_string(),
+            PathMember::String { val, .. } => repr_nu_key(val),
+        })
+        .collect();
+
+    members.join(".")
+}
+
+/// represent the *key* of a record as it would be written in a Nushell cell path
+///
+/// the keys that would not be parsed as is, e.g. with spaces or only digits, are quoted, e.g.
+/// `"my key"`, the others are left bare.
+pub(crate) fn repr_nu_key(key: &str) -> String {
+    let is_bare = !key.is_empty()
+        && !key.chars().all(|c| c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    if is_bare {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// write a Nushell script reproducing the *edited* cells of *value*, e.g. to share a fix of the data
+///
+/// the script is a pipeline of `update <path> <value>` commands, with the values as NUON, see
+/// [`to_nuon`]. the cells that are not in the data anymore are skipped.
+pub(crate) fn edits_to_script(value: &Value, edited: &[CellPath]) -> String {
+    edited
+        .iter()
+        .filter(|path| !path.members.is_empty())
+        .filter_map(|path| {
+            let cell = value.clone().follow_cell_path(&path.members, false).ok()?;
+            Some(repr_update(path, &to_nuon(&cell)))
+        })
+        .collect::<Vec<String>>()
+        .join(" |\n")
+}
+
+/// represent the *value* at *cell_path* as a NUON record with both, e.g. `{path: $.r.a, value: 1}`,
+/// to keep track of where a value comes from when sharing it
+pub(crate) fn value_with_path(cell_path: &CellPath, value: &Value) -> String {
+    let path = if cell_path.members.is_empty() {
+        "$".into()
+    } else {
+        format!("$.{}", repr_nu_path(cell_path))
+    };
+    format!("{{path: {}, value: {}}}", path, to_nuon(value))
+}
+
+/// tell whether a key can be written as is in JSONPath and JMESPath, i.e. it's an identifier
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// represent *cell_path* as a JSONPath expression, e.g. `$.l[1].a`, for tools outside of Nushell
+///
+/// the keys that are not identifiers are written with brackets, e.g. `$['a b']`.
+pub(crate) fn cell_path_to_jsonpath(cell_path: &CellPath) -> String {
+    let mut path = String::from("$");
+    for member in &cell_path.members {
+        match member {
+            PathMember::Int { val, .. } => path.push_str(&format!("[{}]", val)),
+            PathMember::String { val, .. } if is_identifier(val) => {
+                path.push_str(&format!(".{}", val))
+            }
+            PathMember::String { val, .. } => path.push_str(&format!(
+                "['{}']",
+                val.replace('\\', "\\\\").replace('\'', "\\'")
+            )),
+        }
+    }
+    path
+}
+
+/// parse a cell path written in Nushell, JSONPath or JMESPath, e.g. `$.r.a`, `r."a b"`, `$.l[1]`
+/// or `$['a b']`, the reverse of [`cell_path_to_jsonpath`] and [`cell_path_to_jmespath`]
+///
+/// the members written as numbers are indices, which might be keys of records in the data, see
+/// [`reconcile_cell_path`].
+pub(crate) fn parse_cell_path(input: &str) -> Option<CellPath> {
+    fn read_quoted(
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+        quote: char,
+    ) -> Option<String> {
+        let mut val = String::new();
+        loop {
+            match chars.next()? {
+                '\\' => val.push(chars.next()?),
+                c if c == quote => return Some(val),
+                c => val.push(c),
+            }
+        }
+    }
+
+    fn member(val: String) -> PathMember {
+        match val.parse::<usize>() {
+            Ok(val) => PathMember::Int {
+                val,
+                span: Span::unknown(),
+                optional: false,
+            },
+            Err(_) => PathMember::String {
+                val,
+                span: Span::unknown(),
+                optional: false,
+            },
+        }
+    }
+
+    let input = input.trim();
+    let input = input.strip_prefix('$').unwrap_or(input);
+    if input.is_empty() || input == "@" {
+        return Some(CellPath { members: vec![] });
+    }
+
+    let mut chars = input.chars().peekable();
+    let mut members = vec![];
+    // NOTE: the first member may or may not come after a dot, e.g. `$.a` and `a`
+    if chars.peek() == Some(&'.') {
+        chars.next();
+    }
+    loop {
+        match chars.next()? {
+            '[' => match chars.next()? {
+                q @ ('"' | '\'') => {
+                    let val = read_quoted(&mut chars, q)?;
+                    if chars.next()? != ']' {
+                        return None;
+                    }
+                    members.push(PathMember::String {
+                        val,
+                        span: Span::unknown(),
+                        optional: false,
+                    });
+                }
+                c => {
+                    let mut index = c.to_string();
+                    loop {
+                        match chars.next()? {
+                            ']' => break,
+                            c => index.push(c),
+                        }
+                    }
+                    members.push(PathMember::Int {
+                        val: index.trim().parse().ok()?,
+                        span: Span::unknown(),
+                        optional: false,
+                    });
+                }
+            },
+            q @ ('"' | '\'') => members.push(PathMember::String {
+                val: read_quoted(&mut chars, q)?,
+                span: Span::unknown(),
+                optional: false,
+            }),
+            c => {
+                let mut val = c.to_string();
+                while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                    val.push(c);
+                }
+                members.push(member(val));
+            }
+        }
+
+        match chars.peek() {
+            None => return Some(CellPath { members }),
+            Some('.') => {
+                chars.next();
+            }
+            Some('[') => {}
+            Some(_) => return None,
+        }
+    }
+}
+
+/// match a parsed *cell_path* with the containers of *value*, or give nothing if it does not lead
+/// to a cell
+///
+/// because the syntax does not tell them apart, an index of a record is used as its key, e.g. `1`
+/// in `{1: a}`, and a key that is a number in a list is used as its index.
+pub(crate) fn reconcile_cell_path(value: &Value, cell_path: &CellPath) -> Option<CellPath> {
+    let mut current = value.clone();
+    let mut members = vec![];
+    for member in &cell_path.members {
+        let member = match (&current, member) {
+            (Value::List { vals, .. }, PathMember::Int { val, .. }) if *val < vals.len() => {
+                member.clone()
+            }
+            (Value::List { vals, .. }, PathMember::String { val, .. }) => {
+                match val.parse::<usize>() {
+                    Ok(val) if val < vals.len() => PathMember::Int {
+                        val,
+                        span: Span::unknown(),
+                        optional: false,
+                    },
+                    _ => return None,
+                }
+            }
+            (Value::Record { val: rec, .. }, PathMember::String { val, .. })
+                if rec.get(val).is_some() =>
+            {
+                member.clone()
+            }
+            (Value::Record { val: rec, .. }, PathMember::Int { val, .. })
+                if rec.get(val.to_string()).is_some() =>
+            {
+                PathMember::String {
+                    val: val.to_string(),
+                    span: Span::unknown(),
+                    optional: false,
+                }
+            }
+            _ => return None,
+        };
+        current = current.follow_cell_path(&[member.clone()], false).ok()?;
+        members.push(member);
+    }
+
+    Some(CellPath { members })
+}
+
+/// represent *cell_path* as a JMESPath expression, e.g. `l[1].a`, for tools outside of Nushell
+///
+/// the keys that are not identifiers are quoted, e.g. `"a b"`, and the empty path is `@`.
+pub(crate) fn cell_path_to_jmespath(cell_path: &CellPath) -> String {
+    if cell_path.members.is_empty() {
+        return "@".into();
+    }
+
+    let mut path = String::new();
+    for member in &cell_path.members {
+        match member {
+            PathMember::Int { val, .. } => path.push_str(&format!("[{}]", val)),
+            PathMember::String { val, .. } => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                if is_identifier(val) {
+                    path.push_str(val);
+                } else {
+                    path.push_str(&format!(
+                        "\"{}\"",
+                        val.replace('\\', "\\\\").replace('"', "\\\"")
+                    ));
+                }
+            }
+        }
+    }
+    path
+}
+
+/// reverse the order of the items of a list or of the fields of a record, any other value being
+/// given back as is
+pub(crate) fn reverse_container(value: Value) -> Value {
+    match value {
+        Value::List { mut vals, .. } => {
+            vals.reverse();
+            Value::list(vals, Span::unknown())
+        }
+        Value::Record { val: rec, .. } => Value::record(
+            rec.iter()
+                .rev()
+                .map(|(col, val)| (col.clone(), val.clone()))
+                .collect(),
+            Span::unknown(),
+        ),
+        value => value,
+    }
+}
+
+/// give the path leading from the cell at *from* to the cell at *to*, if *to* is inside *from*
+///
+/// the spans and optionality of the members are ignored, only their values are compared.
+pub(crate) fn relative_path(from: &CellPath, to: &CellPath) -> Option<CellPath> {
+    if from.members.len() > to.members.len() {
+        return None;
+    }
+
+    let same = |a: &PathMember, b: &PathMember| match (a, b) {
+        (PathMember::Int { val: a, .. }, PathMember::Int { val: b, .. }) => a == b,
+        (PathMember::String { val: a, .. }, PathMember::String { val: b, .. }) => a == b,
+        _ => false,
+    };
+    if !from
+        .members
+        .iter()
+        .zip(&to.members)
+        .all(|(a, b)| same(a, b))
+    {
+        return None;
+    }
+
+    Some(CellPath {
+        members: to.members[from.members.len()..].to_vec(),
+    })
+}
+
+/// represent a value as pretty-printed JSON, e.g. to share it outside of Nushell
+pub(crate) fn repr_json(value: &Value) -> String {
+    serde_json::to_string_pretty(&to_json(value)).unwrap_or_default()
+}
+
+/// give the approximate size of a value in bytes, as the length of its compact JSON, see
+/// [`repr_json`]
+///
+/// this is not the memory taken by the value in Nushell but it grows with it, e.g. to find the
+/// biggest parts of the data.
+pub(crate) fn approx_size(value: &Value) -> usize {
+    serde_json::to_string(&to_json(value))
+        .map(|json| json.len())
+        .unwrap_or_default()
+}
+
+/// represent a value as YAML, through the same conversion as [`repr_json`], only available with
+/// the `yaml` feature
+#[cfg(feature = "yaml")]
+pub(crate) fn to_yaml(value: &Value) -> Result<String, String> {
+    serde_yaml::to_string(&to_json(value)).map_err(|err| format!("could not write YAML: {err}"))
+}
+
+/// represent a value as YAML, through the same conversion as [`repr_json`], only available with
+/// the `yaml` feature
+#[cfg(not(feature = "yaml"))]
+pub(crate) fn to_yaml(_value: &Value) -> Result<String, String> {
+    Err("YAML is not available, please build `explore` with the `yaml` feature".into())
+}
+
+/// flatten a list or a record into a record of its leaves, the keys being the dotted paths to them,
+/// e.g. `{r: {a: 1}, l: [x]}` becomes `{"r.a": 1, "l.0": "x"}`
+///
+/// the keys that are empty or have dots or quotes in them are quoted, so that `{"a.b": 1, a: {b: 2}}`
+/// gives two keys, `"a.b"` and `a.b`. the nested empty lists and records are kept as leaves, an empty
+/// list or record gives an empty record and the values that are not lists nor records are given
+/// back as is.
+pub(crate) fn flatten_to_dotted(value: &Value) -> Value {
+    fn push_leaves(value: &Value, prefix: Option<String>, record: &mut Record) {
+        let children: Vec<(String, &Value)> = match value {
+            Value::List { vals, .. } => vals
+                .iter()
+                .enumerate()
+                .map(|(i, val)| (i.to_string(), val))
+                .collect(),
+            Value::Record { val: rec, .. } => rec
+                .iter()
+                .map(|(col, val)| {
+                    if col.is_empty() || col.contains(['.', '"']) {
+                        (quote(col), val)
+                    } else {
+                        (col.clone(), val)
+                    }
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        if children.is_empty() {
+            record.push(prefix.unwrap_or_default(), value.clone());
+            return;
+        }
+        for (key, child) in children {
+            let key = match &prefix {
+                Some(prefix) => format!("{prefix}.{key}"),
+                None => key,
+            };
+            push_leaves(child, Some(key), record);
+        }
+    }
+
+    if !matches!(value, Value::List { .. } | Value::Record { .. }) {
+        return value.clone();
+    }
+
+    let mut record = Record::new();
+    if !value.is_empty() {
+        push_leaves(value, None, &mut record);
+    }
+    Value::record(record, Span::unknown())
+}
+
+/// the number of unchanged lines kept around the changed ones in the hunks of [`to_patch`]
+const PATCH_CONTEXT: usize = 3;
+
+/// give a unified diff from the pretty JSON of *original* to the one of *edited*, see
+/// [`repr_json`], or nothing when they are the same, see [`diff_values`]
+///
+/// the patch starts with `--- original` and `+++ edited` headers and has one hunk per group of
+/// changed lines, with [`PATCH_CONTEXT`] lines around them.
+/// NOTE: the lines common to both ends are skipped before matching the lines in between, so that a
+/// few edits in a large value stay cheap
+pub(crate) fn to_patch(original: &Value, edited: &Value) -> Option<String> {
+    if diff_values(original, edited).is_empty() {
+        return None;
+    }
+
+    let (old, new) = (repr_json(original), repr_json(edited));
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut lines: Vec<(char, &str)> = old[..prefix].iter().map(|l| (' ', *l)).collect();
+    lines.extend(diff_lines(a, b));
+    lines.extend(old[old.len() - suffix..].iter().map(|l| (' ', *l)));
+
+    // NOTE: the changes closer than twice the context share the same hunk
+    let changes: Vec<usize> = (0..lines.len()).filter(|&k| lines[k].0 != ' ').collect();
+    let mut groups: Vec<(usize, usize)> = vec![];
+    for &k in &changes {
+        match groups.last_mut() {
+            Some((_, last)) if k - *last <= 2 * PATCH_CONTEXT => *last = k,
+            _ => groups.push((k, k)),
+        }
+    }
+
+    let mut patch = String::from("--- original\n+++ edited\n");
+    for (first, last) in groups {
+        let start = first.saturating_sub(PATCH_CONTEXT);
+        let end = (last + PATCH_CONTEXT + 1).min(lines.len());
+
+        let count =
+            |range: &[(char, &str)], skip: char| range.iter().filter(|l| l.0 != skip).count();
+        let range = |before: usize, len: usize| {
+            if len == 0 {
+                format!("{before},0")
+            } else {
+                format!("{},{len}", before + 1)
+            }
+        };
+        patch.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(count(&lines[..start], '+'), count(&lines[start..end], '+')),
+            range(count(&lines[..start], '-'), count(&lines[start..end], '-')),
+        ));
+        for (tag, line) in &lines[start..end] {
+            patch.push_str(&format!("{tag}{line}\n"));
+        }
+    }
+
+    Some(patch)
+}
+
+/// match the lines of *a* and *b* with the algorithm of Myers, giving each line tagged with ` `
+/// when it's in both, `-` when it's only in *a* and `+` when it's only in *b*
+///
+/// the time and the memory grow with the number of changed lines *d* as `(a + b) * d` and `d * d`,
+/// instead of `a * b` for a full table of the longest common subsequences.
+fn diff_lines<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<(char, &'a str)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    // NOTE: the furthest `x` reached on each diagonal `k = x - y` after each number of changes,
+    // only the diagonals `-d..=d` being kept for the step `d`
+    let mut trace: Vec<Vec<isize>> = vec![];
+    let mut v = vec![0; 2 * (n + m) as usize + 3];
+    let offset = n + m + 1;
+    let at = |k: isize| (k + offset) as usize;
+
+    'search: for d in 0..=(n + m) {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+                v[at(k + 1)]
+            } else {
+                v[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                (x, y) = (x + 1, y + 1);
+            }
+            v[at(k)] = x;
+            if x >= n && y >= m {
+                trace.push(v[at(-d)..=at(d)].to_vec());
+                break 'search;
+            }
+        }
+        trace.push(v[at(-d)..=at(d)].to_vec());
+    }
+
+    let mut lines = vec![];
+    let (mut x, mut y) = (n, m);
+    for d in (1..trace.len() as isize).rev() {
+        let previous = &trace[d as usize - 1];
+        let before = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let k = if k == -d || (k != d && before(k - 1) < before(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let (px, py) = (before(k), before(k) - k);
+        while x > px && y > py {
+            lines.push((' ', a[x as usize - 1]));
+            (x, y) = (x - 1, y - 1);
+        }
+        if x == px {
+            lines.push(('+', b[y as usize - 1]));
+        } else {
+            lines.push(('-', a[x as usize - 1]));
+        }
+        (x, y) = (px, py);
+    }
+    while x > 0 && y > 0 {
+        lines.push((' ', a[x as usize - 1]));
+        (x, y) = (x - 1, y - 1);
+    }
+
+    lines.reverse();
+    lines
+}
+
+/// represent a list of records or a single record as CSV, with a header of the columns and one line
+/// per record
+///
+/// the fields with a comma, a double quote or a line break are quoted as in RFC 4180, the nested
+/// lists and records are written as JSON and the missing cells are left empty. the lines end with
+/// `\n`, not `\r\n`, to be pasted as is in a terminal.
+pub(crate) fn to_csv(value: &Value) -> Result<String, String> {
+    fn field(value: Option<&Value>) -> String {
+        let text = match value.map(to_json) {
+            None | Some(serde_json::Value::Null) => String::new(),
+            Some(serde_json::Value::String(s)) => s,
+            Some(x) => x.to_string(),
+        };
+        if text.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text
+        }
+    }
+
+    let records: Vec<&Record> = match value {
+        Value::Record { val: rec, .. } => vec![rec],
+        Value::List { vals, .. }
+            if !vals.is_empty() && vals.iter().all(|v| v.as_record().is_ok()) =>
+        {
+            vals.iter().map(|v| v.as_record().unwrap()).collect()
+        }
+        _ => return Err("only records and lists of records can be written as CSV".into()),
+    };
+
+    let mut cols: Vec<String> = vec![];
+    for rec in &records {
+        for col in rec.columns() {
+            if !cols.contains(col) {
+                cols.push(col.clone());
+            }
+        }
+    }
+
+    let mut lines = vec![cols
+        .iter()
+        .map(|c| field(Some(&Value::string(c, Span::unknown()))))
+        .collect::<Vec<String>>()
+        .join(",")];
+    for rec in records {
+        lines.push(
+            cols.iter()
+                .map(|c| field(rec.get(c)))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// compute the SHA-256 digest of a value as lowercase hex, e.g. to check a secret or a config
+/// value against a known one
+///
+/// strings are hashed as they are, any other value as its compact JSON, see [`repr_json`].
+#[cfg(feature = "hash")]
+pub(crate) fn hash_value(value: &Value) -> String {
+    use sha2::{Digest, Sha256};
+    use std::fmt::Write;
+
+    let text = match value {
+        Value::String { val, .. } => val.clone(),
+        _ => serde_json::to_string(&to_json(value)).unwrap_or_default(),
+    };
+
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// the same as [`hash_value`], only available with the `hash` feature
+#[cfg(feature = "hash")]
+pub(crate) fn try_hash_value(value: &Value) -> Result<String, String> {
+    Ok(hash_value(value))
+}
+
+/// the same as [`hash_value`], only available with the `hash` feature
+#[cfg(not(feature = "hash"))]
+pub(crate) fn try_hash_value(_value: &Value) -> Result<String, String> {
+    Err("hashing is not available, please build `explore` with the `hash` feature".into())
+}
+
+/// represent a list or a record as a GitHub-flavored Markdown table, e.g. to paste it in an issue
+///
+/// - a list of records gives one row per record and one column per field
+/// - a record gives one row per field, with its key and value
+/// - any other list gives one row per item, e.g. none for an empty list
+///
+/// the lists and records inside the cells are abbreviated.
+pub(crate) fn to_markdown_table(value: &Value) -> String {
+    fn cell(value: Option<&Value>) -> String {
+        value
+            .map(|v| v.to_abbreviated_string(&nu_protocol::Config::default()))
+            .unwrap_or_default()
+            .replace('|', "\\|")
+            .replace('\n', "<br>")
+    }
+
+    let (header, rows): (Vec<String>, Vec<Vec<String>>) = match value {
+        Value::Record { val: rec, .. } => (
+            vec!["key".into(), "value".into()],
+            rec.iter()
+                .map(|(c, v)| {
+                    vec![
+                        cell(Some(&Value::string(c, Span::unknown()))),
+                        cell(Some(v)),
+                    ]
+                })
+                .collect(),
+        ),
+        // NOTE: an empty list has no columns and is an empty list of items
+        Value::List { vals, .. }
+            if !vals.is_empty() && vals.iter().all(|v| v.as_record().is_ok()) =>
+        {
+            let mut cols: Vec<String> = vec![];
+            for val in vals {
+                for col in val.as_record().unwrap().columns() {
+                    if !cols.contains(col) {
+                        cols.push(col.clone());
+                    }
+                }
+            }
+
+            let rows = vals
+                .iter()
+                .map(|v| {
+                    let rec = v.as_record().unwrap();
+                    cols.iter().map(|c| cell(rec.get(c))).collect()
+                })
+                .collect();
+            (cols, rows)
+        }
+        Value::List { vals, .. } => (
+            vec!["item".into()],
+            vals.iter().map(|v| vec![cell(Some(v))]).collect(),
+        ),
+        x => (vec!["value".into()], vec![vec![cell(Some(x))]]),
+    };
+
+    let line = |cells: &[String]| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![
+        line(&header),
+        line(&header.iter().map(|_| "---".to_string()).collect::<Vec<_>>()),
+    ];
+    lines.extend(rows.iter().map(|r| line(r)));
+    lines.join("\n")
+}
+
+/// the name of the column giving the key of the table each row comes from in [`flatten_one_level`]
+pub(crate) const SOURCE_FIELD: &str = "source";
+
+/// flatten a record of tables into a single table, with the key of each table in a
+/// [`SOURCE_FIELD`] column
+///
+/// the tables need to have the same columns for the result to be a table too, otherwise the value
+/// is given back untouched, as are the values that are not records of lists of records.
+pub(crate) fn flatten_one_level(value: &Value) -> Value {
+    let rec = match value {
+        Value::Record { val, .. } => val,
+        _ => return value.clone(),
+    };
+
+    let mut rows = vec![];
+    for (key, table) in rec.iter() {
+        let vals = match table {
+            Value::List { vals, .. } => vals,
+            _ => return value.clone(),
+        };
+        for row in vals {
+            let row = match row {
+                Value::Record { val, .. } => val,
+                _ => return value.clone(),
+            };
+
+            let mut flat = Record::new();
+            flat.push(SOURCE_FIELD, Value::string(key, Span::unknown()));
+            for (col, val) in row.iter() {
+                flat.push(col, val.clone());
+            }
+            rows.push(Value::record(flat, Span::unknown()));
+        }
+    }
+
+    let flat = Value::list(rows, Span::unknown());
+    if is_table(&flat) {
+        flat
+    } else {
+        value.clone()
+    }
+}
+
+/// infer the schema of a value, i.e. the same structure with the names of the types in place of
+/// the values, e.g. to understand the shape of an API response
+///
+/// - a record gives a record with the schema of each field
+/// - a table gives a list with the schema of its rows, the cells of each column being typed like
+///   in [`infer_columns`] and the nested lists and records coming from the first non-empty cell
+/// - any other value gives the name of its type, e.g. `list<string>` or `int`
+pub(crate) fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Record { val: rec, .. } => {
+            let mut schema = Record::new();
+            for (col, val) in rec.iter() {
+                schema.push(col, infer_schema(val));
+            }
+            Value::record(schema, Span::unknown())
+        }
+        Value::List { vals, .. } if is_table(value) => {
+            let mut schema = Record::new();
+            for (col, ty) in infer_columns(value) {
+                let nested = vals
+                    .iter()
+                    .filter_map(|row| row.get_data_by_key(&col))
+                    .find(|cell| matches!(cell, Value::List { .. } | Value::Record { .. }));
+                let cell = match nested {
+                    Some(cell) if !ty.is_numeric() => infer_schema(&cell),
+                    _ => Value::string(ty.to_string(), Span::unknown()),
+                };
+                schema.push(col, cell);
+            }
+            Value::list(
+                vec![Value::record(schema, Span::unknown())],
+                Span::unknown(),
+            )
+        }
+        _ => Value::string(value.get_type().to_string(), Span::unknown()),
+    }
+}
+
+/// tell whether a value is a non-empty list of numbers, to be summarized as a sparkline
+pub(crate) fn is_numeric_list(value: &Value) -> bool {
+    match value {
+        Value::List { vals, .. } => {
+            !vals.is_empty()
+                && vals
+                    .iter()
+                    .all(|v| matches!(v, Value::Int { .. } | Value::Float { .. }))
+        }
+        _ => false,
+    }
+}
+
+/// infer the columns of a table and the type of their values, in the order of the first row
+///
+/// the empty cells do not count and a column mixing `int`s and `float`s is of type `number`, like
+/// in [`is_table`]. values that are not tables have no columns.
+pub(crate) fn infer_columns(value: &Value) -> Vec<(String, Type)> {
+    if !is_table(value) {
+        return vec![];
+    }
+    let Value::List { vals, .. } = value else {
+        return vec![];
+    };
+
+    // NOTE: all the rows of a table are records with the same columns
+    let rows = vals
+        .iter()
+        .filter_map(|v| v.as_record().ok())
+        .collect::<Vec<&Record>>();
+
+    rows[0]
+        .cols
+        .iter()
+        .map(|col| {
+            let mut ty = Type::Nothing;
+            for cell_type in rows.iter().filter_map(|r| r.get(col)).map(Value::get_type) {
+                if matches!(cell_type, Type::Nothing) {
+                    continue;
+                }
+                ty = if ty.is_numeric() && cell_type.is_numeric() && ty != cell_type {
+                    Type::Number
+                } else {
+                    cell_type
+                };
+            }
+            (col.clone(), ty)
+        })
+        .collect()
+}
+
+pub(crate) fn is_table(value: &Value) -> bool {
+    match value {
+        Value::List { vals, .. } => {
+            if vals.is_empty() {
+                return false;
+            }
+
+            // extract the columns of each row as hashmaps for easier access
+            let mut rows = Vec::new();
+            for val in vals {
+                match val.get_type() {
+                    Type::Record(fields) => {
+                        rows.push(fields.into_iter().collect::<HashMap<String, Type>>())
+                    }
+                    _ => return false,
+                };
+            }
+
+            // check the number of columns for each row
+            let n = rows[0].keys().len();
+            for row in rows.iter().skip(1) {
+                if row.keys().len() != n {
+                    return false;
+                }
+            }
+
+            // check the actual types for each column
+            // - if a row has a null, it doesn't count as "not a table"
+            // - if two rows are numeric, then the check can continue
+            for (key, val) in rows[0].iter() {
+                let mut ty = val;
+
+                for row in rows.iter().skip(1) {
+                    match row.get(key) {
+                        Some(v) => match ty {
+                            Type::Nothing => ty = v,
+                            _ => {
+                                if !matches!(v, Type::Nothing) {
+                                    if v.is_numeric() && ty.is_numeric() {
+                                    } else if (!v.is_numeric() && ty.is_numeric())
+                                        | (v.is_numeric() && !ty.is_numeric())
+                                        // NOTE: this might need a bit more work to include more
+                                        // tables
+                                        | (v != ty)
+                                    {
+                                        return false;
+                                    }
+                                }
+                            }
+                        },
+                        None => return false,
+                    }
+                }
+            }
+
+            true
+        }
+        _ => false,
+    }
+}
+
+/// this effectively implements the following idempotent `transpose` command written in Nushell
+/// ```nushell
+/// alias "core transpose" = transpose
+///
+/// def transpose []: [table -> any, record -> table] {
+///     let data = $in
+///
+///     if ($data | columns) == (seq 1 ($data | columns | length) | into string) {
+///         if ($data | columns | length) == 2 {
+///             return ($data | core transpose --header-row | into record)
+///         } else {
+///             return ($data | core transpose --header-row)
+///         }
+///     }
+///
+///     $data | core transpose | rename --block {
+///         ($in | str replace "column" "" | into int) + 1 | into string
+///     }
+/// }
+///
+/// #[test]
+/// def transposition [] {
+///     use std assert
+///
+///     assert equal (ls | transpose explore | transpose) (ls)
+///     assert equal (open Cargo.toml | transpose | transpose) (open Cargo.toml)
+/// }
+/// ```
+pub(crate) fn transpose(value: &Value) -> Value {
+    if is_table(value) {
+        let value_rows = match value {
+            Value::List { vals, .. } => vals,
+            _ => return value.clone(),
+        };
+
+        let first_row = value_rows[0].as_record().unwrap();
+
+        let full_columns = (1..=(first_row.len()))
+            .map(|i| format!("{i}"))
+            .collect::<Vec<String>>();
+
+        if first_row.cols == full_columns {
+            if first_row.len() == 2 {
+                let cols: Vec<String> = value_rows
+                    .iter()
+                    .map(|row| row.get_data_by_key("1").unwrap().as_str().unwrap().into())
+                    .collect();
+
+                let vals: Vec<Value> = value_rows
+                    .iter()
+                    .map(|row| row.get_data_by_key("2").unwrap())
+                    .collect();
+
+                return Value::record(
+                    Record::from_raw_cols_vals(cols, vals, Span::unknown(), Span::unknown())
+                        .unwrap(),
+                    Span::unknown(),
+                );
+            } else {
+                let mut rows = vec![];
+                let cols: Vec<String> = value_rows
+                    .iter()
+                    .map(|v| v.get_data_by_key("1").unwrap().as_str().unwrap().into())
+                    .collect();
+
+                for i in 0..(first_row.len() - 1) {
+                    rows.push(Value::record(
+                        Record::from_raw_cols_vals(
+                            cols.clone(),
+                            value_rows
+                                .iter()
+                                .map(|v| v.get_data_by_key(&format!("{}", i + 2)).unwrap())
+                                .collect(),
+                            Span::unknown(),
+                            Span::unknown(),
+                        )
+                        .unwrap(),
+                        Span::unknown(),
+                    ));
+                }
+
+                return Value::list(rows, Span::unknown());
+            }
+        }
+
+        let mut rows = vec![];
+        for col in value_rows[0].columns() {
+            let mut cols = vec!["1".into()];
+            let mut vs = vec![Value::string(col, Span::unknown())];
+
+            for (i, v) in value_rows.iter().enumerate() {
+                cols.push(format!("{}", i + 2));
+                vs.push(v.get_data_by_key(col).unwrap());
+            }
+
+            rows.push(Value::record(
+                Record::from_raw_cols_vals(cols, vs, Span::unknown(), Span::unknown()).unwrap(),
+                Span::unknown(),
+            ));
+        }
+
+        return Value::list(rows, Span::unknown());
+    }
+
+    match value {
+        Value::Record { val: rec, .. } => {
+            let mut rows = vec![];
+            for (col, val) in rec.iter() {
+                rows.push(Value::record(
+                    record! {
+                        "1" => Value::string(col, Span::unknown()),
+                        "2" => val.clone(),
+                    },
+                    Span::unknown(),
+                ));
+            }
+
+            Value::list(rows, Span::unknown())
+        }
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        approx_size, cell_path_to_jmespath, cell_path_to_jsonpath, collect_leaves,
+        column_histogram, count_nodes, date_to_epoch, describe_container, diff_lines, diff_values,
+        duplicate_cell, edits_to_script, filter_list, find_matches, flatten_one_level,
+        flatten_to_dotted, format_with_separators, infer_columns, infer_schema, insert_value_cell,
+        is_table, join_list, lift_cell, mutate_value_cell, numeric_stats, parse_as_type,
+        parse_cell_path, parse_envish, path_to_get_closure, path_to_nested_record, pop_list_item,
+        push_list_item, reconcile_cell_path, record_values_to_list, records_to_columns,
+        relative_path, remove_list_items, rename_field, replace_in_subtree, repr_debug, repr_json,
+        repr_type, reverse_container, search_results, sort_container, sort_list, split_string,
+        summarize_list, to_csv, to_markdown_table, to_patch, to_update_pipeline, to_where_clause,
+        value_to_rust_literal, value_with_path, wrap_in_list, wrap_in_record, SortBy, SortKind,
+    };
+    use crate::nu::{
+        cell_path::{to_path_member_vec, PM},
+        value::transpose,
+    };
+    use nu_protocol::{ast::CellPath, record, Config, Type, Value};
+
+    #[test]
+    fn parse_and_reconcile_cell_paths() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_string("x"), Value::test_string("y")]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "a b" => Value::test_int(2),
+                "1" => Value::test_int(3),
+            }),
+        });
+        let resolve = |s: &str| parse_cell_path(s).and_then(|p| reconcile_cell_path(&value, &p));
+        let path = |members: &[PM]| {
+            Some(CellPath {
+                members: to_path_member_vec(members),
+            })
+        };
+
+        assert_eq!(resolve("$.r.a"), path(&[PM::S("r"), PM::S("a")]));
+        // NOTE: the paths copied in other formats are understood too
+        assert_eq!(resolve("r.\"a b\""), path(&[PM::S("r"), PM::S("a b")]));
+        assert_eq!(resolve("$['r']['a b']"), path(&[PM::S("r"), PM::S("a b")]));
+        assert_eq!(resolve("$.l[1]"), path(&[PM::S("l"), PM::I(1)]));
+        assert_eq!(resolve("l.1"), path(&[PM::S("l"), PM::I(1)]));
+        assert_eq!(resolve("$"), path(&[]));
+        // NOTE: a number is a key in a record
+        assert_eq!(resolve("$.r.1"), path(&[PM::S("r"), PM::S("1")]));
+
+        assert_eq!(resolve("$.r.b"), None);
+        assert_eq!(resolve("$.l[2]"), None);
+        assert_eq!(resolve("$.l.a"), None);
+        assert_eq!(parse_cell_path("$.r."), None);
+        assert_eq!(parse_cell_path("$.l[x]"), None);
+        assert_eq!(parse_cell_path("$.r[\"a"), None);
+    }
+
+    #[test]
+    fn schema() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_string("my"), Value::test_string("list")]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_float(2.0),
+            }),
+            "t" => Value::test_list(vec![
+                Value::test_record(record! {
+                    "n" => Value::test_int(1),
+                    "r" => Value::test_nothing(),
+                }),
+                Value::test_record(record! {
+                    "n" => Value::test_float(1.5),
+                    "r" => Value::test_record(record! { "x" => Value::test_bool(true) }),
+                }),
+            ]),
+            "s" => Value::test_string("some string"),
+        });
+
+        assert_eq!(
+            infer_schema(&value),
+            Value::test_record(record! {
+                "l" => Value::test_string("list<string>"),
+                "r" => Value::test_record(record! {
+                    "a" => Value::test_string("int"),
+                    "b" => Value::test_string("float"),
+                }),
+                // NOTE: a table gives the schema of its rows, with the nested values of any row
+                "t" => Value::test_list(vec![Value::test_record(record! {
+                    "n" => Value::test_string("number"),
+                    "r" => Value::test_record(record! { "x" => Value::test_string("bool") }),
+                })]),
+                "s" => Value::test_string("string"),
+            })
+        );
+        assert_eq!(infer_schema(&Value::test_int(1)), Value::test_string("int"));
+    }
+
+    #[test]
+    fn approx_sizes() {
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_string("foo"),
+            "l" => Value::test_list(vec![Value::test_bool(true), Value::test_nothing()]),
+        });
+        // NOTE: `{"a":1,"b":"foo","l":[true,null]}`
+        assert_eq!(approx_size(&record), 33);
+        assert_eq!(approx_size(&Value::test_string("foo")), 5);
+    }
+
+    #[test]
+    fn describe_containers() {
+        let list = Value::test_list(vec![
+            Value::test_string("a"),
+            Value::test_string("b"),
+            Value::test_string("c"),
+        ]);
+        assert_eq!(describe_container(&list), "list of 3 strings");
+
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_string("foo"),
+            "l" => Value::test_list(vec![]),
+            "r" => Value::test_record(record! {}),
+        });
+        assert_eq!(describe_container(&record), "record of 4 fields");
+
+        let cases = vec![
+            (Value::test_list(vec![Value::test_int(1)]), "list of 1 int"),
+            (
+                Value::test_list(vec![Value::test_int(1), Value::test_string("a")]),
+                "list of 2 items",
+            ),
+            (
+                Value::test_list(vec![record.clone(), record.clone()]),
+                "list of 2 records",
+            ),
+            (Value::test_list(vec![]), "empty list"),
+            (
+                Value::test_record(record! { "a" => Value::test_int(1) }),
+                "record of 1 field",
+            ),
+            (Value::test_record(record! {}), "empty record"),
+            (Value::test_int(1), "int"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(describe_container(&value), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn patch_of_edits() {
+        let original = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            "s" => Value::test_string("foo"),
+            "i" => Value::test_int(123),
+        });
+        assert_eq!(to_patch(&original, &original), None);
+
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("s")]),
+        };
+        let edited = mutate_value_cell(&original, &path, &Value::test_string("bar"));
+        assert_eq!(
+            to_patch(&original, &edited),
+            Some(
+                [
+                    "--- original",
+                    "+++ edited",
+                    "@@ -3,6 +3,6 @@",
+                    "     1,",
+                    "     2",
+                    "   ],",
+                    "-  \"s\": \"foo\",",
+                    "+  \"s\": \"bar\",",
+                    "   \"i\": 123",
+                    " }",
+                    "",
+                ]
+                .join("\n")
+            )
+        );
+
+        // NOTE: a new item is only an added line
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("l")]),
+        };
+        let list = Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_int(2),
+            Value::test_int(3),
+        ]);
+        let patch = to_patch(&original, &mutate_value_cell(&original, &path, &list)).unwrap();
+        let changed = |tag: char| patch.lines().skip(2).filter(|l| l.starts_with(tag)).count();
+        assert_eq!((changed('-'), changed('+')), (1, 2));
+    }
+
+    #[test]
+    fn shortest_line_diff() {
+        let a = ["a", "b", "c", "a", "b", "b", "a"];
+        let b = ["c", "b", "a", "b", "a", "c"];
+        let lines = diff_lines(&a, &b);
+
+        let side = |skip: char| -> Vec<&str> {
+            lines.iter().filter(|l| l.0 != skip).map(|l| l.1).collect()
+        };
+        assert_eq!(side('+'), a);
+        assert_eq!(side('-'), b);
+        assert_eq!(lines.iter().filter(|l| l.0 != ' ').count(), 5);
+
+        assert_eq!(diff_lines(&[], &["x"]), vec![('+', "x")]);
+        assert_eq!(diff_lines(&["x"], &[]), vec![('-', "x")]);
+        assert_eq!(diff_lines(&[], &[]), vec![]);
+    }
+
+    #[test]
+    fn dotted_pairs() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "e" => Value::test_record(record! {}),
+            "i" => Value::test_int(123),
+        });
+        assert_eq!(
+            flatten_to_dotted(&value),
+            Value::test_record(record! {
+                "l.0" => Value::test_string("my"),
+                "l.1" => Value::test_string("list"),
+                "r.a" => Value::test_int(1),
+                "r.b" => Value::test_int(2),
+                "e" => Value::test_record(record! {}),
+                "i" => Value::test_int(123),
+            })
+        );
+
+        // NOTE: a leaf has nothing to flatten
+        assert_eq!(flatten_to_dotted(&Value::test_int(1)), Value::test_int(1));
+        assert_eq!(
+            flatten_to_dotted(&Value::test_record(record! {})),
+            Value::test_record(record! {})
+        );
+        assert_eq!(
+            flatten_to_dotted(&Value::test_list(vec![])),
+            Value::test_record(record! {})
+        );
+
+        // NOTE: the keys that would be mistaken for paths are quoted
+        let value = Value::test_record(record! {
+            "a.b" => Value::test_int(1),
+            "a" => Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "" => Value::test_int(3),
+            }),
+        });
+        assert_eq!(
+            flatten_to_dotted(&value),
+            Value::test_record(record! {
+                "\"a.b\"" => Value::test_int(1),
+                "a.b" => Value::test_int(2),
+                "a.\"\"" => Value::test_int(3),
+            })
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("foo"),
+                "size" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("bar, \"baz\""),
+                "size" => Value::test_nothing(),
+            }),
+        ]);
+        assert_eq!(
+            to_csv(&table),
+            Ok("name,size\nfoo,1\n\"bar, \"\"baz\"\"\",\n".into())
+        );
+
+        // NOTE: a record is a table of a single row
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+        });
+        assert_eq!(to_csv(&record), Ok("a,l\n1,\"[1,2]\"\n".into()));
+
+        assert_eq!(
+            to_csv(&Value::test_list(vec![Value::test_int(1)])),
+            Err("only records and lists of records can be written as CSV".into())
+        );
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn hash_values() {
+        use super::hash_value;
+
+        assert_eq!(
+            hash_value(&Value::test_string("abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // NOTE: the other values are hashed as JSON, e.g. `[1,2]`
+        assert_eq!(
+            hash_value(&Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2)
+            ])),
+            hash_value(&Value::test_string("[1,2]"))
+        );
+    }
+
+    fn default_value_repr(value: &Value) -> String {
+        value.to_expanded_string(" ", &Config::default())
+    }
+
+    #[test]
+    fn value_mutation() {
+        let list = Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_int(2),
+            Value::test_int(3),
+        ]);
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        });
+
+        let cases = vec![
+            // simple value -> simple value
+            (
                 Value::test_string("foo"),
                 vec![],
                 Value::test_string("bar"),
@@ -327,77 +2400,1364 @@ mod tests {
             ),
             // mutate a record field with a simple value
             (
-                record.clone(),
-                vec![PM::S("a")],
-                Value::test_nothing(),
+                record.clone(),
+                vec![PM::S("a")],
+                Value::test_nothing(),
+                Value::test_record(record! {
+                    "a" => Value::test_nothing(),
+                    "b" => Value::test_int(2),
+                    "c" => Value::test_int(3),
+                }),
+            ),
+            // mutate a record field with a complex value
+            (
+                record.clone(),
+                vec![PM::S("c")],
+                list.clone(),
+                Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "b" => Value::test_int(2),
+                    "c" => list.clone(),
+                }),
+            ),
+            // mutate a deeply-nested list element
+            (
+                Value::test_list(vec![Value::test_list(vec![Value::test_list(vec![
+                    Value::test_string("foo"),
+                ])])]),
+                vec![PM::I(0), PM::I(0), PM::I(0)],
+                Value::test_string("bar"),
+                Value::test_list(vec![Value::test_list(vec![Value::test_list(vec![
+                    Value::test_string("bar"),
+                ])])]),
+            ),
+            // mutate a deeply-nested record field
+            (
+                Value::test_record(record! {
+                    "a" => Value::test_record(record! {
+                        "b" => Value::test_record(record! {
+                            "c" => Value::test_string("foo"),
+                        }),
+                    }),
+                }),
+                vec![PM::S("a"), PM::S("b"), PM::S("c")],
+                Value::test_string("bar"),
+                Value::test_record(record! {
+                    "a" => Value::test_record(record! {
+                        "b" => Value::test_record(record! {
+                            "c" => Value::test_string("bar"),
+                        }),
+                    }),
+                }),
+            ),
+        ];
+
+        for (value, members, cell, expected) in cases {
+            let cell_path = CellPath {
+                members: to_path_member_vec(&members),
+            };
+
+            let result = mutate_value_cell(&value, &cell_path, &cell);
+            assert_eq!(
+                result,
+                expected,
+                "mutating {} at {:?} with {} should give {}, found {}",
+                default_value_repr(&value),
+                PM::as_cell_path(&members),
+                default_value_repr(&cell),
+                default_value_repr(&expected),
+                default_value_repr(&result)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_text_as_type() {
+        let cases = vec![
+            ("foo", Type::String, Ok(Value::test_string("foo"))),
+            ("123", Type::String, Ok(Value::test_string("123"))),
+            ("foo", Type::Nothing, Ok(Value::test_string("foo"))),
+            ("123", Type::Int, Ok(Value::test_int(123))),
+            (" 123\n", Type::Int, Ok(Value::test_int(123))),
+            ("1.5", Type::Float, Ok(Value::test_float(1.5))),
+            ("true", Type::Bool, Ok(Value::test_bool(true))),
+            (
+                "foo",
+                Type::Int,
+                Err("could not parse `foo` as int".to_string()),
+            ),
+            (
+                "foo",
+                Type::List(Box::new(Type::Int)),
+                Err("cannot set a list<int> cell from raw text".to_string()),
+            ),
+        ];
+
+        for (input, ty, expected) in cases {
+            assert_eq!(
+                parse_as_type(input, &ty),
+                expected,
+                "unexpected result when parsing {input:?} as {ty}"
+            );
+        }
+    }
+
+    #[test]
+    fn node_count() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        });
+
+        assert_eq!(count_nodes(&value, usize::MAX), 10);
+        assert_eq!(count_nodes(&value, 5), 5);
+        assert_eq!(count_nodes(&Value::test_int(0), usize::MAX), 1);
+        assert_eq!(count_nodes(&Value::test_list(vec![]), usize::MAX), 1);
+    }
+
+    #[test]
+    fn debug_string() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        });
+
+        let debug = repr_debug(&value);
+        let expected_start = r#"Record {
+    val: Record {
+        cols: [
+            "l",
+            "r",
+            "s",
+            "i",
+        ],
+        vals: [
+            List {
+                vals: [
+                    String {
+                        val: "my","#;
+        assert!(
+            debug.starts_with(expected_start),
+            "unexpected start of the debug string: {debug}"
+        );
+        assert!(debug.contains(r#"val: "some string","#));
+        assert!(debug.contains("val: 123,"));
+        assert!(debug.ends_with(
+            r#"    internal_span: Span {
+        start: 0,
+        end: 0,
+    },
+}"#
+        ));
+    }
+
+    #[test]
+    fn nested_record() {
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("r"), PM::S("a")]),
+        };
+        assert_eq!(
+            path_to_nested_record(&path, &Value::test_int(9)),
+            Some(Value::test_record(record! {
+                "r" => Value::test_record(record! {
+                    "a" => Value::test_int(9),
+                }),
+            }))
+        );
+
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("l"), PM::I(1)]),
+        };
+        // NOTE: the indices of lists have no record equivalent
+        assert_eq!(path_to_nested_record(&path, &Value::test_string("x")), None);
+
+        let path = CellPath { members: vec![] };
+        assert_eq!(
+            path_to_nested_record(&path, &Value::test_int(9)),
+            Some(Value::test_int(9))
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let old = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            "r" => Value::test_record(record! {"x" => Value::test_string("x")}),
+        });
+        assert_eq!(diff_values(&old, &old), vec![]);
+
+        let new = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(20), Value::test_int(3)]),
+            "r" => Value::test_string("r"),
+            "n" => Value::test_nothing(),
+        });
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+        assert_eq!(
+            diff_values(&old, &new),
+            vec![
+                path(&[PM::S("l"), PM::I(1)]),
+                path(&[PM::S("l"), PM::I(2)]),
+                path(&[PM::S("r")]),
+                path(&[PM::S("n")]),
+            ]
+        );
+
+        assert_eq!(
+            diff_values(&Value::test_int(1), &Value::test_int(2)),
+            vec![path(&[])]
+        );
+    }
+
+    #[test]
+    fn markdown_table() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("foo"),
+                "size" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("a|b"),
+                "size" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            }),
+        ]);
+        assert_eq!(
+            to_markdown_table(&table),
+            "| name | size |\n| --- | --- |\n| foo | 1 |\n| a\\|b | [list 2 items] |"
+        );
+
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+        });
+        assert_eq!(
+            to_markdown_table(&record),
+            "| key | value |\n| --- | --- |\n| a | 1 |"
+        );
+
+        let list = Value::test_list(vec![Value::test_int(1), Value::test_string("x")]);
+        assert_eq!(to_markdown_table(&list), "| item |\n| --- |\n| 1 |\n| x |");
+
+        let empty = Value::test_list(vec![]);
+        assert_eq!(to_markdown_table(&empty), "| item |\n| --- |");
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(
+            wrap_in_list(&Value::test_int(1)),
+            Value::test_list(vec![Value::test_int(1)])
+        );
+        assert_eq!(
+            wrap_in_record(&Value::test_list(vec![])),
+            Value::test_record(record! {
+                "value" => Value::test_list(vec![]),
+            })
+        );
+    }
+
+    #[test]
+    fn replace_in_subtrees() {
+        let list = Value::test_list(vec![
+            Value::test_string("my"),
+            Value::test_string("list"),
+            Value::test_string("elements"),
+        ]);
+        assert_eq!(
+            replace_in_subtree(&list, "my", "MY"),
+            (
+                Value::test_list(vec![
+                    Value::test_string("MY"),
+                    Value::test_string("list"),
+                    Value::test_string("elements"),
+                ]),
+                1
+            )
+        );
+
+        let record = Value::test_record(record! {
+            "my" => Value::test_int(1),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_string("my my"),
+                "b" => Value::test_string("mine"),
+            }),
+        });
+        // NOTE: the keys and the other scalars are not replaced
+        assert_eq!(
+            replace_in_subtree(&record, "my", "your"),
+            (
                 Value::test_record(record! {
-                    "a" => Value::test_nothing(),
-                    "b" => Value::test_int(2),
-                    "c" => Value::test_int(3),
+                    "my" => Value::test_int(1),
+                    "r" => Value::test_record(record! {
+                        "a" => Value::test_string("your your"),
+                        "b" => Value::test_string("mine"),
+                    }),
                 }),
+                1
+            )
+        );
+        assert_eq!(replace_in_subtree(&list, "", "x"), (list, 0));
+    }
+
+    #[test]
+    fn split_strings() {
+        assert_eq!(
+            split_string(&Value::test_string("a,b,c"), ","),
+            Ok(Value::test_list(vec![
+                Value::test_string("a"),
+                Value::test_string("b"),
+                Value::test_string("c"),
+            ]))
+        );
+        assert_eq!(
+            split_string(&Value::test_string("a,,b"), ","),
+            Ok(Value::test_list(vec![
+                Value::test_string("a"),
+                Value::test_string(""),
+                Value::test_string("b"),
+            ]))
+        );
+        assert_eq!(
+            split_string(&Value::test_string("abc"), ","),
+            Ok(Value::test_list(vec![Value::test_string("abc")]))
+        );
+        assert!(split_string(&Value::test_int(1), ",").is_err());
+    }
+
+    #[test]
+    fn join_lists() {
+        let list = Value::test_list(vec![Value::test_string("a"), Value::test_string("b")]);
+        assert_eq!(join_list(&list, "-"), Ok(Value::test_string("a-b")));
+        assert_eq!(
+            join_list(&Value::test_list(vec![]), "-"),
+            Ok(Value::test_string(""))
+        );
+
+        let mixed = Value::test_list(vec![Value::test_string("a"), Value::test_int(1)]);
+        assert!(join_list(&mixed, "-").is_err());
+        assert!(join_list(&Value::test_string("a"), "-").is_err());
+
+        // NOTE: joining is the inverse of splitting
+        let split = split_string(&Value::test_string("a,b,c"), ",").unwrap();
+        assert_eq!(join_list(&split, ","), Ok(Value::test_string("a,b,c")));
+    }
+
+    #[test]
+    fn script_of_edits() {
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+        let value = Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(9),
+            }),
+            "l" => Value::test_list(vec![Value::test_string("foo")]),
+            "a b" => Value::test_record(record! {
+                "c" => Value::test_int(1),
+            }),
+        });
+
+        assert_eq!(
+            edits_to_script(&value, &[path(&[PM::S("r"), PM::S("a")])]),
+            "update r.a 9"
+        );
+        assert_eq!(
+            edits_to_script(
+                &value,
+                &[
+                    path(&[PM::S("l"), PM::I(0)]),
+                    path(&[PM::S("r"), PM::S("b")]),
+                    path(&[PM::S("a b")]),
+                ]
             ),
-            // mutate a record field with a complex value
-            (
-                record.clone(),
-                vec![PM::S("c")],
-                list.clone(),
+            "update l.0 \"foo\" |\nupdate \"a b\" {c: 1}"
+        );
+        assert_eq!(edits_to_script(&value, &[]), "");
+    }
+
+    #[test]
+    fn values_with_path() {
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            value_with_path(&path(&[PM::S("r"), PM::S("a")]), &Value::test_int(1)),
+            "{path: $.r.a, value: 1}"
+        );
+        assert_eq!(
+            value_with_path(
+                &path(&[PM::S("a b"), PM::I(0)]),
+                &Value::test_list(vec![Value::test_string("foo")])
+            ),
+            "{path: $.\"a b\".0, value: [\"foo\"]}"
+        );
+        assert_eq!(
+            value_with_path(&path(&[]), &Value::test_bool(true)),
+            "{path: $, value: true}"
+        );
+        assert_eq!(
+            value_with_path(&path(&[PM::S("size")]), &Value::test_filesize(1024)),
+            "{path: $.size, value: 1024b}"
+        );
+    }
+
+    #[test]
+    fn stats() {
+        let ints = [1, 2, 3].map(Value::test_int);
+        assert_eq!(
+            numeric_stats(&ints),
+            Value::test_record(record! {
+                "min" => Value::test_int(1),
+                "max" => Value::test_int(3),
+                "sum" => Value::test_int(6),
+                "mean" => Value::test_float(2.0),
+            })
+        );
+
+        // NOTE: the values that are not numbers are ignored
+        let mixed = vec![
+            Value::test_int(1),
+            Value::test_string("foo"),
+            Value::test_float(2.5),
+        ];
+        assert_eq!(
+            numeric_stats(&mixed),
+            Value::test_record(record! {
+                "min" => Value::test_float(1.0),
+                "max" => Value::test_float(2.5),
+                "sum" => Value::test_float(3.5),
+                "mean" => Value::test_float(1.75),
+            })
+        );
+
+        // NOTE: the big numbers are summed exactly, as a float when they overflow
+        let big = [i64::MAX, 1, -2].map(Value::test_int);
+        assert_eq!(
+            numeric_stats(&big).get_data_by_key("sum").unwrap(),
+            Value::test_int(i64::MAX - 1)
+        );
+        let overflow = [i64::MAX, i64::MAX].map(Value::test_int);
+        assert_eq!(
+            numeric_stats(&overflow).get_data_by_key("sum").unwrap(),
+            Value::test_float(2.0 * i64::MAX as f64)
+        );
+
+        assert_eq!(
+            numeric_stats(&[]),
+            Value::test_record(record! {
+                "min" => Value::test_nothing(),
+                "max" => Value::test_nothing(),
+                "sum" => Value::test_int(0),
+                "mean" => Value::test_nothing(),
+            })
+        );
+    }
+
+    #[test]
+    fn histogram() {
+        let table = Value::test_list(
+            ["cat", "dog", "bird", "dog", "cat", "dog"]
+                .into_iter()
+                .enumerate()
+                .map(|(i, animal)| {
+                    Value::test_record(record! {
+                        "id" => Value::test_int(i as i64),
+                        "animal" => Value::test_string(animal),
+                    })
+                })
+                .collect(),
+        );
+
+        assert_eq!(
+            column_histogram(&table, "animal"),
+            vec![
+                ("dog".to_string(), 3),
+                ("cat".to_string(), 2),
+                ("bird".to_string(), 1),
+            ]
+        );
+        assert_eq!(column_histogram(&table, "id").len(), 6);
+        assert_eq!(column_histogram(&table, "nope"), vec![]);
+        assert_eq!(column_histogram(&Value::test_int(1), "animal"), vec![]);
+    }
+
+    #[test]
+    fn rename_fields() {
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+        });
+        assert_eq!(
+            rename_field(&record, "a", "c"),
+            Ok(Value::test_record(record! {
+                "c" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }))
+        );
+        assert_eq!(rename_field(&record, "a", "a"), Ok(record.clone()));
+        assert_eq!(
+            rename_field(&record, "a", "b"),
+            Err("there is already a field named `b`".into())
+        );
+    }
+
+    #[test]
+    fn sort_containers() {
+        let list = Value::test_list(vec![
+            Value::test_int(3),
+            Value::test_int(1),
+            Value::test_int(2),
+        ]);
+        assert_eq!(
+            sort_container(&list, SortBy::Value, false),
+            Some(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2),
+                Value::test_int(3),
+            ]))
+        );
+        assert_eq!(
+            sort_container(&list, SortBy::Value, true),
+            Some(Value::test_list(vec![
+                Value::test_int(3),
+                Value::test_int(2),
+                Value::test_int(1),
+            ]))
+        );
+        // NOTE: the items of a list have no key
+        assert_eq!(sort_container(&list, SortBy::Key, false), None);
+
+        let record = Value::test_record(record! {
+            "b" => Value::test_int(1),
+            "c" => Value::test_int(3),
+            "a" => Value::test_int(2),
+        });
+        let columns = |value: Option<Value>| -> Vec<String> {
+            value
+                .unwrap()
+                .as_record()
+                .unwrap()
+                .columns()
+                .cloned()
+                .collect()
+        };
+        assert_eq!(
+            columns(sort_container(&record, SortBy::Key, false)),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            columns(sort_container(&record, SortBy::Key, true)),
+            vec!["c", "b", "a"]
+        );
+        assert_eq!(
+            columns(sort_container(&record, SortBy::Value, false)),
+            vec!["b", "a", "c"]
+        );
+
+        assert_eq!(
+            sort_container(&Value::test_int(1), SortBy::Value, false),
+            None
+        );
+    }
+
+    #[test]
+    fn sort_lists() {
+        let strings =
+            |vals: &[&str]| Value::test_list(vals.iter().map(|v| Value::test_string(*v)).collect());
+        let list = strings(&["10", "2", "1"]);
+
+        assert_eq!(
+            sort_list(&list, SortKind::Numeric),
+            Some(strings(&["1", "2", "10"]))
+        );
+        assert_eq!(
+            sort_list(&list, SortKind::Lexical),
+            Some(strings(&["1", "10", "2"]))
+        );
+
+        // NOTE: the items that are not numbers come last, in their original order
+        let mixed = Value::test_list(vec![
+            Value::test_string("b"),
+            Value::test_float(2.5),
+            Value::test_string("a"),
+            Value::test_int(1),
+        ]);
+        assert_eq!(
+            sort_list(&mixed, SortKind::Numeric),
+            Some(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_float(2.5),
+                Value::test_string("b"),
+                Value::test_string("a"),
+            ]))
+        );
+
+        assert_eq!(sort_list(&Value::test_int(1), SortKind::Lexical), None);
+    }
+
+    #[test]
+    fn flatten_record_of_tables() {
+        let table = |names: &[&str]| {
+            Value::test_list(
+                names
+                    .iter()
+                    .map(|n| {
+                        Value::test_record(record! {
+                            "name" => Value::test_string(*n),
+                            "size" => Value::test_int(n.len() as i64),
+                        })
+                    })
+                    .collect(),
+            )
+        };
+        let row = |source: &str, name: &str| {
+            Value::test_record(record! {
+                "source" => Value::test_string(source),
+                "name" => Value::test_string(name),
+                "size" => Value::test_int(name.len() as i64),
+            })
+        };
+
+        let value = Value::test_record(record! {
+            "src" => table(&["lib.rs", "main.rs"]),
+            "tests" => table(&["a.rs"]),
+        });
+        assert_eq!(
+            flatten_one_level(&value),
+            Value::test_list(vec![
+                row("src", "lib.rs"),
+                row("src", "main.rs"),
+                row("tests", "a.rs"),
+            ])
+        );
+
+        // NOTE: these cannot be flattened into a table
+        let values = vec![
+            Value::test_list(vec![Value::test_int(1)]),
+            Value::test_record(record! {
+                "a" => table(&["foo"]),
+                "b" => Value::test_int(1),
+            }),
+            Value::test_record(record! {
+                "a" => table(&["foo"]),
+                "b" => Value::test_list(vec![Value::test_record(record! {
+                    "other" => Value::test_int(1),
+                })]),
+            }),
+        ];
+        for value in values {
+            assert_eq!(flatten_one_level(&value), value);
+        }
+    }
+
+    #[test]
+    fn table_to_columns() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_string("x"),
+            }),
+            Value::test_record(record! {
+                "a" => Value::test_int(2),
+                "b" => Value::test_string("y"),
+            }),
+        ]);
+        assert_eq!(
+            records_to_columns(&table),
+            Value::test_record(record! {
+                "a" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                "b" => Value::test_list(vec![Value::test_string("x"), Value::test_string("y")]),
+            })
+        );
+
+        // NOTE: only the tables have columns
+        let list = Value::test_list(vec![Value::test_int(1), Value::test_int(2)]);
+        assert_eq!(records_to_columns(&list), list);
+    }
+
+    #[test]
+    fn summarize_lists() {
+        let list = Value::test_list((1..=10).map(Value::test_int).collect());
+        assert_eq!(
+            summarize_list(&list),
+            Value::test_record(record! {
+                "count" => Value::test_int(10),
+                "first" => Value::test_int(1),
+                "last" => Value::test_int(10),
+            })
+        );
+
+        assert_eq!(
+            summarize_list(&Value::test_list(vec![])),
+            Value::test_record(record! {
+                "count" => Value::test_int(0),
+                "first" => Value::test_nothing(),
+                "last" => Value::test_nothing(),
+            })
+        );
+
+        // NOTE: only the lists are summarized
+        let record = Value::test_record(record! { "a" => Value::test_int(1) });
+        assert_eq!(summarize_list(&record), record);
+    }
+
+    #[test]
+    fn record_values() {
+        assert_eq!(
+            record_values_to_list(&Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            })),
+            Some(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2)
+            ]))
+        );
+        assert_eq!(
+            record_values_to_list(&Value::test_record(record! {})),
+            Some(Value::test_list(vec![]))
+        );
+        assert_eq!(record_values_to_list(&Value::test_int(1)), None);
+    }
+
+    #[test]
+    fn insert_value_cells() {
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "new" => Value::test_int(2),
+        });
+
+        assert_eq!(
+            insert_value_cell(&record, &Value::test_nothing()),
+            Some((
+                Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "new" => Value::test_int(2),
+                    "new_2" => Value::test_nothing(),
+                }),
+                "new_2".into()
+            ))
+        );
+        assert_eq!(
+            insert_value_cell(&Value::test_record(record! {}), &Value::test_int(0)),
+            Some((
                 Value::test_record(record! {
+                    "new" => Value::test_int(0),
+                }),
+                "new".into()
+            ))
+        );
+        assert_eq!(
+            insert_value_cell(&Value::test_list(vec![]), &Value::test_int(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn lift_cells() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(0)]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+        });
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            lift_cell(&value, &path(&[PM::S("r"), PM::S("a")])),
+            Some(Value::test_record(record! {
+                "l" => Value::test_list(vec![Value::test_int(0)]),
+                "r" => Value::test_int(1),
+            }))
+        );
+        assert_eq!(
+            lift_cell(&value, &path(&[PM::S("l"), PM::I(0)])),
+            Some(Value::test_record(record! {
+                "l" => Value::test_int(0),
+                "r" => Value::test_record(record! {
                     "a" => Value::test_int(1),
                     "b" => Value::test_int(2),
-                    "c" => list.clone(),
                 }),
+            }))
+        );
+        // NOTE: the root cannot be replaced
+        assert_eq!(lift_cell(&value, &path(&[PM::S("r")])), None);
+        assert_eq!(lift_cell(&value, &path(&[PM::S("r"), PM::S("c")])), None);
+    }
+
+    #[test]
+    fn remove_items() {
+        let list = Value::test_list((0..5).map(Value::test_int).collect());
+
+        assert_eq!(
+            remove_list_items(&list, 1..4),
+            Some(Value::test_list(vec![
+                Value::test_int(0),
+                Value::test_int(4)
+            ]))
+        );
+        // NOTE: the range is clamped to the list
+        assert_eq!(
+            remove_list_items(&list, 3..10),
+            Some(Value::test_list((0..3).map(Value::test_int).collect()))
+        );
+        assert_eq!(remove_list_items(&Value::test_int(0), 0..1), None);
+    }
+
+    #[test]
+    fn push_and_pop_list_items() {
+        let list = Value::test_list(vec![Value::test_int(1), Value::test_int(2)]);
+
+        assert_eq!(
+            push_list_item(&list, &Value::test_nothing()),
+            Some(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2),
+                Value::test_nothing(),
+            ]))
+        );
+        assert_eq!(
+            pop_list_item(&list),
+            Some(Value::test_list(vec![Value::test_int(1)]))
+        );
+        assert_eq!(pop_list_item(&Value::test_list(vec![])), None);
+        assert_eq!(
+            push_list_item(&Value::test_record(record! {}), &Value::test_int(0)),
+            None
+        );
+        assert_eq!(pop_list_item(&Value::test_int(0)), None);
+    }
+
+    #[test]
+    fn filter_lists() {
+        let list = Value::test_list((1..=5).map(Value::test_int).collect());
+        let is_odd = |v: &Value| -> Result<bool, String> {
+            match v {
+                Value::Int { val, .. } => Ok(val % 2 == 1),
+                _ => Err("not an int".into()),
+            }
+        };
+
+        assert_eq!(
+            filter_list(&list, is_odd),
+            Some(Ok(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(3),
+                Value::test_int(5),
+            ])))
+        );
+        assert_eq!(
+            filter_list(
+                &Value::test_list(vec![Value::test_int(1), Value::test_string("a")]),
+                is_odd
             ),
-            // mutate a deeply-nested list element
+            Some(Err("not an int".into()))
+        );
+        assert_eq!(filter_list(&Value::test_int(1), is_odd), None);
+    }
+
+    #[test]
+    fn epoch_of_dates() {
+        let date = |s: &str| Value::test_date(s.parse().unwrap());
+
+        assert_eq!(
+            date_to_epoch(&date("2024-01-01T00:00:00+00:00")),
+            Some(1_704_067_200)
+        );
+        // NOTE: the offset is taken into account
+        assert_eq!(
+            date_to_epoch(&date("2024-01-01T01:00:00+01:00")),
+            Some(1_704_067_200)
+        );
+        assert_eq!(date_to_epoch(&date("1969-12-31T23:59:59+00:00")), Some(-1));
+        assert_eq!(date_to_epoch(&Value::test_int(1_704_067_200)), None);
+    }
+
+    #[test]
+    fn numbers_with_separators() {
+        let format = |value: Value| format_with_separators(&value, ",");
+
+        assert_eq!(format(Value::test_int(1234567)), Some("1,234,567".into()));
+        assert_eq!(format(Value::test_int(123)), Some("123".into()));
+        assert_eq!(format(Value::test_int(-1234)), Some("-1,234".into()));
+        assert_eq!(format(Value::test_int(0)), Some("0".into()));
+        assert_eq!(
+            format(Value::test_float(1234567.25)),
+            Some("1,234,567.25".into())
+        );
+        assert_eq!(
+            format_with_separators(&Value::test_int(1234567), "_"),
+            Some("1_234_567".into())
+        );
+        assert_eq!(format(Value::test_string("1234")), None);
+    }
+
+    #[test]
+    fn duplicate_cells() {
+        let list = Value::test_list(vec![Value::test_int(1), Value::test_int(2)]);
+        assert_eq!(
+            duplicate_cell(&list, &to_path_member_vec(&[PM::I(0)])[0]),
+            Some(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(1),
+                Value::test_int(2),
+            ]))
+        );
+        assert_eq!(
+            duplicate_cell(&list, &to_path_member_vec(&[PM::I(2)])[0]),
+            None
+        );
+
+        let record = Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "a_copy" => Value::test_int(2),
+            "b" => Value::test_int(3),
+        });
+        assert_eq!(
+            duplicate_cell(&record, &to_path_member_vec(&[PM::S("a")])[0]),
+            Some(Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "a_copy_2" => Value::test_int(1),
+                "a_copy" => Value::test_int(2),
+                "b" => Value::test_int(3),
+            }))
+        );
+        assert_eq!(
+            duplicate_cell(&record, &to_path_member_vec(&[PM::S("c")])[0]),
+            None
+        );
+    }
+
+    #[test]
+    fn rust_literal() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "f" => Value::test_float(2.0),
+            }),
+            "e" => Value::test_list(vec![]),
+            "n" => Value::test_nothing(),
+        });
+
+        let expected = r#"Value::test_record(record! {
+    "l" => Value::test_list(vec![
+        Value::test_string("my"),
+        Value::test_string("list"),
+    ]),
+    "r" => Value::test_record(record! {
+        "a" => Value::test_int(1),
+        "f" => Value::test_float(2.0),
+    }),
+    "e" => Value::test_list(vec![]),
+    "n" => Value::test_nothing(),
+})"#;
+        assert_eq!(value_to_rust_literal(&value), expected);
+
+        assert_eq!(
+            value_to_rust_literal(&Value::test_string("a \"quote\"")),
+            r#"Value::test_string("a \"quote\"")"#
+        );
+    }
+
+    #[test]
+    fn leaves() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_list(vec![Value::test_string("list")]),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "e" => Value::test_list(vec![]),
+        });
+
+        assert_eq!(
+            collect_leaves(value.get_data_by_key("r").as_ref().unwrap()),
+            vec![Value::test_int(1), Value::test_int(2)]
+        );
+        assert_eq!(
+            collect_leaves(&value),
+            vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_int(1),
+                Value::test_int(2),
+            ]
+        );
+        assert_eq!(
+            collect_leaves(&Value::test_int(123)),
+            vec![Value::test_int(123)]
+        );
+    }
+
+    #[test]
+    fn type_name() {
+        let list = Value::test_list(vec![
+            Value::test_string("my"),
+            Value::test_string("list"),
+            Value::test_string("elements"),
+        ]);
+        assert_eq!(repr_type(&list), "list<string>");
+
+        assert_eq!(repr_type(&Value::test_int(123)), "int");
+        assert_eq!(
+            repr_type(&Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_string("foo"),
+            })),
+            "record<a: int, b: string>"
+        );
+    }
+
+    #[test]
+    fn jsonpath_and_jmespath() {
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        let cases = vec![
+            (path(&[PM::S("r"), PM::S("a")]), "$.r.a", "r.a"),
+            (path(&[PM::S("l"), PM::I(1)]), "$.l[1]", "l[1]"),
             (
-                Value::test_list(vec![Value::test_list(vec![Value::test_list(vec![
-                    Value::test_string("foo"),
-                ])])]),
-                vec![PM::I(0), PM::I(0), PM::I(0)],
-                Value::test_string("bar"),
-                Value::test_list(vec![Value::test_list(vec![Value::test_list(vec![
-                    Value::test_string("bar"),
-                ])])]),
+                path(&[PM::S("l"), PM::I(1), PM::S("a")]),
+                "$.l[1].a",
+                "l[1].a",
             ),
-            // mutate a deeply-nested record field
+            (path(&[PM::I(0), PM::I(2)]), "$[0][2]", "[0][2]"),
             (
-                Value::test_record(record! {
-                    "a" => Value::test_record(record! {
-                        "b" => Value::test_record(record! {
-                            "c" => Value::test_string("foo"),
-                        }),
-                    }),
-                }),
-                vec![PM::S("a"), PM::S("b"), PM::S("c")],
-                Value::test_string("bar"),
-                Value::test_record(record! {
-                    "a" => Value::test_record(record! {
-                        "b" => Value::test_record(record! {
-                            "c" => Value::test_string("bar"),
-                        }),
-                    }),
-                }),
+                path(&[PM::S("a b"), PM::S("it's")]),
+                r#"$['a b']['it\'s']"#,
+                r#""a b"."it's""#,
             ),
+            (path(&[]), "$", "@"),
         ];
 
-        for (value, members, cell, expected) in cases {
-            let cell_path = CellPath {
-                members: to_path_member_vec(&members),
-            };
-
-            let result = mutate_value_cell(&value, &cell_path, &cell);
-            assert_eq!(
-                result,
-                expected,
-                "mutating {} at {:?} with {} should give {}, found {}",
-                default_value_repr(&value),
-                PM::as_cell_path(&members),
-                default_value_repr(&cell),
-                default_value_repr(&expected),
-                default_value_repr(&result)
-            );
+        for (path, jsonpath, jmespath) in cases {
+            assert_eq!(cell_path_to_jsonpath(&path), jsonpath);
+            assert_eq!(cell_path_to_jmespath(&path), jmespath);
         }
     }
 
+    #[test]
+    fn reverse_containers() {
+        assert_eq!(
+            reverse_container(Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_int(2),
+                Value::test_int(3),
+            ])),
+            Value::test_list(vec![
+                Value::test_int(3),
+                Value::test_int(2),
+                Value::test_int(1),
+            ])
+        );
+        assert_eq!(
+            reverse_container(Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            })),
+            Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "a" => Value::test_int(1),
+            })
+        );
+        assert_eq!(reverse_container(Value::test_int(1)), Value::test_int(1));
+    }
+
+    #[test]
+    fn relative_paths() {
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            relative_path(&path(&[PM::S("r")]), &path(&[PM::S("r"), PM::S("a")])),
+            Some(path(&[PM::S("a")]))
+        );
+        assert_eq!(
+            relative_path(&path(&[]), &path(&[PM::S("l"), PM::I(1)])),
+            Some(path(&[PM::S("l"), PM::I(1)]))
+        );
+        assert_eq!(
+            relative_path(&path(&[PM::S("r")]), &path(&[PM::S("r")])),
+            Some(path(&[]))
+        );
+        assert_eq!(
+            relative_path(&path(&[PM::S("r")]), &path(&[PM::S("s"), PM::S("a")])),
+            None
+        );
+        assert_eq!(
+            relative_path(&path(&[PM::S("r"), PM::S("a")]), &path(&[PM::S("r")])),
+            None
+        );
+        assert_eq!(
+            relative_path(&path(&[PM::I(0)]), &path(&[PM::S("0")])),
+            None
+        );
+    }
+
+    #[test]
+    fn get_closure() {
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            path_to_get_closure(&path(&[PM::S("r"), PM::S("a")])),
+            "{|| get r.a}"
+        );
+        assert_eq!(
+            path_to_get_closure(&path(&[PM::S("l"), PM::I(2), PM::S("my-key")])),
+            "{|| get l.2.my-key}"
+        );
+        assert_eq!(
+            path_to_get_closure(&path(&[PM::S("a b"), PM::S("12"), PM::S("x\"y")])),
+            r#"{|| get "a b"."12"."x\"y"}"#
+        );
+        assert_eq!(path_to_get_closure(&path(&[])), "{|| $in}");
+    }
+
+    #[test]
+    fn where_clause() {
+        assert_eq!(
+            to_where_clause("name", &Value::test_string("foo")),
+            r#"where name == "foo""#
+        );
+        assert_eq!(
+            to_where_clause("my key", &Value::test_int(1)),
+            r#"where "my key" == 1"#
+        );
+        assert_eq!(
+            to_where_clause("size", &Value::test_filesize(1024)),
+            "where size == 1024b"
+        );
+    }
+
+    #[test]
+    fn update_pipeline() {
+        let path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            to_update_pipeline(&path(&[PM::S("r"), PM::S("a")]), &Value::test_int(9)),
+            "$in | update r.a { 9 }"
+        );
+        assert_eq!(
+            to_update_pipeline(
+                &path(&[PM::S("a b"), PM::I(1)]),
+                &Value::test_list(vec![Value::test_string("x")])
+            ),
+            r#"$in | update "a b".1 { ["x"] }"#
+        );
+        assert_eq!(
+            to_update_pipeline(&path(&[PM::S("d")]), &Value::test_duration(1_000)),
+            "$in | update d { 1000ns }"
+        );
+        assert_eq!(
+            to_update_pipeline(&path(&[]), &Value::test_bool(true)),
+            "$in | do { true }"
+        );
+    }
+
+    #[test]
+    fn envish_strings() {
+        assert_eq!(
+            parse_envish("/usr/local/bin:/usr/bin:/bin"),
+            Some(Value::test_list(vec![
+                Value::test_string("/usr/local/bin"),
+                Value::test_string("/usr/bin"),
+                Value::test_string("/bin"),
+            ]))
+        );
+        assert_eq!(
+            parse_envish(r"C:\bin;D:\tools\bin;"),
+            Some(Value::test_list(vec![
+                Value::test_string(r"C:\bin"),
+                Value::test_string(r"D:\tools\bin"),
+            ]))
+        );
+        assert_eq!(
+            parse_envish("k=v;k2=v2"),
+            Some(Value::test_record(record! {
+                "k" => Value::test_string("v"),
+                "k2" => Value::test_string("v2"),
+            }))
+        );
+        assert_eq!(
+            parse_envish("A=1\nB=x=y\n"),
+            Some(Value::test_record(record! {
+                "A" => Value::test_string("1"),
+                "B" => Value::test_string("x=y"),
+            }))
+        );
+
+        assert_eq!(parse_envish("k=v;oops"), None);
+        assert_eq!(parse_envish("/usr/bin"), None);
+        assert_eq!(parse_envish("some string"), None);
+    }
+
+    #[test]
+    fn json_string() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_string("my"), Value::test_nothing()]),
+            "r" => Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "a" => Value::test_float(1.5),
+            }),
+        });
+
+        let r = value.get_data_by_key("r").unwrap();
+        // NOTE: the keys keep their order
+        assert_eq!(repr_json(&r), "{\n  \"b\": 2,\n  \"a\": 1.5\n}");
+        assert_eq!(
+            repr_json(&value),
+            "{\n  \"l\": [\n    \"my\",\n    null\n  ],\n  \"r\": {\n    \"b\": 2,\n    \"a\": 1.5\n  }\n}"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_string() {
+        use super::to_yaml;
+
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_string("my"), Value::test_nothing()]),
+            "r" => Value::test_record(record! {
+                "b" => Value::test_int(2),
+                "a" => Value::test_float(1.5),
+            }),
+            "s" => Value::test_string("some: string"),
+        });
+
+        let yaml = to_yaml(&value).unwrap();
+        assert_eq!(
+            yaml,
+            "l:\n- my\n- null\nr:\n  b: 2\n  a: 1.5\ns: 'some: string'\n"
+        );
+        // NOTE: the YAML is valid and gives the same data back
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(
+            serde_yaml::to_string(&parsed).unwrap(),
+            yaml,
+            "the YAML does not round-trip"
+        );
+    }
+
+    #[test]
+    fn search() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        });
+
+        let cell_path = |members: &[PM]| CellPath {
+            members: to_path_member_vec(members),
+        };
+
+        assert_eq!(
+            find_matches(&value, "list"),
+            vec![cell_path(&[PM::S("l"), PM::I(1)])]
+        );
+        assert_eq!(
+            find_matches(&value, "S"),
+            vec![
+                cell_path(&[PM::S("l"), PM::I(1)]),
+                cell_path(&[PM::S("l"), PM::I(2)]),
+                cell_path(&[PM::S("s")]),
+            ]
+        );
+        assert_eq!(
+            find_matches(&value, "1"),
+            vec![
+                cell_path(&[PM::S("r"), PM::S("a")]),
+                cell_path(&[PM::S("i")])
+            ]
+        );
+        assert!(find_matches(&value, "foo").is_empty());
+        assert!(find_matches(&Value::test_string("foo"), "foo").is_empty());
+
+        let results = search_results(&value, &find_matches(&value, "list"));
+        let expected = Value::test_list(vec![Value::test_record(record! {
+            "path" => Value::test_cell_path(cell_path(&[PM::S("l"), PM::I(1)])),
+            "value" => Value::test_string("list"),
+        })]);
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn infer_table_columns() {
+        let table = Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("alice"),
+                "age" => Value::test_int(31),
+                "score" => Value::test_nothing(),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("bob"),
+                "age" => Value::test_int(27),
+                "score" => Value::test_float(1.5),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("carol"),
+                "age" => Value::test_int(45),
+                "score" => Value::test_int(2),
+            }),
+        ]);
+
+        assert_eq!(
+            infer_columns(&table),
+            vec![
+                ("name".to_string(), Type::String),
+                ("age".to_string(), Type::Int),
+                ("score".to_string(), Type::Number),
+            ]
+        );
+
+        assert_eq!(infer_columns(&Value::test_list(vec![])), vec![]);
+        assert_eq!(infer_columns(&Value::test_int(1)), vec![]);
+    }
+
     #[test]
     fn is_a_table() {
         let table = Value::test_list(vec![
@@ -0,0 +1,119 @@
+//! open values in the pager of the system, e.g. to read a very long string with `less`
+//!
+//! the pager is the command in the `PAGER` environment variable, with its arguments if any, or
+//! `less` by default.
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use nu_protocol::Value;
+
+use crate::nu::value::repr_json;
+
+/// the pager used when `PAGER` is not set
+const DEFAULT_PAGER: &str = "less";
+
+/// give the text of a value as it is fed to the pager
+///
+/// strings are given as they are, to read them with their line breaks, and any other value as
+/// pretty-printed JSON.
+pub(crate) fn pager_text(value: &Value) -> String {
+    match value {
+        Value::String { val, .. } => val.clone(),
+        x => repr_json(x),
+    }
+}
+
+/// split a pager command into its program and its arguments, falling back to [`DEFAULT_PAGER`]
+fn pager_command(pager: Option<&str>) -> (String, Vec<String>) {
+    let mut parts = pager
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(String::from);
+    match parts.next() {
+        Some(program) => (program, parts.collect()),
+        None => (DEFAULT_PAGER.into(), vec![]),
+    }
+}
+
+/// write *text* to the standard input of the pager and wait for the user to quit it
+///
+/// the terminal should be given back to the user before, see
+/// [`Tui::suspend`](crate::tui::Tui::suspend).
+pub(crate) fn open(text: &str) -> Result<(), String> {
+    let (program, args) = pager_command(std::env::var("PAGER").ok().as_deref());
+
+    // NOTE: the standard output of the plugin is the channel to Nushell, so the pager writes to
+    // the terminal through the standard error instead, like the rest of the TUI
+    run_pager(&program, &args, text, Stdio::from(io::stderr()))
+}
+
+/// run the pager *program* with its *args*, writing *text* to its standard input, its output going
+/// to the *terminal*
+fn run_pager(program: &str, args: &[String], text: &str, terminal: Stdio) -> Result<(), String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(terminal)
+        .spawn()
+        .map_err(|err| format!("could not run the pager `{program}`: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // NOTE: the pager may be quit before reading all the text
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child
+        .wait()
+        .map_err(|err| format!("could not wait for the pager `{program}`: {err}"))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Stdio;
+
+    use nu_protocol::{record, Value};
+
+    use super::{pager_command, pager_text, run_pager};
+
+    #[test]
+    fn text_for_the_pager() {
+        assert_eq!(
+            pager_text(&Value::test_string("line 1\nline 2")),
+            "line 1\nline 2"
+        );
+        assert_eq!(
+            pager_text(&Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "l" => Value::test_list(vec![Value::test_bool(true)]),
+            })),
+            "{\n  \"a\": 1,\n  \"l\": [\n    true\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn commands_of_the_pager() {
+        assert_eq!(pager_command(None), ("less".into(), vec![]));
+        assert_eq!(pager_command(Some("  ")), ("less".into(), vec![]));
+        assert_eq!(
+            pager_command(Some("less -R -S")),
+            ("less".into(), vec!["-R".into(), "-S".into()])
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pager_writes_to_terminal() {
+        let file = std::env::temp_dir().join(format!(
+            "nu_plugin_explore_pager_{}.txt",
+            std::process::id()
+        ));
+        let terminal = Stdio::from(std::fs::File::create(&file).unwrap());
+
+        run_pager("cat", &[], "line 1\nline 2", terminal).unwrap();
+        let written = std::fs::read_to_string(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(written, "line 1\nline 2");
+
+        assert!(run_pager("not-a-pager", &[], "", Stdio::null()).is_err());
+    }
+}
@@ -0,0 +1,552 @@
+//! flatten the data into the rows of the tree view and navigate in it
+use std::collections::HashSet;
+
+use nu_protocol::{ast::PathMember, Span, Value};
+
+use crate::{
+    app::App,
+    config::Config,
+    navigation::{self, Direction},
+};
+
+/// the maximum depth to which [`expand_all`] goes when triggered by the user
+///
+/// this avoids huge expansions, and thus huge renders, of very deep data.
+pub(crate) const EXPAND_ALL_MAX_DEPTH: usize = 5;
+
+/// a hashable version of a [`PathMember`], without the span
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Key {
+    /// the [`PathMember::String`] variant
+    Name(String),
+    /// the [`PathMember::Int`] variant
+    Index(usize),
+}
+
+/// the set of the nodes of the tree that are expanded, identified by their path
+pub(crate) type ExpandedPaths = HashSet<Vec<Key>>;
+
+/// convert a *cell path* to a path of hashable [`Key`]s
+pub(crate) fn to_keys(members: &[PathMember]) -> Vec<Key> {
+    members
+        .iter()
+        .map(|m| match m {
+            PathMember::String { val, .. } => Key::Name(val.clone()),
+            PathMember::Int { val, .. } => Key::Index(*val),
+        })
+        .collect()
+}
+
+/// a single visible row of the tree view
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TreeRow {
+    /// the full path to the node of the row
+    pub path: Vec<PathMember>,
+    /// the depth of the node, the children of the root being at depth 0
+    pub depth: usize,
+    /// the name of the node in its parent, either a column or an index
+    pub name: String,
+    /// whether the node is a non-empty list or record
+    pub is_container: bool,
+    /// whether the node is shown with its children
+    pub is_expanded: bool,
+    /// the number of items or fields of the node if it's a list or a record
+    pub size: Option<usize>,
+    /// the value of the node
+    pub value: Value,
+}
+
+/// give the children of a value with the *cell path* member to reach them
+///
+/// only lists and records have children.
+fn children(value: &Value) -> Vec<(PathMember, &Value)> {
+    match value {
+        Value::List { vals, .. } => vals
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let member = PathMember::Int {
+                    val: i,
+                    span: Span::unknown(),
+                    optional: false,
+                };
+                (member, v)
+            })
+            .collect(),
+        Value::Record { val: rec, .. } => rec
+            .iter()
+            .map(|(c, v)| {
+                let member = PathMember::String {
+                    val: c.clone(),
+                    span: Span::unknown(),
+                    optional: false,
+                };
+                (member, v)
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn push_rows(
+    value: &Value,
+    path: &[PathMember],
+    depth: usize,
+    expanded: &ExpandedPaths,
+    rows: &mut Vec<TreeRow>,
+) {
+    for (member, child) in children(value) {
+        let mut child_path = path.to_vec();
+        child_path.push(member.clone());
+
+        let size = match child {
+            Value::List { vals, .. } => Some(vals.len()),
+            Value::Record { val: rec, .. } => Some(rec.len()),
+            _ => None,
+        };
+        let is_container = size.is_some_and(|size| size > 0);
+        let is_expanded = is_container && expanded.contains(&to_keys(&child_path));
+
+        rows.push(TreeRow {
+            path: child_path.clone(),
+            depth,
+            name: match member {
+                PathMember::String { val, .. } => val,
+                PathMember::Int { val, .. } => val.to_string(),
+            },
+            is_container,
+            is_expanded,
+            size,
+            value: child.clone(),
+        });
+
+        if is_expanded {
+            push_rows(child, &child_path, depth + 1, expanded, rows);
+        }
+    }
+}
+
+/// flatten the data into the rows that are visible in the tree view
+///
+/// the root itself is not shown, its children are the rows at depth 0 and the children of a
+/// node are only shown when the node is in the *expanded* set.
+pub(crate) fn visible_rows(value: &Value, expanded: &ExpandedPaths) -> Vec<TreeRow> {
+    let mut rows = vec![];
+    push_rows(value, &[], 0, expanded, &mut rows);
+    rows
+}
+
+/// flatten the whole data into the depth of each of its nodes, in the order of the fully expanded
+/// tree view, along with the index of the node at *members* if there is one
+///
+/// unlike [`visible_rows`], the nodes are not cloned, to keep this cheap on big data.
+pub(crate) fn node_depths(value: &Value, members: &[PathMember]) -> (Vec<usize>, Option<usize>) {
+    fn walk(
+        value: &Value,
+        depth: usize,
+        path: &mut Vec<Key>,
+        target: &[Key],
+        depths: &mut Vec<usize>,
+        index: &mut Option<usize>,
+    ) {
+        for (member, child) in children(value) {
+            path.push(to_keys(&[member])[0].clone());
+            if path.as_slice() == target {
+                *index = Some(depths.len());
+            }
+            depths.push(depth);
+            walk(child, depth + 1, path, target, depths, index);
+            path.pop();
+        }
+    }
+
+    let mut depths = vec![];
+    let mut index = None;
+    walk(
+        value,
+        0,
+        &mut vec![],
+        &to_keys(members),
+        &mut depths,
+        &mut index,
+    );
+    (depths, index)
+}
+
+/// compute the set of all the nodes of the data that are expanded at most *max_depth* levels deep
+///
+/// with a *max_depth* of 1, only the direct children of the root are expanded.
+pub(crate) fn expand_all(value: &Value, max_depth: usize) -> ExpandedPaths {
+    let mut expanded = HashSet::new();
+
+    let mut stack = vec![(vec![], value, 0)];
+    while let Some((path, value, depth)) = stack.pop() {
+        if depth >= max_depth {
+            continue;
+        }
+
+        for (member, child) in children(value) {
+            if children(child).is_empty() {
+                continue;
+            }
+
+            let mut child_path = path.clone();
+            child_path.push(to_keys(&[member])[0].clone());
+            expanded.insert(child_path.clone());
+            stack.push((child_path, child, depth + 1));
+        }
+    }
+
+    expanded
+}
+
+/// expand all the ancestors of a node so that it is visible in the tree view
+pub(crate) fn expand_ancestors(expanded: &mut ExpandedPaths, members: &[PathMember]) {
+    let keys = to_keys(members);
+    for i in 1..keys.len() {
+        expanded.insert(keys[..i].to_vec());
+    }
+}
+
+/// collapse all the nodes that are not on the path to a node, i.e. all but its ancestors and the
+/// node itself, to focus on it in the tree view
+pub(crate) fn collapse_others(expanded: &mut ExpandedPaths, members: &[PathMember]) {
+    let current = to_keys(members);
+    expanded.retain(|path| current.starts_with(path));
+    expand_ancestors(expanded, members);
+}
+
+/// go up or down in the visible rows of the tree, wrapping around at both ends
+pub(crate) fn go_up_or_down(app: &mut App, direction: Direction) {
+    if app.is_at_bottom() {
+        return;
+    }
+
+    let rows = visible_rows(&app.value, &app.expanded);
+    if rows.is_empty() {
+        return;
+    }
+
+    let current = to_keys(&app.position.members);
+    let index = rows
+        .iter()
+        .position(|r| to_keys(&r.path) == current)
+        .unwrap_or(0) as i32;
+    let len = rows.len() as i32;
+    let new_index = match direction {
+        Direction::Up => (index - 1 + len) % len,
+        Direction::Down => (index + 1) % len,
+    };
+
+    app.position.members = rows[new_index as usize].path.clone();
+}
+
+/// go to the previous or next sibling of the current node, skipping the rows of the expanded
+/// children and wrapping around in its container, see [`crate::app::App::scope_lock`]
+pub(crate) fn go_up_or_down_in_container(app: &mut App, direction: Direction) {
+    if app.is_at_bottom() {
+        return;
+    }
+
+    let current = to_keys(&app.position.members);
+    let Some((_, container)) = current.split_last() else {
+        return;
+    };
+    let is_sibling = |keys: &[Key]| keys.len() == current.len() && keys.starts_with(container);
+
+    let rows = visible_rows(&app.value, &app.expanded);
+    let Some(index) = rows.iter().position(|r| to_keys(&r.path) == current) else {
+        return;
+    };
+    let len = rows.len();
+    let sibling = (1..len)
+        .map(|step| match direction {
+            Direction::Up => (index + len - step) % len,
+            Direction::Down => (index + step) % len,
+        })
+        .find(|&i| is_sibling(&to_keys(&rows[i].path)));
+
+    if let Some(i) = sibling {
+        app.position.members = rows[i].path.clone();
+    }
+}
+
+/// expand the current node and go to its first child or, on a leaf, go to the bottom
+pub(crate) fn go_deeper(app: &mut App, config: &Config) {
+    let cell = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+        .unwrap_or_else(|_| {
+            panic!(
+                "unexpected error when following {:?} in {}",
+                app.position.members,
+                app.value
+                    .to_expanded_string(" ", &nu_protocol::Config::default())
+            )
+        });
+
+    match children(&cell).into_iter().next() {
+        Some(_) if app.is_at_max_depth() => app.message = Some(navigation::MAX_DEPTH.into()),
+        Some((member, _)) => {
+            app.expanded.insert(to_keys(&app.position.members));
+            app.position.members.push(member);
+        }
+        None => navigation::go_deeper_in_data(app, config),
+    }
+}
+
+/// collapse the current node if it is expanded or, otherwise, go to its parent
+pub(crate) fn collapse_or_go_to_parent(app: &mut App) {
+    if app.expanded.remove(&to_keys(&app.position.members)) {
+        return;
+    }
+
+    if app.position.members.len() > 1 {
+        app.position.members.pop();
+    }
+}
+
+/// scroll the tree view so that the current node is in the middle of the data pane
+///
+/// the scroll is clamped at the first row of the tree, so the nodes near the top of the data stay
+/// where they are, above the middle of the pane. near the bottom, the node is centered anyway,
+/// with empty lines below the last row.
+///
+/// with [`Config::collapse_on_center`], the siblings of the node are collapsed first, to only
+/// show the node and its ancestors.
+pub(crate) fn center(app: &mut App, config: &Config) {
+    let current = to_keys(&app.position.members);
+
+    if config.collapse_on_center {
+        if let Some((last, parent)) = current.split_last() {
+            app.expanded.retain(|path| {
+                path.len() <= parent.len()
+                    || path[..parent.len()] != *parent
+                    || path[parent.len()] == *last
+            });
+        }
+    }
+
+    let selected = visible_rows(&app.value, &app.expanded)
+        .iter()
+        .position(|r| to_keys(&r.path) == current)
+        .unwrap_or(0);
+    // NOTE: the status bar, and the cell path when shown, are below the data pane
+    let height = app
+        .height
+        .saturating_sub(app.bars_height(config) + 2 * app.border_width(config))
+        as usize;
+
+    app.tree_offset = selected.saturating_sub(height / 2);
+}
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::{record, Value};
+
+    use super::{
+        center, collapse_or_go_to_parent, collapse_others, expand_all, node_depths, visible_rows,
+        ExpandedPaths, Key,
+    };
+    use crate::{
+        app::App,
+        config::Config,
+        nu::cell_path::{to_path_member_vec, PM},
+    };
+
+    fn test_value() -> Value {
+        Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_string("my"),
+                Value::test_string("list"),
+                Value::test_string("elements"),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        })
+    }
+
+    fn names(value: &Value, expanded: &ExpandedPaths) -> Vec<(usize, String)> {
+        visible_rows(value, expanded)
+            .into_iter()
+            .map(|r| (r.depth, r.name))
+            .collect()
+    }
+
+    #[test]
+    fn depths_of_all_nodes() {
+        let value = test_value();
+
+        let (depths, index) = node_depths(&value, &to_path_member_vec(&[PM::S("r"), PM::S("a")]));
+        assert_eq!(depths, vec![0, 1, 1, 1, 0, 1, 1, 0, 0]);
+        assert_eq!(index, Some(5));
+
+        let (_, index) = node_depths(&value, &to_path_member_vec(&[PM::S("l"), PM::I(0)]));
+        assert_eq!(index, Some(1));
+        assert_eq!(node_depths(&value, &[]).1, None);
+    }
+
+    #[test]
+    fn collapsed_tree() {
+        let value = test_value();
+        assert_eq!(
+            names(&value, &ExpandedPaths::new()),
+            vec![
+                (0, "l".into()),
+                (0, "r".into()),
+                (0, "s".into()),
+                (0, "i".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_one_node() {
+        let value = test_value();
+        let expanded = ExpandedPaths::from([vec![Key::Name("r".into())]]);
+        assert_eq!(
+            names(&value, &expanded),
+            vec![
+                (0, "l".into()),
+                (0, "r".into()),
+                (1, "a".into()),
+                (1, "b".into()),
+                (0, "s".into()),
+                (0, "i".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_all_nodes() {
+        let value = test_value();
+
+        assert_eq!(visible_rows(&value, &expand_all(&value, 0)).len(), 4);
+        assert_eq!(visible_rows(&value, &expand_all(&value, 1)).len(), 9);
+        assert_eq!(visible_rows(&value, &expand_all(&value, 2)).len(), 9);
+
+        let nested = Value::test_record(record! {
+            "a" => Value::test_record(record! {
+                "b" => Value::test_record(record! {
+                    "c" => Value::test_record(record! {
+                        "d" => Value::test_int(0),
+                    }),
+                }),
+            }),
+        });
+        assert_eq!(visible_rows(&nested, &expand_all(&nested, 2)).len(), 3);
+        assert_eq!(visible_rows(&nested, &expand_all(&nested, 10)).len(), 4);
+    }
+
+    #[test]
+    fn center_on_node() {
+        let mut app = App::from_value(Value::test_list((0..50).map(Value::test_int).collect()));
+        app.position.members = to_path_member_vec(&[PM::I(20)]);
+        app.height = 12;
+
+        // NOTE: the data pane is 10 rows high, below the status bar and the cell path
+        center(&mut app, &Config::default());
+        assert_eq!(app.tree_offset, 15);
+
+        app.position.members = to_path_member_vec(&[PM::I(2)]);
+        center(&mut app, &Config::default());
+        assert_eq!(app.tree_offset, 0);
+    }
+
+    #[test]
+    fn center_and_collapse_siblings() {
+        let mut app = App::from_value(test_value());
+        app.expanded = expand_all(&app.value, 10);
+        app.position.members = to_path_member_vec(&[PM::S("r")]);
+
+        let config = Config {
+            collapse_on_center: true,
+            ..Default::default()
+        };
+        center(&mut app, &config);
+        assert_eq!(
+            app.expanded,
+            ExpandedPaths::from([vec![Key::Name("r".into())]])
+        );
+    }
+
+    #[test]
+    fn collapse_all_but_ancestors() {
+        let value = Value::test_record(record! {
+            "n" => Value::test_record(record! {
+                "r" => Value::test_record(record! {
+                    "a" => Value::test_record(record! { "x" => Value::test_int(1) }),
+                }),
+                "l" => Value::test_list(vec![Value::test_list(vec![Value::test_int(1)])]),
+            }),
+            "m" => Value::test_record(record! { "b" => Value::test_int(2) }),
+        });
+        let mut expanded = expand_all(&value, 10);
+
+        collapse_others(
+            &mut expanded,
+            &to_path_member_vec(&[PM::S("n"), PM::S("r"), PM::S("a")]),
+        );
+        // NOTE: the focused node itself stays expanded
+        assert_eq!(
+            expanded,
+            ExpandedPaths::from([
+                vec![Key::Name("n".into())],
+                vec![Key::Name("n".into()), Key::Name("r".into())],
+                vec![
+                    Key::Name("n".into()),
+                    Key::Name("r".into()),
+                    Key::Name("a".into())
+                ],
+            ])
+        );
+        assert_eq!(
+            names(&value, &expanded),
+            vec![
+                (0, "n".into()),
+                (1, "r".into()),
+                (2, "a".into()),
+                (3, "x".into()),
+                (1, "l".into()),
+                (0, "m".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapse_then_go_to_parent() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "n" => Value::test_record(record! {
+                "r" => Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                }),
+            }),
+        }));
+        app.expanded = expand_all(&app.value, 10);
+        app.position.members = to_path_member_vec(&[PM::S("n"), PM::S("r")]);
+
+        collapse_or_go_to_parent(&mut app);
+        assert_eq!(
+            app.position.members,
+            to_path_member_vec(&[PM::S("n"), PM::S("r")])
+        );
+        assert_eq!(
+            app.expanded,
+            ExpandedPaths::from([vec![Key::Name("n".into())]])
+        );
+
+        collapse_or_go_to_parent(&mut app);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("n")]));
+
+        // NOTE: the top-level nodes have no parent to go to
+        collapse_or_go_to_parent(&mut app);
+        collapse_or_go_to_parent(&mut app);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("n")]));
+        assert!(app.expanded.is_empty());
+    }
+}
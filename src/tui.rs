@@ -1,17 +1,51 @@
 use anyhow::Result;
+use crossterm::cursor::SetCursorStyle;
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::Backend;
 use ratatui::prelude::Rect;
 use ratatui::Terminal;
-use std::io;
+use std::io::{self, Write};
 use std::panic;
 
-use crate::app::App;
+use crate::app::{App, Mode};
 use crate::config::Config;
 use crate::event::EventHandler;
 use crate::ui;
 
+/// Sets the terminal properties up, writing the commands to the *writer*.
+///
+/// The alternate screen is only entered with *alt_screen*, otherwise the interface is rendered
+/// inline, in the main screen of the terminal.
+pub fn setup_terminal<W: Write>(writer: &mut W, alt_screen: bool) -> io::Result<()> {
+    if alt_screen {
+        crossterm::execute!(writer, EnterAlternateScreen)?;
+    }
+    crossterm::execute!(writer, EnableMouseCapture)
+}
+
+/// Reverts the terminal properties set by [`setup_terminal`] and [`set_cursor_style`].
+pub fn restore_terminal<W: Write>(writer: &mut W, alt_screen: bool) -> io::Result<()> {
+    if alt_screen {
+        crossterm::execute!(writer, LeaveAlternateScreen)?;
+    }
+    crossterm::execute!(
+        writer,
+        DisableMouseCapture,
+        SetCursorStyle::DefaultUserShape
+    )
+}
+
+/// Sets the shape of the cursor for the *mode*, writing the command to the *writer*.
+///
+/// The cursor is a bar in INSERT and FORM modes, where text is typed, and a block otherwise.
+pub fn set_cursor_style<W: Write>(writer: &mut W, mode: &Mode) -> io::Result<()> {
+    match mode {
+        Mode::Insert | Mode::Form => crossterm::execute!(writer, SetCursorStyle::SteadyBar),
+        _ => crossterm::execute!(writer, SetCursorStyle::SteadyBlock),
+    }
+}
+
 /// Representation of a terminal user interface.
 ///
 /// It is responsible for setting up the terminal,
@@ -22,12 +56,21 @@ pub struct Tui<B: Backend> {
     terminal: Terminal<B>,
     /// Terminal event handler.
     pub events: EventHandler,
+    /// Whether to use the alternate screen of the terminal.
+    alt_screen: bool,
+    /// The mode of the last shape given to the cursor, if any, see [`set_cursor_style`].
+    cursor_mode: Option<Mode>,
 }
 
 impl<B: Backend> Tui<B> {
     /// Constructs a new instance of [`Tui`].
-    pub fn new(terminal: Terminal<B>, events: EventHandler) -> Self {
-        Self { terminal, events }
+    pub fn new(terminal: Terminal<B>, events: EventHandler, alt_screen: bool) -> Self {
+        Self {
+            terminal,
+            events,
+            alt_screen,
+            cursor_mode: None,
+        }
     }
 
     /// Initializes the terminal interface.
@@ -35,13 +78,14 @@ impl<B: Backend> Tui<B> {
     /// It enables the raw mode and sets terminal properties.
     pub fn init(&mut self) -> Result<()> {
         terminal::enable_raw_mode()?;
-        crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+        setup_terminal(&mut io::stderr(), self.alt_screen)?;
 
         // Define a custom panic hook to reset the terminal properties.
         // This way, you won't have your terminal messed up if an unexpected error happens.
+        let alt_screen = self.alt_screen;
         let panic_hook = panic::take_hook();
         panic::set_hook(Box::new(move |panic| {
-            Self::reset().expect("failed to reset the terminal");
+            Self::reset(alt_screen).expect("failed to reset the terminal");
             panic_hook(panic);
         }));
 
@@ -60,13 +104,22 @@ impl<B: Backend> Tui<B> {
         Ok(())
     }
 
+    /// Sets the shape of the cursor for the *mode*, only when it changes.
+    pub fn set_cursor_style(&mut self, mode: &Mode) -> Result<()> {
+        if self.cursor_mode.as_ref() != Some(mode) {
+            set_cursor_style(&mut io::stderr(), mode)?;
+            self.cursor_mode = Some(mode.clone());
+        }
+        Ok(())
+    }
+
     /// Resets the terminal interface.
     ///
     /// This function is also used for the panic hook to revert
     /// the terminal properties if unexpected errors occur.
-    fn reset() -> Result<()> {
+    fn reset(alt_screen: bool) -> Result<()> {
         terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture)?;
+        restore_terminal(&mut io::stderr(), alt_screen)?;
         Ok(())
     }
 
@@ -74,12 +127,82 @@ impl<B: Backend> Tui<B> {
     ///
     /// It disables the raw mode and reverts back the terminal properties.
     pub fn exit(&mut self) -> Result<()> {
-        Self::reset()?;
+        Self::reset(self.alt_screen)?;
         self.terminal.show_cursor()?;
         Ok(())
     }
 
+    /// Gives the terminal back to the user, e.g. to run a pager.
+    ///
+    /// The events of the terminal are not read until [`Tui::resume`] is called.
+    pub fn suspend(&mut self) -> Result<()> {
+        self.events.pause();
+        self.exit()
+    }
+
+    /// Takes the terminal back after [`Tui::suspend`].
+    pub fn resume(&mut self) -> Result<()> {
+        terminal::enable_raw_mode()?;
+        setup_terminal(&mut io::stderr(), self.alt_screen)?;
+        // NOTE: the shape of the cursor has been restored by the suspension
+        self.cursor_mode = None;
+        self.terminal.hide_cursor()?;
+        self.terminal.clear()?;
+        self.events.resume();
+        Ok(())
+    }
+
     pub fn size(&self) -> Result<Rect> {
         Ok(self.terminal.size()?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{restore_terminal, set_cursor_style, setup_terminal};
+    use crate::app::Mode;
+
+    const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+    const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+    const DEFAULT_CURSOR: &str = "\x1b[0 q";
+    const BLOCK_CURSOR: &str = "\x1b[2 q";
+    const BAR_CURSOR: &str = "\x1b[6 q";
+
+    fn commands(f: fn(&mut Vec<u8>, bool) -> std::io::Result<()>, alt_screen: bool) -> String {
+        let mut writer = vec![];
+        f(&mut writer, alt_screen).unwrap();
+        String::from_utf8(writer).unwrap()
+    }
+
+    #[test]
+    fn alt_screen() {
+        assert!(commands(setup_terminal, true).contains(ENTER_ALT_SCREEN));
+        assert!(commands(restore_terminal, true).contains(LEAVE_ALT_SCREEN));
+    }
+
+    #[test]
+    fn cursor_style() {
+        let style = |mode| {
+            let mut writer = vec![];
+            set_cursor_style(&mut writer, &mode).unwrap();
+            String::from_utf8(writer).unwrap()
+        };
+
+        assert_eq!(style(Mode::Insert), BAR_CURSOR);
+        assert_eq!(style(Mode::Normal), BLOCK_CURSOR);
+        assert_eq!(style(Mode::Peeking), BLOCK_CURSOR);
+
+        // NOTE: the shape of the user is restored on exit
+        assert!(commands(restore_terminal, false).contains(DEFAULT_CURSOR));
+    }
+
+    #[test]
+    fn no_alt_screen() {
+        let setup = commands(setup_terminal, false);
+        assert!(!setup.contains(ENTER_ALT_SCREEN));
+        // NOTE: the rest of the setup still happens
+        assert!(!setup.is_empty());
+
+        assert!(!commands(restore_terminal, false).contains(LEAVE_ALT_SCREEN));
+    }
+}
@@ -1,38 +1,382 @@
 //! the module responsible for rendering the TUI
-use crate::nu::{strings::SpecialString, value::is_table};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::time::Instant;
 
-use super::config::{repr_keycode, Layout};
+use crate::ansi;
+use crate::fold::FoldedJson;
+use crate::mouse::visible_view_rows;
+use crate::navigation::{
+    is_container, is_missing, record_group, table_columns_fit, visible_columns,
+};
+use crate::nu::{
+    custom::Converted,
+    strings::{describe_data_uri, SpecialString},
+    value::{
+        infer_columns, is_numeric_list, is_table, repr_debug, repr_nu_key, reverse_container,
+        to_json,
+    },
+};
+use crate::tree::{node_depths, to_keys, visible_rows};
+
+use super::config::{
+    repr_keycode, BreadcrumbStyle, Layout, LeafView, MissingDisplay, PreviewConfig,
+    ScrollbarVisibility,
+};
 use super::{App, Config, Mode};
+use crate::app::{PendingKind, RendererRegistry};
 use crossterm::event::KeyCode;
-use nu_protocol::ast::PathMember;
-use nu_protocol::{Record, Type, Value};
+use nu_protocol::ast::{CellPath, PathMember};
+use nu_protocol::{CustomValue, Record, Type, Value};
 use ratatui::{
     prelude::{Alignment, Constraint, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{
-        Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+        block::{Position, Title},
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Sparkline, Table, TableState, Tabs, Wrap,
     },
     Frame,
 };
 
+/// below this width, the data is shown in a single column, as `key: preview`
+pub(crate) const SINGLE_COLUMN_MAX_WIDTH: u16 = 40;
+
+/// tell whether the data is shown in a single column in a terminal *width* cells wide, see
+/// [`SINGLE_COLUMN_MAX_WIDTH`]
+pub(crate) fn is_single_column(width: u16, config: &Config) -> bool {
+    // NOTE: the tree view is already a single column
+    width < SINGLE_COLUMN_MAX_WIDTH && config.layout != Layout::Tree
+}
+
 /// render the whole ui
 pub(super) fn render_ui(frame: &mut Frame, app: &App, config: &Config, error: Option<&str>) {
-    render_data(frame, app, config);
-    if config.show_cell_path {
-        render_cell_path(frame, app);
+    let single_column = is_single_column(frame.size().width, config);
+    if app.show_compare {
+        render_compare(frame, app, config);
+    } else {
+        render_data(frame, app, config, single_column);
+    }
+    if config.show_cell_path && app.show_chrome {
+        render_cell_path(frame, app, config);
+    }
+    if app.tabs_height(config) > 0 {
+        render_tabs(frame, app, config);
     }
 
     match error {
         Some(err) => render_error(frame, err),
         None => {
-            render_status_bar(frame, app, config);
+            if app.show_chrome {
+                render_status_bar(frame, app, config);
+            }
 
             if app.mode == Mode::Insert {
                 app.editor.render(frame, config);
             }
+            if let (Mode::Form, Some(form)) = (&app.mode, &app.form) {
+                let area = Rect::new(
+                    0,
+                    app.top_bars_height(config),
+                    frame.size().width,
+                    frame.size().height.saturating_sub(app.bars_height(config)),
+                );
+                form.render(frame, area, config);
+            }
+        }
+    }
+
+    if app.row_labels.is_some() && !app.show_compare {
+        render_row_labels(frame, app, config);
+    }
+
+    if app.show_legend {
+        render_legend(frame, config);
+    }
+    if app.show_ancestors {
+        render_ancestors(frame, app, config);
+    }
+    if app.show_minimap {
+        render_minimap(frame, app, config);
+    }
+    if let Some((column, counts)) = &app.histogram {
+        render_histogram(frame, column, counts, config);
+    }
+}
+
+/// render the numbers of the visible rows on top of them, see [`App::row_labels`]
+///
+/// the labels are drawn at the left of the rows, right-aligned, and the ones starting with the
+/// number being typed stand out.
+fn render_row_labels(frame: &mut Frame, app: &App, config: &Config) {
+    let Some((pane, rows)) = visible_view_rows(app, config) else {
+        return;
+    };
+
+    let typed = app.row_labels.unwrap_or(0);
+    let width = rows.to_string().len();
+    let style = Style::default()
+        .fg(config.colors.selected.foreground)
+        .bg(config.colors.selected.background)
+        .add_modifier(Modifier::BOLD);
+
+    for i in 0..rows {
+        let label = format!("{:>width$}", i + 1);
+        let style = if typed > 0 && label.trim_start().starts_with(&typed.to_string()) {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        };
+        frame.render_widget(
+            Paragraph::new(Span::styled(label, style)),
+            Rect::new(app.border_width(config), pane.line_of(i), width as u16, 1),
+        );
+    }
+}
+
+/// render a tab for each container above the cursor on the first line, like in a browser, the
+/// deepest one being selected, see [`App::ancestor_tabs`]
+fn render_tabs(frame: &mut Frame, app: &App, config: &Config) {
+    let titles = app.ancestor_tabs();
+    let selected = titles.len() - 1;
+    let tabs = Tabs::new(titles).select(selected).highlight_style(
+        Style::default()
+            .fg(config.colors.selected.foreground)
+            .bg(config.colors.selected.background),
+    );
+
+    // NOTE: the tabs are below the status bar when it's at the top
+    let y = app.status_bar_on_top(config) as u16;
+    frame.render_widget(tabs, Rect::new(0, y, frame.size().width, 1));
+}
+
+/// the width of [`render_minimap`], borders included
+const MINIMAP_WIDTH: u16 = 14;
+
+/// give the index of the first of *len* nodes in the run of the row *i* of the minimap, when
+/// shrunk to *rows* rows, see [`downsample_depths`]
+fn run_start(i: usize, len: usize, rows: usize) -> usize {
+    i * len / rows
+}
+
+/// give the row of the minimap whose run holds the node at *index* among *len* nodes shrunk to
+/// *rows* rows, see [`downsample_depths`]
+fn minimap_row(index: usize, len: usize, rows: usize) -> usize {
+    (0..rows)
+        .rev()
+        .find(|i| run_start(*i, len, rows) <= index)
+        .unwrap_or_default()
+}
+
+/// shrink the *depths* of the nodes of the data to at most *height* rows, each row standing for
+/// a run of consecutive nodes and showing the shallowest of them, to keep the overall shape
+fn downsample_depths(depths: &[usize], height: usize) -> Vec<usize> {
+    let rows = depths.len().min(height);
+    (0..rows)
+        .map(|i| {
+            let start = run_start(i, depths.len(), rows);
+            let end = run_start(i + 1, depths.len(), rows).max(start + 1);
+            depths[start..end].iter().copied().min().unwrap_or_default()
+        })
+        .collect()
+}
+
+/// render a zoomed-out view of the whole data in a pane on the right, one bar per run of nodes
+/// indented by their depth, with the row of the cursor highlighted
+fn render_minimap(frame: &mut Frame, app: &App, config: &Config) {
+    let width = MINIMAP_WIDTH.min(frame.size().width);
+    let height = frame.size().height.saturating_sub(app.bars_height(config));
+    let area = Rect::new(
+        frame.size().width - width,
+        app.top_bars_height(config),
+        width,
+        height,
+    );
+    let inner_width = width.saturating_sub(2) as usize;
+
+    let (depths, index) = node_depths(&app.value, &app.position.members);
+    let rows = downsample_depths(&depths, height.saturating_sub(2) as usize);
+    let marked = index.map(|index| minimap_row(index, depths.len(), rows.len()));
+
+    let selected = Style::default()
+        .fg(config.colors.selected.foreground)
+        .bg(config.colors.selected.background);
+    let lines: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, depth)| {
+            // NOTE: the deepest nodes are shown at the rightmost column rather than hidden
+            let indent = (*depth).min(inner_width.saturating_sub(1));
+            let bar = format!("{}{}", " ".repeat(indent), "▬".repeat(inner_width - indent));
+            if marked == Some(i) {
+                Line::from(Span::styled(bar, selected))
+            } else {
+                Line::from(bar)
+            }
+        })
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("minimap")),
+        area,
+    );
+}
+
+/// the style of the names of the rows of the data
+fn name_style(config: &Config) -> Style {
+    Style::default()
+        .fg(config.colors.normal.name.foreground)
+        .bg(config.colors.normal.name.background)
+}
+
+/// the style of the data of the rows of the data
+fn data_style(config: &Config) -> Style {
+    Style::default()
+        .fg(config.colors.normal.data.foreground)
+        .bg(config.colors.normal.data.background)
+}
+
+/// the style of the shapes of the rows of the data
+fn shape_style(config: &Config) -> Style {
+    Style::default()
+        .fg(config.colors.normal.shape.foreground)
+        .bg(config.colors.normal.shape.background)
+}
+
+/// the style of the row of the data under the cursor
+fn selected_style(config: &Config) -> Style {
+    Style::default()
+        .fg(config.colors.selected.foreground)
+        .bg(config.colors.selected.background)
+        .add_modifier(config.colors.selected_modifier)
+}
+
+/// render a legend of the colors in a popup, in the top right corner
+///
+/// the entries are styled like the rows of the data, see [`render_data`].
+fn render_legend(frame: &mut Frame, config: &Config) {
+    let entries = [
+        ("name", name_style(config)),
+        ("data", data_style(config)),
+        ("shape", shape_style(config)),
+        ("selected", selected_style(config)),
+    ];
+
+    let lines: Vec<Line> = entries
+        .into_iter()
+        .map(|(name, style)| Line::from(Span::styled(name, style)))
+        .collect();
+
+    let width = 12.min(frame.size().width);
+    let height = (lines.len() as u16 + 2).min(frame.size().height);
+    let area = Rect::new(frame.size().width - width, 0, width, height);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("legend")),
+        area,
+    );
+}
+
+/// render each container above the cursor in a popup, in the top left corner, one per line from
+/// the root down, with the child leading to the cursor highlighted
+///
+/// the records show all their keys and the lists show the index of the child and their length.
+fn render_ancestors(frame: &mut Frame, app: &App, config: &Config) {
+    let selected = Style::default()
+        .fg(config.colors.selected.foreground)
+        .bg(config.colors.selected.background);
+
+    let mut lines = vec![];
+    for depth in 0..app.position.members.len() {
+        let path = CellPath {
+            members: app.position.members[..depth].to_vec(),
+        };
+        let Ok(container) = app.value.clone().follow_cell_path(&path.members, false) else {
+            break;
+        };
+
+        // NOTE: the root is written `$` rather than `$.` or nothing
+        let label = repr_breadcrumb(&path, &app.breadcrumb_style);
+        let label = match label.trim_end_matches('.') {
+            "" => "$",
+            label => label,
+        };
+        let mut spans = vec![Span::raw(format!("{}: ", label))];
+        match (&container, &app.position.members[depth]) {
+            (Value::Record { val: rec, .. }, PathMember::String { val: key, .. }) => {
+                for (i, col) in rec.columns().enumerate() {
+                    if i > 0 {
+                        spans.push(Span::raw(" "));
+                    }
+                    if col == key {
+                        spans.push(Span::styled(col.clone(), selected));
+                    } else {
+                        spans.push(Span::raw(col.clone()));
+                    }
+                }
+            }
+            (Value::List { vals, .. }, PathMember::Int { val: index, .. }) => {
+                spans.push(Span::styled(index.to_string(), selected));
+                spans.push(Span::raw(format!(" of {}", vals.len())));
+            }
+            _ => break,
         }
+        lines.push(Line::from(spans));
     }
+
+    let width = 40.min(frame.size().width);
+    let height = (lines.len() as u16 + 2).min(frame.size().height);
+    let area = Rect::new(0, 0, width, height);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("ancestors")),
+        area,
+    );
+}
+
+/// the symbol of the bars of [`render_histogram`]
+const HISTOGRAM_BAR: &str = "█";
+
+/// render the most frequent values of a *column* and their *counts* in a popup, in the top right
+/// corner, with a bar for each of them
+///
+/// only the values that fit in the height of the terminal are shown, without their escape
+/// sequences when they are interpreted, see [`Config::interpret_ansi`].
+fn render_histogram(frame: &mut Frame, column: &str, counts: &[(String, usize)], config: &Config) {
+    let width = 40.min(frame.size().width);
+    let height = (counts.len() as u16 + 2).min(frame.size().height);
+    let area = Rect::new(frame.size().width - width, 0, width, height);
+
+    let max = counts.iter().map(|(_, c)| *c).max().unwrap_or(1);
+    let count_width = max.to_string().len();
+    // NOTE: the borders and the spaces around the counts take 4 cells
+    let text_width = (width as usize).saturating_sub(4 + count_width) / 2;
+    let bar_width = (width as usize).saturating_sub(4 + count_width + text_width);
+
+    let lines: Vec<Line> = counts
+        .iter()
+        .take(height.saturating_sub(2) as usize)
+        .map(|(text, count)| {
+            let text = if config.interpret_ansi {
+                ansi::strip(text)
+            } else {
+                text.clone()
+            };
+            let text = text.chars().take(text_width).collect::<String>();
+            let bar = HISTOGRAM_BAR.repeat((count * bar_width).div_ceil(max));
+            Line::from(format!("{text:text_width$} {count:>count_width$} {bar}"))
+        })
+        .collect();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(column)),
+        area,
+    );
 }
 
 pub(super) fn render_error(frame: &mut Frame, error: &str) {
@@ -102,13 +446,20 @@ fn repr_list(vals: &[Value]) -> DataRowRepr {
 
 /// compute the preview representation of a record
 ///
+/// the first [`PreviewConfig::record_keys`] keys are listed, followed by an ellipsis if the record
+/// has more keys.
+///
 /// > see the tests for detailed examples
-fn repr_record(cols: &[String]) -> DataRowRepr {
-    let data = match cols.len() {
-        0 => "{}".into(),
-        1 => "{1 field}".into(),
-        x => format!("{{{} fields}}", x),
-    };
+fn repr_record(cols: &[String], preview: &PreviewConfig) -> DataRowRepr {
+    let mut keys = cols
+        .iter()
+        .take(preview.record_keys)
+        .cloned()
+        .collect::<Vec<String>>();
+    if cols.len() > preview.record_keys {
+        keys.push("…".into());
+    }
+    let data = format!("{{{}}}", keys.join(", "));
 
     DataRowRepr {
         name: None,
@@ -117,6 +468,57 @@ fn repr_record(cols: &[String]) -> DataRowRepr {
     }
 }
 
+/// prefix the *data* of a row with the glyph of its *shape*, if any, see [`Config::type_glyphs`]
+fn with_type_glyph(data: String, shape: &str, config: &Config) -> String {
+    match config.type_glyphs.iter().find(|(s, _)| s == shape) {
+        Some((_, glyph)) => format!("{glyph} {data}"),
+        None => data,
+    }
+}
+
+/// give the *data* of a cell with the colors of its ANSI escape sequences, if any, see
+/// [`Config::interpret_ansi`]
+fn data_text(data: String, config: &Config) -> Text<'static> {
+    if config.interpret_ansi && ansi::has_escapes(&data) {
+        ansi::to_text(&data, Style::default())
+    } else {
+        Text::from(data)
+    }
+}
+
+/// give the spans of *data* in *style* with the colors of its ANSI escape sequences, on a single
+/// line, or nothing if there are no sequences to interpret, see [`Config::interpret_ansi`]
+fn ansi_spans(data: &str, style: Style, config: &Config) -> Option<Vec<Span<'static>>> {
+    if !config.interpret_ansi || !ansi::has_escapes(data) {
+        return None;
+    }
+    Some(
+        ansi::to_text(data, style)
+            .lines
+            .into_iter()
+            .flat_map(|line| line.spans)
+            .collect(),
+    )
+}
+
+/// compute the indices shown in the gutter of a list with *len* items
+///
+/// when *relative* is set, the indices are given as the distance to the *selected* item, like the
+/// `relativenumber` option of Vim, except for the selected item which keeps its absolute index.
+///
+/// > see the tests for detailed examples
+fn list_gutter(len: usize, selected: usize, relative: bool) -> Vec<String> {
+    (0..len)
+        .map(|i| {
+            if relative && i != selected {
+                i.abs_diff(selected).to_string()
+            } else {
+                i.to_string()
+            }
+        })
+        .collect()
+}
+
 /// compute the preview representation of a simple value
 ///
 /// > see the tests for detailed examples
@@ -128,21 +530,74 @@ fn repr_simple_value(value: &Value) -> DataRowRepr {
         },
         x => x.get_type().to_string(),
     };
+    let data = match value {
+        Value::String { val, .. } => describe_data_uri(val),
+        _ => None,
+    };
     DataRowRepr {
         name: None,
         shape,
         // FIXME: use a real config
-        data: value.to_expanded_string(" ", &nu_protocol::Config::default()),
+        data: data
+            .unwrap_or_else(|| value.to_expanded_string(" ", &nu_protocol::Config::default())),
+    }
+}
+
+/// compute the preview representation of a custom value, e.g. a dataframe
+///
+/// custom values are leaves of the data: when they can be seen as a table, they are summarized
+/// with their shape and their columns, otherwise their own string representation is used.
+///
+/// the custom values of the explored data have been converted once when it was loaded, see
+/// [`crate::nu::custom::convert_custom_values`], the others are converted here.
+///
+/// > see the tests for detailed examples
+fn repr_custom_value(val: &dyn CustomValue, preview: &PreviewConfig) -> DataRowRepr {
+    let (val, base) = match val.as_any().downcast_ref::<Converted>() {
+        Some(converted) => (converted.inner(), converted.base().cloned()),
+        None => (val, val.to_base_value(nu_protocol::Span::unknown()).ok()),
+    };
+    let data = match base {
+        Some(base) if is_table(&base) => {
+            let recs = base
+                .as_list()
+                .unwrap()
+                .iter()
+                .map(|v| v.as_record().unwrap().clone())
+                .collect::<Vec<Record>>();
+            let (columns, shapes, _) = repr_table(&recs, preview, &RendererRegistry::default());
+
+            format!(
+                "{} ({} rows x {} columns: {})",
+                val.value_string(),
+                recs.len(),
+                columns.len(),
+                columns
+                    .iter()
+                    .zip(shapes)
+                    .map(|(c, s)| format!("{c}: {s}"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        }
+        _ => val.value_string(),
+    };
+
+    DataRowRepr {
+        name: None,
+        shape: val.typetag_name().into(),
+        data,
     }
 }
 
 /// compute the preview representation of a value
 ///
 /// > see the tests for detailed examples
-fn repr_value(value: &Value) -> DataRowRepr {
+fn repr_value(value: &Value, preview: &PreviewConfig) -> DataRowRepr {
     match value {
         Value::List { vals, .. } => repr_list(vals),
-        Value::Record { val: rec, .. } => repr_record(&rec.cols),
+        Value::Record { val: rec, .. } => repr_record(&rec.cols, preview),
+        Value::CustomValue { val, .. } => repr_custom_value(val.as_ref(), preview),
         x => repr_simple_value(x),
     }
 }
@@ -150,7 +605,7 @@ fn repr_value(value: &Value) -> DataRowRepr {
 /// compute the row / item representation of a complete Nushell Value
 ///
 /// > see the tests for detailed examples
-fn repr_data(data: &Value) -> Vec<DataRowRepr> {
+fn repr_data(data: &Value, preview: &PreviewConfig) -> Vec<DataRowRepr> {
     match data {
         Value::List { vals, .. } => {
             if vals.is_empty() {
@@ -160,7 +615,7 @@ fn repr_data(data: &Value) -> Vec<DataRowRepr> {
                     data: "[]".into(),
                 }]
             } else {
-                vals.iter().map(repr_value).collect::<Vec<DataRowRepr>>()
+                repr_items(vals, preview)
             }
         }
         Value::Record { val: rec, .. } => {
@@ -171,23 +626,42 @@ fn repr_data(data: &Value) -> Vec<DataRowRepr> {
                     data: "{}".into(),
                 }]
             } else {
-                rec.iter()
-                    .map(|(col, val)| {
-                        let mut repr = repr_value(val);
-                        repr.name = Some(col.to_string());
-                        repr
-                    })
-                    .collect::<Vec<DataRowRepr>>()
+                repr_fields(rec.iter(), preview)
             }
         }
-        value => vec![repr_simple_value(value)],
+        value => vec![repr_value(value, preview)],
     }
 }
 
+/// compute the representation of the items of a list, see [`repr_data`]
+fn repr_items(vals: &[Value], preview: &PreviewConfig) -> Vec<DataRowRepr> {
+    vals.iter().map(|v| repr_value(v, preview)).collect()
+}
+
+/// compute the representation of the fields of a record, see [`repr_data`]
+fn repr_fields<'a>(
+    fields: impl Iterator<Item = (&'a String, &'a Value)>,
+    preview: &PreviewConfig,
+) -> Vec<DataRowRepr> {
+    fields
+        .map(|(col, val)| {
+            let mut repr = repr_value(val, preview);
+            repr.name = Some(col.to_string());
+            repr
+        })
+        .collect()
+}
+
 /// compute the representation of a complete Nushell table
 ///
+/// the cells whose type has a renderer in *renderers* are shown with it.
+///
 /// > see the tests for detailed examples
-fn repr_table(table: &[Record]) -> (Vec<String>, Vec<String>, Vec<Vec<String>>) {
+fn repr_table(
+    table: &[Record],
+    preview: &PreviewConfig,
+    renderers: &RendererRegistry,
+) -> (Vec<String>, Vec<String>, Vec<Vec<String>>) {
     let mut shapes = vec![Type::Nothing; table[0].len()];
 
     let mut rows = vec![vec![]; table.len()];
@@ -206,7 +680,11 @@ fn repr_table(table: &[Record]) -> (Vec<String>, Vec<String>, Vec<Vec<String>>)
                 }
             }
 
-            rows[i].push(repr_value(val).data);
+            rows[i].push(
+                renderers
+                    .render(val)
+                    .unwrap_or_else(|| repr_value(val, preview).data),
+            );
         }
     }
 
@@ -217,429 +695,3268 @@ fn repr_table(table: &[Record]) -> (Vec<String>, Vec<String>, Vec<Vec<String>>)
     )
 }
 
-/// render the whole data
-///
-/// the layout can be changed from [`crate::config::Config::layout`].
+/// cut *text* into lines of at most *width* characters, keeping its own line breaks
 ///
-/// the data will be rendered on top of the bar, and on top of the cell path in case
-/// [`crate::config::Config::show_cell_path`] is set to `true`.
-fn render_data(frame: &mut Frame, app: &App, config: &Config) {
-    let data_frame_height = if config.show_cell_path {
-        frame.size().height - 2
-    } else {
-        frame.size().height - 1
-    };
-    let rect_without_bottom_bar = Rect::new(0, 0, frame.size().width, data_frame_height);
+/// > see the tests for detailed examples
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    text.split('\n')
+        .flat_map(|line| {
+            let chars = line.chars().collect::<Vec<char>>();
+            if chars.is_empty() {
+                return vec![String::new()];
+            }
+            chars
+                .chunks(width)
+                .map(|chunk| chunk.iter().collect())
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
 
-    let mut data_path = app.position.members.clone();
-    let current = if !app.is_at_bottom() {
-        data_path.pop()
-    } else {
-        None
-    };
+/// represent the value at the bottom of the data, or nothing for the [`LeafView::Plain`] view which
+/// is rendered like any other value
+fn repr_leaf(value: &Value, view: &LeafView) -> Option<String> {
+    match view {
+        LeafView::Plain => None,
+        LeafView::Debug => Some(repr_debug(value)),
+        LeafView::Json => Some(serde_json::to_string(&to_json(value)).unwrap_or_default()),
+    }
+}
 
-    let value = app
-        .value
-        .clone()
-        .follow_cell_path(&data_path, false)
-        .unwrap_or_else(|_| {
-            panic!(
-                "unexpected error when following {:?} in {}",
-                app.position.members,
-                app.value
-                    .to_expanded_string(" ", &nu_protocol::Config::default())
-            )
-        });
+/// give a line with the content of *text* in *style* and its trailing spaces and tabs with the
+/// background of [`crate::config::ColorConfig::trailing_whitespace`], to spot stray whitespace
+pub(crate) fn highlight_trailing_whitespace(
+    text: &str,
+    style: Style,
+    config: &Config,
+) -> Line<'static> {
+    let content = text.trim_end_matches([' ', '\t']);
+    let trailing = &text[content.len()..];
 
-    let normal_name_style = Style::default()
-        .fg(config.colors.normal.name.foreground)
-        .bg(config.colors.normal.name.background);
-    let normal_data_style = Style::default()
-        .fg(config.colors.normal.data.foreground)
-        .bg(config.colors.normal.data.background);
-    let normal_shape_style = Style::default()
-        .fg(config.colors.normal.shape.foreground)
-        .bg(config.colors.normal.shape.background);
-    let highlight_style = Style::default()
-        .fg(config.colors.selected.foreground)
-        .bg(config.colors.selected.background)
-        .add_modifier(config.colors.selected_modifier);
+    let mut spans = vec![Span::styled(content.to_string(), style)];
+    if !trailing.is_empty() {
+        spans.push(Span::styled(
+            trailing.to_string(),
+            style.bg(config.colors.trailing_whitespace),
+        ));
+    }
+    Line::from(spans)
+}
 
-    let selected = match current {
-        Some(PathMember::Int { val, .. }) => val,
-        Some(PathMember::String { val, .. }) => {
-            value.columns().position(|x| x == &val).unwrap_or(0)
+/// compute the range of at most *max* rows to keep out of *len*, around the *selected* one, see
+/// [`Config::max_rows`]
+///
+/// the range is computed before the rows are, for the others not to be represented at all.
+fn capped_rows(len: usize, selected: usize, max: Option<usize>) -> Range<usize> {
+    match max {
+        Some(max) if len > max => {
+            let max = max.max(1);
+            let start = selected.saturating_sub(max - 1).min(len - max);
+            start..start + max
         }
-        None => 0,
-    };
+        _ => 0..len,
+    }
+}
 
-    if is_table(&value) {
-        let (columns, shapes, cells) = match value {
-            Value::List { vals, .. } => {
-                let recs = vals
-                    .iter()
-                    .map(|v| v.as_record().unwrap().clone())
-                    .collect::<Vec<Record>>();
-                repr_table(&recs)
-            }
-            _ => panic!("value is a table but is not a list"),
-        };
+/// follow the *rows* kept by [`capped_rows`] by a `+N more` row for the *more* others, in the given
+/// *column*
+fn with_more_row(mut rows: Vec<Row>, more: usize, column: usize) -> Vec<Row> {
+    if more > 0 {
+        let mut cells = vec![Cell::from(""); column];
+        cells.push(Cell::from(format!("+{more} more")));
+        rows.push(Row::new(cells).style(Style::default().add_modifier(Modifier::DIM)));
+    }
+    rows
+}
 
-        let header = columns
-            .iter()
-            .zip(shapes)
-            .map(|(c, s)| {
-                let spans = vec![
-                    Span::styled(c, normal_name_style),
-                    " (".into(),
-                    Span::styled(s, normal_shape_style),
-                    ")".into(),
-                ];
+/// the data shown instead of the content of the lists and records that are too deep, see
+/// [`App::max_depth`]
+const ELIDED: &str = "…";
 
-                Cell::from(Line::from(spans))
-            })
-            .collect::<Vec<Cell>>();
+/// hide the content of the lists and records among *rows*, when they are too deep to be explored
+fn elide_containers(rows: Vec<DataRowRepr>) -> Vec<DataRowRepr> {
+    rows.into_iter()
+        .map(|row| match row.shape.as_str() {
+            "list" | "record" => DataRowRepr {
+                data: ELIDED.into(),
+                ..row
+            },
+            _ => row,
+        })
+        .collect()
+}
 
-        let widths = header
-            .iter()
-            // FIXME: use an appropriate constraint here
-            .map(|_| Constraint::Min(25))
-            .collect::<Vec<Constraint>>();
+/// show the *key* of a record with quotes when [`App::quote_keys`] is set and it needs them in a
+/// cell path, see [`repr_nu_key`]
+fn display_key(app: &App, key: &str) -> String {
+    if app.quote_keys {
+        repr_nu_key(key)
+    } else {
+        key.to_string()
+    }
+}
 
-        let header = Row::new(header).height(1);
+/// summarize the columns of a table and their types, e.g. `name:string, age:int`
+///
+/// > see the tests for detailed examples
+fn repr_column_summary(columns: &[(String, Type)]) -> String {
+    columns
+        .iter()
+        .map(|(name, ty)| format!("{name}:{ty}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
 
-        let rows: Vec<Row> = cells
-            .iter()
-            .map(|r| Row::new(r.iter().cloned().map(Cell::from).collect::<Vec<Cell>>()))
-            .collect();
+/// append the type of each cell of a table to its representation, e.g. `1 (int)`
+///
+/// the *cells* are the ones given by [`repr_table`] for the same *table*.
+fn annotate_types(table: &[Record], cells: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    table
+        .iter()
+        .zip(cells)
+        .map(|(row, cells)| {
+            table[0]
+                .cols
+                .iter()
+                .zip(cells)
+                .map(|(col, cell)| {
+                    let ty = row.get(col).map(|v| v.get_type()).unwrap_or(Type::Nothing);
+                    format!("{} ({})", cell, ty)
+                })
+                .collect()
+        })
+        .collect()
+}
 
-        let table = Table::new(rows, widths)
-            .header(header)
-            .block(Block::default().borders(Borders::ALL))
-            .highlight_style(highlight_style)
-            .highlight_symbol(config.colors.selected_symbol.clone());
+/// group the rows of a record by the first letter of their name, in alphabetical order
+///
+/// > see the tests for detailed examples
+fn group_rows(rows: Vec<DataRowRepr>) -> Vec<(String, Vec<DataRowRepr>)> {
+    let mut rows = rows;
+    rows.sort_by_key(|row| row.name.clone().unwrap_or_default().to_lowercase());
 
-        frame.render_stateful_widget(
-            table,
-            rect_without_bottom_bar,
-            &mut TableState::default().with_selected(Some(selected)),
-        );
+    let mut groups: Vec<(String, Vec<DataRowRepr>)> = vec![];
+    for row in rows {
+        let group = record_group(row.name.as_deref().unwrap_or_default());
 
-        return;
+        match groups.last_mut() {
+            Some((last, rows)) if *last == group => rows.push(row),
+            _ => groups.push((group, vec![row])),
+        }
     }
 
-    match config.layout {
-        Layout::Compact => {
-            let items: Vec<ListItem> = repr_data(&value)
-                .iter()
-                .cloned()
-                .map(|row| {
-                    let mut spans = vec![];
-                    if let Some(name) = row.name {
-                        spans.push(Span::styled(name, normal_name_style));
-                        spans.push(": ".into());
-                    }
-                    spans.push("(".into());
-                    spans.push(Span::styled(row.shape, normal_shape_style));
-                    spans.push(") ".into());
-                    spans.push(Span::styled(row.data, normal_data_style));
+    groups
+}
 
-                    ListItem::new(Line::from(spans))
-                })
-                .collect();
+/// keep only the first row of the *collapsed* groups, which stands for its group, and tell how many
+/// rows are hidden in the name of the group, e.g. `a (+2 collapsed)`, see [`App::collapsed_groups`]
+fn collapse_groups(
+    groups: Vec<(String, Vec<DataRowRepr>)>,
+    collapsed: &HashSet<String>,
+) -> Vec<(String, Vec<DataRowRepr>)> {
+    groups
+        .into_iter()
+        .map(|(group, mut rows)| {
+            if !collapsed.contains(&group) || rows.len() < 2 {
+                return (group, rows);
+            }
+            let hidden = rows.len() - 1;
+            rows.truncate(1);
+            (format!("{} (+{} collapsed)", group, hidden), rows)
+        })
+        .collect()
+}
 
-            let items = List::new(items)
-                .highlight_style(highlight_style)
-                .highlight_symbol(&config.colors.selected_symbol);
+/// the names of the sections of the fields of a record, see [`Config::group_by_type`]
+const SCALARS_GROUP: &str = "scalars";
+const CONTAINERS_GROUP: &str = "containers";
 
-            frame.render_stateful_widget(
-                items,
-                rect_without_bottom_bar,
-                &mut ListState::default().with_selected(Some(selected)),
-            )
+/// group the rows of the fields of a record in a section for the scalars and one for the lists and
+/// records, keeping their order and dropping the empty sections
+fn group_rows_by_type(rows: Vec<DataRowRepr>, rec: &Record) -> Vec<(String, Vec<DataRowRepr>)> {
+    let (containers, scalars): (Vec<DataRowRepr>, Vec<DataRowRepr>) = rows
+        .into_iter()
+        .partition(|row| is_container(row.name.as_ref().and_then(|n| rec.get(n))));
+
+    [(SCALARS_GROUP, scalars), (CONTAINERS_GROUP, containers)]
+        .into_iter()
+        .filter(|(_, rows)| !rows.is_empty())
+        .map(|(name, rows)| (name.to_string(), rows))
+        .collect()
+}
+
+/// the name of the group of the pinned fields, when grouping records
+const PINNED_GROUP: &str = "pinned";
+
+/// split the rows of a record between the *pinned* ones and the others, keeping their order
+fn pin_rows(
+    rows: Vec<DataRowRepr>,
+    pinned: &HashSet<String>,
+) -> (Vec<DataRowRepr>, Vec<DataRowRepr>) {
+    rows.into_iter()
+        .partition(|row| row.name.as_ref().is_some_and(|n| pinned.contains(n)))
+}
+
+/// put the rows of the *pinned* fields first, see [`pin_rows`]
+fn pinned_first(rows: Vec<DataRowRepr>, pinned: &HashSet<String>) -> Vec<DataRowRepr> {
+    let (mut rows, others) = pin_rows(rows, pinned);
+    rows.extend(others);
+    rows
+}
+
+/// compute the index of the row called `name` in grouped rows, counting the group headers
+fn grouped_row_index(groups: &[(String, Vec<DataRowRepr>)], name: &str) -> Option<usize> {
+    let mut index = 0;
+    for (_, rows) in groups {
+        // NOTE: skip the header of the group
+        index += 1;
+        if let Some(i) = rows.iter().position(|r| r.name.as_deref() == Some(name)) {
+            return Some(index + i);
         }
-        Layout::Table => {
-            let (header, rows, constraints) = match value {
-                Value::List { .. } => {
-                    let header = Row::new(vec![
-                        Cell::from("item")
-                            .style(normal_data_style.add_modifier(Modifier::REVERSED)),
-                        Cell::from("shape")
-                            .style(normal_shape_style.add_modifier(Modifier::REVERSED)),
-                    ]);
-                    let rows: Vec<Row> = repr_data(&value)
-                        .iter()
-                        .cloned()
-                        .map(|row| {
-                            let data_style = match row.data.as_str() {
-                                "record" | "list" => normal_data_style.add_modifier(Modifier::DIM),
-                                _ => normal_data_style,
-                            };
+        index += rows.len();
+    }
 
-                            Row::new(vec![
-                                Cell::from(row.data).style(data_style),
-                                Cell::from(row.shape).style(normal_shape_style),
-                            ])
-                        })
-                        .collect();
+    None
+}
+
+/// give the summary of a list or a record with more than [`PreviewConfig::collapse_over`] items
+/// or fields, if any
+fn collapsed(value: &Value, preview: &PreviewConfig) -> Option<String> {
+    match value {
+        Value::List { vals, .. } if vals.len() > preview.collapse_over => {
+            Some(format!("[{} items]", vals.len()))
+        }
+        Value::Record { val: rec, .. } if rec.len() > preview.collapse_over => {
+            Some(format!("{{{} fields}}", rec.len()))
+        }
+        _ => None,
+    }
+}
+
+/// compute the lines showing a value in full, one field or item per line
+///
+/// the lists and records that are too large are collapsed into a summary, see [`collapsed`].
+///
+/// > see the tests for detailed examples
+fn preview_lines(value: &Value, indent: usize, preview: &PreviewConfig) -> Vec<String> {
+    fn push_lines(
+        value: &Value,
+        depth: usize,
+        width: usize,
+        preview: &PreviewConfig,
+        lines: &mut Vec<String>,
+    ) {
+        let indent = " ".repeat(width * depth);
+        let children: Vec<(String, &Value)> = match value {
+            Value::List { vals, .. } => vals.iter().map(|v| ("-".to_string(), v)).collect(),
+            Value::Record { val: rec, .. } => {
+                rec.iter().map(|(c, v)| (format!("{c}:"), v)).collect()
+            }
+            _ => vec![],
+        };
 
-                    let constraints = vec![Constraint::Percentage(90), Constraint::Percentage(10)];
+        for (name, child) in children {
+            if let Some(summary) = collapsed(child, preview) {
+                lines.push(format!("{indent}{name} {summary}"));
+                continue;
+            }
 
-                    (header, rows, constraints)
+            match child {
+                Value::List { vals, .. } if !vals.is_empty() => {
+                    lines.push(format!("{indent}{name}"));
+                    push_lines(child, depth + 1, width, preview, lines);
                 }
-                Value::Record { .. } => {
-                    let header = Row::new(vec![
-                        Cell::from("key").style(normal_name_style.add_modifier(Modifier::REVERSED)),
-                        Cell::from("field")
-                            .style(normal_data_style.add_modifier(Modifier::REVERSED)),
-                        Cell::from("shape")
-                            .style(normal_shape_style.add_modifier(Modifier::REVERSED)),
-                    ]);
+                Value::Record { val: rec, .. } if !rec.is_empty() => {
+                    lines.push(format!("{indent}{name}"));
+                    push_lines(child, depth + 1, width, preview, lines);
+                }
+                Value::List { .. } => lines.push(format!("{indent}{name} []")),
+                Value::Record { .. } => lines.push(format!("{indent}{name} {{}}")),
+                x => lines.push(format!(
+                    "{indent}{name} {}",
+                    x.to_expanded_string(" ", &nu_protocol::Config::default())
+                )),
+            }
+        }
+    }
 
-                    let rows: Vec<Row> = repr_data(&value)
-                        .iter()
-                        .cloned()
-                        .map(|row| {
-                            let data_style = match row.data.as_str() {
-                                "record" | "list" => normal_data_style.add_modifier(Modifier::DIM),
-                                _ => normal_data_style,
-                            };
+    if let Some(summary) = collapsed(value, preview) {
+        return vec![summary];
+    }
 
-                            Row::new(vec![
-                                Cell::from(row.name.unwrap_or("".into())).style(normal_name_style),
-                                Cell::from(row.data).style(data_style),
-                                Cell::from(row.shape).style(normal_shape_style),
-                            ])
-                        })
-                        .collect();
+    match value {
+        Value::List { vals, .. } if vals.is_empty() => vec!["[]".into()],
+        Value::Record { val: rec, .. } if rec.is_empty() => vec!["{}".into()],
+        Value::List { .. } | Value::Record { .. } => {
+            let mut lines = vec![];
+            push_lines(value, 0, indent, preview, &mut lines);
+            lines
+        }
+        x => vec![x.to_expanded_string(" ", &nu_protocol::Config::default())],
+    }
+}
 
-                    let constraints = vec![
-                        Constraint::Percentage(20),
-                        Constraint::Percentage(70),
-                        Constraint::Percentage(10),
-                    ];
+/// render the foldable JSON of the list or the record at the bottom of the data in *area*, scrolled
+/// to keep the line under its cursor in view
+fn render_folded_json(frame: &mut Frame, folded: &FoldedJson, config: &Config, area: Rect) {
+    let normal_data_style = data_style(config);
+    let highlight_style = selected_style(config);
 
-                    (header, rows, constraints)
-                }
-                v => {
-                    let repr = repr_simple_value(&v);
-                    let spans = vec![
-                        Span::styled(repr.data, normal_data_style),
-                        " is of shape ".into(),
-                        Span::styled(repr.shape, normal_shape_style),
-                    ];
+    let rows = folded.rows();
+    let selected = rows
+        .iter()
+        .position(|(i, _)| *i == folded.cursor)
+        .unwrap_or(0);
+    let height = area.height.saturating_sub(2) as usize;
+    let scroll = (selected + 1).saturating_sub(height);
 
-                    frame.render_widget(
-                        Paragraph::new(Line::from(spans))
-                            .block(Block::default().borders(Borders::ALL))
-                            .wrap(Wrap { trim: false }),
-                        rect_without_bottom_bar,
-                    );
-                    return;
-                }
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(i, row)| {
+            let style = if i == folded.cursor {
+                highlight_style
+            } else {
+                normal_data_style
             };
+            Line::from(Span::styled(row, style))
+        })
+        .collect();
 
-            let table = if config.show_table_header {
-                Table::new(rows, constraints).header(header.height(1))
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(normal_data_style)
+            .block(Block::default().borders(Borders::ALL))
+            .scroll((scroll as u16, 0)),
+        area,
+    );
+}
+
+/// render the value under the cursor in full in a pane, scrolled by [`App::preview_scroll`]
+fn render_preview_pane(frame: &mut Frame, app: &App, config: &Config, area: Rect) {
+    let value = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+        .unwrap_or_else(|_| Value::nothing(nu_protocol::Span::unknown()));
+
+    let style = data_style(config);
+    // NOTE: the lines are cut to the width of the pane after their escape sequences are interpreted
+    let lines: Vec<Line> = preview_lines(&value, config.indent, &config.preview)
+        .into_iter()
+        .map(|l| match ansi_spans(&l, style, config) {
+            Some(spans) => Line::from(spans),
+            None => Line::from(Span::styled(l, style)),
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("preview"))
+            .scroll((app.preview_scroll, 0)),
+        area,
+    );
+}
+
+/// the number of levels of the bars of the sparklines, i.e. the number of bar symbols
+const SPARKLINE_LEVELS: u64 = 8;
+
+/// scale the numbers of a list to the levels of a sparkline, the smallest one being the lowest
+/// visible bar
+fn sparkline_data(vals: &[Value]) -> Vec<u64> {
+    let numbers: Vec<f64> = vals
+        .iter()
+        .filter_map(|v| match v {
+            Value::Int { val, .. } => Some(*val as f64),
+            Value::Float { val, .. } => Some(*val),
+            _ => None,
+        })
+        .collect();
+
+    let min = numbers.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    numbers
+        .iter()
+        .map(|n| {
+            if range > 0.0 {
+                1 + ((n - min) / range * (SPARKLINE_LEVELS - 1) as f64).round() as u64
             } else {
-                Table::new(rows, constraints)
+                SPARKLINE_LEVELS
             }
-            .block(Block::default().borders(Borders::ALL))
-            .highlight_style(highlight_style)
-            .highlight_symbol(config.colors.selected_symbol.clone());
+        })
+        .collect()
+}
 
-            frame.render_stateful_widget(
-                table,
-                rect_without_bottom_bar,
-                &mut TableState::default().with_selected(Some(selected)),
+/// summarize a list of numbers with a sparkline of one bar per number, see [`is_numeric_list`]
+fn render_sparkline(frame: &mut Frame, value: &Value, area: Rect) {
+    let Value::List { vals, .. } = value else {
+        return;
+    };
+
+    let data = sparkline_data(vals);
+    frame.render_widget(
+        Sparkline::default()
+            .data(&data)
+            .max(SPARKLINE_LEVELS)
+            .style(Style::default().fg(Color::Cyan)),
+        area,
+    );
+}
+
+/// tell whether to show the scrollbar of *total* rows when only *visible* of them fit
+fn show_scrollbar(visibility: &ScrollbarVisibility, total: usize, visible: usize) -> bool {
+    match visibility {
+        ScrollbarVisibility::Always => true,
+        ScrollbarVisibility::Auto => total > visible,
+        ScrollbarVisibility::Never => false,
+    }
+}
+
+/// give the part of *area* with the rows of the data, i.e. without the *top* and *bottom* lines of
+/// the borders and the header
+fn rows_area(area: Rect, top: u16, bottom: u16) -> Rect {
+    Rect::new(
+        area.x,
+        area.y + top.min(area.height),
+        area.width,
+        area.height.saturating_sub(top + bottom),
+    )
+}
+
+/// render the scrollbar of *total* rows on the right of their *area*, see [`Config::scrollbar`]
+fn render_scrollbar(frame: &mut Frame, area: Rect, total: usize, selected: usize, config: &Config) {
+    if !show_scrollbar(&config.scrollbar.visibility, total, area.height as usize) {
+        return;
+    }
+
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_symbol(&config.scrollbar.thumb)
+        .track_symbol(Some(&config.scrollbar.track));
+    frame.render_stateful_widget(
+        scrollbar,
+        area,
+        &mut ScrollbarState::new(total)
+            .position(selected)
+            .viewport_content_length(area.height as usize),
+    );
+}
+
+/// give the hint to show in place of the rows of an empty list or record, if *view* is one
+fn empty_hint(view: &Value, config: &Config) -> Option<String> {
+    let what = match view {
+        Value::List { vals, .. } if vals.is_empty() => "this list has no items",
+        Value::Record { val: rec, .. } if rec.is_empty() => "this record has no keys",
+        _ => return None,
+    };
+
+    Some(format!(
+        "{what} — press {} to go back",
+        repr_keycode(&config.keybindings.navigation.left)
+    ))
+}
+
+/// give the zebra background to every other row of a table, see [`Config::zebra_stripes`]
+fn stripe(style: Style, index: usize, config: &Config) -> Style {
+    if config.zebra_stripes && index % 2 == 1 {
+        style.bg(config.colors.zebra)
+    } else {
+        style
+    }
+}
+
+/// tell whether the row at *member* of the view at *path* differs from the snapshot
+///
+/// *diffs* are the differences with the snapshot, see [`App::snapshot`], and a row has changed
+/// when one of them is inside it or contains it.
+fn is_changed(diffs: &[CellPath], path: &[PathMember], member: PathMember) -> bool {
+    let mut row = path.to_vec();
+    row.push(member);
+    diffs
+        .iter()
+        .any(|diff| diff.members.starts_with(&row) || row.starts_with(&diff.members))
+}
+
+/// give the foreground of [`crate::config::ColorConfig::changed`] to the rows that have changed
+fn highlight_change(style: Style, changed: bool, config: &Config) -> Style {
+    if changed {
+        style.fg(config.colors.changed)
+    } else {
+        style
+    }
+}
+
+/// render the original data to the left of the edited one, both at the path of the cursor, see
+/// [`App::original_value`]
+fn render_compare(frame: &mut Frame, app: &App, config: &Config) {
+    let height = frame.size().height.saturating_sub(app.bars_height(config));
+    let width = frame.size().width;
+    let top = app.top_bars_height(config);
+
+    let mut data_path = app.position.members.clone();
+    let current = if !app.is_at_bottom() {
+        data_path.pop()
+    } else {
+        None
+    };
+
+    let panes = [
+        (
+            "original",
+            app.original_value(),
+            Rect::new(0, top, width / 2, height),
+        ),
+        (
+            "edited",
+            app.value.clone(),
+            Rect::new(width / 2, top, width - width / 2, height),
+        ),
+    ];
+    for (title, value, area) in panes {
+        let view = value.follow_cell_path(&data_path, false).ok();
+        render_compare_pane(frame, title, view, current.as_ref(), area, config);
+    }
+}
+
+/// render one side of [`render_compare`], i.e. the *view* at the path of the cursor with the row
+/// of *current* selected
+fn render_compare_pane(
+    frame: &mut Frame,
+    title: &str,
+    view: Option<Value>,
+    current: Option<&PathMember>,
+    area: Rect,
+    config: &Config,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string());
+    let Some(view) = view else {
+        frame.render_widget(Paragraph::new("not in this data").block(block), area);
+        return;
+    };
+
+    let (names, selected) = match &view {
+        Value::List { vals, .. } => {
+            let selected = match current {
+                Some(PathMember::Int { val, .. }) if *val < vals.len() => Some(*val),
+                _ => None,
+            };
+            ((0..vals.len()).map(|i| i.to_string()).collect(), selected)
+        }
+        Value::Record { val: rec, .. } => {
+            let selected = match current {
+                Some(PathMember::String { val, .. }) => rec.columns().position(|c| c == val),
+                _ => None,
+            };
+            (rec.columns().cloned().collect::<Vec<String>>(), selected)
+        }
+        leaf => {
+            let repr = repr_value(leaf, &config.preview);
+            frame.render_widget(
+                Paragraph::new(format!("{} is of shape {}", repr.data, repr.shape))
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                area,
+            );
+            return;
+        }
+    };
+
+    let normal_name_style = name_style(config);
+    let normal_data_style = data_style(config);
+    let highlight_style = selected_style(config);
+
+    let rows: Vec<Row> = repr_data(&view, &config.preview)
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            Row::new(vec![
+                Cell::from(names.get(i).cloned().unwrap_or_default()).style(normal_name_style),
+                Cell::from(row.data).style(normal_data_style),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        vec![Constraint::Percentage(30), Constraint::Percentage(70)],
+    )
+    .block(block)
+    .highlight_style(highlight_style)
+    .highlight_symbol(config.colors.selected_symbol.clone());
+    frame.render_stateful_widget(
+        table,
+        area,
+        &mut TableState::default().with_selected(selected),
+    );
+}
+
+/// split the area of the data between the rows and the preview pane, if it's shown
+fn split_preview_pane(area: Rect, config: &Config) -> (Rect, Option<Rect>) {
+    if !config.show_preview_pane {
+        return (area, None);
+    }
+
+    let data_width = area.width * 3 / 5;
+    (
+        Rect::new(area.x, area.y, data_width, area.height),
+        Some(Rect::new(
+            area.x + data_width,
+            area.y,
+            area.width - data_width,
+            area.height,
+        )),
+    )
+}
+
+/// give the area of the rows of the data in a terminal of the size of the *app*, i.e. without the
+/// bars, the border and the preview pane, as laid out by [`render_data`]
+pub(crate) fn data_area(app: &App, config: &Config) -> Rect {
+    let full_rect = Rect::new(
+        0,
+        app.top_bars_height(config),
+        app.width,
+        app.height.saturating_sub(app.bars_height(config)),
+    );
+    let full_rect = if config.show_border {
+        Block::default().borders(Borders::ALL).inner(full_rect)
+    } else {
+        full_rect
+    };
+
+    split_preview_pane(full_rect, config).0
+}
+
+/// the width left to the cells of the [`Layout::Table`] layout in an area of the given *width*,
+/// i.e. without the borders of the table and the symbol of the selected row
+fn table_inner_width(width: u16, config: &Config) -> usize {
+    width.saturating_sub(2 + config.colors.selected_symbol.chars().count() as u16) as usize
+}
+
+/// the width of the column of the indices of a list, see [`list_gutter`]
+fn gutter_width(gutter: &[String]) -> usize {
+    gutter.iter().map(|i| i.len()).max().unwrap_or(1).max(1)
+}
+
+/// wrap the *data* of the selected row to the width of its column, as given by its *percentage*
+/// of the *width* of the table, minus the spacing between the columns, and give its height
+fn wrap_cell(data: String, width: usize, percentage: usize, config: &Config) -> (String, u16) {
+    let width = (width * percentage / 100).saturating_sub(2);
+    // NOTE: the escape sequences are not shown when they are interpreted, so they are not cut
+    let lines = if config.interpret_ansi && ansi::has_escapes(&data) {
+        ansi::wrap(&data, width)
+    } else {
+        wrap_text(&data, width)
+    };
+    let height = lines.len() as u16;
+    (lines.join("\n"), height)
+}
+
+/// give the number of lines taken by the selected row of the [`Layout::Table`] layout, more than
+/// one when it's wrapped, see [`App::wrap_focused`]
+pub(crate) fn focused_row_height(app: &App, config: &Config) -> u16 {
+    if !app.wrap_focused || app.is_at_bottom() {
+        return 1;
+    }
+
+    let mut path = app.position.members.clone();
+    let Some(current) = path.pop() else {
+        return 1;
+    };
+    let Ok(view) = app.value.clone().follow_cell_path(&path, false) else {
+        return 1;
+    };
+    let Ok(cell) = view.clone().follow_cell_path(&[current.clone()], false) else {
+        return 1;
+    };
+
+    // NOTE: this mirrors the rows of [`render_data`]
+    let mut repr = repr_value(&cell, &config.preview);
+    if app.is_at_max_depth() {
+        repr = elide_containers(vec![repr]).remove(0);
+    }
+    let data = with_type_glyph(repr.data, &repr.shape, config);
+    let inner_width = table_inner_width(data_area(app, config).width, config);
+
+    match (&view, current) {
+        (Value::List { vals, .. }, PathMember::Int { val, .. }) => {
+            let gutter = list_gutter(vals.len(), val, app.relative_indices);
+            wrap_cell(
+                data,
+                inner_width.saturating_sub(gutter_width(&gutter)),
+                90,
+                config,
             )
+            .1
         }
+        (Value::Record { .. }, _) => wrap_cell(data, inner_width, 70, config).1,
+        _ => 1,
     }
 }
 
-/// render the cell path just above the status bar
+/// tell whether the table *value* is shown with a column per key, see [`App::table_view`]
 ///
-/// this line can be removed through config, see [`crate::config::Config::show_cell_path`]
+/// at the maximum depth, the cells of the rows are too deep to be shown, see [`App::max_depth`].
+pub(crate) fn is_table_view(app: &App, value: &Value, single_column: bool) -> bool {
+    is_table(value) && app.table_view && !single_column && !app.is_at_max_depth()
+}
+
+/// render the whole data
 ///
-/// # Examples
-/// > :bulb: **Note**  
-/// > the `...` are here to signify that the bar might be truncated and the `||` at the start and
-/// the end of the lines are just to represent the borders of the terminal but will not appear in
-/// the TUI.
-/// - at the beginning
-/// ```text
-/// ||cell path: $.   ...||
-/// ```
-/// - after some navigation, might look like
-/// ```text
-/// ||cell path: $.foo.bar.2.baz    ...||
-/// ```
-fn render_cell_path(frame: &mut Frame, app: &App) {
-    let next_to_bottom_bar_rect = Rect::new(0, frame.size().height - 2, frame.size().width, 1);
-    let cell_path = format!(
-        "cell path: $.{}",
-        app.position
-            .members
-            .iter()
-            .map(|m| {
-                match m {
-                    PathMember::Int { val, .. } => val.to_string(),
-                    PathMember::String { val, .. } => val.to_string(),
-                }
-            })
-            .collect::<Vec<String>>()
-            .join(".")
-    );
+/// the layout can be changed from [`crate::config::Config::layout`].
+///
+/// the data will be rendered on top of the bar, and on top of the cell path in case
+/// [`crate::config::Config::show_cell_path`] is set to `true`, unless they are hidden with
+/// [`App::show_chrome`].
+///
+/// when there is a snapshot of the data, the rows of tables that differ from it are highlighted.
+fn render_data(frame: &mut Frame, app: &App, config: &Config, single_column: bool) {
+    let data_frame_height = frame.size().height.saturating_sub(app.bars_height(config));
+    let top = app.top_bars_height(config);
+    let full_rect = Rect::new(0, top, frame.size().width, data_frame_height);
+    let full_rect = if config.show_border {
+        let mut container = app.absolute_position();
+        if !app.is_at_bottom() {
+            container.members.pop();
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(repr_breadcrumb(&container, &app.breadcrumb_style));
+        let inner = block.inner(full_rect);
+        frame.render_widget(block, full_rect);
+        inner
+    } else {
+        full_rect
+    };
+    let (rect_without_bottom_bar, preview_rect) = split_preview_pane(full_rect, config);
+    if let Some(preview_rect) = preview_rect {
+        render_preview_pane(frame, app, config, preview_rect);
+    }
+
+    if let Some(folded) = app.folded_json.as_ref().filter(|_| app.is_at_bottom()) {
+        render_folded_json(frame, folded, config, rect_without_bottom_bar);
+        return;
+    }
+
+    let mut data_path = app.position.members.clone();
+    let current = if !app.is_at_bottom() {
+        data_path.pop()
+    } else {
+        None
+    };
+
+    let value = app
+        .value
+        .clone()
+        .follow_cell_path(&data_path, false)
+        .unwrap_or_else(|_| {
+            panic!(
+                "unexpected error when following {:?} in {}",
+                app.position.members,
+                app.value
+                    .to_expanded_string(" ", &nu_protocol::Config::default())
+            )
+        });
+    // NOTE: the rows are reversed in the view only, the positions still point to the data, and the
+    // fields of records are reversed once ordered, e.g. with the pinned ones first, for the view to
+    // be the exact reverse of the navigation
+    let value = match value {
+        Value::Record { val: rec, .. } if app.reversed => Value::record(
+            visible_columns(&rec, config, app)
+                .into_iter()
+                .rev()
+                .filter_map(|col| rec.get(&col).map(|val| (col.clone(), val.clone())))
+                .collect::<Record>(),
+            nu_protocol::Span::unknown(),
+        ),
+        value if app.reversed => reverse_container(value),
+        value => value,
+    };
+    // NOTE: the fields of a reversed record are already in order
+    let pinned = if app.reversed {
+        HashSet::new()
+    } else {
+        app.pinned.clone()
+    };
+    let reversed_len = match &value {
+        Value::List { vals, .. } if app.reversed => Some(vals.len()),
+        _ => None,
+    };
+    let reversed_index = |i: usize| match reversed_len {
+        Some(len) => len.saturating_sub(1 + i),
+        None => i,
+    };
+
+    let diffs = &app.changes;
+    let changed_index = |i: usize| {
+        let member = PathMember::Int {
+            val: reversed_index(i),
+            span: nu_protocol::Span::unknown(),
+            optional: false,
+        };
+        is_changed(diffs, &data_path, member)
+    };
+    let changed_name = |name: &Option<String>| match name {
+        Some(name) => {
+            let member = PathMember::String {
+                val: name.clone(),
+                span: nu_protocol::Span::unknown(),
+                optional: false,
+            };
+            is_changed(diffs, &data_path, member)
+        }
+        None => false,
+    };
+
+    let elide = |rows: Vec<DataRowRepr>| {
+        if app.is_at_max_depth() {
+            elide_containers(rows)
+        } else {
+            rows
+        }
+    };
+    let rows_of = |value: &Value| elide(repr_data(value, &config.preview));
+
+    let normal_name_style = name_style(config);
+    let normal_data_style = data_style(config);
+    let normal_shape_style = shape_style(config);
+    let highlight_style = selected_style(config);
+    // NOTE: the colors are inverted for a moment after a jump, for the eye to find the cell
+    let highlight_style = if app.is_flashing(Instant::now()) {
+        highlight_style.add_modifier(Modifier::REVERSED)
+    } else {
+        highlight_style
+    };
+
+    if let Some(hint) = empty_hint(&value, config) {
+        let hint = if config.missing_display == MissingDisplay::Missing
+            && is_missing(&app.position.members)
+        {
+            format!("(missing) {hint}")
+        } else {
+            hint
+        };
+        let block = Block::default().borders(Borders::ALL);
+        let inner = block.inner(rect_without_bottom_bar);
+        frame.render_widget(block, rect_without_bottom_bar);
+        frame.render_widget(
+            Paragraph::new(hint)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 2),
+        );
+        return;
+    }
+
+    if config.layout == Layout::Tree && !app.is_at_bottom() {
+        let rows = visible_rows(&app.value, &app.expanded);
+        let current = to_keys(&app.position.members);
+        let selected = rows
+            .iter()
+            .position(|r| to_keys(&r.path) == current)
+            .unwrap_or(0);
+
+        let total = rows.len();
+        // NOTE: the sizes of the containers are aligned to the right, left of the scrollbar if any
+        let scrollbar = show_scrollbar(
+            &config.scrollbar.visibility,
+            total,
+            rect_without_bottom_bar.height as usize,
+        );
+        let width = (rect_without_bottom_bar.width as usize)
+            .saturating_sub(config.colors.selected_symbol.chars().count())
+            .saturating_sub(scrollbar as usize);
+        let items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|row| {
+                let marker = match (row.is_container, row.is_expanded) {
+                    (false, _) => "  ",
+                    (true, false) => "▸ ",
+                    (true, true) => "▾ ",
+                };
+                let repr = repr_value(&row.value, &config.preview);
+
+                let mut spans = vec![
+                    " ".repeat(config.indent * row.depth).into(),
+                    marker.into(),
+                    Span::styled(row.name, normal_name_style),
+                    ": (".into(),
+                    Span::styled(repr.shape, normal_shape_style),
+                    ") ".into(),
+                ];
+                // NOTE: the width of the sizes is computed without the escape sequences
+                match ansi_spans(&repr.data, normal_data_style, config) {
+                    Some(ansi) => spans.extend(ansi),
+                    None => spans.push(Span::styled(repr.data, normal_data_style)),
+                }
+                if let Some(size) = row.size {
+                    let size = format!(" ({})", size);
+                    let used = Line::from(spans.clone()).width();
+                    spans.push(" ".repeat(width.saturating_sub(used + size.len())).into());
+                    spans.push(Span::styled(size, normal_shape_style));
+                }
+
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let items = List::new(items)
+            .highlight_style(highlight_style)
+            .highlight_symbol(&config.colors.selected_symbol);
+
+        frame.render_stateful_widget(
+            items,
+            rect_without_bottom_bar,
+            &mut ListState::default()
+                .with_offset(app.tree_offset)
+                .with_selected(Some(selected)),
+        );
+        render_scrollbar(frame, rect_without_bottom_bar, total, selected, config);
+
+        return;
+    }
+
+    let rect_without_bottom_bar = if config.show_sparkline && is_numeric_list(&value) {
+        let area = rect_without_bottom_bar;
+        render_sparkline(frame, &value, Rect::new(area.x, area.y, area.width, 1));
+        Rect::new(
+            area.x,
+            area.y + 1,
+            area.width,
+            area.height.saturating_sub(1),
+        )
+    } else {
+        rect_without_bottom_bar
+    };
+
+    let groups = match &value {
+        Value::Record { val: rec, .. } if config.group_records || config.group_by_type => {
+            let (pinned, others) = pin_rows(rows_of(&value), &pinned);
+            let mut groups = if config.group_by_type {
+                group_rows_by_type(others, rec)
+            } else {
+                collapse_groups(group_rows(others), &app.collapsed_groups)
+            };
+            if !pinned.is_empty() {
+                groups.insert(0, (PINNED_GROUP.into(), pinned));
+            }
+            Some(groups)
+        }
+        _ => None,
+    };
+
+    let focused_name = match &current {
+        Some(PathMember::String { val, .. }) => Some(val.clone()),
+        _ => None,
+    };
+    let selected = match current {
+        Some(PathMember::Int { val, .. }) => reversed_index(val),
+        Some(PathMember::String { val, .. }) => match (&groups, &value) {
+            (Some(groups), _) => grouped_row_index(groups, &val).unwrap_or(0),
+            (None, Value::Record { val: rec, .. }) if app.reversed => {
+                rec.cols.iter().position(|x| x == &val).unwrap_or(0)
+            }
+            (None, Value::Record { val: rec, .. }) => visible_columns(rec, config, app)
+                .iter()
+                .position(|x| x == &val)
+                .unwrap_or(0),
+            (None, _) => 0,
+        },
+        None => 0,
+    };
+    let group_style = normal_name_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    // NOTE: when the keys are focused, only the key of the selected row is highlighted
+    let key_focus = app.focused_key().is_some() && matches!(value, Value::Record { .. });
+    let is_focused_key = |name: &Option<String>| key_focus && name == &focused_name;
+    let row_highlight_style = if key_focus {
+        Style::default()
+    } else {
+        highlight_style
+    };
+
+    let gutter = match &value {
+        Value::List { vals, .. } if app.reversed => {
+            let mut gutter =
+                list_gutter(vals.len(), reversed_index(selected), app.relative_indices);
+            gutter.reverse();
+            gutter
+        }
+        Value::List { vals, .. } => list_gutter(vals.len(), selected, app.relative_indices),
+        _ => vec![],
+    };
+
+    if is_table_view(app, &value, single_column) {
+        let mut block = Block::default().borders(Borders::ALL);
+        if config.show_column_summary {
+            block = block.title(repr_column_summary(&infer_columns(&value)));
+        }
+
+        // NOTE: only the rows that are kept are represented, so the shapes are the ones of these rows
+        let (total, kept, columns, shapes, cells) = match value {
+            Value::List { vals, .. } => {
+                let kept = capped_rows(vals.len(), selected, config.max_rows);
+                let recs = vals[kept.clone()]
+                    .iter()
+                    .map(|v| v.as_record().unwrap().clone())
+                    .collect::<Vec<Record>>();
+                let (columns, shapes, cells) = repr_table(&recs, &config.preview, &app.renderers);
+                let cells = if app.inspect {
+                    annotate_types(&recs, cells)
+                } else {
+                    cells
+                };
+                (vals.len(), kept, columns, shapes, cells)
+            }
+            _ => panic!("value is a table but is not a list"),
+        };
+
+        // NOTE: the columns that do not fit are paged through, see `App::column_offset`, with an
+        // indicator at the bottom because the top border may show the summary of the columns
+        let fit = table_columns_fit(rect_without_bottom_bar.width, config);
+        let offset = app.column_offset.min(columns.len().saturating_sub(fit));
+        let end = (offset + fit).min(columns.len());
+        if columns.len() > fit {
+            block = block.title(
+                Title::from(format!("cols {}-{} of {}", offset + 1, end, columns.len()))
+                    .alignment(Alignment::Right)
+                    .position(Position::Bottom),
+            );
+        }
+        let cells = cells
+            .into_iter()
+            .map(|row| row[offset..end].to_vec())
+            .collect::<Vec<Vec<String>>>();
+
+        let header = columns[offset..end]
+            .iter()
+            .zip(&shapes[offset..end])
+            .map(|(c, s)| {
+                let spans = vec![
+                    Span::styled(display_key(app, c), normal_name_style),
+                    " (".into(),
+                    Span::styled(s, normal_shape_style),
+                    ")".into(),
+                ];
+
+                Cell::from(Line::from(spans))
+            })
+            .collect::<Vec<Cell>>();
+
+        let widths = header
+            .iter()
+            // FIXME: use an appropriate constraint here
+            .map(|_| Constraint::Min(25))
+            .collect::<Vec<Constraint>>();
+
+        let header = Row::new(header).height(1);
+
+        let rows: Vec<Row> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let i = kept.start + i;
+                Row::new(
+                    r.iter()
+                        .map(|c| Cell::from(data_text(c.clone(), config)))
+                        .collect::<Vec<Cell>>(),
+                )
+                .style(highlight_change(
+                    stripe(Style::default(), i, config),
+                    changed_index(i),
+                    config,
+                ))
+            })
+            .collect();
+
+        let rows = with_more_row(rows, total - kept.len(), 0);
+        let shown = selected - kept.start;
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .highlight_style(highlight_style)
+            .highlight_symbol(config.colors.selected_symbol.clone());
+
+        frame.render_stateful_widget(
+            table,
+            rect_without_bottom_bar,
+            &mut TableState::default().with_selected(Some(shown)),
+        );
+        // NOTE: the rows are below the top border and the header
+        render_scrollbar(
+            frame,
+            rows_area(rect_without_bottom_bar, 1 + 1, 1),
+            total,
+            selected,
+            config,
+        );
+
+        return;
+    }
+
+    if app.is_at_bottom() {
+        if let Some(text) = repr_leaf(&value, &config.leaf_view) {
+            frame.render_widget(
+                Paragraph::new(text)
+                    .style(normal_data_style)
+                    .block(Block::default().borders(Borders::ALL))
+                    .wrap(Wrap { trim: false }),
+                rect_without_bottom_bar,
+            );
+            return;
+        }
+    }
+
+    let layout = if single_column {
+        &Layout::Compact
+    } else {
+        &config.layout
+    };
+    match layout {
+        // NOTE: the tree view shows the bottom of the data like the compact layout
+        Layout::Compact | Layout::Tree => {
+            let to_item = |row: DataRowRepr| {
+                let mut spans = vec![];
+                let row_name_style = if is_focused_key(&row.name) {
+                    highlight_style
+                } else {
+                    normal_name_style
+                };
+                if let Some(name) = row.name {
+                    // NOTE: the names of the items of lists are their indices
+                    let name = match value {
+                        Value::Record { .. } => display_key(app, &name),
+                        _ => name,
+                    };
+                    spans.push(Span::styled(name, row_name_style));
+                    spans.push(": ".into());
+                }
+                // NOTE: the inspect mode is all about the types
+                if !single_column || app.inspect {
+                    spans.push("(".into());
+                    spans.push(Span::styled(row.shape, normal_shape_style));
+                    spans.push(") ".into());
+                }
+                if let Some(ansi) = ansi_spans(&row.data, normal_data_style, config) {
+                    spans.extend(ansi);
+                } else if app.is_at_bottom() && matches!(value, Value::String { .. }) {
+                    spans.extend(
+                        highlight_trailing_whitespace(&row.data, normal_data_style, config).spans,
+                    );
+                } else {
+                    spans.push(Span::styled(row.data, normal_data_style));
+                }
+
+                ListItem::new(Line::from(spans))
+            };
+
+            let items: Vec<ListItem> = match groups {
+                Some(groups) => groups
+                    .into_iter()
+                    .flat_map(|(group, rows)| {
+                        std::iter::once(ListItem::new(Span::styled(group, group_style)))
+                            .chain(rows.into_iter().map(to_item))
+                    })
+                    .collect(),
+                None => pinned_first(rows_of(&value), &pinned)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, mut row)| {
+                        if let Some(index) = gutter.get(i) {
+                            row.name = Some(index.clone());
+                        }
+                        to_item(row)
+                    })
+                    .collect(),
+            };
+
+            let total = items.len();
+            let items = List::new(items)
+                .highlight_style(row_highlight_style)
+                .highlight_symbol(&config.colors.selected_symbol);
+
+            frame.render_stateful_widget(
+                items,
+                rect_without_bottom_bar,
+                &mut ListState::default().with_selected(Some(selected)),
+            );
+            render_scrollbar(frame, rect_without_bottom_bar, total, selected, config);
+        }
+        Layout::Table => {
+            let inner_width = table_inner_width(rect_without_bottom_bar.width, config);
+            // NOTE: only the rows that are kept are represented, but the grouped fields are all
+            // represented to be grouped
+            let (total, kept, header, rows, constraints) = match value {
+                Value::List { ref vals, .. } => {
+                    let header = Row::new(vec![
+                        Cell::from("#").style(normal_name_style.add_modifier(Modifier::REVERSED)),
+                        Cell::from("item")
+                            .style(normal_data_style.add_modifier(Modifier::REVERSED)),
+                        Cell::from("shape")
+                            .style(normal_shape_style.add_modifier(Modifier::REVERSED)),
+                    ]);
+                    let gutter_width = gutter_width(&gutter);
+                    let kept = capped_rows(vals.len(), selected, config.max_rows);
+                    let rows = if vals.is_empty() {
+                        rows_of(&value)
+                    } else {
+                        elide(repr_items(&vals[kept.clone()], &config.preview))
+                    };
+                    let rows: Vec<Row> = rows
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, row)| {
+                            let i = kept.start + i;
+                            let data_style = match row.data.as_str() {
+                                "record" | "list" => normal_data_style.add_modifier(Modifier::DIM),
+                                _ => normal_data_style,
+                            };
+                            let style = |style| {
+                                highlight_change(stripe(style, i, config), changed_index(i), config)
+                            };
+                            let data = with_type_glyph(row.data, &row.shape, config);
+                            let (data, height) = if app.wrap_focused && i == selected {
+                                wrap_cell(
+                                    data,
+                                    inner_width.saturating_sub(gutter_width),
+                                    90,
+                                    config,
+                                )
+                            } else {
+                                (data, 1)
+                            };
+
+                            Row::new(vec![
+                                Cell::from(gutter.get(i).cloned().unwrap_or_default())
+                                    .style(style(normal_name_style)),
+                                Cell::from(data_text(data, config)).style(style(data_style)),
+                                Cell::from(row.shape).style(style(normal_shape_style)),
+                            ])
+                            .height(height)
+                        })
+                        .collect();
+
+                    let constraints = vec![
+                        Constraint::Length(gutter_width as u16),
+                        Constraint::Percentage(90),
+                        Constraint::Percentage(10),
+                    ];
+
+                    (vals.len(), kept, header, rows, constraints)
+                }
+                Value::Record { val: ref rec, .. } => {
+                    let header = Row::new(vec![
+                        Cell::from("key").style(normal_name_style.add_modifier(Modifier::REVERSED)),
+                        Cell::from("field")
+                            .style(normal_data_style.add_modifier(Modifier::REVERSED)),
+                        Cell::from("shape")
+                            .style(normal_shape_style.add_modifier(Modifier::REVERSED)),
+                    ]);
+
+                    let to_row = |(i, row): (usize, DataRowRepr)| {
+                        let data_style = match row.data.as_str() {
+                            "record" | "list" => normal_data_style.add_modifier(Modifier::DIM),
+                            _ => normal_data_style,
+                        };
+                        let changed = changed_name(&row.name);
+                        let style =
+                            |style| highlight_change(stripe(style, i, config), changed, config);
+                        let row_name_style = if is_focused_key(&row.name) {
+                            highlight_style
+                        } else {
+                            style(normal_name_style)
+                        };
+                        let data = with_type_glyph(row.data, &row.shape, config);
+                        let (data, height) = if app.wrap_focused && row.name == focused_name {
+                            wrap_cell(data, inner_width, 70, config)
+                        } else {
+                            (data, 1)
+                        };
+
+                        Row::new(vec![
+                            Cell::from(row.name.map(|n| display_key(app, &n)).unwrap_or_default())
+                                .style(row_name_style),
+                            Cell::from(data_text(data, config)).style(style(data_style)),
+                            Cell::from(row.shape).style(style(normal_shape_style)),
+                        ])
+                        .height(height)
+                    };
+
+                    let (total, kept, rows): (_, _, Vec<Row>) = match groups {
+                        Some(groups) => {
+                            let rows: Vec<Row> = groups
+                                .into_iter()
+                                .flat_map(|(group, rows)| {
+                                    std::iter::once(Row::new(vec![
+                                        Cell::from(group).style(group_style)
+                                    ]))
+                                    .chain(rows.into_iter().enumerate().map(to_row))
+                                })
+                                .collect();
+                            let total = rows.len();
+                            let kept = capped_rows(total, selected, config.max_rows);
+                            let rows = rows.into_iter().skip(kept.start).take(kept.len()).collect();
+                            (total, kept, rows)
+                        }
+                        None if rec.is_empty() => (
+                            1,
+                            0..1,
+                            rows_of(&value)
+                                .into_iter()
+                                .enumerate()
+                                .map(to_row)
+                                .collect(),
+                        ),
+                        None => {
+                            // NOTE: the pinned fields come first
+                            let fields = rec
+                                .iter()
+                                .filter(|(col, _)| pinned.contains(*col))
+                                .chain(rec.iter().filter(|(col, _)| !pinned.contains(*col)));
+                            let kept = capped_rows(rec.len(), selected, config.max_rows);
+                            let rows = repr_fields(
+                                fields.skip(kept.start).take(kept.len()),
+                                &config.preview,
+                            );
+                            let rows = elide(rows)
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, row)| to_row((kept.start + i, row)))
+                                .collect();
+                            (rec.len(), kept, rows)
+                        }
+                    };
+
+                    let constraints = vec![
+                        Constraint::Percentage(20),
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(10),
+                    ];
+
+                    (total, kept, header, rows, constraints)
+                }
+                v => {
+                    let repr = repr_value(&v, &config.preview);
+                    let mut spans = match (ansi_spans(&repr.data, normal_data_style, config), v) {
+                        (Some(spans), _) => spans,
+                        (None, Value::String { .. }) => {
+                            highlight_trailing_whitespace(&repr.data, normal_data_style, config)
+                                .spans
+                        }
+                        (None, _) => vec![Span::styled(repr.data, normal_data_style)],
+                    };
+                    spans.push(" is of shape ".into());
+                    spans.push(Span::styled(repr.shape, normal_shape_style));
+
+                    frame.render_widget(
+                        Paragraph::new(Line::from(spans))
+                            .block(Block::default().borders(Borders::ALL))
+                            .wrap(Wrap { trim: false }),
+                        rect_without_bottom_bar,
+                    );
+                    return;
+                }
+            };
+
+            // NOTE: the indicator is in the column of the data, after the names or the indices
+            let rows = with_more_row(rows, total - kept.len(), 1);
+            let shown = selected - kept.start;
+            let table = if config.show_table_header {
+                Table::new(rows, constraints).header(header.height(1))
+            } else {
+                Table::new(rows, constraints)
+            }
+            .block(Block::default().borders(Borders::ALL))
+            .highlight_style(row_highlight_style)
+            .highlight_symbol(config.colors.selected_symbol.clone());
+
+            frame.render_stateful_widget(
+                table,
+                rect_without_bottom_bar,
+                &mut TableState::default().with_selected(Some(shown)),
+            );
+            let header = if config.show_table_header { 1 } else { 0 };
+            render_scrollbar(
+                frame,
+                rows_area(rect_without_bottom_bar, 1 + header, 1),
+                total,
+                selected,
+                config,
+            );
+        }
+    }
+}
+
+/// render the cell path just above the status bar
+///
+/// this line can be removed through config, see [`crate::config::Config::show_cell_path`]
+///
+/// # Examples
+/// > :bulb: **Note**  
+/// > the `...` are here to signify that the bar might be truncated and the `||` at the start and
+/// the end of the lines are just to represent the borders of the terminal but will not appear in
+/// the TUI.
+/// - at the beginning
+/// ```text
+/// ||cell path: $.   ...||
+/// ```
+/// - after some navigation, might look like
+/// ```text
+/// ||cell path: $.foo.bar.2.baz    ...||
+/// ```
+fn render_cell_path(frame: &mut Frame, app: &App, config: &Config) {
+    // NOTE: the cell path takes the last line when the status bar is at the top
+    let y = if app.status_bar_on_top(config) {
+        frame.size().height - 1
+    } else {
+        frame.size().height - 2
+    };
+    let next_to_bottom_bar_rect = Rect::new(0, y, frame.size().width, 1);
+    let cell_path = format!(
+        "cell path: {}",
+        repr_breadcrumb(&app.absolute_position(), &app.breadcrumb_style)
+    );
+
+    frame.render_widget(
+        Paragraph::new(cell_path).alignment(Alignment::Left),
+        next_to_bottom_bar_rect,
+    );
+}
+
+/// write a cell path in the given [`BreadcrumbStyle`], e.g. `$.r.a`, `$in.r.a` or `r.a`
+fn repr_breadcrumb(cell_path: &CellPath, style: &BreadcrumbStyle) -> String {
+    let members = cell_path
+        .members
+        .iter()
+        .map(|m| match m {
+            PathMember::Int { val, .. } => val.to_string(),
+            PathMember::String { val, .. } => val.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(".");
+
+    match style {
+        BreadcrumbStyle::Leading => format!("$.{}", members),
+        BreadcrumbStyle::In if members.is_empty() => "$in".into(),
+        BreadcrumbStyle::In => format!("$in.{}", members),
+        BreadcrumbStyle::Bare => members,
+    }
+}
+
+/// render the status bar at the bottom
+///
+/// the bar takes the last line of the TUI only and renders, from left to right
+/// - the current mode
+/// - hints about next bindings to press and actions to do
+///
+/// the color depending of the mode is completely configurable!
+///
+/// # Examples
+/// > :bulb: **Note**  
+/// > - the `...` are here to signify that the bar might be truncated and the `||` at the start and
+/// the end of the lines are just to represent the borders of the terminal but will not appear in
+/// the TUI.
+/// > - these examples use the default bindings
+/// - in NORMAL mode
+/// ```text
+/// ||NORMAL  ...                                     i to INSERT | hjkl to move around | p to peek | q to quit||
+/// ```
+/// - in INSERT mode
+/// ```text
+/// ||INSERT  ...                                                                               <esc> to NORMAL||
+/// ```
+/// - in PEEKING mode
+/// ```text
+/// ||PEEKING ... <esc> to NORMAL | hjkl to move around | a to peek all | c to peek current view | u to peek under cursor | q to quit||
+/// ```
+fn render_status_bar(frame: &mut Frame, app: &App, config: &Config) {
+    let y = if app.status_bar_on_top(config) {
+        0
+    } else {
+        frame.size().height - 1
+    };
+    let bottom_bar_rect = Rect::new(0, y, frame.size().width, 1);
+
+    let bg_style = match app.mode {
+        Mode::Normal => Style::default().bg(config.colors.status_bar.normal.background),
+        // NOTE: the form is a kind of editor
+        Mode::Insert | Mode::Form => {
+            Style::default().bg(config.colors.status_bar.insert.background)
+        }
+        Mode::Peeking => Style::default().bg(config.colors.status_bar.peek.background),
+        Mode::Bottom => Style::default().bg(config.colors.status_bar.bottom.background),
+        // NOTE: replacing is a kind of search
+        Mode::Search | Mode::Replace => {
+            Style::default().bg(config.colors.status_bar.search.background)
+        }
+    };
+
+    let style = match app.mode {
+        Mode::Normal => bg_style.fg(config.colors.status_bar.normal.foreground),
+        Mode::Insert | Mode::Form => bg_style.fg(config.colors.status_bar.insert.foreground),
+        Mode::Peeking => bg_style.fg(config.colors.status_bar.peek.foreground),
+        Mode::Bottom => bg_style.fg(config.colors.status_bar.bottom.foreground),
+        Mode::Search | Mode::Replace => bg_style.fg(config.colors.status_bar.search.foreground),
+    };
+
+    let hints = match (&app.pending_sequence, &app.mode) {
+        (None, Mode::Normal) if app.pending_operator.is_some() => format!(
+            "a count then {} or {} to delete the items up to there, {} for the items from the cursor | {} to cancel",
+            repr_keycode(&config.keybindings.navigation.down),
+            repr_keycode(&config.keybindings.navigation.up),
+            repr_keycode(&config.keybindings.delete_operator),
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::Mark), _) => format!(
+            "waiting for a letter to mark the current cell | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::JumpToMark), _) => format!(
+            "waiting for the letter of a mark to jump to | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::TypeFilter), _) => format!(
+            "waiting for the letter of a type, e.g. s for strings, or another letter to stop filtering | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::SplitString), _) => format!(
+            "waiting for the delimiter to split the string with | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::Sort), _) => format!(
+            "n to sort numerically, l to sort lexically | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::CommitSort), _) => format!(
+            "k to sort by key, v to sort by value, K and V in descending order | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::TypeAhead), _) => format!(
+            "waiting for the first letter of a key to jump to | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::YankRelativePath), _) => format!(
+            "waiting for the letter of a mark to copy the path from | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (Some(PendingKind::JoinList), _) => format!(
+            "waiting for the separator to join the list with | {} to cancel",
+            repr_keycode(&KeyCode::Esc),
+        ),
+        (None, Mode::Normal) => format!(
+            "{} to {} | {}{}{}{} to move around | {} to peek | {} to transpose | {} to quit",
+            repr_keycode(&config.keybindings.insert),
+            Mode::Insert,
+            repr_keycode(&config.keybindings.navigation.left),
+            repr_keycode(&config.keybindings.navigation.down),
+            repr_keycode(&config.keybindings.navigation.up),
+            repr_keycode(&config.keybindings.navigation.right),
+            repr_keycode(&config.keybindings.peek),
+            repr_keycode(&config.keybindings.transpose),
+            repr_keycode(&config.keybindings.quit),
+        ),
+        (None, Mode::Insert) => format!(
+            "{} to quit | {}{}{}{} to move the cursor | {}{} to delete characters | {} to confirm | ctrl+n to set to null",
+            repr_keycode(&KeyCode::Esc),
+            repr_keycode(&KeyCode::Left),
+            repr_keycode(&KeyCode::Right),
+            repr_keycode(&KeyCode::Up),
+            repr_keycode(&KeyCode::Down),
+            repr_keycode(&KeyCode::Backspace),
+            repr_keycode(&KeyCode::Delete),
+            repr_keycode(&KeyCode::Enter),
+        ),
+        (None, Mode::Form) => format!(
+            "{} to quit | {} and {} to go to the next and previous fields | {} to confirm",
+            repr_keycode(&KeyCode::Esc),
+            repr_keycode(&KeyCode::Tab),
+            repr_keycode(&KeyCode::BackTab),
+            repr_keycode(&KeyCode::Enter),
+        ),
+        (None, Mode::Peeking) => format!(
+            "{} to {} | {}{}{}{} to move around | {} to peek all | {} to peek current view | {} to peek under cursor | {} to peek the cell path | {} to peek the config | {} to change the format, {}",
+            repr_keycode(&config.keybindings.normal),
+            Mode::Normal,
+            repr_keycode(&config.keybindings.navigation.left),
+            repr_keycode(&config.keybindings.navigation.down),
+            repr_keycode(&config.keybindings.navigation.up),
+            repr_keycode(&config.keybindings.navigation.right),
+            repr_keycode(&config.keybindings.peeking.all),
+            repr_keycode(&config.keybindings.peeking.view),
+            repr_keycode(&config.keybindings.peeking.under),
+            repr_keycode(&config.keybindings.peeking.cell_path),
+            repr_keycode(&config.keybindings.peeking.config),
+            repr_keycode(&config.keybindings.peeking.cycle_format),
+            app.peek_format,
+        ),
+        (None, Mode::Bottom) if app.folded_json.is_some() => format!(
+            "{} to {} | {}{} to move the cursor | {} to fold or unfold | {} to peek | {} to quit",
+            repr_keycode(&config.keybindings.navigation.left),
+            Mode::Normal,
+            repr_keycode(&config.keybindings.navigation.up),
+            repr_keycode(&config.keybindings.navigation.down),
+            repr_keycode(&config.keybindings.toggle_fold),
+            repr_keycode(&config.keybindings.peek),
+            repr_keycode(&config.keybindings.quit),
+        ),
+        (None, Mode::Bottom) => format!(
+            "{} to {} | {} to peek | {} to quit",
+            repr_keycode(&config.keybindings.navigation.left),
+            Mode::Normal,
+            repr_keycode(&config.keybindings.peek),
+            repr_keycode(&config.keybindings.quit),
+        ),
+        (None, Mode::Search) => {
+            let count = match app.live_matches {
+                Some(1) => " | 1 match".into(),
+                Some(n) => format!(" | {} matches", n),
+                None => "".into(),
+            };
+            format!(
+                "{}{}{} | {} to search | {} to cancel",
+                repr_keycode(&config.keybindings.search),
+                app.search_query,
+                count,
+                repr_keycode(&KeyCode::Enter),
+                repr_keycode(&config.keybindings.normal),
+            )
+        }
+        (None, Mode::Replace) => match &app.replace_find {
+            None => format!(
+                "replace: {} | {} to type the replacement | {} to cancel",
+                app.replace_input,
+                repr_keycode(&KeyCode::Enter),
+                repr_keycode(&config.keybindings.normal),
+            ),
+            Some(find) => format!(
+                "replace `{}` with: {} | {} to replace | {} to cancel",
+                find,
+                app.replace_input,
+                repr_keycode(&KeyCode::Enter),
+                repr_keycode(&config.keybindings.normal),
+            ),
+        },
+    };
+
+    let left = Line::from(Span::styled(
+        format!(" {} ", app.mode),
+        style.add_modifier(Modifier::REVERSED),
+    ));
+    let mut right = match &app.message {
+        Some(message) => Line::from(Span::styled(message.as_str(), style)),
+        None => Line::from(Span::styled(hints, style)),
+    };
+    if let Some(prompt) = repr_right_prompt(app) {
+        right.spans.push(Span::styled(" | ", style));
+        right
+            .spans
+            .push(Span::styled(prompt, style.add_modifier(Modifier::BOLD)));
+    }
+
+    frame.render_widget(
+        Paragraph::new(left)
+            .alignment(Alignment::Left)
+            .style(bg_style),
+        bottom_bar_rect,
+    );
+    frame.render_widget(
+        Paragraph::new(right).alignment(Alignment::Right),
+        bottom_bar_rect,
+    );
+}
+
+/// evaluate [`App::right_prompt`] on the value under the cursor, if any
+///
+/// errors are not fatal and are shown as `<err>`.
+fn repr_right_prompt(app: &App) -> Option<String> {
+    let prompt = app.right_prompt.as_ref()?;
+
+    let cell = app
+        .value
+        .clone()
+        .follow_cell_path(&app.position.members, false)
+        .map_err(|e| e.to_string());
+
+    Some(cell.and_then(|c| prompt(&c)).unwrap_or("<err>".into()))
+}
+
+// TODO: add proper assert error messages
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::time::Instant;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use nu_protocol::{
+        ast::{CellPath, PathMember},
+        record, CustomValue, ShellError, Span, Value,
+    };
+    use ratatui::{backend::TestBackend, Terminal};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        app::{App, RendererRegistry, SEARCH_DEBOUNCE},
+        config::{
+            BreadcrumbStyle, Config, Layout, LeafView, MissingDisplay, PreviewConfig,
+            ScrollbarConfig, ScrollbarVisibility, StatusBarPosition,
+        },
+        handler::handle_key_events,
+        nu::cell_path::{to_path_member_vec, PM},
+        tree::expand_all,
+    };
+
+    use super::{
+        downsample_depths, group_rows, group_rows_by_type, grouped_row_index, list_gutter,
+        minimap_row, preview_lines, render_ui, repr_breadcrumb, repr_data, repr_leaf, repr_list,
+        repr_record, repr_simple_value, repr_table, repr_value, wrap_text, DataRowRepr,
+    };
+
+    /// render the whole UI in a terminal of the given size and give back its lines
+    fn render(app: &App, config: &Config, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, app, config, None))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer.get(x, y).symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn simple_value() {
+        #[rustfmt::skip]
+        let cases = vec![
+            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
+            (Value::test_int(1), DataRowRepr::unnamed("1", "int")),
+            (Value::test_bool(true), DataRowRepr::unnamed("true", "bool")),
+            (Value::test_nothing(), DataRowRepr::unnamed("", "nothing")),
+            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(repr_simple_value(&value), expected);
+        }
+    }
+
+    #[test]
+    fn list() {
+        let list = vec![
+            Value::test_string("a"),
+            Value::test_int(1),
+            Value::test_bool(false),
+        ];
+
+        #[rustfmt::skip]
+        let cases = vec![
+            (list, DataRowRepr::unnamed("[3 items]", "list")),
+            (vec![], DataRowRepr::unnamed("[]", "list")),
+            (vec![Value::test_nothing()], DataRowRepr::unnamed("[1 item]", "list")),
+        ];
+
+        for (list, expected) in cases {
+            assert_eq!(repr_list(&list), expected);
+        }
+    }
+
+    #[test]
+    fn full_preview() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_string("my"), Value::test_list(vec![])]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+            }),
+            "s" => Value::test_string("some string"),
+        });
+        let preview = Config::default().preview;
+
+        assert_eq!(
+            preview_lines(&value, 2, &preview),
+            vec!["l:", "  - my", "  - []", "r:", "  a: 1", "s: some string"]
+        );
+        assert_eq!(preview_lines(&Value::test_int(1), 2, &preview), vec!["1"]);
+        assert_eq!(
+            preview_lines(&Value::test_record(record! {}), 2, &preview),
+            vec!["{}"]
+        );
+    }
+
+    #[test]
+    fn collapse_large_containers() {
+        let preview = PreviewConfig {
+            collapse_over: 2,
+            ..Config::default().preview
+        };
+
+        let list = Value::test_list((0..1000).map(Value::test_int).collect());
+        assert_eq!(
+            preview_lines(&list, 2, &Config::default().preview),
+            vec!["[1000 items]"]
+        );
+
+        // NOTE: only the containers above the threshold are collapsed, at any depth
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![
+                Value::test_int(1),
+                Value::test_list((0..3).map(Value::test_int).collect()),
+            ]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+                "c" => Value::test_int(3),
+            }),
+        });
+        assert_eq!(
+            preview_lines(&value, 2, &preview),
+            vec!["l:", "  - 1", "  - [3 items]", "r: {3 fields}"]
+        );
+    }
+
+    #[test]
+    fn scroll_preview_pane() {
+        let config = Config {
+            show_preview_pane: true,
+            ..Default::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "l" => Value::test_list((0..20).map(Value::test_int).collect()),
+            "s" => Value::test_string("some string"),
+        }));
+
+        // NOTE: the preview pane takes the right 40% of the width, inside a border
+        let preview = |lines: &[String]| lines[1].chars().skip(61).collect::<String>();
+
+        let lines = render(&app, &config, 100, 10);
+        assert!(preview(&lines).starts_with("- 0"), "{:?}", preview(&lines));
+
+        for _ in 0..3 {
+            handle_key_events(
+                KeyEvent::new(
+                    config.keybindings.scroll_preview_down,
+                    KeyModifiers::empty(),
+                ),
+                &mut app,
+                &config,
+            )
+            .unwrap();
+        }
+
+        let lines = render(&app, &config, 100, 10);
+        assert!(preview(&lines).starts_with("- 3"), "{:?}", preview(&lines));
+        assert_eq!(
+            app.position.members,
+            vec![PathMember::String {
+                val: "l".into(),
+                span: Span::unknown(),
+                optional: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn render_legend() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+        }));
+
+        assert!(!render(&app, &config, 60, 10)[0].contains("legend"));
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.toggle_legend, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        let line = |y: u16| -> String { (0..60).map(|x| buffer.get(x, y).symbol()).collect() };
+        assert!(line(0).contains("legend"), "{:?}", line(0));
+
+        let colors = &config.colors;
+        let entries = [
+            ("name", colors.normal.name.foreground),
+            ("data", colors.normal.data.foreground),
+            ("shape", colors.normal.shape.foreground),
+            ("selected", colors.selected.foreground),
+        ];
+        // NOTE: the legend is 12 cells wide, in the top right corner and inside a border
+        for (y, (name, color)) in entries.into_iter().enumerate() {
+            let y = y as u16 + 1;
+            assert!(line(y).ends_with(&format!("│{name:10}│")), "{:?}", line(y));
+            assert_eq!(buffer.get(49, y).fg, color, "wrong color for {name}");
+        }
+        assert!(buffer
+            .get(49, 4)
+            .modifier
+            .contains(config.colors.selected_modifier));
+    }
+
+    #[test]
+    fn render_ancestors() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(0)]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.toggle_ancestors, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        let line = |y: u16| -> String { (0..60).map(|x| buffer.get(x, y).symbol()).collect() };
+        assert!(line(0).starts_with("┌ancestors"), "{:?}", line(0));
+        assert!(line(1).starts_with("│$: l r "), "{:?}", line(1));
+        assert!(line(2).starts_with("│$.r: a b "), "{:?}", line(2));
+        assert!(line(3).starts_with('└'), "{:?}", line(3));
+
+        // NOTE: only the child leading to the cursor is highlighted at each level
+        let selected = config.colors.selected.background;
+        assert_eq!(buffer.get(6, 1).bg, selected, "`r` is not highlighted");
+        assert_ne!(buffer.get(4, 1).bg, selected, "`l` is highlighted");
+        assert_eq!(buffer.get(6, 2).bg, selected, "`a` is not highlighted");
+        assert_ne!(buffer.get(8, 2).bg, selected, "`b` is highlighted");
+    }
+
+    #[test]
+    fn render_table_view() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            Value::test_record(record! {
+                "a" => Value::test_int(3),
+                "b" => Value::test_int(4),
+            }),
+        ]));
+        app.position.members = to_path_member_vec(&[PM::I(0)]);
+        let toggle = |app: &mut App| {
+            handle_key_events(
+                KeyEvent::new(config.keybindings.toggle_table_view, KeyModifiers::empty()),
+                app,
+                &config,
+            )
+            .unwrap();
+        };
+
+        let lines = render(&app, &config, 60, 10);
+        assert!(lines[1].contains("a (int)"), "{:?}", lines);
+        assert!(!lines.iter().any(|l| l.contains("{a, b}")), "{:?}", lines);
+
+        toggle(&mut app);
+        let lines = render(&app, &config, 60, 10);
+        assert!(!lines.iter().any(|l| l.contains("a (int)")), "{:?}", lines);
+        assert!(lines.iter().any(|l| l.contains("{a, b}")), "{:?}", lines);
+
+        toggle(&mut app);
+        let lines = render(&app, &config, 60, 10);
+        assert!(lines[1].contains("a (int)"), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_row_labels() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_list(
+            (0..12)
+                .map(|i| Value::test_string(format!("s{i}")))
+                .collect(),
+        ));
+        app.position.members = to_path_member_vec(&[PM::I(0)]);
+        app.height = 20;
+        app.width = 60;
+
+        let press = |app: &mut App, code: KeyCode, modifiers: KeyModifiers| {
+            handle_key_events(KeyEvent::new(code, modifiers), app, &config).unwrap();
+        };
+
+        press(&mut app, config.keybindings.label_rows, KeyModifiers::ALT);
+        assert_eq!(app.row_labels, Some(0));
+
+        // NOTE: the rows are below the top border and the header of the table
+        let lines = render(&app, &config, 40, 20);
+        for i in 0..12 {
+            let label = format!("{:>2}", i + 1);
+            assert!(lines[2 + i].starts_with(&label), "{:?}", lines);
+        }
+        assert!(lines[14].starts_with('│'), "{:?}", lines);
+
+        // NOTE: `1` is ambiguous with `10`, `11` and `12`, but `3` is not
+        press(&mut app, KeyCode::Char('3'), KeyModifiers::empty());
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(2)]));
+        assert_eq!(app.row_labels, None);
+
+        press(&mut app, config.keybindings.label_rows, KeyModifiers::ALT);
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::empty());
+        assert_eq!(app.row_labels, Some(1));
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::empty());
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(10)]));
+
+        press(&mut app, config.keybindings.label_rows, KeyModifiers::ALT);
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+
+        // NOTE: any other key hides the labels without moving
+        press(&mut app, config.keybindings.label_rows, KeyModifiers::ALT);
+        press(&mut app, KeyCode::Esc, KeyModifiers::empty());
+        assert_eq!(app.row_labels, None);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::I(0)]));
+        let lines = render(&app, &config, 40, 20);
+        assert!(lines[3].starts_with('│'), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_type_glyphs() {
+        let mut config = Config {
+            layout: Layout::Table,
+            ..Config::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "i" => Value::test_int(1),
+            "l" => Value::test_list(vec![Value::test_int(1)]),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+
+        let row = |lines: &[String], key: &str| {
+            lines
+                .iter()
+                .find(|l| l.starts_with(&format!("│{key} ")))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let lines = render(&app, &config, 60, 10);
+        assert!(row(&lines, "l").contains("[1 item]"), "{:?}", lines);
+        assert!(!row(&lines, "l").contains("[] [1 item]"), "{:?}", lines);
+
+        config.type_glyphs = vec![("int".into(), "#".into()), ("list".into(), "[]".into())];
+        let lines = render(&app, &config, 60, 10);
+        assert!(row(&lines, "l").contains("[] [1 item]"), "{:?}", lines);
+        assert!(row(&lines, "i").contains("# 1"), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_ansi_colors() {
+        let mut config = Config {
+            layout: Layout::Table,
+            interpret_ansi: true,
+            ..Config::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "i" => Value::test_int(1),
+            "s" => Value::test_string("\x1b[31mred\x1b[0m text"),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("i")]);
+
+        let draw = |config: &Config| {
+            let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+            terminal
+                .draw(|frame| render_ui(frame, &app, config, None))
+                .unwrap();
+            terminal.backend().buffer().clone()
+        };
+
+        let buffer = draw(&config);
+        let line = |y: u16| -> String { (0..60).map(|x| buffer.get(x, y).symbol()).collect() };
+        let y = (0..10).find(|y| line(*y).starts_with("│s ")).unwrap();
+        let x = line(y).chars().collect::<Vec<char>>();
+        let x = x
+            .windows(8)
+            .position(|w| w.iter().collect::<String>() == "red text");
+        let x = x.expect("the escape sequences should be interpreted") as u16;
+        assert_eq!(buffer.get(x, y).fg, ratatui::style::Color::Red);
+        assert_ne!(buffer.get(x + 4, y).fg, ratatui::style::Color::Red);
+
+        config.interpret_ansi = false;
+        let buffer = draw(&config);
+        let line: String = (0..60).map(|x| buffer.get(x, y).symbol()).collect();
+        assert!(line.contains("[31mred"), "{:?}", line);
+    }
+
+    #[test]
+    fn render_folded_json() {
+        let config = Config::default();
+        let kmap = config.clone().keybindings;
+        let mut app = App::from_value(Value::test_record(record! {
+            "d" => Value::test_record(record! {
+                "l" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+                "i" => Value::test_int(3),
+            }),
+        }));
+        let press = |app: &mut App, key, modifiers| {
+            handle_key_events(KeyEvent::new(key, modifiers), app, &config).unwrap();
+        };
+        let has = |lines: &[String], text: &str| lines.iter().any(|line| line.contains(text));
+
+        press(&mut app, kmap.fold_view, KeyModifiers::ALT);
+        assert!(app.is_at_bottom());
+        let lines = render(&app, &config, 40, 12);
+        assert!(has(&lines, r#"▾   "l": ["#), "{:#?}", lines);
+        assert!(has(&lines, "      2"), "{:#?}", lines);
+        assert!(has(&lines, r#"    "i": 3"#), "{:#?}", lines);
+
+        press(&mut app, kmap.navigation.down, KeyModifiers::empty());
+        press(&mut app, kmap.toggle_fold, KeyModifiers::empty());
+        let lines = render(&app, &config, 40, 12);
+        assert!(has(&lines, r#"▸   "l": […],"#), "{:#?}", lines);
+        assert!(!has(&lines, "      2"), "{:#?}", lines);
+        assert!(has(&lines, r#"    "i": 3"#), "{:#?}", lines);
+
+        // NOTE: the view is dropped when going back to the data
+        press(&mut app, kmap.navigation.left, KeyModifiers::empty());
+        assert!(app.folded_json.is_none());
+    }
+
+    #[test]
+    fn render_key_focus() {
+        let config = Config {
+            layout: Layout::Table,
+            ..Config::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "key" => Value::test_string("value"),
+            "other" => Value::test_string("field"),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("key")]);
+
+        // NOTE: gives the background of the key and of the value of the selected row
+        let backgrounds = |app: &App| {
+            let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+            terminal
+                .draw(|frame| render_ui(frame, app, &config, None))
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            let line = |y: u16| -> String { (0..60).map(|x| buffer.get(x, y).symbol()).collect() };
+            let y = (0..10).find(|y| line(*y).contains("value")).unwrap();
+            let x = |text: &str| {
+                let line = line(y);
+                line[..line.find(text).unwrap()].chars().count() as u16
+            };
+            (buffer.get(x("key"), y).bg, buffer.get(x("value"), y).bg)
+        };
+        let selected = config.colors.selected.background;
+
+        let (key, value) = backgrounds(&app);
+        assert_eq!(value, selected);
+        assert_eq!(key, selected);
+
+        app.key_focus = true;
+        let (key, value) = backgrounds(&app);
+        assert_eq!(key, selected);
+        assert_ne!(value, selected);
+    }
+
+    #[test]
+    fn render_status_bar_on_top() {
+        let mut config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("a")]);
+
+        let lines = render(&app, &config, 40, 10);
+        let status_bar = lines[9].clone();
+        assert!(lines[0].starts_with('┌'), "{:?}", lines);
+        assert!(lines[8].starts_with("cell path: $.a"), "{:?}", lines);
+
+        config.status_bar_position = StatusBarPosition::Top;
+        let lines = render(&app, &config, 40, 10);
+        assert_eq!(lines[0], status_bar);
+        // NOTE: the data pane moves down and the cell path takes the last line
+        assert!(lines[1].starts_with('┌'), "{:?}", lines);
+        assert!(lines[9].starts_with("cell path: $.a"), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_border() {
+        let mut config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+            }),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+
+        let lines = render(&app, &config, 40, 10);
+        assert!(!lines[0].contains("$.r"), "{:?}", lines);
+
+        config.show_border = true;
+        let lines = render(&app, &config, 40, 10);
+        // NOTE: the title is the path of the container of the cursor
+        assert!(lines[0].starts_with("┌$.r─"), "{:?}", lines);
+        assert!(lines[0].ends_with('┐'), "{:?}", lines);
+        assert!(lines[1].starts_with("│┌"), "{:?}", lines);
+        assert!(lines[7].starts_with('└'), "{:?}", lines);
+        assert!(lines[2].contains('a'), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_quoted_keys() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "my key" => Value::test_int(1),
+            "a" => Value::test_int(2),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("a")]);
+
+        let lines = render(&app, &config, 60, 10);
+        assert!(lines.iter().any(|l| l.contains("│my key")), "{:?}", lines);
+        assert!(
+            !lines.iter().any(|l| l.contains("\"my key\"")),
+            "{:?}",
+            lines
+        );
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.toggle_quoted_keys, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let lines = render(&app, &config, 60, 10);
+        assert!(
+            lines.iter().any(|l| l.contains("│\"my key\"")),
+            "{:?}",
+            lines
+        );
+        // NOTE: the keys that do not need quotes are left bare
+        assert!(lines.iter().any(|l| l.contains("│a ")), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_wide_table() {
+        let config = Config::default();
+        let row = |i: i64| {
+            let mut rec = nu_protocol::Record::new();
+            for c in 0..10 {
+                rec.push(format!("c{c}"), Value::test_int(i * 100 + c));
+            }
+            Value::test_record(rec)
+        };
+        let mut app = App::from_value(Value::test_list(vec![row(0), row(1)]));
+        app.position.members = to_path_member_vec(&[PM::I(0)]);
+        app.width = 80;
+
+        let lines = render(&app, &config, 80, 10);
+        assert!(lines[7].contains("cols 1-3 of 10"), "{:?}", lines);
+        assert!(lines[1].contains("c2 (int)"), "{:?}", lines);
+        assert!(!lines[1].contains("c3 (int)"), "{:?}", lines);
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.columns_right, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let lines = render(&app, &config, 80, 10);
+        assert!(lines[7].contains("cols 4-6 of 10"), "{:?}", lines);
+        assert!(lines[1].contains("c3 (int)"), "{:?}", lines);
+        assert!(lines[2].contains('5'), "{:?}", lines);
+        assert!(!lines[1].contains("c2 (int)"), "{:?}", lines);
+    }
+
+    #[test]
+    fn render_tabs() {
+        let config = Config {
+            show_tabs: true,
+            ..Default::default()
+        };
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        let line = |y: u16| -> String { (0..40).map(|x| buffer.get(x, y).symbol()).collect() };
+        assert!(line(0).starts_with(" root │ r "), "{:?}", line(0));
+        // NOTE: the data is below the tabs
+        assert!(line(1).starts_with('┌'), "{:?}", line(1));
+
+        // NOTE: only the deepest tab is selected
+        let selected = config.colors.selected.background;
+        assert_eq!(buffer.get(8, 0).bg, selected, "`r` is not selected");
+        assert_ne!(buffer.get(1, 0).bg, selected, "`root` is selected");
+    }
+
+    #[test]
+    fn render_minimap() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(0), Value::test_int(1)]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("r"), PM::S("a")]);
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.toggle_minimap, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        // NOTE: one row per node, `l`, `l.0`, `l.1`, `r`, `r.a` and `r.b`, indented by depth
+        let line = |y: u16| -> String { (46..60).map(|x| buffer.get(x, y).symbol()).collect() };
+        assert_eq!(line(0), "┌minimap─────┐");
+        assert_eq!(line(1), "│▬▬▬▬▬▬▬▬▬▬▬▬│");
+        assert_eq!(line(2), "│ ▬▬▬▬▬▬▬▬▬▬▬│");
+        assert_eq!(line(4), "│▬▬▬▬▬▬▬▬▬▬▬▬│");
+        assert_eq!(line(5), "│ ▬▬▬▬▬▬▬▬▬▬▬│");
+
+        let selected = config.colors.selected.background;
+        for y in 1..7 {
+            assert_eq!(
+                buffer.get(50, y).bg == selected,
+                y == 5,
+                "row {} is marked wrongly",
+                y
+            );
+        }
+    }
+
+    #[test]
+    fn downsample_minimap() {
+        assert_eq!(downsample_depths(&[0, 1, 1, 0], 10), vec![0, 1, 1, 0]);
+        assert_eq!(downsample_depths(&[0, 1, 2, 1, 0, 1], 3), vec![0, 1, 0]);
+        assert_eq!(downsample_depths(&[], 3), Vec::<usize>::new());
+
+        // NOTE: the rows stand for the runs `[0]`, `[1, 2]` and `[3, 4]` of the nodes
+        let rows: Vec<usize> = (0..5).map(|index| minimap_row(index, 5, 3)).collect();
+        assert_eq!(rows, vec![0, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn render_form() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "name" => Value::test_string("foo"),
+            "size" => Value::test_int(12),
+            "tags" => Value::test_list(vec![]),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("size")]);
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.form, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(app.mode, crate::app::Mode::Form);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        let line = |y: u16| -> String { (0..40).map(|x| buffer.get(x, y).symbol()).collect() };
+        assert!(line(0).starts_with("┌form"), "{:?}", line(0));
+        assert!(line(1).starts_with("│name: foo "), "{:?}", line(1));
+        assert!(line(2).starts_with("│size: 12 "), "{:?}", line(2));
+        assert!(line(3).starts_with("│tags: list<any> "), "{:?}", line(3));
+
+        // NOTE: the field of the cursor is the one being typed in
+        let selected = config.colors.selected.background;
+        assert_eq!(buffer.get(7, 2).bg, selected, "`size` is not focused");
+        assert_ne!(buffer.get(7, 1).bg, selected, "`name` is focused");
+    }
+
+    #[test]
+    fn render_compare() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+        }));
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("r"), PM::S("a")]),
+        };
+        app.position = path.clone();
+        app.apply_edit(&path, &Value::test_int(9));
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.toggle_compare, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let line = |y: u16, xs: std::ops::Range<u16>| -> String {
+            xs.map(|x| buffer.get(x, y).symbol()).collect()
+        };
+
+        assert!(
+            line(0, 0..30).starts_with("┌original"),
+            "{:?}",
+            line(0, 0..30)
+        );
+        assert!(
+            line(0, 30..60).starts_with("┌edited"),
+            "{:?}",
+            line(0, 30..60)
+        );
+
+        // NOTE: both panes show `r` with `a` selected, only the values differ
+        let row = |y: u16, xs: std::ops::Range<u16>| -> Vec<String> {
+            line(y, xs)
+                .trim_matches(|c| c == '│')
+                .split_whitespace()
+                .map(String::from)
+                .collect()
+        };
+        assert_eq!(row(1, 0..30), vec!["a", "1"]);
+        assert_eq!(row(1, 30..60), vec!["a", "9"]);
+        assert_eq!(row(2, 0..30), vec!["b", "2"]);
+        assert_eq!(buffer.get(1, 1).bg, config.colors.selected.background);
+        assert_eq!(buffer.get(31, 1).bg, config.colors.selected.background);
+        assert_ne!(buffer.get(1, 2).bg, config.colors.selected.background);
+    }
+
+    #[test]
+    fn render_histogram() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_list(
+            ["cat", "dog", "dog"]
+                .into_iter()
+                .map(|animal| {
+                    Value::test_record(record! { "animal" => Value::test_string(animal) })
+                })
+                .collect(),
+        ));
+        let key = KeyEvent::new(config.keybindings.toggle_histogram, KeyModifiers::empty());
+
+        // NOTE: the cursor needs to be in a row of the table
+        handle_key_events(key, &mut app, &config).unwrap();
+        assert_eq!(app.histogram, None);
+        assert!(app.message.is_some());
+
+        app.position.members = to_path_member_vec(&[PM::I(0), PM::S("animal")]);
+        handle_key_events(key, &mut app, &config).unwrap();
+
+        let lines = render(&app, &config, 60, 10);
+        assert!(
+            lines[0].ends_with("┌animal────────────────────────────────┐"),
+            "{:?}",
+            lines[0]
+        );
+        assert!(
+            lines[1].ends_with("│dog               2 ██████████████████│"),
+            "{:?}",
+            lines[1]
+        );
+        assert!(
+            lines[2].ends_with("│cat               1 █████████         │"),
+            "{:?}",
+            lines[2]
+        );
+
+        handle_key_events(key, &mut app, &config).unwrap();
+        assert_eq!(app.histogram, None);
+    }
+
+    #[test]
+    fn right_prompt() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_list(vec![Value::test_int(1), Value::test_int(2)]),
+            "b" => Value::test_string("foo"),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("a")]);
+
+        // NOTE: a mock of the evaluation of a closure by Nushell
+        app.right_prompt = Some(Rc::new(|value: &Value| match value {
+            Value::List { vals, .. } => Ok(format!("{} items", vals.len())),
+            _ => Err("not a list".into()),
+        }));
+
+        let status_bar = |app: &App| render(app, &config, 100, 10)[9].clone();
+
+        assert!(status_bar(&app).trim_end().ends_with("| 2 items"));
+
+        app.position.members = to_path_member_vec(&[PM::S("b")]);
+        assert!(status_bar(&app).trim_end().ends_with("| <err>"));
+
+        app.right_prompt = None;
+        assert!(!status_bar(&app).contains("<err>"));
+    }
+
+    #[test]
+    fn tree_indent() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_record(record! {
+                "b" => Value::test_record(record! {
+                    "c" => Value::test_int(1),
+                }),
+            }),
+        }));
+        app.expanded = expand_all(&app.value, 10);
+
+        for indent in [2, 4] {
+            let config = Config {
+                layout: Layout::Tree,
+                indent,
+                ..Default::default()
+            };
+            let lines = render(&app, &config, 40, 6);
+
+            // NOTE: the markers of the containers and the padding of the leaves are 2 wide
+            assert!(lines[0].starts_with("▾ a: (record)"), "{:?}", lines[0]);
+            assert!(
+                lines[1].starts_with(&format!("{}▾ b: (record)", " ".repeat(indent))),
+                "{:?}",
+                lines[1]
+            );
+            assert!(
+                lines[2].starts_with(&format!("{}  c: (int) 1", " ".repeat(2 * indent))),
+                "{:?}",
+                lines[2]
+            );
+        }
+    }
+
+    #[test]
+    fn tree_sizes() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_int(2),
+            }),
+            "l" => Value::test_list(vec![]),
+            "i" => Value::test_int(3),
+        }));
+        app.expanded = expand_all(&app.value, 10);
+        let config = Config {
+            layout: Layout::Tree,
+            ..Default::default()
+        };
+
+        let lines = render(&app, &config, 40, 8);
+        assert!(lines[0].starts_with("▾ r: (record)"), "{:?}", lines[0]);
+        assert!(lines[0].ends_with(" (2)"), "{:?}", lines[0]);
+        // NOTE: the leaves have no size, the empty containers do
+        assert!(!lines[1].ends_with(')'), "{:?}", lines[1]);
+        assert!(lines[3].ends_with(" (0)"), "{:?}", lines[3]);
+        assert!(!lines[4].ends_with(')'), "{:?}", lines[4]);
+    }
+
+    #[test]
+    fn single_column() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_string("foo"),
+            "l" => Value::test_list(vec![
+                Value::test_record(record! { "x" => Value::test_int(1) }),
+            ]),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("a")]);
+        let config = Config::default();
+
+        let lines = render(&app, &config, 20, 6);
+        assert_eq!(lines[0].trim_end(), "a: 1");
+        assert_eq!(lines[1].trim_end(), "b: foo");
+        assert_eq!(lines[2].trim_end(), "l: [1 item]");
+
+        // NOTE: tables are shown in a single column too
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+        let lines = render(&app, &config, 20, 6);
+        assert_eq!(lines[0].trim_end(), "0: {x}");
+
+        let lines = render(&app, &config, 60, 6);
+        assert!(lines[0].starts_with('┌'), "{:?}", lines[0]);
+    }
+
+    #[test]
+    fn pinned_fields() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "b" => Value::test_int(1),
+            "z" => Value::test_int(2),
+            "a" => Value::test_int(3),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("b")]);
+        app.pinned.insert("z".into());
+
+        let config = Config {
+            layout: Layout::Compact,
+            ..Default::default()
+        };
+        let lines = render(&app, &config, 60, 6);
+        assert!(lines[0].starts_with("z: "), "{:?}", lines[0]);
+        assert!(lines[1].starts_with("b: "), "{:?}", lines[1]);
+        assert!(lines[2].starts_with("a: "), "{:?}", lines[2]);
+
+        // NOTE: sorting would place `z` last
+        let config = Config {
+            layout: Layout::Compact,
+            group_records: true,
+            ..Default::default()
+        };
+        let lines = render(&app, &config, 60, 8);
+        assert_eq!(lines[0].trim_end(), "pinned");
+        assert!(lines[1].starts_with("z: "), "{:?}", lines[1]);
+        assert_eq!(lines[2].trim_end(), "a");
+        assert!(lines[3].starts_with("a: "), "{:?}", lines[3]);
+    }
+
+    #[test]
+    fn render_collapsed_group() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a1" => Value::test_int(1),
+            "a2" => Value::test_int(2),
+            "b1" => Value::test_int(3),
+        }));
+        app.collapsed_groups.insert("a".into());
+        let config = Config {
+            layout: Layout::Compact,
+            group_records: true,
+            ..Default::default()
+        };
+
+        let lines = render(&app, &config, 60, 8);
+        assert_eq!(lines[0].trim_end(), "a (+1 collapsed)");
+        assert!(lines[1].starts_with("a1: "), "{:?}", lines[1]);
+        assert_eq!(lines[2].trim_end(), "b");
+        assert!(lines[3].starts_with("b1: "), "{:?}", lines[3]);
+    }
+
+    #[test]
+    fn zebra_stripes() {
+        let value = Value::test_list((0..4).map(|i| Value::test_string(i.to_string())).collect());
+        let app = App::from_value(value);
+
+        let background = |config: &Config, y: u16| {
+            let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+            terminal
+                .draw(|frame| render_ui(frame, &app, config, None))
+                .unwrap();
+            terminal.backend().buffer().get(1, y).bg
+        };
+
+        // NOTE: the rows start on the third line, below the border and the header, and the first
+        // one is selected
+        let config = Config::default();
+        assert_eq!(background(&config, 3), background(&config, 4));
+
+        let config = Config {
+            zebra_stripes: true,
+            ..Default::default()
+        };
+        assert_eq!(background(&config, 3), config.colors.zebra);
+        assert_eq!(background(&config, 4), config.colors.normal.name.background);
+        assert_eq!(background(&config, 5), config.colors.zebra);
+    }
+
+    #[test]
+    fn changed_rows() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(2),
+            "c" => Value::test_list(vec![Value::test_int(3)]),
+        }));
+        app.snapshot = Some(app.value.clone());
+        app.set_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+            "b" => Value::test_int(20),
+            "c" => Value::test_list(vec![Value::test_int(3), Value::test_int(4)]),
+        }));
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let foreground = |y: u16| terminal.backend().buffer().get(1, y).fg;
+
+        // NOTE: the rows start below the border and the header, and the first one is selected
+        assert_eq!(foreground(3), config.colors.changed);
+        assert_eq!(foreground(4), config.colors.changed);
+
+        app.set_value(app.snapshot.clone().unwrap());
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let foreground = |y: u16| terminal.backend().buffer().get(1, y).fg;
+        assert_eq!(foreground(3), config.colors.normal.name.foreground);
+    }
+
+    #[test]
+    fn empty_containers() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "r" => Value::test_record(record! {}),
+            "l" => Value::test_list(vec![]),
+        }));
+
+        let hint = |app: &App| {
+            render(app, &config, 60, 10)
+                .into_iter()
+                .find(|l| l.contains(" — press h to go back"))
+                .map(|l| l.trim_matches(|c| c == ' ' || c == '│').to_string())
+        };
+        assert_eq!(hint(&app), None);
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.navigation.right, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            hint(&app),
+            Some("this record has no keys — press h to go back".into())
+        );
+
+        app.position.members = to_path_member_vec(&[PM::S("l"), PM::I(0)]);
+        assert_eq!(
+            hint(&app),
+            Some("this list has no items — press h to go back".into())
+        );
+    }
+
+    #[test]
+    fn render_missing_cells() {
+        let value = Value::test_record(record! {
+            "l" => Value::test_list(vec![]),
+        });
+        let cases = vec![
+            (
+                MissingDisplay::Null,
+                "this list has no items — press h to go back",
+            ),
+            (
+                MissingDisplay::Missing,
+                "(missing) this list has no items — press h to go back",
+            ),
+        ];
+
+        for (missing_display, expected) in cases {
+            let config = Config {
+                missing_display,
+                ..Default::default()
+            };
+            let mut app = App::from_value(value.clone());
+            handle_key_events(
+                KeyEvent::new(config.keybindings.navigation.right, KeyModifiers::empty()),
+                &mut app,
+                &config,
+            )
+            .unwrap();
+
+            let lines = render(&app, &config, 60, 10);
+            assert!(
+                lines.iter().any(|l| l.contains(expected)),
+                "{expected:?} not in {lines:#?}"
+            );
+        }
+
+        // NOTE: the cursor stays on the empty list, which is shown as a row
+        let config = Config {
+            missing_display: MissingDisplay::Reconcile,
+            ..Default::default()
+        };
+        let mut app = App::from_value(value);
+        handle_key_events(
+            KeyEvent::new(config.keybindings.navigation.right, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+        let lines = render(&app, &config, 60, 10);
+        assert!(
+            !lines.iter().any(|l| l.contains("has no items")),
+            "{lines:#?}"
+        );
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("l")]));
+    }
+
+    #[test]
+    fn inspect_types() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_record(record! {
+                "a" => Value::test_int(1),
+                "b" => Value::test_string("x"),
+            }),
+            Value::test_record(record! {
+                "a" => Value::test_float(2.5),
+                "b" => Value::test_nothing(),
+            }),
+        ]));
+
+        let lines = render(&app, &config, 60, 8);
+        assert!(!lines.iter().any(|l| l.contains("(int)")), "{:#?}", lines);
+
+        app.inspect = true;
+        // NOTE: the rows start below the border and the header of the table
+        let lines = render(&app, &config, 60, 8);
+        for (line, types) in [
+            (2, ["1 (int)", "x (string)"]),
+            (3, ["2.5 (float)", "(nothing)"]),
+        ] {
+            for ty in types {
+                assert!(lines[line].contains(ty), "{} not in {:?}", ty, lines[line]);
+            }
+        }
+
+        // NOTE: the types are shown even in narrow terminals
+        app.position.members = to_path_member_vec(&[PM::I(0), PM::S("a")]);
+        let lines = render(&app, &config, 30, 8);
+        assert!(lines[0].starts_with("a: (int) 1"), "{:?}", lines[0]);
+    }
+
+    #[test]
+    fn column_summary() {
+        let mut app = App::from_value(Value::test_list(vec![
+            Value::test_record(record! {
+                "name" => Value::test_string("alice"),
+                "age" => Value::test_int(31),
+            }),
+            Value::test_record(record! {
+                "name" => Value::test_string("bob"),
+                "age" => Value::test_int(27),
+            }),
+        ]));
+        app.position.members = to_path_member_vec(&[PM::I(0)]);
+
+        let lines = render(&app, &Config::default(), 60, 8);
+        assert!(lines[0].contains("name:string, age:int"), "{:?}", lines[0]);
+
+        let config = Config {
+            show_column_summary: false,
+            ..Default::default()
+        };
+        let lines = render(&app, &config, 60, 8);
+        assert!(!lines[0].contains("name:string"), "{:?}", lines[0]);
+    }
+
+    #[test]
+    fn wrap_focused_value() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_string("abcdefghijklmnopqrstuvwxyz0123456789"),
+            "b" => Value::test_int(1),
+        }));
+        app.position.members = to_path_member_vec(&[PM::S("a")]);
+
+        // NOTE: the rows start below the border and the header of the table
+        let lines = render(&app, &Config::default(), 40, 10);
+        assert!(
+            lines[2].contains("abcdefghijklmnopqrstuvwx "),
+            "{:?}",
+            lines
+        );
+        assert!(lines[3].starts_with("│b "), "{:?}", lines);
+
+        app.wrap_focused = true;
+        let lines = render(&app, &Config::default(), 40, 10);
+        assert!(
+            lines[2].contains("abcdefghijklmnopqrstuvwx "),
+            "{:?}",
+            lines
+        );
+        assert!(lines[3].contains(" yz0123456789 "), "{:?}", lines);
+        assert!(lines[4].starts_with("│b "), "{:?}", lines);
+
+        // NOTE: only the row under the cursor is wrapped
+        app.position.members = to_path_member_vec(&[PM::S("b")]);
+        let lines = render(&app, &Config::default(), 40, 10);
+        assert!(lines[3].starts_with("│b "), "{:?}", lines);
+    }
+
+    #[test]
+    fn hide_chrome() {
+        let mut app = App::from_value(Value::test_list(vec![Value::test_int(1)]));
+        let config = Config {
+            show_sparkline: false,
+            ..Default::default()
+        };
+
+        let lines = render(&app, &config, 60, 8);
+        assert!(lines[5].starts_with('└'), "{:?}", lines);
+        assert!(lines[6].starts_with("cell path: $.0"), "{:?}", lines);
+
+        // NOTE: the data takes the full height of the terminal
+        app.show_chrome = false;
+        let lines = render(&app, &config, 60, 8);
+        assert!(lines[0].starts_with('┌'), "{:?}", lines);
+        assert!(lines[7].starts_with('└'), "{:?}", lines);
+        assert!(
+            !lines.iter().any(|l| l.contains("cell path")),
+            "{:?}",
+            lines
+        );
+    }
+
+    #[test]
+    fn max_rows() {
+        let mut app = App::from_value(Value::test_list((0..1000).map(Value::test_int).collect()));
+        let config = Config {
+            show_sparkline: false,
+            max_rows: Some(3),
+            ..Default::default()
+        };
+
+        let lines = render(&app, &config, 40, 20);
+        assert!(lines[2].starts_with("│0   0 "), "{:?}", lines);
+        assert!(lines[4].starts_with("│2   2 "), "{:?}", lines);
+        assert!(lines[5].starts_with("│    +997 more"), "{:?}", lines);
+        assert_eq!(lines[6].trim(), "│                                      │");
+
+        // NOTE: the rows follow the cursor
+        app.position.members = to_path_member_vec(&[PM::I(500)]);
+        let lines = render(&app, &config, 40, 20);
+        assert!(lines[2].starts_with("│498 498 "), "{:?}", lines);
+        assert!(lines[4].starts_with("│500 500 "), "{:?}", lines);
+        assert!(lines[5].starts_with("│    +997 more"), "{:?}", lines);
+
+        // NOTE: the fields are capped after the pinned ones are put first
+        let mut app = App::from_value(Value::test_record(
+            (0..10)
+                .map(|i| (format!("f{i}"), Value::test_int(i)))
+                .collect::<nu_protocol::Record>(),
+        ));
+        app.pinned.insert("f7".into());
+        let lines = render(&app, &config, 40, 20);
+        assert!(lines[2].contains("f7 "), "{:?}", lines);
+        assert!(lines[4].contains("f1 "), "{:?}", lines);
+        assert!(lines[5].contains("+7 more"), "{:?}", lines);
+    }
+
+    #[test]
+    fn reverse_view() {
+        let value = Value::test_list(vec![
+            Value::test_string("a"),
+            Value::test_string("b"),
+            Value::test_string("c"),
+        ]);
+        let mut app = App::from_value(value.clone());
+        let config = Config::default();
+
+        let lines = render(&app, &config, 40, 10);
+        assert!(lines[2].starts_with("│0 a"), "{:?}", lines);
+        assert!(lines[4].starts_with("│2 c"), "{:?}", lines);
+
+        // NOTE: the indices are the ones of the data and the cursor stays on the same item
+        app.reversed = true;
+        let lines = render(&app, &config, 40, 10);
+        assert!(lines[2].starts_with("│2 c"), "{:?}", lines);
+        assert!(lines[3].starts_with("│1 b"), "{:?}", lines);
+        assert!(lines[4].starts_with("│0 a"), "{:?}", lines);
+        assert_eq!(app.value, value);
+
+        // NOTE: the pinned fields come first in the data, so last in the reversed view
+        let mut app = App::from_value(Value::test_record(record! {
+            "b" => Value::test_int(1),
+            "a" => Value::test_int(2),
+            "c" => Value::test_int(3),
+        }));
+        app.pinned.insert("c".into());
+        app.reversed = true;
+        let lines = render(&app, &config, 40, 10);
+        assert!(lines[2].starts_with("│a"), "{:?}", lines);
+        assert!(lines[3].starts_with("│b"), "{:?}", lines);
+        assert!(lines[4].starts_with("│c"), "{:?}", lines);
+    }
+
+    #[test]
+    fn leaf_views() {
+        let scalar = Value::test_int(1);
+        let container = Value::test_record(record! {
+            "a" => Value::test_list(vec![Value::test_string("b")]),
+        });
+
+        assert_eq!(repr_leaf(&scalar, &LeafView::Plain), None);
+        assert_eq!(repr_leaf(&container, &LeafView::Plain), None);
+
+        assert_eq!(repr_leaf(&scalar, &LeafView::Json), Some("1".into()));
+        assert_eq!(
+            repr_leaf(&container, &LeafView::Json),
+            Some(r#"{"a":["b"]}"#.into())
+        );
+
+        let debug = repr_leaf(&scalar, &LeafView::Debug).unwrap();
+        assert!(debug.starts_with("Int {\n    val: 1,"), "{}", debug);
+        let debug = repr_leaf(&container, &LeafView::Debug).unwrap();
+        assert!(debug.starts_with("Record {"), "{}", debug);
+        assert!(debug.contains("val: \"b\""), "{}", debug);
+    }
 
-    frame.render_widget(
-        Paragraph::new(cell_path).alignment(Alignment::Left),
-        next_to_bottom_bar_rect,
-    );
-}
+    #[test]
+    fn render_trailing_whitespace() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "s" => Value::test_string("foo  "),
+        }));
+        app.hit_bottom();
+        let config = Config::default();
 
-/// render the status bar at the bottom
-///
-/// the bar takes the last line of the TUI only and renders, from left to right
-/// - the current mode
-/// - hints about next bindings to press and actions to do
-///
-/// the color depending of the mode is completely configurable!
-///
-/// # Examples
-/// > :bulb: **Note**  
-/// > - the `...` are here to signify that the bar might be truncated and the `||` at the start and
-/// the end of the lines are just to represent the borders of the terminal but will not appear in
-/// the TUI.
-/// > - these examples use the default bindings
-/// - in NORMAL mode
-/// ```text
-/// ||NORMAL  ...                                     i to INSERT | hjkl to move around | p to peek | q to quit||
-/// ```
-/// - in INSERT mode
-/// ```text
-/// ||INSERT  ...                                                                               <esc> to NORMAL||
-/// ```
-/// - in PEEKING mode
-/// ```text
-/// ||PEEKING ... <esc> to NORMAL | a to peek all | c to peek current view | u to peek under cursor | q to quit||
-/// ```
-fn render_status_bar(frame: &mut Frame, app: &App, config: &Config) {
-    let bottom_bar_rect = Rect::new(0, frame.size().height - 1, frame.size().width, 1);
+        let mut terminal = Terminal::new(TestBackend::new(40, 8)).unwrap();
+        terminal
+            .draw(|frame| render_ui(frame, &app, &config, None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
 
-    let bg_style = match app.mode {
-        Mode::Normal => Style::default().bg(config.colors.status_bar.normal.background),
-        Mode::Insert => Style::default().bg(config.colors.status_bar.insert.background),
-        Mode::Peeking => Style::default().bg(config.colors.status_bar.peek.background),
-        Mode::Bottom => Style::default().bg(config.colors.status_bar.bottom.background),
-    };
+        let line: String = (0..40).map(|x| buffer.get(x, 1).symbol()).collect();
+        assert!(line.starts_with("│foo   is of shape string"), "{:?}", line);
+        // NOTE: only the two trailing spaces of the string are highlighted, after the border
+        let trailing = config.colors.trailing_whitespace;
+        assert_ne!(buffer.get(3, 1).bg, trailing);
+        assert_eq!(buffer.get(4, 1).bg, trailing);
+        assert_eq!(buffer.get(5, 1).bg, trailing);
+        assert_ne!(buffer.get(6, 1).bg, trailing);
+    }
 
-    let style = match app.mode {
-        Mode::Normal => bg_style.fg(config.colors.status_bar.normal.foreground),
-        Mode::Insert => bg_style.fg(config.colors.status_bar.insert.foreground),
-        Mode::Peeking => bg_style.fg(config.colors.status_bar.peek.foreground),
-        Mode::Bottom => bg_style.fg(config.colors.status_bar.bottom.foreground),
-    };
+    #[test]
+    fn render_leaf_view() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "s" => Value::test_string("foo"),
+        }));
+        app.hit_bottom();
 
-    let hints = match app.mode {
-        Mode::Normal => format!(
-            "{} to {} | {}{}{}{} to move around | {} to peek | {} to transpose | {} to quit",
-            repr_keycode(&config.keybindings.insert),
-            Mode::Insert,
-            repr_keycode(&config.keybindings.navigation.left),
-            repr_keycode(&config.keybindings.navigation.down),
-            repr_keycode(&config.keybindings.navigation.up),
-            repr_keycode(&config.keybindings.navigation.right),
-            repr_keycode(&config.keybindings.peek),
-            repr_keycode(&config.keybindings.transpose),
-            repr_keycode(&config.keybindings.quit),
-        ),
-        Mode::Insert => format!(
-            "{} to quit | {}{}{}{} to move the cursor | {}{} to delete characters | {} to confirm",
-            repr_keycode(&KeyCode::Esc),
-            repr_keycode(&KeyCode::Left),
-            repr_keycode(&KeyCode::Right),
-            repr_keycode(&KeyCode::Up),
-            repr_keycode(&KeyCode::Down),
-            repr_keycode(&KeyCode::Backspace),
-            repr_keycode(&KeyCode::Delete),
-            repr_keycode(&KeyCode::Enter),
-        ),
-        Mode::Peeking => format!(
-            "{} to {} | {} to peek all | {} to peek current view | {} to peek under cursor | {} to peek the cell path",
-            repr_keycode(&config.keybindings.normal),
-            Mode::Normal,
-            repr_keycode(&config.keybindings.peeking.all),
-            repr_keycode(&config.keybindings.peeking.view),
-            repr_keycode(&config.keybindings.peeking.under),
-            repr_keycode(&config.keybindings.peeking.cell_path),
-        ),
-        Mode::Bottom => format!(
-            "{} to {} | {} to peek | {} to quit",
-            repr_keycode(&config.keybindings.navigation.left),
-            Mode::Normal,
-            repr_keycode(&config.keybindings.peek),
-            repr_keycode(&config.keybindings.quit),
-        ),
-    };
+        let lines = render(&app, &Config::default(), 40, 8);
+        assert!(lines[1].contains("foo is of shape string"), "{:?}", lines);
 
-    let left = Line::from(Span::styled(
-        format!(" {} ", app.mode),
-        style.add_modifier(Modifier::REVERSED),
-    ));
-    let right = Line::from(Span::styled(hints, style));
+        let config = Config {
+            leaf_view: LeafView::Json,
+            ..Default::default()
+        };
+        let lines = render(&app, &config, 40, 8);
+        assert!(lines[1].starts_with("│\"foo\" "), "{:?}", lines);
+    }
 
-    frame.render_widget(
-        Paragraph::new(left)
-            .alignment(Alignment::Left)
-            .style(bg_style),
-        bottom_bar_rect,
-    );
-    frame.render_widget(
-        Paragraph::new(right).alignment(Alignment::Right),
-        bottom_bar_rect,
-    );
-}
+    #[test]
+    fn elide_at_max_depth() {
+        let mut app = App::from_value(Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_int(1)]),
+            "i" => Value::test_int(2),
+        }));
+        let config = Config::default();
 
-// TODO: add proper assert error messages
-#[cfg(test)]
-mod tests {
-    use nu_protocol::{record, Value};
+        let lines = render(&app, &config, 40, 8);
+        assert!(lines[2].contains("[1 item]"), "{:?}", lines);
 
-    use super::{repr_data, repr_list, repr_record, repr_simple_value, repr_table, DataRowRepr};
+        // NOTE: the content of the containers is hidden, not the scalars
+        app.max_depth = Some(1);
+        let lines = render(&app, &config, 40, 8);
+        assert!(lines[2].contains('…'), "{:?}", lines);
+        assert!(lines[3].contains('2'), "{:?}", lines);
+
+        // NOTE: the cells of the rows of a table are too deep as well
+        let app = App {
+            max_depth: Some(1),
+            ..App::from_value(Value::test_list(vec![Value::test_record(record! {
+                "name" => Value::test_string("foo"),
+            })]))
+        };
+        let lines = render(&app, &config, 40, 8);
+        assert!(!lines.iter().any(|l| l.contains("foo")), "{:?}", lines);
+        assert!(lines[2].contains('…'), "{:?}", lines);
+    }
 
     #[test]
-    fn simple_value() {
-        #[rustfmt::skip]
+    fn numeric_sparkline() {
+        let config = Config::default();
+        let app = App::from_value(Value::test_list(vec![
+            Value::test_int(0),
+            Value::test_float(3.5),
+            Value::test_int(7),
+            Value::test_int(1),
+        ]));
+
+        let lines = render(&app, &config, 40, 10);
+        assert_eq!(lines[0].trim_end(), "▁▅█▂");
+        // NOTE: the table is below the sparkline
+        assert!(lines[1].starts_with('┌'), "{:?}", lines[1]);
+
+        let config = Config {
+            show_sparkline: false,
+            ..Default::default()
+        };
+        assert!(render(&app, &config, 40, 10)[0].starts_with('┌'));
+
+        let app = App::from_value(Value::test_list(vec![
+            Value::test_int(1),
+            Value::test_string("foo"),
+        ]));
+        assert!(render(&app, &Config::default(), 40, 10)[0].starts_with('┌'));
+    }
+
+    #[test]
+    fn scrollbar() {
+        let config = Config {
+            layout: Layout::Compact,
+            ..Default::default()
+        };
+        let long = App::from_value(Value::test_list(
+            (0..20).map(|i| Value::test_string(i.to_string())).collect(),
+        ));
+        let short = App::from_value(Value::test_list(vec![Value::test_string("foo")]));
+
+        // NOTE: `auto` only shows the scrollbar when some rows are hidden
+        let lines = render(&long, &config, 40, 10);
+        assert!(lines[0].ends_with('█'), "{:?}", lines[0]);
+        assert!(lines[7].ends_with('│'), "{:?}", lines[7]);
+        assert!(!render(&short, &config, 40, 10)[0].ends_with('█'));
+
+        let config = Config {
+            layout: Layout::Compact,
+            scrollbar: ScrollbarConfig {
+                visibility: ScrollbarVisibility::Always,
+                thumb: "#".into(),
+                track: ".".into(),
+            },
+            ..Default::default()
+        };
+        assert!(render(&short, &config, 40, 10)[0].ends_with('#'));
+
+        let config = Config {
+            layout: Layout::Compact,
+            scrollbar: ScrollbarConfig {
+                visibility: ScrollbarVisibility::Never,
+                ..Config::default().scrollbar
+            },
+            ..Default::default()
+        };
+        assert!(!render(&long, &config, 40, 10)[0].ends_with('█'));
+    }
+
+    #[test]
+    fn breadcrumb_styles() {
+        let path = CellPath {
+            members: to_path_member_vec(&[PM::S("r"), PM::S("a")]),
+        };
         let cases = vec![
-            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
-            (Value::test_int(1), DataRowRepr::unnamed("1", "int")),
-            (Value::test_bool(true), DataRowRepr::unnamed("true", "bool")),
-            (Value::test_nothing(), DataRowRepr::unnamed("", "nothing")),
-            (Value::test_string("foo"), DataRowRepr::unnamed("foo", "string")),
+            (BreadcrumbStyle::Leading, "$.r.a", "$."),
+            (BreadcrumbStyle::In, "$in.r.a", "$in"),
+            (BreadcrumbStyle::Bare, "r.a", ""),
         ];
 
-        for (value, expected) in cases {
-            assert_eq!(repr_simple_value(&value), expected);
+        for (style, expected, root) in cases {
+            assert_eq!(repr_breadcrumb(&path, &style), expected);
+            assert_eq!(repr_breadcrumb(&CellPath { members: vec![] }, &style), root);
         }
+
+        // NOTE: the styles are cycled through at runtime, starting from the config
+        let config = Config {
+            breadcrumb_style: BreadcrumbStyle::Bare,
+            ..Default::default()
+        };
+        let mut app = App::new(
+            &Value::test_record(record! { "r" => Value::test_int(1) }),
+            &config,
+        );
+        let lines = render(&app, &config, 40, 10);
+        assert_eq!(lines[8].trim_end(), "cell path: r");
+
+        let key = KeyEvent::new(config.keybindings.cycle_breadcrumb, KeyModifiers::empty());
+        handle_key_events(key, &mut app, &config).unwrap();
+        assert_eq!(app.breadcrumb_style, BreadcrumbStyle::Leading);
+        let lines = render(&app, &config, 40, 10);
+        assert_eq!(lines[8].trim_end(), "cell path: $.r");
     }
 
     #[test]
-    fn list() {
-        let list = vec![
-            Value::test_string("a"),
-            Value::test_int(1),
-            Value::test_bool(false),
-        ];
-
-        #[rustfmt::skip]
+    fn wrap_long_text() {
         let cases = vec![
-            (list, DataRowRepr::unnamed("[3 items]", "list")),
-            (vec![], DataRowRepr::unnamed("[]", "list")),
-            (vec![Value::test_nothing()], DataRowRepr::unnamed("[1 item]", "list")),
+            ("abcdef", 4, vec!["abcd", "ef"]),
+            ("abcd", 4, vec!["abcd"]),
+            ("ab\ncdef", 3, vec!["ab", "cde", "f"]),
+            ("", 3, vec![""]),
+            ("abc", 0, vec!["a", "b", "c"]),
         ];
 
-        for (list, expected) in cases {
-            assert_eq!(repr_list(&list), expected);
+        for (text, width, expected) in cases {
+            assert_eq!(wrap_text(text, width), expected, "{:?} at {}", text, width);
+        }
+    }
+
+    #[test]
+    fn gutter() {
+        assert_eq!(list_gutter(4, 1, false), vec!["0", "1", "2", "3"]);
+        assert_eq!(
+            list_gutter(9, 5, true),
+            vec!["5", "4", "3", "2", "1", "5", "1", "2", "3"]
+        );
+        assert!(list_gutter(0, 0, true).is_empty());
+    }
+
+    #[test]
+    fn render_relative_indices() {
+        let config = Config {
+            show_sparkline: false,
+            ..Default::default()
+        };
+        let mut app = App::from_value(Value::test_list(
+            (0..10).map(|i| Value::test_int(i * 100)).collect(),
+        ));
+        app.position.members[0] = PathMember::Int {
+            val: 5,
+            span: Span::test_data(),
+            optional: false,
+        };
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.toggle_relative, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+
+        // NOTE: the first two lines are the top border and the header of the table
+        let lines = render(&app, &config, 40, 15);
+        for (item, index) in [(3, "2"), (4, "1"), (5, "5"), (6, "1"), (7, "2")] {
+            let line = &lines[item + 2];
+            assert!(
+                line.starts_with(&format!("│{index} {}", item * 100)),
+                "expected {index} next to item {item} in {line:?}",
+            );
         }
     }
 
@@ -647,9 +3964,11 @@ mod tests {
     fn record() {
         #[rustfmt::skip]
         let cases = vec![
-            (vec!["a", "b", "c"], DataRowRepr::unnamed("{3 fields}", "record")),
+            (vec!["a", "b", "c"], DataRowRepr::unnamed("{a, b, c}", "record")),
             (vec![], DataRowRepr::unnamed("{}", "record")),
-            (vec!["a"], DataRowRepr::unnamed("{1 field}", "record")),
+            (vec!["a"], DataRowRepr::unnamed("{a}", "record")),
+            (vec!["a", "b"], DataRowRepr::unnamed("{a, b}", "record")),
+            (vec!["a", "b", "c", "d", "e"], DataRowRepr::unnamed("{a, b, c, …}", "record")),
         ];
 
         for (record, expected) in cases {
@@ -658,11 +3977,23 @@ mod tests {
                     &record
                         .iter()
                         .map(|x| x.to_string())
-                        .collect::<Vec<String>>()
+                        .collect::<Vec<String>>(),
+                    &Config::default().preview,
                 ),
                 expected
             );
         }
+
+        assert_eq!(
+            repr_record(
+                &["a".into(), "b".into(), "c".into()],
+                &PreviewConfig {
+                    record_keys: 1,
+                    ..Config::default().preview
+                }
+            ),
+            DataRowRepr::unnamed("{a, …}", "record")
+        );
     }
 
     #[ignore = "repr_value is just a direct wrapper around repr_list, repr_record and repr_simple_value"]
@@ -685,10 +4016,10 @@ mod tests {
             "i" => Value::test_int(123),
         });
 
-        let result = repr_data(&data);
+        let result = repr_data(&data, &Config::default().preview);
         let expected: Vec<DataRowRepr> = vec![
             DataRowRepr::named("l", "[3 items]", "list"),
-            DataRowRepr::named("r", "{2 fields}", "record"),
+            DataRowRepr::named("r", "{a, b}", "record"),
             DataRowRepr::named("s", "some string", "string"),
             DataRowRepr::named("i", "123", "int"),
         ];
@@ -714,7 +4045,14 @@ mod tests {
             vec![vec!["x".into(), "1".into()], vec!["y".into(), "2".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(
+            repr_table(
+                &table,
+                &Config::default().preview,
+                &RendererRegistry::default()
+            ),
+            expected
+        );
     }
 
     #[test]
@@ -736,7 +4074,14 @@ mod tests {
             vec![vec!["".into(), "1".into()], vec!["".into(), "2".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(
+            repr_table(
+                &table,
+                &Config::default().preview,
+                &RendererRegistry::default()
+            ),
+            expected
+        );
     }
 
     #[test]
@@ -758,7 +4103,14 @@ mod tests {
             vec![vec!["1".into(), "x".into()], vec!["2".into(), "y".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(
+            repr_table(
+                &table,
+                &Config::default().preview,
+                &RendererRegistry::default()
+            ),
+            expected
+        );
     }
 
     #[test]
@@ -780,7 +4132,14 @@ mod tests {
             vec![vec!["x".into(), "".into()], vec!["".into(), "2".into()]],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(
+            repr_table(
+                &table,
+                &Config::default().preview,
+                &RendererRegistry::default()
+            ),
+            expected
+        );
     }
 
     #[test]
@@ -805,6 +4164,236 @@ mod tests {
             ],
         );
 
-        assert_eq!(repr_table(&table), expected);
+        assert_eq!(
+            repr_table(
+                &table,
+                &Config::default().preview,
+                &RendererRegistry::default()
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn repr_table_with_custom_renderer() {
+        let table = vec![
+            record! {
+                "a" => Value::test_string("x"),
+                "b" => Value::test_int(1),
+            },
+            record! {
+                "a" => Value::test_string("y"),
+                "b" => Value::test_int(2),
+            },
+        ];
+
+        let mut renderers = RendererRegistry::default();
+        renderers.register("int", Box::new(|v| format!("#{}", v.as_int().unwrap())));
+
+        let expected = (
+            vec!["a".into(), "b".into()],
+            vec!["string".into(), "int".into()],
+            vec![vec!["x".into(), "#1".into()], vec!["y".into(), "#2".into()]],
+        );
+
+        assert_eq!(
+            repr_table(&table, &Config::default().preview, &renderers),
+            expected
+        );
+    }
+
+    #[test]
+    fn group_record_rows() {
+        let data = Value::test_record(record! {
+            "b1" => Value::test_int(3),
+            "a2" => Value::test_int(2),
+            "a1" => Value::test_int(1),
+        });
+
+        let groups = group_rows(repr_data(&data, &Config::default().preview));
+        let expected = vec![
+            (
+                "a".to_string(),
+                vec![
+                    DataRowRepr::named("a1", "1", "int"),
+                    DataRowRepr::named("a2", "2", "int"),
+                ],
+            ),
+            ("b".to_string(), vec![DataRowRepr::named("b1", "3", "int")]),
+        ];
+        assert_eq!(groups, expected);
+
+        assert_eq!(grouped_row_index(&groups, "a1"), Some(1));
+        assert_eq!(grouped_row_index(&groups, "a2"), Some(2));
+        assert_eq!(grouped_row_index(&groups, "b1"), Some(4));
+        assert_eq!(grouped_row_index(&groups, "x"), None);
+    }
+
+    #[test]
+    fn group_record_rows_by_type() {
+        let data = Value::test_record(record! {
+            "l" => Value::test_list(vec![Value::test_string("my")]),
+            "r" => Value::test_record(record! {
+                "a" => Value::test_int(1),
+            }),
+            "s" => Value::test_string("some string"),
+            "i" => Value::test_int(123),
+        });
+        let rec = data.as_record().unwrap();
+
+        let groups = group_rows_by_type(repr_data(&data, &Config::default().preview), rec);
+        let names = groups
+            .iter()
+            .map(|(group, rows)| {
+                let names = rows.iter().map(|r| r.name.clone().unwrap()).collect();
+                (group.as_str(), names)
+            })
+            .collect::<Vec<(&str, Vec<String>)>>();
+        assert_eq!(
+            names,
+            vec![
+                ("scalars", vec!["s".into(), "i".into()]),
+                ("containers", vec!["l".into(), "r".into()]),
+            ]
+        );
+
+        // NOTE: the navigation goes through the fields in the order of the sections
+        let config = Config {
+            group_by_type: true,
+            ..Default::default()
+        };
+        let mut app = App::new(&data, &config);
+        assert_eq!(app.position.members, to_path_member_vec(&[PM::S("s")]));
+        let down = KeyEvent::new(config.keybindings.navigation.down, KeyModifiers::empty());
+        for expected in ["i", "l", "r", "s"] {
+            handle_key_events(down, &mut app, &config).unwrap();
+            assert_eq!(app.position.members, to_path_member_vec(&[PM::S(expected)]));
+        }
+
+        // NOTE: the sections are below the header of the table
+        let lines = render(&app, &config, 40, 12);
+        assert!(lines[2].starts_with("│scalars "), "{:?}", lines[2]);
+        assert!(lines[5].starts_with("│containe"), "{:?}", lines[5]);
+    }
+
+    #[test]
+    fn render_live_matches() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_string("foo"),
+            "b" => Value::test_string("bar"),
+            "c" => Value::test_string("food"),
+        }));
+        let press = |app: &mut App, code| {
+            handle_key_events(KeyEvent::new(code, KeyModifiers::empty()), app, &config).unwrap();
+        };
+        let status_bar = |app: &App| render(app, &config, 80, 10)[9].clone();
+
+        press(&mut app, config.keybindings.search);
+        for c in "fo".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+
+        // NOTE: the matches are only counted once the query stops changing
+        app.update_live_matches(Instant::now());
+        assert!(
+            !status_bar(&app).contains("matches"),
+            "{:?}",
+            status_bar(&app)
+        );
+
+        app.update_live_matches(Instant::now() + SEARCH_DEBOUNCE);
+        assert!(
+            status_bar(&app).contains("/fo | 2 matches"),
+            "{:?}",
+            status_bar(&app)
+        );
+
+        for c in "od".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        app.update_live_matches(Instant::now() + SEARCH_DEBOUNCE);
+        assert!(
+            status_bar(&app).contains("/food | 1 match "),
+            "{:?}",
+            status_bar(&app)
+        );
+    }
+
+    #[test]
+    fn render_pending_sequence_hint() {
+        let config = Config::default();
+        let mut app = App::from_value(Value::test_record(record! {
+            "a" => Value::test_int(1),
+        }));
+
+        let status_bar = render(&app, &config, 100, 10).pop().unwrap();
+        assert!(
+            !status_bar.contains("waiting for"),
+            "unexpected hint in {status_bar:?}"
+        );
+
+        handle_key_events(
+            KeyEvent::new(config.keybindings.mark, KeyModifiers::empty()),
+            &mut app,
+            &config,
+        )
+        .unwrap();
+
+        let status_bar = render(&app, &config, 100, 10).pop().unwrap();
+        assert!(
+            status_bar.contains("waiting for a letter to mark the current cell"),
+            "expected a hint about the mark in {status_bar:?}"
+        );
+    }
+
+    /// a fake dataframe-like custom value, which can be turned into a table of two rows
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct MockDataFrame;
+
+    #[typetag::serde]
+    impl CustomValue for MockDataFrame {
+        fn clone_value(&self, span: nu_protocol::Span) -> Value {
+            Value::custom_value(Box::new(self.clone()), span)
+        }
+
+        fn value_string(&self) -> String {
+            "MockDataFrame".into()
+        }
+
+        fn to_base_value(&self, _span: nu_protocol::Span) -> Result<Value, ShellError> {
+            Ok(Value::test_list(vec![
+                Value::test_record(record! {
+                    "a" => Value::test_int(1),
+                    "b" => Value::test_string("foo"),
+                }),
+                Value::test_record(record! {
+                    "a" => Value::test_int(2),
+                    "b" => Value::test_string("bar"),
+                }),
+            ]))
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn custom_value() {
+        let value = Value::test_custom_value(Box::new(MockDataFrame));
+        let expected = DataRowRepr::unnamed(
+            "MockDataFrame (2 rows x 2 columns: a: int, b: string)",
+            "MockDataFrame",
+        );
+        assert_eq!(repr_value(&value, &Config::default().preview), expected);
+
+        let config = Config::default();
+        let app = App::new(&Value::test_record(record! { "df" => value }), &config);
+        let lines = render(&app, &config, 120, 10).join("\n");
+        assert!(
+            lines.contains("MockDataFrame (2 rows x 2 columns"),
+            "expected a summary of the custom value in {lines:?}"
+        );
     }
 }